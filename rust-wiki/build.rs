@@ -0,0 +1,43 @@
+// Compiles ffi/harness.c, the C side of the FFI chapter (src/_18_ffi/), with the system C compiler, and links it into
+// the library as a static archive. The harness calls the `#[no_mangle]` functions of src/_18_ffi/_2_exporting_to_c.rs;
+// the library's tests call it back.
+//
+// Crates such as `cc` do this portably (compiler detection, flags, MSVC); this repo has no dependencies, so it runs
+// `cc` and `ar` directly, which limits the harness to Unix-like targets: on others nothing is built, and the tests
+// calling the harness are compiled out (`#[cfg(unix)]`).
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn run(command: &mut Command) {
+    let status = command.status().unwrap_or_else(|error| panic!("failed to run {command:?}: {error}"));
+    assert!(status.success(), "{command:?} failed with {status}");
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed=ffi/harness.c");
+    println!("cargo::rerun-if-changed=ffi/wiki.h");
+    println!("cargo::rerun-if-env-changed=CC");
+
+    // The target's families, e.g. "unix" or "unix,wasm": the build script itself runs on the host.
+    let target_family: String = env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
+    if !target_family.split(',').any(|family| family == "unix") {
+        return;
+    }
+
+    let out_dir: PathBuf = PathBuf::from(env::var("OUT_DIR").expect("cargo sets OUT_DIR for build scripts"));
+    let object: PathBuf = out_dir.join("harness.o");
+    let compiler: String = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+
+    // -fPIC: the object ends up in the cdylib too, which must be position-independent.
+    run(Command::new(compiler)
+        .args(["-std=c99", "-D_POSIX_C_SOURCE=200809L", "-Wall", "-Wextra", "-Werror", "-O1", "-fPIC", "-c"])
+        .arg(Path::new("ffi").join("harness.c"))
+        .arg("-o")
+        .arg(&object));
+    run(Command::new("ar").arg("crs").arg(out_dir.join("libffi_harness.a")).arg(&object));
+
+    println!("cargo::rustc-link-search=native={}", out_dir.display());
+    println!("cargo::rustc-link-lib=static=ffi_harness");
+}
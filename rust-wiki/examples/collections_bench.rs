@@ -0,0 +1,13 @@
+// Prints comparative timings of the standard collections on this machine (see src/_11_collections/_5_benchmarks.rs).
+// > cargo run --release --example collections_bench -- [elements] [runs]
+
+use rust_wiki::_11_collections::_5_benchmarks;
+use rust_wiki::_1_basics::_5_io_and_logging;
+
+fn main() {
+    _5_io_and_logging::init();
+    let mut args = std::env::args().skip(1).map(|arg| arg.parse::<usize>().expect("arguments must be numbers"));
+    let elements: usize = args.next().unwrap_or(100_000);
+    let runs: usize = args.next().unwrap_or(10);
+    _5_benchmarks::benchmarks_example(elements, runs);
+}
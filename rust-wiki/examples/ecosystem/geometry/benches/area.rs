@@ -0,0 +1,30 @@
+// Times `Shape::area` for each kind of shape. With `harness = false`, this `main` is the whole benchmark:
+//   - `cargo bench` builds it in release mode and passes `--bench`: every shape is timed over many iterations.
+//   - `cargo test --benches` builds and runs it as a test, without `--bench`: each shape runs once, as a smoke test.
+
+use geometry::Shape;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let benchmarking: bool = std::env::args().any(|arg| arg == "--bench");
+    let iterations: u32 = if benchmarking { 10_000_000 } else { 1 };
+    let shapes: [(&str, Shape); 3] = [
+        ("circle", Shape::Circle { radius: 1.5 }),
+        ("rectangle", Shape::Rectangle { width: 2.0, height: 3.0 }),
+        ("triangle", Shape::Triangle { a: 3.0, b: 4.0, c: 5.0 }),
+    ];
+    for (name, shape) in shapes {
+        let start: Instant = Instant::now();
+        let mut total: f64 = 0.0;
+        for _ in 0..iterations {
+            // `black_box` stops the optimizer from computing the area once and multiplying.
+            total += black_box(shape).area();
+        }
+        let elapsed: Duration = start.elapsed();
+        assert!(total > 0.0);
+        if benchmarking {
+            println!("{name:<10} {:>8.2} ns/iter", elapsed.as_nanos() as f64 / f64::from(iterations));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// A build script: compiled and run by cargo before the crate itself. It can generate code, compile C (see the FFI
+// chapter), or probe the system, and talks back to cargo by printing `cargo::` lines to stdout.
+//
+// This one turns units.txt into Rust source, written to OUT_DIR (a directory cargo gives each build script), which
+// src/units.rs then `include!`s.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    // Only rerun when the table changes, rather than on every change in the package.
+    println!("cargo::rerun-if-changed=units.txt");
+
+    let table: String = fs::read_to_string("units.txt").expect("units.txt is next to build.rs");
+    let units: Vec<(&str, &str, &str)> = table
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                [name, symbol, meters] => {
+                    meters.parse::<f64>().unwrap_or_else(|_| panic!("units.txt: {meters:?} is not a number"));
+                    (name, symbol, meters)
+                }
+                _ => panic!("units.txt: expected `<Name> <symbol> <meters>`, found {line:?}"),
+            }
+        })
+        .collect();
+
+    let mut code: String = String::from("// Generated by build.rs from units.txt: do not edit.\n\n");
+    code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\npub enum Unit {\n");
+    for (name, _, _) in &units {
+        writeln!(code, "    {name},").unwrap();
+    }
+    code.push_str("}\n\nimpl Unit {\n");
+    let variants: Vec<String> = units.iter().map(|(name, _, _)| format!("Unit::{name}")).collect();
+    writeln!(code, "    pub const ALL: [Unit; {}] = [{}];\n", units.len(), variants.join(", ")).unwrap();
+    code.push_str("    pub fn symbol(self) -> &'static str {\n        match self {\n");
+    for (name, symbol, _) in &units {
+        writeln!(code, "            Unit::{name} => {symbol:?},").unwrap();
+    }
+    code.push_str("        }\n    }\n\n    pub fn meters(self) -> f64 {\n        match self {\n");
+    for (name, _, meters) in &units {
+        writeln!(code, "            Unit::{name} => {meters}_f64,").unwrap();
+    }
+    code.push_str("        }\n    }\n}\n");
+
+    let out_dir: String = env::var("OUT_DIR").expect("cargo sets OUT_DIR for build scripts");
+    fs::write(Path::new(&out_dir).join("units.rs"), code).expect("OUT_DIR is writable");
+
+    // Build scripts can also set environment variables for the crate's compilation, read with `env!`.
+    println!("cargo::rustc-env=GEOMETRY_UNIT_COUNT={}", units.len());
+}
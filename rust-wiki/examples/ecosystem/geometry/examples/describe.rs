@@ -0,0 +1,20 @@
+// Describes a few shapes, and converts a length into every generated unit. `required-features = ["display"]` in
+// Cargo.toml skips this example when the library is built without `Display` impls.
+// > cargo run --example describe
+
+use geometry::{Length, Shape, Unit};
+
+fn main() {
+    let shapes: [Shape; 3] = [
+        Shape::Circle { radius: 1.0 },
+        Shape::Rectangle { width: 2.0, height: 3.0 },
+        Shape::Triangle { a: 3.0, b: 4.0, c: 5.0 },
+    ];
+    for shape in shapes {
+        println!("{shape}: area {:.2}, perimeter {:.2}", shape.area(), shape.perimeter());
+    }
+    let marathon: Length = Length::new(42.195, Unit::Kilometer);
+    for unit in Unit::ALL {
+        println!("{marathon} = {:.3} {}", marathon.to(unit).value, unit.symbol());
+    }
+}
@@ -0,0 +1,82 @@
+// The library crate of the workspace: plane shapes, always; `Display` impls with the `display` feature (on by
+// default); and solids with the `solids` feature.
+//
+// `cfg(feature = "...")` removes the item it is on when the feature is off: the code is not compiled at all, so it
+// costs nothing, and using it is a compile error rather than a runtime one. `cfg!(feature = "...")` is the
+// expression form, a `bool` known at compile time.
+
+pub mod units;
+
+#[cfg(feature = "solids")]
+pub mod solids;
+
+pub use units::{Length, Unit};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Triangle { a: f64, b: f64, c: f64 },
+}
+
+impl Shape {
+    pub fn area(&self) -> f64 {
+        match *self {
+            Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+            Shape::Rectangle { width, height } => width * height,
+            // Heron's formula.
+            Shape::Triangle { a, b, c } => {
+                let s: f64 = (a + b + c) / 2.0;
+                (s * (s - a) * (s - b) * (s - c)).sqrt()
+            }
+        }
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        match *self {
+            Shape::Circle { radius } => 2.0 * std::f64::consts::PI * radius,
+            Shape::Rectangle { width, height } => 2.0 * (width + height),
+            Shape::Triangle { a, b, c } => a + b + c,
+        }
+    }
+}
+
+#[cfg(feature = "display")]
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shape::Circle { radius } => write!(f, "circle of radius {radius}"),
+            Shape::Rectangle { width, height } => write!(f, "{width} x {height} rectangle"),
+            Shape::Triangle { a, b, c } => write!(f, "triangle with sides {a}, {b}, {c}"),
+        }
+    }
+}
+
+// The features this build of the library was compiled with.
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut features: Vec<&'static str> = Vec::new();
+    if cfg!(feature = "display") {
+        features.push("display");
+    }
+    if cfg!(feature = "solids") {
+        features.push("solids");
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_areas() {
+        assert_eq!(Shape::Rectangle { width: 2.0, height: 3.0 }.area(), 6.0);
+        assert_eq!(Shape::Triangle { a: 3.0, b: 4.0, c: 5.0 }.area(), 6.0);
+        assert!((Shape::Circle { radius: 1.0 }.area() - std::f64::consts::PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_enabled_features() {
+        assert_eq!(enabled_features().contains(&"solids"), cfg!(feature = "solids"));
+    }
+}
@@ -0,0 +1,19 @@
+// Only compiled with `--features solids`: without it, `geometry::solids` does not exist.
+
+use crate::Shape;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Solid {
+    Sphere { radius: f64 },
+    // A prism: a plane shape extruded to a height.
+    Prism { base: Shape, height: f64 },
+}
+
+impl Solid {
+    pub fn volume(&self) -> f64 {
+        match *self {
+            Solid::Sphere { radius } => 4.0 / 3.0 * std::f64::consts::PI * radius.powi(3),
+            Solid::Prism { base, height } => base.area() * height,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// The `Unit` enum is GENERATED: build.rs writes it to `$OUT_DIR/units.rs` from the table in units.txt, and
+// `include!` pastes that file here, as if its text had been written in place.
+include!(concat!(env!("OUT_DIR"), "/units.rs"));
+
+// Set by build.rs with `cargo::rustc-env`.
+pub const UNIT_COUNT: &str = env!("GEOMETRY_UNIT_COUNT");
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Length {
+    pub fn new(value: f64, unit: Unit) -> Length {
+        Length { value, unit }
+    }
+
+    pub fn to(self, unit: Unit) -> Length {
+        Length { value: self.value * self.unit.meters() / unit.meters(), unit }
+    }
+}
+
+impl std::str::FromStr for Unit {
+    type Err = String;
+
+    fn from_str(symbol: &str) -> Result<Unit, String> {
+        Unit::ALL.into_iter().find(|unit| unit.symbol() == symbol).ok_or(format!("unknown unit: {symbol:?}"))
+    }
+}
+
+#[cfg(feature = "display")]
+impl std::fmt::Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.value, self.unit.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_units() {
+        assert_eq!(Unit::ALL.len().to_string(), UNIT_COUNT);
+        assert_eq!("km".parse::<Unit>(), Ok(Unit::Kilometer));
+        assert_eq!(Length::new(1.0, Unit::Foot).to(Unit::Inch).value.round(), 12.0);
+    }
+}
@@ -0,0 +1,38 @@
+// An integration test: a separate crate, which only sees the library's public API, as a user would.
+// > cargo test -p geometry --test shapes
+
+use geometry::{Length, Shape, Unit};
+
+#[test]
+fn public_api() {
+    let square: Shape = Shape::Rectangle { width: 2.0, height: 2.0 };
+    assert_eq!((square.area(), square.perimeter()), (4.0, 8.0));
+    assert_eq!(Length::new(1.0, Unit::Mile).to(Unit::Meter).value, 1609.344);
+}
+
+#[test]
+fn generated_code_is_public() {
+    assert_eq!(Unit::ALL.first(), Some(&Unit::Millimeter));
+    assert_eq!(Unit::Inch.symbol(), "in");
+}
+
+// Each feature's items are only there when it is enabled; the tests for them are compiled out with them.
+#[cfg(feature = "display")]
+#[test]
+fn display_feature() {
+    assert_eq!(Shape::Circle { radius: 2.0 }.to_string(), "circle of radius 2");
+    assert_eq!(Length::new(3.0, Unit::Foot).to_string(), "3 ft");
+}
+
+#[cfg(feature = "solids")]
+#[test]
+fn solids_feature() {
+    use geometry::solids::Solid;
+    let cube: Solid = Solid::Prism { base: Shape::Rectangle { width: 2.0, height: 2.0 }, height: 2.0 };
+    assert_eq!(cube.volume(), 8.0);
+}
+
+#[test]
+fn features_match_cfg() {
+    assert_eq!(geometry::enabled_features().contains(&"display"), cfg!(feature = "display"));
+}
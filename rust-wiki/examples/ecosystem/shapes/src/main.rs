@@ -0,0 +1,48 @@
+// A command-line front end to the geometry library:
+// > cargo run -p shapes -- circle 2
+// > cargo run -p shapes -- prism 2 3 10
+// > cargo run -p shapes -- convert 5 km mi
+
+use geometry::solids::Solid;
+use geometry::{Length, Shape, Unit};
+use std::process::ExitCode;
+
+fn numbers(args: &[String]) -> Result<Vec<f64>, String> {
+    args.iter().map(|arg| arg.parse::<f64>().map_err(|_| format!("not a number: {arg:?}"))).collect()
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, rest) = args.split_first().ok_or("usage: shapes <circle|rectangle|triangle|prism|convert> ...")?;
+    let shape: Shape = match (command.as_str(), numbers(rest).as_deref()) {
+        ("circle", Ok(&[radius])) => Shape::Circle { radius },
+        ("rectangle", Ok(&[width, height])) => Shape::Rectangle { width, height },
+        ("triangle", Ok(&[a, b, c])) => Shape::Triangle { a, b, c },
+        ("prism", Ok(&[width, depth, height])) => {
+            let prism: Solid = Solid::Prism { base: Shape::Rectangle { width, height: depth }, height };
+            return Ok(format!("volume {:.2}", prism.volume()));
+        }
+        ("convert", _) => {
+            let [value, from, to] = rest else { return Err(String::from("usage: shapes convert <value> <unit> <unit>")) };
+            let value: f64 = value.parse().map_err(|_| format!("not a number: {value:?}"))?;
+            let length: Length = Length::new(value, from.parse::<Unit>()?).to(to.parse::<Unit>()?);
+            return Ok(format!("{:.3} {}", length.value, length.unit.symbol()));
+        }
+        (_, Err(error)) => return Err(error.clone()),
+        _ => return Err(format!("unknown command or wrong number of arguments: {command}")),
+    };
+    Ok(format!("{shape}: area {:.2}", shape.area()))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
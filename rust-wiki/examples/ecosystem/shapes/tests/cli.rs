@@ -0,0 +1,30 @@
+// Integration tests of a binary crate run the compiled binary: cargo builds it first, and passes its path in
+// `CARGO_BIN_EXE_<name>`.
+
+use std::process::{Command, Output};
+
+fn shapes(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_shapes")).args(args).output().expect("the binary was built by cargo")
+}
+
+#[test]
+fn computes_areas_and_volumes() {
+    let output: Output = shapes(&["rectangle", "2", "3"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2 x 3 rectangle: area 6.00\n");
+    // `prism` needs the library's `solids` feature, which shapes/Cargo.toml enables.
+    assert_eq!(String::from_utf8_lossy(&shapes(&["prism", "2", "3", "10"]).stdout), "volume 60.00\n");
+}
+
+#[test]
+fn converts_with_generated_units() {
+    assert_eq!(String::from_utf8_lossy(&shapes(&["convert", "1", "mi", "km"]).stdout), "1.609 km\n");
+}
+
+#[test]
+fn reports_errors() {
+    let output: Output = shapes(&["circle", "two"]);
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "error: not a number: \"two\"\n");
+    assert!(!shapes(&["convert", "1", "mi", "parsec"]).status.success());
+}
@@ -0,0 +1,113 @@
+/*
+ * C code calling the API in wiki.h, as a C program using the library would.
+ *
+ * build.rs compiles this file into a static library linked into rust_wiki's tests, which call wiki_ffi_harness
+ * (see src/_18_ffi/_2_exporting_to_c.rs). Compiled with -DWIKI_HARNESS_MAIN, it is a standalone program instead,
+ * linked against the cdylib by tests/ffi.rs.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "wiki.h"
+
+static int failures;
+static char *first_failure;
+static size_t first_failure_capacity;
+
+#define CHECK(condition)                                                                          \
+    do {                                                                                          \
+        if (!(condition)) {                                                                       \
+            if (failures++ == 0 && first_failure_capacity > 0) {                                  \
+                snprintf(first_failure, first_failure_capacity, "%s:%d: CHECK(%s) failed",        \
+                         __FILE__, __LINE__, #condition);                                         \
+            }                                                                                     \
+        }                                                                                         \
+    } while (0)
+
+static void check_values(void) {
+    CHECK(wiki_add(2, 3) == 5);
+    CHECK(wiki_add(INT32_MAX, 1) == INT32_MIN);
+}
+
+/* A string allocated by Rust, read by C, and handed back to Rust to be freed. */
+static void check_strings(void) {
+    char *greeting = wiki_greeting("C");
+    CHECK(greeting != NULL && strcmp(greeting, "Hello, C!") == 0);
+    wiki_string_free(greeting);
+
+    CHECK(wiki_greeting(NULL) == NULL);
+    CHECK(wiki_greeting("\xff") == NULL);
+    wiki_string_free(NULL);
+}
+
+/* A buffer allocated by Rust: C must keep its length to free it. */
+static void check_buffers(void) {
+    size_t len = 0;
+    uint64_t *numbers = wiki_fibonacci(94, &len);
+    CHECK(numbers != NULL && len == 94);
+    if (numbers != NULL) {
+        CHECK(numbers[0] == 0 && numbers[1] == 1 && numbers[10] == 55);
+        CHECK(numbers[93] == UINT64_C(12200160415121876738));
+    }
+    wiki_fibonacci_free(numbers, len);
+
+    CHECK(wiki_fibonacci(0, &len) == NULL && len == 0);
+}
+
+/* A buffer owned by C, written by Rust. */
+static void check_caller_buffer(void) {
+    char small[4];
+    char large[16];
+    ssize_t needed = wiki_reverse_into("stressed", small, sizeof small);
+    CHECK(needed == 8);
+    CHECK(strcmp(small, "des") == 0);
+
+    /* The usual two-step: ask for the size, then allocate enough. */
+    CHECK(wiki_reverse_into("stressed", NULL, 0) == 8);
+    CHECK(wiki_reverse_into("stressed", large, (size_t)needed + 1) == 8);
+    CHECK(strcmp(large, "desserts") == 0);
+
+    CHECK(wiki_reverse_into(NULL, large, sizeof large) == -1);
+}
+
+static void check_opaque_handle(void) {
+    WikiCounter *counter = wiki_counter_new(3);
+    CHECK(counter != NULL);
+    for (int i = 0; i < 5; i++) {
+        wiki_counter_increment(counter, (size_t)i % 3);
+    }
+    CHECK(wiki_counter_increment(counter, 0) == 3);
+    CHECK(wiki_counter_increment(counter, 3) == 0);
+    CHECK(wiki_counter_total(counter) == 6);
+    wiki_counter_free(counter);
+}
+
+/* Runs every check; returns the number of failures, and describes the first one in `message`. */
+int wiki_ffi_harness(char *message, size_t capacity) {
+    failures = 0;
+    first_failure = message;
+    first_failure_capacity = capacity;
+    if (capacity > 0) {
+        message[0] = '\0';
+    }
+
+    check_values();
+    check_strings();
+    check_buffers();
+    check_caller_buffer();
+    check_opaque_handle();
+    return failures;
+}
+
+#ifdef WIKI_HARNESS_MAIN
+int main(void) {
+    char message[256];
+    int failed = wiki_ffi_harness(message, sizeof message);
+    if (failed != 0) {
+        fprintf(stderr, "%d check(s) failed, first: %s\n", failed, message);
+        return 1;
+    }
+    printf("all C checks passed\n");
+    return 0;
+}
+#endif
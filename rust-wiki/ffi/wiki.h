@@ -0,0 +1,41 @@
+/*
+ * The C API exported by the rust_wiki library (src/_18_ffi/_2_exporting_to_c.rs).
+ *
+ * Ownership:
+ *   - `const` pointer arguments are borrowed for the duration of the call only.
+ *   - Pointers returned by wiki_greeting, wiki_fibonacci and wiki_counter_new are owned by the caller, and must be
+ *     released exactly once with wiki_string_free, wiki_fibonacci_free and wiki_counter_free respectively: never
+ *     with free(), since they were not allocated by malloc().
+ */
+#ifndef RUST_WIKI_H
+#define RUST_WIKI_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <sys/types.h>
+
+/* a + b, wrapping on overflow. */
+int32_t wiki_add(int32_t a, int32_t b);
+
+/* "Hello, <name>!", or NULL if name is NULL or not UTF-8. Free with wiki_string_free. */
+char *wiki_greeting(const char *name);
+void wiki_string_free(char *s);
+
+/* The first `count` Fibonacci numbers; their number is written to *len. NULL if count is 0.
+ * Free with wiki_fibonacci_free, passing the same len. */
+uint64_t *wiki_fibonacci(size_t count, size_t *len);
+void wiki_fibonacci_free(uint64_t *buffer, size_t len);
+
+/* Writes input, characters reversed, into output (at most `capacity` bytes, NUL included, never splitting a UTF-8
+ * character). Returns the length of the full result, as snprintf does, or -1 if input is NULL or not UTF-8. */
+ssize_t wiki_reverse_into(const char *input, char *output, size_t capacity);
+
+/* An opaque counter with a fixed number of slots. Not thread-safe. */
+typedef struct WikiCounter WikiCounter;
+WikiCounter *wiki_counter_new(size_t slots);
+/* The new value of the slot, or 0 if it does not exist. */
+uint64_t wiki_counter_increment(WikiCounter *counter, size_t slot);
+uint64_t wiki_counter_total(const WikiCounter *counter);
+void wiki_counter_free(WikiCounter *counter);
+
+#endif
@@ -0,0 +1,333 @@
+// `#[derive(Show)]` for `rust_wiki::_3_datatypes_and_traits::_4_traits::Show`, using only the `proc_macro` crate.
+//
+// A derive macro receives the tokens of the item it is attached to, and returns tokens to append after it.
+// Without `syn`, the item is parsed by walking its `TokenTree`s by hand, and without `quote`, the impl is built as
+// a string of Rust source and parsed back into tokens. `show_expansion!` returns that string instead, which lets
+// the expansion itself be tested.
+//
+// The generated `show` prints the item like `Debug` would, but with each field's `Display`:
+//
+//    #[derive(Show)]
+//    struct User { active: bool, #[show(skip)] sign_in_count: u64, username: String }
+//
+//    User { active: true, username: hello }
+//
+// and `AltShowType` is `String`, with `alt_show` returning its argument unchanged.
+
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+
+const TRAIT_PATH: &str = "::rust_wiki::_3_datatypes_and_traits::_4_traits::Show";
+
+#[proc_macro_derive(Show, attributes(show))]
+pub fn derive_show(input: TokenStream) -> TokenStream {
+  expand(input).parse().expect("the generated impl is valid Rust")
+}
+
+// Returns the source code that `#[derive(Show)]` would generate for the item, as a string literal.
+#[proc_macro]
+pub fn show_expansion(input: TokenStream) -> TokenStream {
+  TokenTree::Literal(proc_macro::Literal::string(&expand(input))).into()
+}
+
+fn expand(input: TokenStream) -> String {
+  match parse_item(input) {
+    Ok(item) => generate(&item),
+    Err(message) => format!("::core::compile_error!({message:?});"),
+  }
+}
+
+// -----------------------------------------------
+// ## Parsing
+
+struct Item {
+  name: String,
+  generics: Vec<GenericParam>,
+  kind: ItemKind,
+}
+
+enum ItemKind {
+  Struct(Fields),
+  Enum(Vec<(String, Fields)>),
+}
+
+enum Fields {
+  Named(Vec<Field>),
+  Unnamed(Vec<Field>),
+  Unit,
+}
+
+struct Field {
+  // The field's name, or its index in a tuple struct.
+  name: String,
+  skip: bool,
+}
+
+struct GenericParam {
+  // `'a`, `T` or `N`: what goes in the type's arguments, `Name<'a, T, N>`.
+  name: String,
+  // The whole declaration without its default: `'a: 'b`, `T: Clone`, `const N: usize`.
+  declaration: String,
+  is_type: bool,
+}
+
+fn parse_item(input: TokenStream) -> Result<Item, String> {
+  let tokens: Vec<TokenTree> = input.into_iter().collect();
+  let mut i: usize = skip_attributes_and_visibility(&tokens, 0).0;
+
+  let keyword: String = ident_at(&tokens, i).ok_or("expected `struct` or `enum`")?;
+  let name: String = ident_at(&tokens, i + 1).ok_or("expected the item's name")?;
+  i += 2;
+
+  let mut generics: Vec<GenericParam> = Vec::new();
+  if is_punct(tokens.get(i), '<') {
+    let end: usize = matching_angle_bracket(&tokens, i)?;
+    generics = split_top_level_commas(&tokens[i + 1..end]).iter().map(|param| parse_generic_param(param)).collect();
+    i = end + 1;
+  }
+  if ident_at(&tokens, i).as_deref() == Some("where") {
+    return Err(String::from("#[derive(Show)] does not support `where` clauses"));
+  }
+
+  let kind: ItemKind = match keyword.as_str() {
+    "struct" => ItemKind::Struct(parse_fields(tokens.get(i))?),
+    "enum" => match tokens.get(i) {
+      Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => ItemKind::Enum(parse_variants(group)?),
+      _ => return Err(String::from("expected the enum's variants")),
+    },
+    other => return Err(format!("#[derive(Show)] only supports structs and enums, not `{other}`")),
+  };
+  Ok(Item { name, generics, kind })
+}
+
+// Skips `#[...]` attributes and `pub`/`pub(...)`, returning the next index and whether `#[show(skip)]` was seen.
+fn skip_attributes_and_visibility(tokens: &[TokenTree], mut i: usize) -> (usize, bool) {
+  let mut skip: bool = false;
+  loop {
+    match (tokens.get(i), tokens.get(i + 1)) {
+      (Some(TokenTree::Punct(p)), Some(TokenTree::Group(attr))) if p.as_char() == '#' => {
+        skip |= is_show_skip(attr);
+        i += 2;
+      }
+      (Some(TokenTree::Ident(ident)), next) if ident.to_string() == "pub" => {
+        i += 1;
+        if matches!(next, Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis) {
+          i += 1;
+        }
+      }
+      _ => return (i, skip),
+    }
+  }
+}
+
+// `#[show(skip)]`: the attribute's group holds `show (skip)`.
+fn is_show_skip(attr: &Group) -> bool {
+  let inner: Vec<TokenTree> = attr.stream().into_iter().collect();
+  match inner.as_slice() {
+    [TokenTree::Ident(name), TokenTree::Group(args)] if name.to_string() == "show" => {
+      args.stream().into_iter().any(|token| token.to_string() == "skip")
+    }
+    _ => false,
+  }
+}
+
+fn ident_at(tokens: &[TokenTree], i: usize) -> Option<String> {
+  match tokens.get(i) {
+    Some(TokenTree::Ident(ident)) => Some(ident.to_string()),
+    _ => None,
+  }
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+  matches!(token, Some(TokenTree::Punct(p)) if p.as_char() == c)
+}
+
+// Angle brackets are not token groups, so they are matched by counting. The `>` of `->` is not a bracket.
+fn matching_angle_bracket(tokens: &[TokenTree], open: usize) -> Result<usize, String> {
+  let mut depth: usize = 0;
+  for (i, token) in tokens.iter().enumerate().skip(open) {
+    if let TokenTree::Punct(p) = token {
+      let after_dash: bool =
+        i > 0 && matches!(&tokens[i - 1], TokenTree::Punct(prev) if prev.as_char() == '-' && prev.spacing() == Spacing::Joint);
+      match p.as_char() {
+        '<' => depth += 1,
+        '>' if !after_dash => {
+          depth -= 1;
+          if depth == 0 {
+            return Ok(i);
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+  Err(String::from("unclosed `<`"))
+}
+
+// Splits on commas that are not inside angle brackets, e.g. the fields of `{ a: HashMap<K, V>, b: u8 }`.
+// (Commas inside (), [] and {} are already hidden in groups.)
+fn split_top_level_commas(tokens: &[TokenTree]) -> Vec<Vec<TokenTree>> {
+  let mut parts: Vec<Vec<TokenTree>> = vec![Vec::new()];
+  let mut depth: usize = 0;
+  let mut previous_dash: bool = false;
+  for token in tokens {
+    if let TokenTree::Punct(p) = token {
+      match p.as_char() {
+        '<' => depth += 1,
+        '>' if !previous_dash => depth = depth.saturating_sub(1),
+        ',' if depth == 0 => {
+          parts.push(Vec::new());
+          previous_dash = false;
+          continue;
+        }
+        _ => {}
+      }
+      previous_dash = p.as_char() == '-' && p.spacing() == Spacing::Joint;
+    } else {
+      previous_dash = false;
+    }
+    parts.last_mut().unwrap().push(token.clone());
+  }
+  parts.retain(|part| !part.is_empty());
+  parts
+}
+
+fn tokens_to_string(tokens: &[TokenTree]) -> String {
+  tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+fn parse_generic_param(tokens: &[TokenTree]) -> GenericParam {
+  // Drop a default, `T = i32`: defaults are not allowed on impls.
+  let end: usize = tokens.iter().position(|token| is_punct(Some(token), '=')).unwrap_or(tokens.len());
+  let tokens: &[TokenTree] = &tokens[..end];
+  let declaration: String = tokens_to_string(tokens);
+  match tokens {
+    // A lifetime is the two tokens `'` and `a`.
+    [TokenTree::Punct(p), TokenTree::Ident(name), ..] if p.as_char() == '\'' => {
+      GenericParam { name: format!("'{name}"), declaration, is_type: false }
+    }
+    [TokenTree::Ident(keyword), TokenTree::Ident(name), ..] if keyword.to_string() == "const" => {
+      GenericParam { name: name.to_string(), declaration, is_type: false }
+    }
+    _ => GenericParam { name: tokens.first().map(|t| t.to_string()).unwrap_or_default(), declaration, is_type: true },
+  }
+}
+
+fn parse_fields(body: Option<&TokenTree>) -> Result<Fields, String> {
+  match body {
+    None => Ok(Fields::Unit),
+    Some(TokenTree::Punct(p)) if p.as_char() == ';' => Ok(Fields::Unit),
+    Some(TokenTree::Group(group)) => {
+      let parts: Vec<Vec<TokenTree>> = split_top_level_commas(&group.stream().into_iter().collect::<Vec<_>>());
+      match group.delimiter() {
+        Delimiter::Brace => {
+          let mut fields: Vec<Field> = Vec::new();
+          for part in parts {
+            let (i, skip) = skip_attributes_and_visibility(&part, 0);
+            let name: String = ident_at(&part, i).ok_or("expected a field name")?;
+            fields.push(Field { name, skip });
+          }
+          Ok(Fields::Named(fields))
+        }
+        Delimiter::Parenthesis => Ok(Fields::Unnamed(
+          parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| Field { name: index.to_string(), skip: skip_attributes_and_visibility(part, 0).1 })
+            .collect(),
+        )),
+        _ => Err(String::from("unexpected fields")),
+      }
+    }
+    Some(other) => Err(format!("unexpected `{other}`")),
+  }
+}
+
+fn parse_variants(body: &Group) -> Result<Vec<(String, Fields)>, String> {
+  let tokens: Vec<TokenTree> = body.stream().into_iter().collect();
+  let mut variants: Vec<(String, Fields)> = Vec::new();
+  for part in split_top_level_commas(&tokens) {
+    let (i, _) = skip_attributes_and_visibility(&part, 0);
+    let name: String = ident_at(&part, i).ok_or("expected a variant name")?;
+    // Ignore an explicit discriminant, `= 1`.
+    let fields: Fields = match part.get(i + 1) {
+      Some(TokenTree::Punct(p)) if p.as_char() == '=' => Fields::Unit,
+      next => parse_fields(next)?,
+    };
+    variants.push((name, fields));
+  }
+  Ok(variants)
+}
+
+// -----------------------------------------------
+// ## Generating
+
+fn generate(item: &Item) -> String {
+  let (impl_generics, type_generics): (String, String) = if item.generics.is_empty() {
+    (String::new(), String::new())
+  } else {
+    // Each type parameter must be `Display` for its fields to be shown.
+    let declarations: Vec<String> = item
+      .generics
+      .iter()
+      .map(|param| match (param.is_type, param.declaration.contains(':')) {
+        (true, true) => format!("{} + ::std::fmt::Display", param.declaration),
+        (true, false) => format!("{}: ::std::fmt::Display", param.declaration),
+        (false, _) => param.declaration.clone(),
+      })
+      .collect();
+    let names: Vec<&str> = item.generics.iter().map(|param| param.name.as_str()).collect();
+    (format!("<{}>", declarations.join(", ")), format!("<{}>", names.join(", ")))
+  };
+
+  let body: String = match &item.kind {
+    ItemKind::Struct(fields) => {
+      let (pattern, format) = pattern_and_format(&item.name, "Self", fields);
+      format!("let {pattern} = self; {format}")
+    }
+    ItemKind::Enum(variants) if variants.is_empty() => String::from("match *self {}"),
+    ItemKind::Enum(variants) => {
+      let arms: Vec<String> = variants
+        .iter()
+        .map(|(name, fields)| {
+          let (pattern, format) = pattern_and_format(name, &format!("Self::{name}"), fields);
+          format!("{pattern} => {format},")
+        })
+        .collect();
+      format!("match self {{ {} }}", arms.join(" "))
+    }
+  };
+
+  format!(
+    "impl{impl_generics} {TRAIT_PATH} for {name}{type_generics} {{ \
+       type AltShowType = ::std::string::String; \
+       fn alt_show(s: ::std::string::String) -> ::std::string::String {{ s }} \
+       fn show(&self) -> ::std::string::String {{ {body} }} \
+     }}",
+    name = item.name,
+  )
+}
+
+// The pattern binding the shown fields, and the `format!` call showing them, e.g. for `Move { x: i32, y: i32 }`:
+//    Self::Move { x: __0, y: __1 }                    format!("Move {{ x: {}, y: {} }}", __0, __1)
+// Fields are bound to `__N` names so that tuple fields get names too.
+fn pattern_and_format(label: &str, path: &str, fields: &Fields) -> (String, String) {
+  let (list, is_named): (&Vec<Field>, bool) = match fields {
+    Fields::Unit => return (path.to_string(), format!("::std::string::String::from({label:?})")),
+    Fields::Named(list) => (list, true),
+    Fields::Unnamed(list) => (list, false),
+  };
+  let shown: Vec<(usize, &Field)> = list.iter().enumerate().filter(|(_, field)| !field.skip).collect();
+  let mut bindings: Vec<String> = shown.iter().map(|(index, field)| format!("{}: __{index}", field.name)).collect();
+  if shown.len() < list.len() {
+    bindings.push(String::from(".."));
+  }
+  let pattern: String = format!("{path} {{ {} }}", bindings.join(", "));
+  let args: String = shown.iter().map(|(index, _)| format!(", __{index}")).collect();
+  let template: String = if is_named {
+    let parts: Vec<String> = shown.iter().map(|(_, field)| format!("{}: {{}}", field.name)).collect();
+    format!("{label} {{{{ {} }}}}", parts.join(", "))
+  } else {
+    format!("{label}({})", vec!["{}"; shown.len()].join(", "))
+  };
+  (pattern, format!("::std::format!({template:?}{args})"))
+}
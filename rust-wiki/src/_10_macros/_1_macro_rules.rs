@@ -0,0 +1,235 @@
+// -----------------------------------------------
+// # MACROS
+//
+// A macro is code that writes code: it runs at compile time, taking tokens and expanding to Rust code in their place.
+// Macros can do what functions cannot: take a variable number of arguments (`println!`), define items (`struct`s,
+// `impl`s), or accept syntax that is not Rust at all.
+//
+// Rust has two kinds:
+//   - declarative macros, `macro_rules!` (here): rules matching token patterns, like a `match` on syntax.
+//   - procedural macros (_2_derive_macros.rs): Rust functions from tokens to tokens, compiled as a separate crate.
+//     `#[derive(...)]` is one of these.
+//
+// Syntax:
+//
+//    macro_rules! name {
+//      (pattern) => { expansion };
+//      (pattern) => { expansion };
+//    }
+//
+// The first rule whose pattern matches is expanded. Patterns capture FRAGMENTS with `$name:kind`:
+//    expr    an expression            2 + 2, f(x)
+//    ident   an identifier            x, my_fn
+//    ty      a type                   Vec<i32>
+//    literal a literal                7, "hi"
+//    pat     a pattern                Some(_)
+//    block   a block                  { ... }
+//    tt      a single token tree: one token, or a (...), [...] or {...} group with everything inside it
+//
+// A macro must be defined before it is used in the file (macros are expanded in textual order), unless it is
+// `#[macro_export]`ed, which puts it at the crate root: `rust_wiki::hashmap!`.
+
+use std::collections::HashMap;
+
+// -----------------------------------------------
+// ## Rules
+//
+// Like `match` arms, rules are separated by `;` (forgetting it is the bug in rustlings' macros4).
+macro_rules! my_macro {
+  () => {
+    String::from("Check out my macro!")
+  };
+  ($val:expr) => {
+    format!("Look at this other macro: {}", $val)
+  };
+}
+
+pub fn rules_example() -> (String, String) {
+  (my_macro!(), my_macro!(7777))
+}
+
+// -----------------------------------------------
+// ## Repetition
+//
+// `$( ... ),*` matches the enclosed pattern zero or more times, separated by `,` (`+` for one or more, `?` for at
+// most once). In the expansion, `$( ... )*` repeats once per match, and may use the captured fragments inside it.
+macro_rules! strings {
+  ($($s:expr),* $(,)?) => {
+    vec![$($s.to_string()),*]
+  };
+}
+
+pub fn repetition_example() -> Vec<String> {
+  strings!["a", 1, 'c']
+}
+
+// Repetitions can generate items too: one function per name.
+macro_rules! getters {
+  ($($name:ident: $value:expr),* $(,)?) => {
+    $(
+      pub fn $name() -> i32 {
+        $value
+      }
+    )*
+  };
+}
+
+getters! {
+  answer: 42,
+  zero: 0,
+}
+
+// -----------------------------------------------
+// ## Recursion
+//
+// A macro can expand to a call to itself, handling one piece of the input per step. (The default recursion limit
+// is 128 steps.) Here `max!(a, b, c)` becomes `max2(a, max!(b, c))`, then `max2(a, max2(b, c))`.
+macro_rules! max {
+  ($x:expr) => { $x };
+  ($x:expr, $($rest:expr),+) => {
+    {
+      let (first, rest) = ($x, max!($($rest),+));
+      if first > rest { first } else { rest }
+    }
+  };
+}
+
+// Counting the arguments at compile time: each step replaces one token by `1 +`.
+macro_rules! count {
+  () => { 0usize };
+  ($head:tt $($tail:tt)*) => { 1usize + count!($($tail)*) };
+}
+
+pub fn recursion_example() -> (i32, usize) {
+  (max!(3, 9, 4, 1), count!(a b c d))
+}
+
+// -----------------------------------------------
+// ## TT Munchers
+//
+// A "token-tree muncher" takes its input as a list of `tt`s, consumes ("munches") a few tokens from the front per
+// step, and recurses on the rest. Internal rules, by convention starting with `@`, carry state between steps.
+//
+// `rpn!` evaluates Reverse Polish Notation at compile time: numbers are pushed onto a stack, operators pop two
+// values and push the result. The stack is a `[...]` token tree, its top first.
+//
+//    rpn!(3 4 + 2 *)
+//    rpn!(@ [] 3 4 + 2 *)                 start with an empty stack
+//    rpn!(@ [3] 4 + 2 *)                  push 3
+//    rpn!(@ [4, 3] + 2 *)                 push 4
+//    rpn!(@ [(3 + 4)] 2 *)                pop 4 and 3, push (3 + 4)
+//    rpn!(@ [2, (3 + 4)] *)               push 2
+//    rpn!(@ [((3 + 4) * 2)])              pop, push
+//    ((3 + 4) * 2)                        the input is empty: the result is the only value on the stack
+macro_rules! rpn {
+  (@ [$result:expr]) => { $result };
+  (@ [$b:expr, $a:expr $(, $stack:expr)*] + $($rest:tt)*) => { rpn!(@ [($a + $b) $(, $stack)*] $($rest)*) };
+  (@ [$b:expr, $a:expr $(, $stack:expr)*] - $($rest:tt)*) => { rpn!(@ [($a - $b) $(, $stack)*] $($rest)*) };
+  (@ [$b:expr, $a:expr $(, $stack:expr)*] * $($rest:tt)*) => { rpn!(@ [($a * $b) $(, $stack)*] $($rest)*) };
+  (@ [$b:expr, $a:expr $(, $stack:expr)*] / $($rest:tt)*) => { rpn!(@ [($a / $b) $(, $stack)*] $($rest)*) };
+  (@ [$($stack:expr),*] $num:literal $($rest:tt)*) => { rpn!(@ [$num $(, $stack)*] $($rest)*) };
+  ($($tokens:tt)+) => { rpn!(@ [] $($tokens)+) };
+}
+
+pub fn tt_muncher_example() -> (i32, i32) {
+  (rpn!(3 4 + 2 *), rpn!(10 2 8 * + 3 -))
+}
+
+// -----------------------------------------------
+// ## A hashmap! Literal
+//
+// `vec![...]` builds a `Vec`; the standard library has no equivalent for `HashMap`. This one takes
+// `key => value` pairs, reserves space for all of them up front (using `count!`-style counting), and allows a
+// trailing comma. (An empty map gets a rule of its own, as the general rule would declare a needlessly `mut` map.)
+//
+// `#[macro_export]` makes it usable from other crates as `rust_wiki::hashmap!`. Inside an exported macro, paths
+// should start with `$crate` or `::std`, as the caller may not have imported what the macro uses.
+#[macro_export]
+macro_rules! hashmap {
+  (@count) => { 0usize };
+  (@count $head:tt $($tail:tt)*) => { 1usize + $crate::hashmap!(@count $($tail)*) };
+  () => { ::std::collections::HashMap::new() };
+  ($($key:expr => $value:expr),* $(,)?) => {
+    {
+      let mut map = ::std::collections::HashMap::with_capacity($crate::hashmap!(@count $(($key))*));
+      $(
+        map.insert($key, $value);
+      )*
+      map
+    }
+  };
+}
+
+pub fn hashmap_example() -> HashMap<&'static str, u32> {
+  hashmap! {
+    "one" => 1,
+    "two" => 2,
+    "three" => 3,
+  }
+}
+
+// -----------------------------------------------
+// ## Hygiene
+//
+// Variables introduced by a macro live in the macro's own "syntax context": they cannot clash with, or be seen by,
+// variables at the call site. Below, the macro's `x` and the caller's `x` are different variables.
+macro_rules! add_ten {
+  ($e:expr) => {{
+    let x = 10;
+    $e + x
+  }};
+}
+
+pub fn hygiene_example() -> i32 {
+  let x: i32 = 1;
+  // `$e` is `x`, which still refers to the caller's `x` (1) inside the expansion: 1 + 10.
+  add_ten!(x)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rules() {
+    assert_eq!(rules_example(), (String::from("Check out my macro!"), String::from("Look at this other macro: 7777")));
+  }
+
+  #[test]
+  fn test_repetition() {
+    assert_eq!(repetition_example(), vec!["a", "1", "c"]);
+    let empty: Vec<String> = strings![];
+    assert!(empty.is_empty());
+    assert_eq!((answer(), zero()), (42, 0));
+  }
+
+  #[test]
+  fn test_recursion() {
+    assert_eq!(recursion_example(), (9, 4));
+    assert_eq!(max!(1), 1);
+    assert_eq!(count!(), 0);
+  }
+
+  #[test]
+  fn test_tt_muncher() {
+    assert_eq!(tt_muncher_example(), (14, 23));
+    assert_eq!(rpn!(7), 7);
+    assert_eq!(rpn!(20 4 /), 5);
+  }
+
+  #[test]
+  fn test_hashmap() {
+    let map: HashMap<&str, u32> = hashmap_example();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["two"], 2);
+    assert!(map.capacity() >= 3);
+    let empty: HashMap<i32, i32> = hashmap! {};
+    assert!(empty.is_empty());
+    assert_eq!(hashmap!(@count a b c), 3);
+  }
+
+  #[test]
+  fn test_hygiene() {
+    assert_eq!(hygiene_example(), 11);
+  }
+}
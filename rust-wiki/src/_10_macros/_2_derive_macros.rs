@@ -0,0 +1,176 @@
+// -----------------------------------------------
+// # PROCEDURAL MACROS
+//
+// A procedural macro is a Rust function, run by the compiler, from a `TokenStream` to a `TokenStream`.
+// There are three kinds:
+//   - derive macros:          #[derive(Show)] struct S;     append items (usually an `impl`) after the item
+//   - attribute macros:       #[route(GET, "/")] fn f() {}  replace the item they are attached to
+//   - function-like macros:   show_expansion!(...)          replace the call, like `macro_rules!` macros
+//
+// They must be defined in their own crate, with `proc-macro = true` in its Cargo.toml, because the compiler builds
+// and runs them while compiling the crates that use them. The wiki's is in show_derive/, next to this crate.
+//
+// Most procedural macros parse their input with the `syn` crate and build their output with `quote`. show_derive
+// uses only the compiler's `proc_macro` crate instead, to show what those do: it walks the input's tokens by hand,
+// and writes the impl as a string of source code that it then parses into tokens.
+//
+// ## #[derive(Show)]
+//
+// Implements the `Show` trait of _3_datatypes_and_traits/_4_traits.rs: `show` formats the value like `Debug`
+// would, but with each field's `Display` (`#[show(skip)]` leaves a field out), and `AltShowType` is `String`.
+//
+//    #[derive(Show)]
+//    pub struct Login {
+//      pub username: String,
+//      #[show(skip)]
+//      pub password: String,
+//    }
+//
+// expands to:
+//
+//    impl ::rust_wiki::_3_datatypes_and_traits::_4_traits::Show for Login {
+//      type AltShowType = ::std::string::String;
+//      fn alt_show(s: ::std::string::String) -> ::std::string::String { s }
+//      fn show(&self) -> ::std::string::String {
+//        let Self { username: __0, .. } = self;
+//        ::std::format!("Login {{ username: {} }}", __0)
+//      }
+//    }
+//
+// `show_expansion!(item)` returns that generated code as a string, which the tests below compare exactly.
+// (Alternatively, `cargo expand` shows the expansion of any macro, but is not part of the standard toolchain.)
+
+pub use show_derive::{show_expansion, Show};
+
+// The derive macro `Show` and the trait `Show` live in different namespaces (macros and types), so importing both
+// under one name is fine: `#[derive(Show)]` finds the macro, `impl Show` and `x.show()` the trait.
+pub use crate::_3_datatypes_and_traits::_4_traits::Show;
+
+#[derive(Show)]
+pub struct Login {
+  pub username: String,
+  #[show(skip)]
+  pub password: String,
+}
+
+#[derive(Show)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+#[derive(Show)]
+pub struct Marker;
+
+// The same shape as the wiki's `Message` enum of _2_enums.rs.
+#[derive(Show)]
+pub enum Message {
+  Quit,
+  Move { x: i32, y: i32 },
+  Write(String),
+  ChangeColor(i32, i32, i32),
+}
+
+// Type parameters get a `Display` bound, lifetimes and const parameters are passed through.
+#[derive(Show)]
+pub struct Labelled<'a, T: Clone, const N: usize> {
+  pub label: &'a str,
+  pub value: T,
+}
+
+pub fn derive_example() -> Vec<String> {
+  let login: Login = Login { username: String::from("ferris"), password: String::from("hunter2") };
+  let messages: [Message; 2] = [Message::Move { x: 1, y: 2 }, Message::Write(String::from("hi"))];
+  let labelled: Labelled<f64, 2> = Labelled { label: "half", value: 0.5 };
+  let shown: Vec<String> = vec![
+    login.show(),
+    Rgb(255, 0, 128).show(),
+    Marker.show(),
+    messages[0].show(),
+    messages[1].show(),
+    labelled.show(),
+  ];
+  for line in &shown {
+    crate::debug!("{line}");
+  }
+  shown
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_derive_example() {
+    assert_eq!(
+      derive_example(),
+      vec![
+        "Login { username: ferris }",
+        "Rgb(255, 0, 128)",
+        "Marker",
+        "Move { x: 1, y: 2 }",
+        "Write(hi)",
+        "Labelled { label: half, value: 0.5 }",
+      ]
+    );
+  }
+
+  #[test]
+  fn test_derived_default_method_and_alt_show() {
+    assert_eq!(Message::Quit.show_twice(), "Quitworld");
+    assert_eq!(Marker::alt_show(String::from("as is")), "as is");
+    assert_eq!(Message::ChangeColor(0, 5, 7).show(), "ChangeColor(0, 5, 7)");
+  }
+
+  #[test]
+  fn test_expansion_of_named_struct_with_skip() {
+    let expansion: &str = show_expansion! {
+      pub struct Login {
+        pub username: String,
+        #[show(skip)]
+        pub password: String,
+      }
+    };
+    assert_eq!(
+      expansion,
+      "impl ::rust_wiki::_3_datatypes_and_traits::_4_traits::Show for Login { \
+         type AltShowType = ::std::string::String; \
+         fn alt_show(s: ::std::string::String) -> ::std::string::String { s } \
+         fn show(&self) -> ::std::string::String { \
+           let Self { username: __0, .. } = self; \
+           ::std::format!(\"Login {{ username: {} }}\", __0) } \
+       }"
+    );
+  }
+
+  #[test]
+  fn test_expansion_of_enum() {
+    let expansion: &str = show_expansion! {
+      enum Shape { Dot, Circle(f64), Rect { w: u32, h: u32 } }
+    };
+    assert!(expansion.contains("match self { \
+      Self::Dot => ::std::string::String::from(\"Dot\"), \
+      Self::Circle { 0: __0 } => ::std::format!(\"Circle({})\", __0), \
+      Self::Rect { w: __0, h: __1 } => ::std::format!(\"Rect {{ w: {}, h: {} }}\", __0, __1), }"));
+  }
+
+  #[test]
+  fn test_expansion_of_generics() {
+    let expansion: &str = show_expansion! {
+      struct Pair<'a, T: Clone, U = i32> { a: &'a T, b: U }
+    };
+    assert!(expansion.starts_with(
+      "impl<'a, T : Clone + ::std::fmt::Display, U: ::std::fmt::Display> \
+       ::rust_wiki::_3_datatypes_and_traits::_4_traits::Show for Pair<'a, T, U> {"
+    ));
+  }
+
+  #[test]
+  fn test_expansion_errors() {
+    assert_eq!(
+      show_expansion!(union U { a: u8 }),
+      "::core::compile_error!(\"#[derive(Show)] only supports structs and enums, not `union`\");"
+    );
+    assert_eq!(
+      show_expansion!(struct W<T> where T: Copy { t: T }),
+      "::core::compile_error!(\"#[derive(Show)] does not support `where` clauses\");"
+    );
+  }
+}
@@ -0,0 +1,204 @@
+// -----------------------------------------------
+// # COLLECTIONS
+//
+// The standard library's collections (`std::collections`, plus `Vec` in the prelude) all store their elements on
+// the heap, so they can grow and shrink at runtime:
+//   - sequences (here):                      `Vec<T>`, `VecDeque<T>`, `LinkedList<T>`.
+//   - maps and sets (_2_maps_and_sets.rs):    `HashMap<K, V>`, `HashSet<T>`, `BTreeMap<K, V>`, `BTreeSet<T>`.
+//   - priority queue (_3_binary_heap.rs):     `BinaryHeap<T>`.
+//   - custom keys (_4_custom_hash_and_ord.rs): implementing `Hash`, `Eq` and `Ord` by hand.
+//   - timings (_5_benchmarks.rs):             a benchmark harness comparing them on this machine.
+//
+// Which to use (from the std::collections documentation):
+//   - a list of values:                          `Vec` (by default, for almost everything).
+//   - a queue, pushed/popped at both ends:       `VecDeque`.
+//   - values looked up by key:                   `HashMap`, or `BTreeMap` to keep the keys sorted.
+//   - unique values:                             `HashSet` / `BTreeSet`.
+//   - always taking the largest (or smallest):   `BinaryHeap`.
+//   - `LinkedList`: almost never (see below).
+
+use std::collections::{LinkedList, VecDeque};
+
+// -----------------------------------------------
+// ## Vec<T>
+//
+// A `Vec` is { ptr, capacity, len }: a heap buffer with room for `capacity` elements, of which the first `len`
+// are initialised. Pushing past the capacity allocates a bigger buffer (roughly doubling it) and moves every
+// element there, so a push is O(1) amortised.
+//
+//    Stack                      Heap
+//    +-------------------+      +----+----+----+----+
+//    | v: { ptr, cap: 4, | ---> |  1 |  2 |  3 |    |
+//    |      len: 3 }     |      +----+----+----+----+
+//    +-------------------+
+//
+// Returns the distinct capacities seen while pushing `n` elements: each one is a reallocation.
+pub fn capacity_growth(n: usize) -> Vec<usize> {
+  let mut v: Vec<usize> = Vec::new();
+  let mut capacities: Vec<usize> = vec![v.capacity()];
+  for i in 0..n {
+    v.push(i);
+    if v.capacity() != *capacities.last().unwrap() {
+      capacities.push(v.capacity());
+    }
+  }
+  capacities
+}
+
+// When the final size is known, `Vec::with_capacity` allocates once.
+pub fn with_capacity_allocates_once(n: usize) -> bool {
+  let mut v: Vec<usize> = Vec::with_capacity(n);
+  let buffer: *const usize = v.as_ptr();
+  v.extend(0..n);
+  v.as_ptr() == buffer
+}
+
+// `rustlings/exercises/05_vecs`: doubling every element, in place and by mapping into a new `Vec`.
+pub fn vec_fill_and_map(input: &[i32]) -> (Vec<i32>, Vec<i32>) {
+  let mut in_place: Vec<i32> = input.to_vec();
+  for element in in_place.iter_mut() {
+    *element *= 2;
+  }
+  let mapped: Vec<i32> = input.iter().map(|element| element * 2).collect();
+  (in_place, mapped)
+}
+
+// Removing from the middle shifts every later element, so it is O(n). When the order does not matter,
+// `swap_remove` moves the last element into the hole instead, in O(1).
+pub fn remove_vs_swap_remove() -> (Vec<char>, Vec<char>) {
+  let mut removed: Vec<char> = vec!['a', 'b', 'c', 'd', 'e'];
+  removed.remove(1);
+  let mut swap_removed: Vec<char> = vec!['a', 'b', 'c', 'd', 'e'];
+  swap_removed.swap_remove(1);
+  (removed, swap_removed)
+}
+
+// Sorting, deduplicating and searching. `binary_search` needs a sorted slice, and returns `Err(index)` with the
+// position where the value could be inserted to keep it sorted.
+pub fn sort_dedup_search(mut values: Vec<i32>, needle: i32) -> (Vec<i32>, Result<usize, usize>) {
+  values.sort_unstable();
+  // `dedup` only removes *consecutive* duplicates, which sorting has gathered together.
+  values.dedup();
+  let found: Result<usize, usize> = values.binary_search(&needle);
+  (values, found)
+}
+
+// `retain` keeps the elements matching a predicate, in place and in one pass.
+pub fn retain_even(mut values: Vec<u32>) -> Vec<u32> {
+  values.retain(|value| value % 2 == 0);
+  values
+}
+
+// -----------------------------------------------
+// ## VecDeque<T>
+//
+// A double-ended queue: a ring buffer, where the elements may wrap around the end of the buffer. Pushing and
+// popping at either end is O(1) (a `Vec` is O(n) at the front, as every element must shift).
+//
+//    push_back(3), push_back(4), push_front(2), push_front(1) in a buffer of 6:
+//
+//    buffer: [ 3 | 4 | _ | _ | 1 | 2 ]
+//                              ^ head: the front of the queue
+// The elements are therefore not always contiguous: `as_slices` returns the two halves, and `make_contiguous`
+// rotates them into one slice.
+pub fn deque_both_ends() -> (Vec<i32>, usize) {
+  let mut deque: VecDeque<i32> = VecDeque::with_capacity(6);
+  deque.push_back(3);
+  deque.push_back(4);
+  deque.push_front(2);
+  deque.push_front(1);
+  // Only the number of slices is reported: how the elements wrap depends on the implementation.
+  let (front, back) = deque.as_slices();
+  let slices: usize = [front, back].iter().filter(|slice| !slice.is_empty()).count();
+  deque.make_contiguous();
+  (deque.into_iter().collect(), slices)
+}
+
+// A sliding window over a stream: push each new value at the back, and drop the oldest from the front.
+pub fn moving_average(values: &[f64], window: usize) -> Vec<f64> {
+  let mut current: VecDeque<f64> = VecDeque::with_capacity(window);
+  let mut sum: f64 = 0.0;
+  let mut averages: Vec<f64> = Vec::new();
+  for &value in values {
+    current.push_back(value);
+    sum += value;
+    if current.len() > window {
+      sum -= current.pop_front().unwrap();
+    }
+    if current.len() == window {
+      averages.push(sum / window as f64);
+    }
+  }
+  averages
+}
+
+// A round-robin scheduler: take the task at the front, run one slice of it, and put it back at the end if it is
+// not finished.
+pub fn round_robin(tasks: &[(&'static str, u32)], slice: u32) -> Vec<&'static str> {
+  let mut queue: VecDeque<(&'static str, u32)> = tasks.iter().copied().collect();
+  let mut finished: Vec<&'static str> = Vec::new();
+  while let Some((name, remaining)) = queue.pop_front() {
+    if remaining <= slice {
+      finished.push(name);
+    } else {
+      queue.push_back((name, remaining - slice));
+    }
+  }
+  finished
+}
+
+// -----------------------------------------------
+// ## LinkedList<T>
+//
+// A doubly linked list: every element is a separate heap allocation with pointers to its neighbours.
+// Its only O(1) operations that a `Vec`/`VecDeque` lacks are `append` (moving a whole list onto the end of another)
+// and `split_off` once the split point has been reached. Walking the list jumps around the heap, which is slow on
+// modern CPUs (see the iteration timings of _5_benchmarks.rs), so a `Vec` or `VecDeque` is nearly always faster.
+pub fn linked_list_splice() -> (Vec<i32>, Vec<i32>) {
+  let mut first: LinkedList<i32> = LinkedList::from([1, 2, 3]);
+  let mut second: LinkedList<i32> = LinkedList::from([4, 5, 6]);
+  // O(1): relinks the ends of the two lists; `second` is left empty.
+  first.append(&mut second);
+  assert!(second.is_empty());
+  // O(n): walks to the split point.
+  let tail: LinkedList<i32> = first.split_off(4);
+  (first.into_iter().collect(), tail.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_capacity_grows_geometrically() {
+    let capacities: Vec<usize> = capacity_growth(100);
+    assert_eq!(capacities[0], 0);
+    assert!(*capacities.last().unwrap() >= 100);
+    // Far fewer reallocations than pushes.
+    assert!(capacities.len() < 10);
+    assert!(with_capacity_allocates_once(1000));
+  }
+
+  #[test]
+  fn test_vec_operations() {
+    assert_eq!(vec_fill_and_map(&[1, 2, 3]), (vec![2, 4, 6], vec![2, 4, 6]));
+    assert_eq!(remove_vs_swap_remove(), (vec!['a', 'c', 'd', 'e'], vec!['a', 'e', 'c', 'd']));
+    assert_eq!(sort_dedup_search(vec![5, 1, 3, 1, 5], 3), (vec![1, 3, 5], Ok(1)));
+    assert_eq!(sort_dedup_search(vec![5, 1], 4).1, Err(1));
+    assert_eq!(retain_even(vec![1, 2, 3, 4]), vec![2, 4]);
+  }
+
+  #[test]
+  fn test_deque() {
+    let (elements, slices) = deque_both_ends();
+    assert_eq!(elements, vec![1, 2, 3, 4]);
+    assert!(slices == 1 || slices == 2);
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0, 4.0], 2), vec![1.5, 2.5, 3.5]);
+    assert_eq!(round_robin(&[("a", 3), ("b", 1), ("c", 2)], 1), vec!["b", "c", "a"]);
+  }
+
+  #[test]
+  fn test_linked_list() {
+    assert_eq!(linked_list_splice(), (vec![1, 2, 3, 4], vec![5, 6]));
+  }
+}
@@ -0,0 +1,232 @@
+// -----------------------------------------------
+// # MAPS AND SETS
+//
+// A map stores values by key; a set is a map without values (only the keys), used to keep unique elements.
+//   - `HashMap<K, V>` / `HashSet<T>`: hashes each key to find its bucket. O(1) on average, in no particular order.
+//                                     Needs `K: Hash + Eq`.
+//   - `BTreeMap<K, V>` / `BTreeSet<T>`: a B-tree, keeping the keys sorted. O(log n), iterates in key order and
+//                                     supports range queries. Needs `K: Ord`.
+//
+// `HashMap` uses SipHash seeded randomly per map, which resists an attacker choosing keys that all collide; the
+// iteration order therefore changes from run to run. Never rely on it (or sort the result, or use a `BTreeMap`).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+// -----------------------------------------------
+// ## The Entry API
+//
+// Updating a map often means "insert if missing, otherwise modify". Looking the key up twice (`contains_key` then
+// `insert`) hashes it twice; `entry(key)` looks it up once and returns an `Entry`, either `Occupied` or `Vacant`:
+//   - `.or_insert(v)`:          inserts `v` if vacant, then returns `&mut V` to the value.
+//   - `.or_insert_with(f)`:     same, but only calls `f` (e.g. an allocation) if vacant.
+//   - `.or_default()`:          inserts `V::default()` if vacant.
+//   - `.and_modify(f)`:         runs `f` on the value if occupied.
+
+// `rustlings/exercises/11_hashmaps/hashmaps2.rs`: fill the basket with the fruits it is missing, without touching
+// the ones already there.
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord)]
+pub enum Fruit {
+  Apple,
+  Banana,
+  Mango,
+  Lychee,
+  Pineapple,
+}
+
+pub fn fruit_basket(basket: &mut HashMap<Fruit, u32>) {
+  let fruit_kinds = [Fruit::Apple, Fruit::Banana, Fruit::Mango, Fruit::Lychee, Fruit::Pineapple];
+  for fruit in fruit_kinds {
+    basket.entry(fruit).or_insert(1);
+  }
+}
+
+// `rustlings/exercises/11_hashmaps/hashmaps3.rs`: a scores table built from lines of
+// "<team_1>,<team_2>,<team_1_goals>,<team_2_goals>".
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct TeamScores {
+  pub goals_scored: u8,
+  pub goals_conceded: u8,
+}
+
+// The keys borrow from `results`, so the table cannot outlive it.
+pub fn build_scores_table(results: &str) -> HashMap<&str, TeamScores> {
+  let mut scores: HashMap<&str, TeamScores> = HashMap::new();
+  for line in results.lines() {
+    let mut split_iterator = line.split(',');
+    let team_1_name: &str = split_iterator.next().unwrap();
+    let team_2_name: &str = split_iterator.next().unwrap();
+    let team_1_score: u8 = split_iterator.next().unwrap().parse().unwrap();
+    let team_2_score: u8 = split_iterator.next().unwrap().parse().unwrap();
+
+    let team_1: &mut TeamScores = scores.entry(team_1_name).or_default();
+    team_1.goals_scored += team_1_score;
+    team_1.goals_conceded += team_2_score;
+    // `team_1` is no longer used, so `scores` can be borrowed mutably again.
+    let team_2: &mut TeamScores = scores.entry(team_2_name).or_default();
+    team_2.goals_scored += team_2_score;
+    team_2.goals_conceded += team_1_score;
+  }
+  scores
+}
+
+// Counting: the classic use of `or_insert(0)`. A `BTreeMap` returns the counts sorted by word.
+pub fn word_frequencies(text: &str) -> BTreeMap<String, usize> {
+  let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+  for word in text.split_whitespace() {
+    *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+  }
+  counts
+}
+
+// Grouping: each key maps to a `Vec`, created empty on first use by `or_default()`.
+pub fn group_by_length<'a>(words: &[&'a str]) -> BTreeMap<usize, Vec<&'a str>> {
+  let mut groups: BTreeMap<usize, Vec<&'a str>> = BTreeMap::new();
+  for &word in words {
+    groups.entry(word.len()).or_default().push(word);
+  }
+  groups
+}
+
+// Matching on the `Entry` itself, to do something different in each case.
+pub fn insert_or_report(map: &mut HashMap<String, u32>, key: &str, value: u32) -> String {
+  use std::collections::hash_map::Entry;
+
+  match map.entry(key.to_string()) {
+    Entry::Occupied(occupied) => format!("{key} already maps to {}", occupied.get()),
+    Entry::Vacant(vacant) => {
+      vacant.insert(value);
+      format!("inserted {key}")
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Borrowed Lookups
+//
+// A `HashMap<String, V>` can be queried with a `&str`: `get` takes any `&Q` where `String: Borrow<Q>`, so no
+// `String` has to be allocated just to look a key up. (This requires `Q` to hash and compare exactly like the key,
+// see _4_custom_hash_and_ord.rs.)
+pub fn lookup_by_str(map: &HashMap<String, u32>, key: &str) -> Option<u32> {
+  map.get(key).copied()
+}
+
+// -----------------------------------------------
+// ## Sets
+//
+// Set operations return lazy iterators over references into the two sets.
+pub fn set_operations(a: &[i32], b: &[i32]) -> [Vec<i32>; 4] {
+  let a: BTreeSet<i32> = a.iter().copied().collect();
+  let b: BTreeSet<i32> = b.iter().copied().collect();
+  [
+    a.union(&b).copied().collect(),
+    a.intersection(&b).copied().collect(),
+    a.difference(&b).copied().collect(),
+    a.symmetric_difference(&b).copied().collect(),
+  ]
+}
+
+// `insert` returns `false` if the value was already present: a one-pass duplicate finder.
+pub fn first_duplicate<T: std::hash::Hash + Eq + Copy>(values: &[T]) -> Option<T> {
+  let mut seen: HashSet<T> = HashSet::new();
+  values.iter().copied().find(|&value| !seen.insert(value))
+}
+
+// -----------------------------------------------
+// ## Ordered Maps: Ranges
+//
+// A `BTreeMap` keeps its keys sorted, so it can answer "everything between a and b" or "the closest key below x",
+// which a `HashMap` can only do by scanning every entry.
+pub struct Timeline {
+  events: BTreeMap<u32, &'static str>,
+}
+
+impl Timeline {
+  pub fn new(events: &[(u32, &'static str)]) -> Timeline {
+    Timeline { events: events.iter().copied().collect() }
+  }
+
+  // Events with `from <= time < to`, in order.
+  pub fn between(&self, from: u32, to: u32) -> Vec<&'static str> {
+    self.events.range(from..to).map(|(_, &event)| event).collect()
+  }
+
+  // The last event at or before `time`.
+  pub fn latest_at(&self, time: u32) -> Option<&'static str> {
+    self.events.range(..=time).next_back().map(|(_, &event)| event)
+  }
+
+  pub fn first_and_last(&self) -> Option<(&'static str, &'static str)> {
+    Some((*self.events.first_key_value()?.1, *self.events.last_key_value()?.1))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RESULTS: &str = "England,France,4,2
+France,Italy,3,1
+Poland,Spain,2,0
+Germany,England,2,1
+England,Spain,1,0";
+
+  #[test]
+  fn test_fruit_basket() {
+    let mut basket: HashMap<Fruit, u32> = HashMap::from([(Fruit::Apple, 4), (Fruit::Mango, 2), (Fruit::Lychee, 5)]);
+    fruit_basket(&mut basket);
+    assert_eq!(basket[&Fruit::Apple], 4);
+    assert_eq!(basket[&Fruit::Mango], 2);
+    assert_eq!(basket[&Fruit::Lychee], 5);
+    assert_eq!(basket.len(), 5);
+    assert!(basket.values().sum::<u32>() > 11);
+  }
+
+  #[test]
+  fn test_build_scores_table() {
+    let scores: HashMap<&str, TeamScores> = build_scores_table(RESULTS);
+    let mut teams: Vec<&str> = scores.keys().copied().collect();
+    teams.sort_unstable();
+    assert_eq!(teams, vec!["England", "France", "Germany", "Italy", "Poland", "Spain"]);
+    assert_eq!(scores["England"], TeamScores { goals_scored: 6, goals_conceded: 4 });
+    assert_eq!(scores["Spain"], TeamScores { goals_scored: 0, goals_conceded: 3 });
+  }
+
+  #[test]
+  fn test_counting_and_grouping() {
+    let counts: BTreeMap<String, usize> = word_frequencies("the cat saw The dog");
+    assert_eq!(counts.into_iter().collect::<Vec<_>>()[..2], [("cat".to_string(), 1), ("dog".to_string(), 1)]);
+    assert_eq!(word_frequencies("a A a")["a"], 3);
+    let groups: BTreeMap<usize, Vec<&str>> = group_by_length(&["hi", "bye", "yo", "hey"]);
+    assert_eq!(groups[&2], vec!["hi", "yo"]);
+    assert_eq!(groups[&3], vec!["bye", "hey"]);
+  }
+
+  #[test]
+  fn test_entry_match_and_borrowed_lookup() {
+    let mut map: HashMap<String, u32> = HashMap::new();
+    assert_eq!(insert_or_report(&mut map, "a", 1), "inserted a");
+    assert_eq!(insert_or_report(&mut map, "a", 2), "a already maps to 1");
+    assert_eq!(lookup_by_str(&map, "a"), Some(1));
+    assert_eq!(lookup_by_str(&map, "b"), None);
+  }
+
+  #[test]
+  fn test_sets() {
+    let [union, intersection, difference, symmetric] = set_operations(&[1, 2, 3], &[2, 3, 4]);
+    assert_eq!(union, vec![1, 2, 3, 4]);
+    assert_eq!(intersection, vec![2, 3]);
+    assert_eq!(difference, vec![1]);
+    assert_eq!(symmetric, vec![1, 4]);
+    assert_eq!(first_duplicate(&[3, 1, 4, 1, 5]), Some(1));
+    assert_eq!(first_duplicate(&["a", "b"]), None);
+  }
+
+  #[test]
+  fn test_ranges() {
+    let timeline: Timeline = Timeline::new(&[(30, "lunch"), (10, "wake up"), (50, "sleep"), (20, "work")]);
+    assert_eq!(timeline.between(15, 50), vec!["work", "lunch"]);
+    assert_eq!(timeline.latest_at(29), Some("work"));
+    assert_eq!(timeline.latest_at(5), None);
+    assert_eq!(timeline.first_and_last(), Some(("wake up", "sleep")));
+  }
+}
@@ -0,0 +1,121 @@
+// -----------------------------------------------
+// # BINARY HEAP
+//
+// A `BinaryHeap<T>` is a priority queue: `pop` always returns the largest element (by `Ord`).
+// It is stored in a `Vec`, as a complete binary tree where every parent is >= its children: element `i` has its
+// children at `2i + 1` and `2i + 2`.
+//
+//                9               [ 9, 7, 8, 3, 5, 1 ]
+//              /   \
+//             7     8
+//            / \   /
+//           3   5 1
+//
+//   - `push`: append at the end, then swap upwards while bigger than the parent. O(log n).
+//   - `pop`:  take the root, move the last element there, then swap it downwards. O(log n).
+//   - `peek`: the root. O(1).
+// Iterating (`iter`, `into_vec`) is in the heap's internal order, *not* sorted; `into_sorted_vec` sorts.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Popping everything returns the elements from largest to smallest (a heap sort).
+pub fn drain_in_order(values: &[i32]) -> Vec<i32> {
+  let mut heap: BinaryHeap<i32> = values.iter().copied().collect();
+  let mut popped: Vec<i32> = Vec::with_capacity(heap.len());
+  while let Some(largest) = heap.pop() {
+    popped.push(largest);
+  }
+  popped
+}
+
+// The heap is a max-heap only. `Reverse<T>` inverts `T`'s order, turning it into a min-heap.
+pub fn smallest_first(values: &[i32]) -> Vec<i32> {
+  let mut heap: BinaryHeap<Reverse<i32>> = values.iter().map(|&value| Reverse(value)).collect();
+  let mut popped: Vec<i32> = Vec::with_capacity(heap.len());
+  while let Some(Reverse(smallest)) = heap.pop() {
+    popped.push(smallest);
+  }
+  popped
+}
+
+// The `k` largest values of a stream, in O(n log k): keep a min-heap of the best `k` so far, and evict its
+// smallest whenever it grows past `k`.
+pub fn top_k(values: impl IntoIterator<Item = u32>, k: usize) -> Vec<u32> {
+  let mut best: BinaryHeap<Reverse<u32>> = BinaryHeap::with_capacity(k + 1);
+  for value in values {
+    best.push(Reverse(value));
+    if best.len() > k {
+      best.pop();
+    }
+  }
+  // `into_sorted_vec` sorts ascending by `Reverse`, i.e. descending by value.
+  best.into_sorted_vec().into_iter().map(|Reverse(value)| value).collect()
+}
+
+// -----------------------------------------------
+// ## Example: Shortest Paths
+//
+// Dijkstra's algorithm explores nodes from the closest to the furthest, which is exactly what a min-heap of
+// (distance, node) gives. Tuples compare field by field, so the heap is ordered by distance first.
+pub fn shortest_paths(edges: &[(usize, usize, u32)], nodes: usize, start: usize) -> Vec<Option<u32>> {
+  let mut neighbours: Vec<Vec<(usize, u32)>> = vec![Vec::new(); nodes];
+  for &(from, to, cost) in edges {
+    neighbours[from].push((to, cost));
+  }
+
+  let mut distances: Vec<Option<u32>> = vec![None; nodes];
+  let mut frontier: BinaryHeap<Reverse<(u32, usize)>> = BinaryHeap::new();
+  distances[start] = Some(0);
+  frontier.push(Reverse((0, start)));
+
+  while let Some(Reverse((distance, node))) = frontier.pop() {
+    // A node can be pushed several times, with shorter and shorter distances; only its first pop counts.
+    if distances[node].is_some_and(|best| distance > best) {
+      continue;
+    }
+    for &(next, cost) in &neighbours[node] {
+      let through_node: u32 = distance + cost;
+      if distances[next].is_none_or(|best| through_node < best) {
+        distances[next] = Some(through_node);
+        frontier.push(Reverse((through_node, next)));
+      }
+    }
+  }
+  distances
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_max_and_min_heaps() {
+    assert_eq!(drain_in_order(&[3, 9, 1, 7]), vec![9, 7, 3, 1]);
+    assert_eq!(smallest_first(&[3, 9, 1, 7]), vec![1, 3, 7, 9]);
+    let heap: BinaryHeap<i32> = BinaryHeap::from(vec![1, 5, 3]);
+    assert_eq!(heap.peek(), Some(&5));
+    assert_eq!(heap.into_sorted_vec(), vec![1, 3, 5]);
+  }
+
+  #[test]
+  fn test_heap_property() {
+    let heap: Vec<i32> = BinaryHeap::from(vec![3, 1, 8, 5, 9, 7]).into_vec();
+    for i in 1..heap.len() {
+      assert!(heap[(i - 1) / 2] >= heap[i]);
+    }
+  }
+
+  #[test]
+  fn test_top_k() {
+    assert_eq!(top_k([5, 1, 9, 3, 7, 9], 3), vec![9, 9, 7]);
+    assert_eq!(top_k([2, 1], 5), vec![2, 1]);
+  }
+
+  #[test]
+  fn test_shortest_paths() {
+    // 0 -> 1 costs 4 directly, but 1 via 2 (0 -> 2 -> 1) costs 3. Node 4 is unreachable.
+    let edges = [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)];
+    assert_eq!(shortest_paths(&edges, 5, 0), vec![Some(0), Some(3), Some(1), Some(4), None]);
+  }
+}
@@ -0,0 +1,253 @@
+// -----------------------------------------------
+// # CUSTOM KEYS: Hash, Eq AND Ord
+//
+// `#[derive(Hash, PartialEq, Eq, PartialOrd, Ord)]` compares and hashes every field, in declaration order.
+// To key a collection on something else (ignoring a field, ignoring case, a different ordering), implement the
+// traits by hand, keeping them consistent with each other:
+//   - `Hash` and `Eq`:  a == b  must imply  hash(a) == hash(b). Otherwise equal keys land in different buckets, and
+//                       a `HashMap` holds "duplicate" keys or fails to find them.
+//   - `Ord` and `Eq`:   a.cmp(b) == Equal  exactly when  a == b. Otherwise a `BTreeMap` and `binary_search`
+//                       disagree with `==` about which keys exist.
+//   - `PartialOrd`:     must agree with `Ord`; implement it as `Some(self.cmp(other))`.
+// Breaking these rules is not undefined behaviour, but the collections then behave unpredictably (lost entries,
+// wrong results, panics), so it is a logic error.
+//
+// A key must also not change while it is in a collection (through a `Cell`, say), since its position depends on it.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+// -----------------------------------------------
+// ## Hash: Ignoring Case
+//
+// Header names are case-insensitive. `Eq` ignores case, so `Hash` must too: both look only at the lowercased bytes.
+#[derive(Debug, Clone)]
+pub struct HeaderName(pub String);
+
+impl PartialEq for HeaderName {
+  fn eq(&self, other: &Self) -> bool {
+    self.0.eq_ignore_ascii_case(&other.0)
+  }
+}
+
+impl Eq for HeaderName {}
+
+impl Hash for HeaderName {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    for byte in self.0.bytes() {
+      state.write_u8(byte.to_ascii_lowercase());
+    }
+    // Like `str`'s own `Hash`, mark the end, so that ("ab", "c") and ("a", "bc") hash differently in a tuple.
+    state.write_u8(0xff);
+  }
+}
+
+pub fn headers_example() -> (usize, Option<&'static str>) {
+  let mut headers: HashMap<HeaderName, &'static str> = HashMap::new();
+  headers.insert(HeaderName("Content-Type".to_string()), "text/html");
+  // The same key, so this replaces the first value.
+  headers.insert(HeaderName("content-type".to_string()), "application/json");
+  let found: Option<&'static str> = headers.get(&HeaderName("CONTENT-TYPE".to_string())).copied();
+  (headers.len(), found)
+}
+
+// With the derived (case-sensitive) `Hash` but a case-insensitive `Eq`, the rule above is broken: the two spellings
+// are equal, but usually hash to different buckets, so the set keeps both. Clippy denies this combination by default.
+#[allow(clippy::derived_hash_with_manual_eq)]
+#[derive(Debug, Hash)]
+pub struct BrokenHeaderName(pub String);
+
+impl PartialEq for BrokenHeaderName {
+  fn eq(&self, other: &Self) -> bool {
+    self.0.eq_ignore_ascii_case(&other.0)
+  }
+}
+
+impl Eq for BrokenHeaderName {}
+
+pub fn broken_hash_example() -> usize {
+  let set: HashSet<BrokenHeaderName> =
+    ["Accept", "ACCEPT", "accept"].iter().map(|name| BrokenHeaderName(name.to_string())).collect();
+  set.len()
+}
+
+// -----------------------------------------------
+// ## Eq and Hash: Ignoring a Field
+//
+// A user is identified by its id alone; the cached display name is not part of its identity.
+#[derive(Debug, Clone)]
+pub struct User {
+  pub id: u32,
+  pub display_name: String,
+}
+
+impl PartialEq for User {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id
+  }
+}
+
+impl Eq for User {}
+
+impl Hash for User {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.id.hash(state);
+  }
+}
+
+// -----------------------------------------------
+// ## Ord: Semantic Versions
+//
+// The derived `Ord` would compare the fields in order, which is right for (major, minor, patch), but a
+// pre-release ("1.0.0-beta") must sort *before* its release ("1.0.0"), while `None < Some(_)` would put it after.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+  pub major: u32,
+  pub minor: u32,
+  pub patch: u32,
+  pub pre_release: Option<String>,
+}
+
+impl Version {
+  pub fn parse(s: &str) -> Option<Version> {
+    let (numbers, pre_release) = match s.split_once('-') {
+      Some((numbers, pre_release)) => (numbers, Some(pre_release.to_string())),
+      None => (s, None),
+    };
+    let mut parts = numbers.split('.').map(|part| part.parse::<u32>().ok());
+    let version = Version { major: parts.next()??, minor: parts.next()??, patch: parts.next()??, pre_release };
+    parts.next().is_none().then_some(version)
+  }
+}
+
+impl Ord for Version {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch)).then_with(|| {
+      match (&self.pre_release, &other.pre_release) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(a), Some(b)) => a.cmp(b),
+      }
+    })
+  }
+}
+
+impl PartialOrd for Version {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+pub fn sorted_versions(versions: &[&str]) -> Vec<String> {
+  let sorted: BTreeSet<Version> = versions.iter().filter_map(|version| Version::parse(version)).collect();
+  sorted
+    .into_iter()
+    .map(|version| match version.pre_release {
+      Some(pre_release) => format!("{}.{}.{}-{pre_release}", version.major, version.minor, version.patch),
+      None => format!("{}.{}.{}", version.major, version.minor, version.patch),
+    })
+    .collect()
+}
+
+// -----------------------------------------------
+// ## Ord: Priorities in a Heap
+//
+// A job queue pops the most urgent job first, and among equally urgent jobs, the oldest (lowest sequence number).
+// `Ord` ignores the name, so `PartialEq` must too: two jobs are equal exactly when they compare `Equal`, i.e. have the
+// same priority and sequence number. (A derived `PartialEq` would also compare the names.)
+#[derive(Debug)]
+pub struct Job {
+  pub priority: u8,
+  pub sequence: u64,
+  pub name: &'static str,
+}
+
+impl PartialEq for Job {
+  fn eq(&self, other: &Self) -> bool {
+    (self.priority, self.sequence) == (other.priority, other.sequence)
+  }
+}
+
+impl Eq for Job {}
+
+impl Ord for Job {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // Higher priority is greater; *lower* sequence is greater, hence the reversed comparison.
+    self.priority.cmp(&other.priority).then_with(|| other.sequence.cmp(&self.sequence))
+  }
+}
+
+impl PartialOrd for Job {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+pub fn job_queue_example() -> Vec<&'static str> {
+  let jobs = [(1, "log rotation"), (5, "page the on-call"), (3, "send email"), (5, "restart server")];
+  let mut queue: BinaryHeap<Job> =
+    jobs.iter().enumerate().map(|(i, &(priority, name))| Job { priority, sequence: i as u64, name }).collect();
+  let mut order: Vec<&'static str> = Vec::new();
+  while let Some(job) = queue.pop() {
+    order.push(job.name);
+  }
+  order
+}
+
+// `sort_by_key` and friends order by a derived key without implementing `Ord` at all, which is usually enough when
+// the ordering is only needed in one place.
+pub fn sort_by_length_then_alphabetically(mut words: Vec<&str>) -> Vec<&str> {
+  words.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+  words
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_case_insensitive_keys() {
+    assert_eq!(headers_example(), (1, Some("application/json")));
+  }
+
+  #[test]
+  fn test_inconsistent_hash_keeps_equal_keys() {
+    // All three are `==`, so a correct set would hold one. (Two could collide by chance, but not all three here.)
+    assert!(broken_hash_example() > 1);
+  }
+
+  #[test]
+  fn test_identity_ignores_display_name() {
+    let mut users: HashSet<User> = HashSet::new();
+    assert!(users.insert(User { id: 1, display_name: "Ferris".to_string() }));
+    assert!(!users.insert(User { id: 1, display_name: "ferris the crab".to_string() }));
+    assert_eq!(users.len(), 1);
+  }
+
+  #[test]
+  fn test_versions() {
+    assert_eq!(Version::parse("1.2"), None);
+    assert_eq!(Version::parse("1.2.x"), None);
+    assert_eq!(
+      sorted_versions(&["1.10.0", "1.2.0", "1.0.0", "1.0.0-beta", "1.0.0-alpha", "0.9.9", "1.2.0"]),
+      vec!["0.9.9", "1.0.0-alpha", "1.0.0-beta", "1.0.0", "1.2.0", "1.10.0"]
+    );
+  }
+
+  #[test]
+  fn test_job_queue() {
+    assert_eq!(job_queue_example(), vec!["page the on-call", "restart server", "send email", "log rotation"]);
+    // `==` agrees with `cmp`: the name takes part in neither.
+    let (a, b) = (Job { priority: 2, sequence: 7, name: "a" }, Job { priority: 2, sequence: 7, name: "b" });
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert_eq!(a, b);
+    assert_ne!(a, Job { priority: 2, sequence: 8, name: "a" });
+  }
+
+  #[test]
+  fn test_sort_by() {
+    assert_eq!(sort_by_length_then_alphabetically(vec!["pear", "fig", "apple", "kiwi"]), vec!["fig", "kiwi", "pear", "apple"]);
+  }
+}
@@ -0,0 +1,288 @@
+// -----------------------------------------------
+// # BENCHMARKING THE COLLECTIONS
+//
+// Big-O notation says how an operation scales, not how fast it is: a `Vec` scan is O(n) and a `HashMap` lookup
+// O(1), yet for a few dozen elements the scan usually wins. Memory layout matters as much as the algorithm: a `Vec`
+// is one contiguous buffer that the CPU cache and prefetcher love, a `LinkedList` is a pointer chase between
+// scattered allocations. The only way to know is to measure, on the actual machine.
+//
+// This is a minimal harness with no dependencies (a real project would use `criterion`, or the nightly `#[bench]`):
+//   1. Run the operation once to warm up (fault in memory, fill the caches, train the branch predictor).
+//   2. Time it `runs` times with `Instant`, and keep the best and the median: the best is the least disturbed by
+//      other processes, the median shows what is typical.
+//   3. Pass inputs and results through `std::hint::black_box`, which stops the optimiser from computing them at
+//      compile time or deleting work whose result is unused.
+//
+// Timings are only meaningful for an optimised build:
+// > cargo run --release --example collections_bench -- 100000
+//
+// The results are printed as a table, per operation, relative to the fastest collection:
+//
+//    insert 100000 elements
+//      Vec         0.312ms      3.1ns/element   1.00x
+//      VecDeque    0.401ms      4.0ns/element   1.29x
+//      ...
+
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// -----------------------------------------------
+// ## Measuring
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+  pub operation: &'static str,
+  pub collection: &'static str,
+  pub elements: usize,
+  pub best: Duration,
+  pub median: Duration,
+}
+
+impl Measurement {
+  pub fn nanos_per_element(&self) -> f64 {
+    self.best.as_secs_f64() * 1e9 / self.elements.max(1) as f64
+  }
+}
+
+// Times `routine` `runs` times (after one warm-up run) and returns the (best, median) duration.
+// `setup` runs before each timed run, outside the timing, to build the routine's input.
+pub fn measure<I, R>(runs: usize, mut setup: impl FnMut() -> I, mut routine: impl FnMut(I) -> R) -> (Duration, Duration) {
+  assert!(runs > 0, "at least one run is needed");
+  black_box(routine(black_box(setup())));
+  let mut timings: Vec<Duration> = (0..runs)
+    .map(|_| {
+      let input: I = black_box(setup());
+      let start: Instant = Instant::now();
+      let output: R = routine(input);
+      let elapsed: Duration = start.elapsed();
+      // Dropped after the timing, so deallocation is not measured.
+      black_box(output);
+      elapsed
+    })
+    .collect();
+  timings.sort_unstable();
+  (timings[0], timings[timings.len() / 2])
+}
+
+// Deterministic pseudo-random keys (xorshift64), so that every collection gets the same input, in an order that
+// is not already sorted.
+pub fn shuffled_keys(count: usize, seed: u64) -> Vec<u64> {
+  let mut state: u64 = seed.max(1);
+  (0..count)
+    .map(|_| {
+      state ^= state << 13;
+      state ^= state >> 7;
+      state ^= state << 17;
+      state
+    })
+    .collect()
+}
+
+// -----------------------------------------------
+// ## The Benchmarks
+//
+// Each benchmark is a (collection name, closure) pair; `bench_group` measures them all for one operation.
+type Benchmark<'a> = (&'static str, Box<dyn FnMut() -> u64 + 'a>);
+
+fn bench_group(operation: &'static str, elements: usize, runs: usize, benchmarks: Vec<Benchmark<'_>>) -> Vec<Measurement> {
+  benchmarks
+    .into_iter()
+    .map(|(collection, mut routine)| {
+      let (best, median) = measure(runs, || (), |()| routine());
+      Measurement { operation, collection, elements, best, median }
+    })
+    .collect()
+}
+
+// Building each collection from `keys`, one element at a time. Each routine returns the collection it built, so
+// `measure` drops it after the timing: freeing it is not part of inserting. (A `Benchmark` returns a `u64`, which
+// would free the collection inside the routine, so these call `measure` directly, once per collection type.)
+fn insert_benchmark<C>(collection: &'static str, keys: &[u64], runs: usize, mut build: impl FnMut() -> C) -> Measurement {
+  let (best, median) = measure(runs, || (), |()| build());
+  Measurement { operation: "insert", collection, elements: keys.len(), best, median }
+}
+
+pub fn insert_benchmarks(keys: &[u64], runs: usize) -> Vec<Measurement> {
+  vec![
+    insert_benchmark("Vec", keys, runs, || {
+      let mut collection: Vec<u64> = Vec::new();
+      keys.iter().for_each(|&key| collection.push(key));
+      collection
+    }),
+    insert_benchmark("VecDeque", keys, runs, || {
+      let mut collection: VecDeque<u64> = VecDeque::new();
+      keys.iter().for_each(|&key| collection.push_back(key));
+      collection
+    }),
+    insert_benchmark("LinkedList", keys, runs, || {
+      let mut collection: LinkedList<u64> = LinkedList::new();
+      keys.iter().for_each(|&key| collection.push_back(key));
+      collection
+    }),
+    insert_benchmark("HashMap", keys, runs, || {
+      let mut collection: HashMap<u64, u64> = HashMap::new();
+      keys.iter().for_each(|&key| {
+        collection.insert(key, key);
+      });
+      collection
+    }),
+    insert_benchmark("BTreeMap", keys, runs, || {
+      let mut collection: BTreeMap<u64, u64> = BTreeMap::new();
+      keys.iter().for_each(|&key| {
+        collection.insert(key, key);
+      });
+      collection
+    }),
+    insert_benchmark("BinaryHeap", keys, runs, || {
+      let mut collection: BinaryHeap<u64> = BinaryHeap::new();
+      keys.iter().for_each(|&key| collection.push(key));
+      collection
+    }),
+  ]
+}
+
+// Looking every key up once, in a different order than inserted. Sequences have no O(1) lookup: a sorted `Vec`
+// with `binary_search` is the fair comparison (a linear scan per key would be O(n²)).
+pub fn lookup_benchmarks(keys: &[u64], runs: usize) -> Vec<Measurement> {
+  let mut sorted: Vec<u64> = keys.to_vec();
+  sorted.sort_unstable();
+  let hash_set: HashSet<u64> = keys.iter().copied().collect();
+  let btree_set: BTreeSet<u64> = keys.iter().copied().collect();
+  let mut queries: Vec<u64> = keys.to_vec();
+  queries.reverse();
+  let queries: &[u64] = &queries;
+
+  let benchmarks: Vec<Benchmark<'_>> = vec![
+    ("Vec (sorted)", Box::new(|| queries.iter().filter(|key| sorted.binary_search(key).is_ok()).count() as u64)),
+    ("HashSet", Box::new(|| queries.iter().filter(|key| hash_set.contains(key)).count() as u64)),
+    ("BTreeSet", Box::new(|| queries.iter().filter(|key| btree_set.contains(key)).count() as u64)),
+  ];
+  bench_group("lookup", keys.len(), runs, benchmarks)
+}
+
+// Summing every element. All are O(n); the differences come from memory layout alone.
+pub fn iterate_benchmarks(keys: &[u64], runs: usize) -> Vec<Measurement> {
+  let vec: Vec<u64> = keys.to_vec();
+  let deque: VecDeque<u64> = keys.iter().copied().collect();
+  let list: LinkedList<u64> = keys.iter().copied().collect();
+  let hash_map: HashMap<u64, u64> = keys.iter().map(|&key| (key, key)).collect();
+  let btree_map: BTreeMap<u64, u64> = keys.iter().map(|&key| (key, key)).collect();
+  let heap: BinaryHeap<u64> = keys.iter().copied().collect();
+
+  fn sum<'a>(values: impl Iterator<Item = &'a u64>) -> u64 {
+    values.fold(0u64, |total, &value| total.wrapping_add(value))
+  }
+
+  let benchmarks: Vec<Benchmark<'_>> = vec![
+    ("Vec", Box::new(|| sum(vec.iter()))),
+    ("VecDeque", Box::new(|| sum(deque.iter()))),
+    ("LinkedList", Box::new(|| sum(list.iter()))),
+    ("HashMap", Box::new(|| sum(hash_map.values()))),
+    ("BTreeMap", Box::new(|| sum(btree_map.values()))),
+    ("BinaryHeap", Box::new(|| sum(heap.iter()))),
+  ];
+  bench_group("iterate", keys.len(), runs, benchmarks)
+}
+
+pub fn run_benchmarks(elements: usize, runs: usize) -> Vec<Measurement> {
+  let keys: Vec<u64> = shuffled_keys(elements, 0x5eed);
+  let mut measurements: Vec<Measurement> = insert_benchmarks(&keys, runs);
+  measurements.extend(lookup_benchmarks(&keys, runs));
+  measurements.extend(iterate_benchmarks(&keys, runs));
+  measurements
+}
+
+// -----------------------------------------------
+// ## Reporting
+
+pub fn render_report(measurements: &[Measurement]) -> String {
+  let mut report: String = String::new();
+  let mut operations: Vec<&'static str> = measurements.iter().map(|m| m.operation).collect();
+  operations.dedup();
+  for operation in operations {
+    let group: Vec<&Measurement> = measurements.iter().filter(|m| m.operation == operation).collect();
+    let fastest: Duration = group.iter().map(|m| m.best).min().unwrap_or_default().max(Duration::from_nanos(1));
+    writeln!(report, "{operation} {} elements", group[0].elements).unwrap();
+    for m in group {
+      writeln!(
+        report,
+        "  {:<14}{:>10.3}ms {:>10.1}ns/element {:>8.2}x   (median {:.3}ms)",
+        m.collection,
+        m.best.as_secs_f64() * 1000.0,
+        m.nanos_per_element(),
+        m.best.as_secs_f64() / fastest.as_secs_f64(),
+        m.median.as_secs_f64() * 1000.0,
+      )
+      .unwrap();
+    }
+  }
+  report
+}
+
+pub fn benchmarks_example(elements: usize, runs: usize) {
+  if cfg!(debug_assertions) {
+    crate::warn!("debug build: run with --release for meaningful timings");
+  }
+  print!("{}", render_report(&run_benchmarks(elements, runs)));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Timings vary between machines and runs, so the tests only check the harness itself, not which collection wins.
+
+  #[test]
+  fn test_measure_runs_setup_before_each_run() {
+    let mut setups: usize = 0;
+    let mut routines: usize = 0;
+    let (best, median) = measure(
+      5,
+      || setups += 1,
+      |()| {
+        routines += 1;
+        std::thread::sleep(Duration::from_millis(1));
+      },
+    );
+    // One warm-up run, then five timed ones.
+    assert_eq!((setups, routines), (6, 6));
+    assert!(best >= Duration::from_millis(1));
+    assert!(best <= median);
+  }
+
+  #[test]
+  fn test_shuffled_keys_are_deterministic_and_distinct() {
+    let keys: Vec<u64> = shuffled_keys(1000, 42);
+    assert_eq!(keys, shuffled_keys(1000, 42));
+    assert_eq!(keys.iter().collect::<HashSet<_>>().len(), 1000);
+    assert!(!keys.is_sorted());
+  }
+
+  #[test]
+  fn test_every_collection_is_measured() {
+    let measurements: Vec<Measurement> = run_benchmarks(200, 2);
+    let names: Vec<(&str, &str)> = measurements.iter().map(|m| (m.operation, m.collection)).collect();
+    assert_eq!(names.len(), 6 + 3 + 6);
+    assert!(names.contains(&("lookup", "HashSet")));
+    assert!(names.contains(&("iterate", "LinkedList")));
+    assert!(measurements.iter().all(|m| m.elements == 200 && m.best <= m.median));
+  }
+
+  #[test]
+  fn test_report_is_relative_to_the_fastest() {
+    let measurement = |collection, micros| Measurement {
+      operation: "insert",
+      collection,
+      elements: 1000,
+      best: Duration::from_micros(micros),
+      median: Duration::from_micros(micros),
+    };
+    let report: String = render_report(&[measurement("Vec", 100), measurement("LinkedList", 250)]);
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "insert 1000 elements");
+    assert!(lines[1].starts_with("  Vec") && lines[1].contains("100.0ns/element") && lines[1].contains("1.00x"));
+    assert!(lines[2].starts_with("  LinkedList") && lines[2].contains("2.50x"));
+  }
+}
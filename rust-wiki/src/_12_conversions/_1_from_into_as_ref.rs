@@ -0,0 +1,125 @@
+// -----------------------------------------------
+// # CONVERSIONS
+//
+// Rust never converts between types implicitly (apart from deref coercion, e.g. `&String` to `&str`). Every
+// conversion is a call to one of a handful of traits, which the rest of this chapter builds on:
+//
+//   trait               signature                         when
+//   ------------------  --------------------------------  ------------------------------------------------------
+//   From<T> / Into<U>   T -> U                            infallible, lossless conversions (here)
+//   AsRef<T> / AsMut<T> &self -> &T                       cheap reference-to-reference conversions (here)
+//   `as`                (an operator, not a trait)        primitive casts, which may silently lose data
+//                                                         (_2_casts.rs)
+//   FromStr             &str -> Result<Self, Err>         parsing text, via `str::parse` (_3_from_str.rs)
+//   TryFrom<T> / TryInto<U>  T -> Result<U, Error>        fallible conversions (_4_try_from.rs)
+//
+// The conversions of rustlings/exercises/23_conversions are solved along the way.
+
+// -----------------------------------------------
+// ## From and Into
+//
+// `From<T> for U` says how to build a `U` from a `T`. Implementing it also provides `Into<U> for T` for free, via
+// a blanket impl in the standard library:
+//
+//    impl<T, U: From<T>> Into<U> for T {
+//      fn into(self) -> U { U::from(self) }
+//    }
+//
+// So implement `From`, and accept `impl Into<U>` in function parameters, so callers can pass anything convertible.
+//
+// `rustlings/exercises/23_conversions/from_into.rs`: a `Person` from "name,age". `From` cannot fail, so any
+// malformed input falls back to the default person.
+#[derive(Debug, PartialEq)]
+pub struct Person {
+  pub name: String,
+  pub age: u8,
+}
+
+impl Default for Person {
+  fn default() -> Self {
+    Self { name: String::from("John"), age: 30 }
+  }
+}
+
+impl From<&str> for Person {
+  fn from(s: &str) -> Self {
+    let Some((name, age)) = s.split_once(',') else {
+      return Person::default();
+    };
+    // "a,b,c" has a comma in its "age", so fails to parse, like any other extra field would.
+    match (name, age.parse::<u8>()) {
+      ("", _) | (_, Err(_)) => Person::default(),
+      (name, Ok(age)) => Person { name: name.to_string(), age },
+    }
+  }
+}
+
+// Widening integer conversions are lossless, so they implement `From`; narrowing ones do not (see `TryFrom`).
+pub fn widen(small: u8, signed: i16) -> (u32, i64, f64) {
+  (u32::from(small), signed.into(), f64::from(signed))
+}
+
+// `impl Into<String>` accepts `&str`, `String`, `char`, `Box<str>`, `Cow<str>`, ...
+pub fn greeting(name: impl Into<String>) -> String {
+  let mut greeting: String = name.into();
+  greeting.insert_str(0, "Hello, ");
+  greeting
+}
+
+// -----------------------------------------------
+// ## AsRef and AsMut
+//
+// `AsRef<T>` borrows a `&T` out of `&self`, without copying. A function taking `impl AsRef<str>` accepts `&str`,
+// `String` and `&String` alike (and `impl AsRef<Path>` accepts strings, `PathBuf`s, `OsStr`s, ...).
+//
+// `rustlings/exercises/23_conversions/as_ref_mut.rs`:
+pub fn byte_counter<T: AsRef<str>>(arg: T) -> usize {
+  arg.as_ref().len()
+}
+
+pub fn char_counter<T: AsRef<str>>(arg: T) -> usize {
+  arg.as_ref().chars().count()
+}
+
+// `Box<u32>` implements `AsMut<u32>`, so this squares the number through any mutable wrapper of it.
+pub fn num_sq<T: AsMut<u32>>(arg: &mut T) {
+  let number: &mut u32 = arg.as_mut();
+  *number *= *number;
+}
+
+// `AsRef` vs `Borrow`: both give a `&T`, but `Borrow` additionally promises that the borrowed form hashes and
+// compares like the owned one, which is why `HashMap::get` takes `Borrow` (see _11_collections/_2_maps_and_sets.rs).
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_person_from_str() {
+    assert_eq!(Person::from("Mark,20"), Person { name: "Mark".to_string(), age: 20 });
+    let gerald: Person = "Gerald,70".into();
+    assert_eq!(gerald.age, 70);
+    for bad in ["", "Mark", "Mark,", ",1", ",", "Mark,twenty", "Mark,20,man", "Mark,256"] {
+      assert_eq!(Person::from(bad), Person::default(), "{bad:?}");
+    }
+  }
+
+  #[test]
+  fn test_from_and_into() {
+    assert_eq!(widen(255, -2), (255, -2, -2.0));
+    assert_eq!(greeting("Ferris"), "Hello, Ferris");
+    assert_eq!(greeting(String::from("crab")), "Hello, crab");
+    assert_eq!(greeting('🦀'), "Hello, 🦀");
+  }
+
+  #[test]
+  fn test_as_ref_and_as_mut() {
+    let s: String = String::from("Café au lait");
+    assert_eq!(byte_counter(&s), 13);
+    assert_eq!(char_counter(s), 12);
+    assert_eq!(byte_counter("Cafe au lait"), char_counter("Cafe au lait"));
+    let mut num: Box<u32> = Box::new(3);
+    num_sq(&mut num);
+    assert_eq!(*num, 9);
+  }
+}
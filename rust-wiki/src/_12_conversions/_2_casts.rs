@@ -0,0 +1,262 @@
+// -----------------------------------------------
+// # NUMERIC CASTS WITH `as`
+//
+// `as` converts between any two primitive numeric types, and never fails. When the value does not fit, it is
+// silently changed instead:
+//
+//   cast                  out of range / inexact                          example
+//   --------------------  ----------------------------------------------  --------------------------------------
+//   int -> int            keeps the low bits (wraps modulo 2^bits),      300_i32 as u8 == 44,  -1_i32 as u32 == 4294967295
+//                         which may flip the sign
+//   float -> int          truncates towards zero, saturates at the       2.9_f64 as u8 == 2,  300.0 as u8 == 255,
+//                         target's MIN/MAX, and NaN becomes 0             f64::NAN as i32 == 0
+//   int -> float          rounds to the nearest representable float      16_777_217_i32 as f32 == 16_777_216.0
+//   f64 -> f32            rounds, and overflows to +-infinity             1e40_f64 as f32 == f32::INFINITY
+//   f32 -> f64            always exact
+//
+// None of this is undefined behaviour, but it is a classic source of silent bugs. For integers, prefer the
+// checked conversions (`u8::try_from(x)`, see _4_try_from.rs), or `From` when the conversion is lossless. The
+// standard library has no `TryFrom` between floats and integers, though: this module fills the gap with `cast`, which
+// does exactly what `as` does, and also reports whether (and how) the value was changed.
+
+use std::error::Error;
+use std::fmt;
+
+// `rustlings/exercises/23_conversions/using_as.rs`: `values.len()` is a `usize`, and `f64 / usize` does not
+// compile, so the length must be cast. (It is only exact up to 2^53 elements, which is plenty.)
+pub fn average(values: &[f64]) -> f64 {
+  let total: f64 = values.iter().sum::<f64>();
+  total / values.len() as f64
+}
+
+// -----------------------------------------------
+// ## Reporting Lossy Casts
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loss {
+  // int -> int: the value did not fit, and its low bits were kept.
+  Wrapped,
+  // float -> int: the fractional part was dropped.
+  Truncated,
+  // float -> int: out of range, clamped to the target's MIN or MAX. f64 -> f32: overflowed to infinity.
+  Saturated,
+  // int -> float, f64 -> f32: rounded to the nearest representable value.
+  Rounded,
+  // float -> int: NaN became 0.
+  NotANumber,
+}
+
+impl fmt::Display for Loss {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let description: &str = match self {
+      Loss::Wrapped => "wrapped around",
+      Loss::Truncated => "fractional part truncated",
+      Loss::Saturated => "out of range, saturated",
+      Loss::Rounded => "rounded",
+      Loss::NotANumber => "NaN converted to 0",
+    };
+    write!(f, "lossy cast: {description}")
+  }
+}
+
+impl Error for Loss {}
+
+// The result of `as`, together with what was lost, if anything.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cast<T> {
+  pub value: T,
+  pub loss: Option<Loss>,
+}
+
+impl<T> Cast<T> {
+  pub fn is_exact(&self) -> bool {
+    self.loss.is_none()
+  }
+
+  // The value if the cast was exact, like a `TryFrom` would return.
+  pub fn exact(self) -> Result<T, Loss> {
+    match self.loss {
+      None => Ok(self.value),
+      Some(loss) => Err(loss),
+    }
+  }
+}
+
+// Like `From`/`Into`, `CastFrom` is the trait to implement, and `CastInto` comes with it from a blanket impl.
+pub trait CastFrom<S>: Sized {
+  fn cast_from(source: S) -> Cast<Self>;
+}
+
+pub trait CastInto<T> {
+  fn cast_into(self) -> Cast<T>;
+}
+
+impl<S, T: CastFrom<S>> CastInto<T> for S {
+  fn cast_into(self) -> Cast<T> {
+    T::cast_from(self)
+  }
+}
+
+// `cast::<u8, _>(300_i32)`, for when the target type cannot be inferred.
+pub fn cast<T: CastFrom<S>, S>(source: S) -> Cast<T> {
+  T::cast_from(source)
+}
+
+// -----------------------------------------------
+// ## The Implementations
+//
+// One impl per (source, target) pair is generated by macros. Each performs the `as` cast, then checks it by
+// comparing the source and the result in a type wide enough to hold both exactly: every integer of 64 bits or fewer,
+// and every integer-valued float below 2^127, fits in an `i128`. (`i128` and `u128` themselves are left out, as no
+// wider type exists to check them.)
+//
+// Comparing in the source or target type instead would be wrong: `u64::MAX as f64` rounds up to 2^64, which casts
+// back to `u64::MAX` (saturating), so the round trip alone would report the cast as exact.
+macro_rules! int_to_int {
+  ($($source:ty),*) => {
+    $( int_to_int!(@from $source => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize); )*
+  };
+  (@from $source:ty => $($target:ty),*) => {
+    $(
+      impl CastFrom<$source> for $target {
+        fn cast_from(source: $source) -> Cast<$target> {
+          let value: $target = source as $target;
+          let loss: Option<Loss> = (value as i128 != source as i128).then_some(Loss::Wrapped);
+          Cast { value, loss }
+        }
+      }
+    )*
+  };
+}
+
+int_to_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! float_and_int {
+  ($($float:ty),*) => {
+    $( float_and_int!(@with $float => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize); )*
+  };
+  (@with $float:ty => $($int:ty),*) => {
+    $(
+      impl CastFrom<$float> for $int {
+        fn cast_from(source: $float) -> Cast<$int> {
+          let value: $int = source as $int;
+          let loss: Option<Loss> = if source.is_nan() {
+            Some(Loss::NotANumber)
+          } else if source.trunc() as i128 != value as i128 {
+            // Also catches the infinities, and floats beyond `i128`, which saturate to `i128::MIN`/`MAX`.
+            Some(Loss::Saturated)
+          } else if source.fract() != 0.0 {
+            Some(Loss::Truncated)
+          } else {
+            None
+          };
+          Cast { value, loss }
+        }
+      }
+
+      impl CastFrom<$int> for $float {
+        fn cast_from(source: $int) -> Cast<$float> {
+          let value: $float = source as $float;
+          // `value` is at most 2^64, so converting it to `i128` is exact.
+          let loss: Option<Loss> = (value as i128 != source as i128).then_some(Loss::Rounded);
+          Cast { value, loss }
+        }
+      }
+    )*
+  };
+}
+
+float_and_int!(f32, f64);
+
+impl CastFrom<f64> for f32 {
+  fn cast_from(source: f64) -> Cast<f32> {
+    let value: f32 = source as f32;
+    let loss: Option<Loss> = if value.is_infinite() && source.is_finite() {
+      Some(Loss::Saturated)
+    } else if !source.is_nan() && value as f64 != source {
+      Some(Loss::Rounded)
+    } else {
+      None
+    };
+    Cast { value, loss }
+  }
+}
+
+impl CastFrom<f32> for f64 {
+  fn cast_from(source: f32) -> Cast<f64> {
+    Cast { value: source as f64, loss: None }
+  }
+}
+
+// -----------------------------------------------
+// ## Example: Colour Channels
+//
+// A colour channel in [0.0, 1.0] is stored as a byte in [0, 255]. With a plain `as`, an out-of-range channel (or a
+// NaN from a division by zero) silently becomes 255 or 0; `cast` says so.
+pub fn channel_to_byte(channel: f32) -> Cast<u8> {
+  let cast: Cast<u8> = (channel * 255.0).round().cast_into();
+  cast
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_average() {
+    assert_eq!(average(&[3.5, 0.3, 13.0, 11.7]), 7.125);
+  }
+
+  #[test]
+  fn test_int_to_int() {
+    assert_eq!(cast::<u8, _>(200_i32), Cast { value: 200, loss: None });
+    assert_eq!(cast::<u8, _>(300_i32), Cast { value: 44, loss: Some(Loss::Wrapped) });
+    assert_eq!(cast::<u32, _>(-1_i32), Cast { value: u32::MAX, loss: Some(Loss::Wrapped) });
+    assert_eq!(cast::<i64, _>(u64::MAX), Cast { value: -1, loss: Some(Loss::Wrapped) });
+    assert!(cast::<usize, _>(u32::MAX).is_exact());
+    assert_eq!(cast::<i8, _>(-128_i64).exact(), Ok(-128));
+  }
+
+  #[test]
+  fn test_float_to_int() {
+    assert_eq!(cast::<u8, _>(2.9_f64), Cast { value: 2, loss: Some(Loss::Truncated) });
+    assert_eq!(cast::<i32, _>(-2.9_f64), Cast { value: -2, loss: Some(Loss::Truncated) });
+    assert_eq!(cast::<u8, _>(300.0_f64), Cast { value: 255, loss: Some(Loss::Saturated) });
+    assert_eq!(cast::<u8, _>(-1.0_f32), Cast { value: 0, loss: Some(Loss::Saturated) });
+    assert_eq!(cast::<i32, _>(f64::NAN), Cast { value: 0, loss: Some(Loss::NotANumber) });
+    assert_eq!(cast::<i64, _>(f64::INFINITY), Cast { value: i64::MAX, loss: Some(Loss::Saturated) });
+    assert_eq!(cast::<u64, _>(1e300_f64).loss, Some(Loss::Saturated));
+    // 2^64 rounds to itself as an f64, but is one more than `u64::MAX`.
+    assert_eq!(cast::<u64, _>(18_446_744_073_709_551_616.0_f64).loss, Some(Loss::Saturated));
+    assert_eq!(cast::<u64, _>(9_007_199_254_740_992.0_f64), Cast { value: 1 << 53, loss: None });
+    assert_eq!(cast::<i8, _>(-0.0_f32), Cast { value: 0, loss: None });
+  }
+
+  #[test]
+  fn test_int_to_float() {
+    assert_eq!(cast::<f32, _>(16_777_216_i32), Cast { value: 16_777_216.0, loss: None });
+    assert_eq!(cast::<f32, _>(16_777_217_i32), Cast { value: 16_777_216.0, loss: Some(Loss::Rounded) });
+    assert_eq!(cast::<f64, _>(u64::MAX).loss, Some(Loss::Rounded));
+    assert_eq!(cast::<f64, _>(1_u64 << 63).loss, None);
+    assert!(cast::<f64, _>(i32::MIN).is_exact());
+  }
+
+  #[test]
+  fn test_float_to_float() {
+    assert_eq!(cast::<f32, _>(0.5_f64), Cast { value: 0.5, loss: None });
+    assert_eq!(cast::<f32, _>(0.1_f64).loss, Some(Loss::Rounded));
+    assert_eq!(cast::<f32, _>(1e40_f64), Cast { value: f32::INFINITY, loss: Some(Loss::Saturated) });
+    assert!(cast::<f32, _>(f64::NEG_INFINITY).is_exact());
+    assert!(cast::<f32, _>(f64::NAN).value.is_nan());
+    assert!(cast::<f64, _>(0.1_f32).is_exact());
+  }
+
+  #[test]
+  fn test_channel_to_byte() {
+    assert_eq!(channel_to_byte(0.5).exact(), Ok(128));
+    assert_eq!(channel_to_byte(1.0).exact(), Ok(255));
+    assert_eq!(channel_to_byte(1.5), Cast { value: 255, loss: Some(Loss::Saturated) });
+    assert_eq!(channel_to_byte(f32::NAN), Cast { value: 0, loss: Some(Loss::NotANumber) });
+    assert_eq!(channel_to_byte(2.0).exact().unwrap_err().to_string(), "lossy cast: out of range, saturated");
+  }
+}
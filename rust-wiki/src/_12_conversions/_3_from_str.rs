@@ -0,0 +1,362 @@
+// -----------------------------------------------
+// # PARSING TEXT: FromStr
+//
+// `FromStr` is the fallible conversion from `&str`. Implementing it enables `str::parse`:
+//
+//    let n = "42".parse::<u32>()?;         // calls <u32 as FromStr>::from_str("42")
+//    let n: u32 = "42".parse()?;           // the same, with the target type inferred
+//
+// Unlike `TryFrom<&str>`, the result cannot borrow from the input: `from_str` returns an owned `Self`.
+
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+// -----------------------------------------------
+// ## Implementing FromStr
+//
+// `rustlings/exercises/23_conversions/from_str.rs`: the `Person` of _1_from_into_as_ref.rs, but this time the
+// errors are reported instead of replaced by a default.
+#[derive(Debug, PartialEq)]
+pub struct Person {
+  pub name: String,
+  pub age: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParsePersonError {
+  // Not exactly two comma-separated fields.
+  BadLen,
+  NoName,
+  ParseInt(ParseIntError),
+}
+
+impl fmt::Display for ParsePersonError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParsePersonError::BadLen => write!(f, "expected \"name,age\""),
+      ParsePersonError::NoName => write!(f, "the name is empty"),
+      ParsePersonError::ParseInt(_) => write!(f, "invalid age"),
+    }
+  }
+}
+
+impl Error for ParsePersonError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ParsePersonError::ParseInt(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl FromStr for Person {
+  type Err = ParsePersonError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let [name, age] = s.split(',').collect::<Vec<&str>>()[..] else {
+      return Err(ParsePersonError::BadLen);
+    };
+    if name.is_empty() {
+      return Err(ParsePersonError::NoName);
+    }
+    let age: u8 = age.parse().map_err(ParsePersonError::ParseInt)?;
+    Ok(Person { name: name.to_string(), age })
+  }
+}
+
+// -----------------------------------------------
+// ## FromStr Combinators
+//
+// Every parser above has the same shape: split the text, parse each piece with *its* type's `FromStr`, and wrap
+// the errors. Generic wrapper types can capture those shapes once. Each wrapper implements `FromStr` by delegating
+// to the `FromStr` of its type parameters, so they nest, and the whole grammar is spelled out in the type:
+//
+//    "1,2;3,4".parse::<List<List<u8, ','>, ';'>>()              // [[1, 2], [3, 4]]
+//    "x = 5".parse::<Pair<Trimmed<String>, Trimmed<i32>, '='>>() // ("x", 5)
+//
+// The separators are const generic parameters (a `char` is allowed as a const parameter), so they are part of the
+// type too.
+
+// `T`, with surrounding whitespace ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trimmed<T>(pub T);
+
+impl<T: FromStr> FromStr for Trimmed<T> {
+  type Err = T::Err;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.trim().parse().map(Trimmed)
+  }
+}
+
+// An optional `T`: an empty (or blank) string is `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Maybe<T>(pub Option<T>);
+
+impl<T: FromStr> FromStr for Maybe<T> {
+  type Err = T::Err;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s.trim().is_empty() {
+      Ok(Maybe(None))
+    } else {
+      s.parse().map(|value| Maybe(Some(value)))
+    }
+  }
+}
+
+// Items separated by `SEP`. An empty string is an empty list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct List<T, const SEP: char>(pub Vec<T>);
+
+// Which item failed, and why.
+#[derive(Debug, PartialEq)]
+pub struct ListError<E> {
+  pub index: usize,
+  pub item: String,
+  pub source: E,
+}
+
+impl<E: fmt::Display> fmt::Display for ListError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "item {} ({:?}): {}", self.index, self.item, self.source)
+  }
+}
+
+impl<E: Error + 'static> Error for ListError<E> {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(&self.source)
+  }
+}
+
+impl<T: FromStr, const SEP: char> FromStr for List<T, SEP> {
+  type Err = ListError<T::Err>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s.is_empty() {
+      return Ok(List(Vec::new()));
+    }
+    s.split(SEP)
+      .enumerate()
+      .map(|(index, item)| item.parse().map_err(|source| ListError { index, item: item.to_string(), source }))
+      .collect::<Result<Vec<T>, _>>()
+      .map(List)
+  }
+}
+
+// Two values separated by the first `SEP`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair<A, B, const SEP: char>(pub A, pub B);
+
+#[derive(Debug, PartialEq)]
+pub enum PairError<EA, EB> {
+  MissingSeparator(char),
+  First(EA),
+  Second(EB),
+}
+
+impl<EA: fmt::Display, EB: fmt::Display> fmt::Display for PairError<EA, EB> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PairError::MissingSeparator(separator) => write!(f, "missing separator {separator:?}"),
+      PairError::First(e) => write!(f, "first value: {e}"),
+      PairError::Second(e) => write!(f, "second value: {e}"),
+    }
+  }
+}
+
+impl<EA: Error + 'static, EB: Error + 'static> Error for PairError<EA, EB> {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      PairError::MissingSeparator(_) => None,
+      PairError::First(e) => Some(e),
+      PairError::Second(e) => Some(e),
+    }
+  }
+}
+
+impl<A: FromStr, B: FromStr, const SEP: char> FromStr for Pair<A, B, SEP> {
+  type Err = PairError<A::Err, B::Err>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (first, second) = s.split_once(SEP).ok_or(PairError::MissingSeparator(SEP))?;
+    Ok(Pair(first.parse().map_err(PairError::First)?, second.parse().map_err(PairError::Second)?))
+  }
+}
+
+// A `T` that must also satisfy a check, e.g. a non-empty string or a positive number. The check is a type
+// implementing `Validate`, so that it too is part of the parsed type.
+pub trait Validate<T> {
+  const DESCRIPTION: &'static str;
+  fn is_valid(value: &T) -> bool;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checked<T, V>(pub T, std::marker::PhantomData<V>);
+
+impl<T, V> Checked<T, V> {
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CheckedError<E> {
+  Parse(E),
+  Invalid(&'static str),
+}
+
+impl<E: fmt::Display> fmt::Display for CheckedError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      CheckedError::Parse(e) => e.fmt(f),
+      CheckedError::Invalid(description) => write!(f, "must be {description}"),
+    }
+  }
+}
+
+impl<E: Error + 'static> Error for CheckedError<E> {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      CheckedError::Parse(e) => Some(e),
+      CheckedError::Invalid(_) => None,
+    }
+  }
+}
+
+impl<T: FromStr, V: Validate<T>> FromStr for Checked<T, V> {
+  type Err = CheckedError<T::Err>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let value: T = s.parse().map_err(CheckedError::Parse)?;
+    if V::is_valid(&value) {
+      Ok(Checked(value, std::marker::PhantomData))
+    } else {
+      Err(CheckedError::Invalid(V::DESCRIPTION))
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NonEmpty;
+
+impl Validate<String> for NonEmpty {
+  const DESCRIPTION: &'static str = "non-empty";
+
+  fn is_valid(value: &String) -> bool {
+    !value.is_empty()
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Positive;
+
+impl<T: Default + PartialOrd> Validate<T> for Positive {
+  const DESCRIPTION: &'static str = "positive";
+
+  fn is_valid(value: &T) -> bool {
+    *value > T::default()
+  }
+}
+
+// -----------------------------------------------
+// ## Example: Person Again, From Combinators
+//
+// The hand-written `Person` parser above, as a type. Only the error type differs: it is the nested errors of the
+// combinators, instead of a dedicated enum.
+pub type PersonFields = Pair<Checked<String, NonEmpty>, u8, ','>;
+
+pub fn parse_person(s: &str) -> Result<Person, <PersonFields as FromStr>::Err> {
+  let Pair(name, age) = s.parse::<PersonFields>()?;
+  Ok(Person { name: name.into_inner(), age })
+}
+
+// A line of a config file, "key = value1, value2, ...", where a value may be left empty.
+pub type ConfigLine = Pair<Trimmed<String>, List<Maybe<Trimmed<u16>>, ','>, '='>;
+
+pub type Ports = (String, Vec<Option<u16>>);
+
+pub fn parse_ports(line: &str) -> Result<Ports, Box<dyn Error>> {
+  let Pair(Trimmed(key), List(values)) = line.parse::<ConfigLine>()?;
+  Ok((key, values.into_iter().map(|Maybe(value)| value.map(|Trimmed(port)| port)).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::num::ParseFloatError;
+  use ParsePersonError::*;
+
+  #[test]
+  fn test_person_from_str() {
+    assert_eq!("John,32".parse::<Person>(), Ok(Person { name: "John".to_string(), age: 32 }));
+    assert_eq!("".parse::<Person>(), Err(BadLen));
+    assert_eq!("John".parse::<Person>(), Err(BadLen));
+    assert_eq!("John,32,".parse::<Person>(), Err(BadLen));
+    assert_eq!("John,32,man".parse::<Person>(), Err(BadLen));
+    assert_eq!(",1".parse::<Person>(), Err(NoName));
+    assert!(matches!("John,".parse::<Person>(), Err(ParseInt(_))));
+    assert!(matches!("John,twenty".parse::<Person>(), Err(ParseInt(_))));
+    assert!(matches!(",".parse::<Person>(), Err(NoName | ParseInt(_))));
+    let err: ParsePersonError = "John,300".parse::<Person>().unwrap_err();
+    assert_eq!(err.source().unwrap().to_string(), "number too large to fit in target type");
+  }
+
+  #[test]
+  fn test_trimmed_and_maybe() {
+    assert_eq!(" 7 ".parse::<Trimmed<u8>>(), Ok(Trimmed(7)));
+    assert!(" 7 ".parse::<u8>().is_err());
+    assert_eq!("".parse::<Maybe<u8>>(), Ok(Maybe(None)));
+    assert_eq!("8".parse::<Maybe<u8>>(), Ok(Maybe(Some(8))));
+    assert!("x".parse::<Maybe<u8>>().is_err());
+  }
+
+  #[test]
+  fn test_lists() {
+    assert_eq!("1,2,3".parse::<List<u8, ','>>(), Ok(List(vec![1, 2, 3])));
+    assert_eq!("".parse::<List<u8, ','>>(), Ok(List(vec![])));
+    assert_eq!("1,2;3,4".parse::<List<List<u8, ','>, ';'>>(), Ok(List(vec![List(vec![1, 2]), List(vec![3, 4])])));
+    let err: ListError<ParseFloatError> = "1.5 2.5 x".parse::<List<f64, ' '>>().unwrap_err();
+    assert_eq!((err.index, err.item.as_str()), (2, "x"));
+    assert_eq!(err.to_string(), "item 2 (\"x\"): invalid float literal");
+    // The error of the inner list is the source of the outer one.
+    let err = "1,2;3,x".parse::<List<List<u8, ','>, ';'>>().unwrap_err();
+    assert_eq!(err.to_string(), "item 1 (\"3,x\"): item 1 (\"x\"): invalid digit found in string");
+    assert!(err.source().unwrap().source().is_some());
+  }
+
+  #[test]
+  fn test_pairs() {
+    assert_eq!("x = 5".parse::<Pair<Trimmed<String>, Trimmed<i32>, '='>>(), Ok(Pair(Trimmed("x".to_string()), Trimmed(5))));
+    assert_eq!("3:4".parse::<Pair<u8, u8, ':'>>(), Ok(Pair(3, 4)));
+    assert_eq!("3".parse::<Pair<u8, u8, ':'>>(), Err(PairError::MissingSeparator(':')));
+    assert!(matches!("a:4".parse::<Pair<u8, u8, ':'>>(), Err(PairError::First(_))));
+    assert!(matches!("3:b".parse::<Pair<u8, u8, ':'>>(), Err(PairError::Second(_))));
+    // Only the first separator splits, so the second value may contain more.
+    assert_eq!("a=b=c".parse::<Pair<String, String, '='>>(), Ok(Pair("a".to_string(), "b=c".to_string())));
+  }
+
+  #[test]
+  fn test_checked() {
+    assert_eq!("5".parse::<Checked<i32, Positive>>().map(Checked::into_inner), Ok(5));
+    assert_eq!("-5".parse::<Checked<i32, Positive>>(), Err(CheckedError::Invalid("positive")));
+    assert_eq!("0.5".parse::<Checked<f64, Positive>>().unwrap().into_inner(), 0.5);
+    assert_eq!("".parse::<Checked<String, NonEmpty>>().unwrap_err().to_string(), "must be non-empty");
+  }
+
+  #[test]
+  fn test_person_from_combinators() {
+    assert_eq!(parse_person("Mark,20"), Ok(Person { name: "Mark".to_string(), age: 20 }));
+    assert_eq!(parse_person(",20"), Err(PairError::First(CheckedError::Invalid("non-empty"))));
+    assert!(matches!(parse_person("Mark"), Err(PairError::MissingSeparator(','))));
+    assert!(matches!(parse_person("Mark,old"), Err(PairError::Second(_))));
+  }
+
+  #[test]
+  fn test_config_line() {
+    assert_eq!(parse_ports("ports = 80, , 443").unwrap(), ("ports".to_string(), vec![Some(80), None, Some(443)]));
+    assert_eq!(parse_ports("ports = 80, 70000").unwrap_err().to_string(), "second value: item 1 (\" 70000\"): number too large to fit in target type");
+  }
+}
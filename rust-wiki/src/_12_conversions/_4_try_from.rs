@@ -0,0 +1,243 @@
+// -----------------------------------------------
+// # FALLIBLE CONVERSIONS: TryFrom AND TryInto
+//
+// `TryFrom<T> for U` is `From` for conversions that can fail, returning `Result<U, Self::Error>`. As with `From`,
+// implementing it provides `TryInto<U> for T`. And every `From` conversion is also a `TryFrom` one, which cannot
+// fail (its `Error` is `Infallible`).
+//
+// The standard library implements it for:
+//   - narrowing integer conversions:  `u8::try_from(300_i32)` is `Err(TryFromIntError)`.
+//   - slices to arrays:               `<[T; N]>::try_from(&slice[..])` succeeds only if `slice.len() == N`.
+//   - `Vec<T>` to `[T; N]`, `char` from `u32`, and more.
+// Tuples get nothing: a tuple has no length to check, only element types to convert, which must be done one by one.
+
+use std::error::Error;
+use std::fmt;
+
+// -----------------------------------------------
+// ## Color From Tuples, Arrays and Slices
+//
+// `rustlings/exercises/23_conversions/try_from_into.rs`: a `Color` from three integers, each of which must fit in
+// a `u8`. The exercise converts from `i16`; here, the impls are generic over any integer type `T` that `u8` can be
+// converted from (`u8: TryFrom<T>`), so they work for `i16`, `u32`, `i64`, ...
+#[derive(Debug, PartialEq)]
+pub struct Color {
+  pub red: u8,
+  pub green: u8,
+  pub blue: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IntoColorError {
+  // The slice does not have exactly three elements.
+  BadLen(usize),
+  // The component at this index is not in 0..=255.
+  IntConversion(usize),
+}
+
+impl fmt::Display for IntoColorError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      IntoColorError::BadLen(len) => write!(f, "expected 3 components, found {len}"),
+      IntoColorError::IntConversion(index) => write!(f, "component {index} is not in 0..=255"),
+    }
+  }
+}
+
+impl Error for IntoColorError {}
+
+// Converts the component at `index`, reporting which one was out of range.
+fn component<T>(value: T, index: usize) -> Result<u8, IntoColorError>
+where
+  u8: TryFrom<T>,
+{
+  u8::try_from(value).map_err(|_| IntoColorError::IntConversion(index))
+}
+
+// Tuples: each element converted one by one.
+impl<T> TryFrom<(T, T, T)> for Color
+where
+  u8: TryFrom<T>,
+{
+  type Error = IntoColorError;
+
+  fn try_from((red, green, blue): (T, T, T)) -> Result<Self, Self::Error> {
+    Ok(Color { red: component(red, 0)?, green: component(green, 1)?, blue: component(blue, 2)? })
+  }
+}
+
+// Arrays: the length is already checked by the type, so only the values can be wrong.
+impl<T> TryFrom<[T; 3]> for Color
+where
+  u8: TryFrom<T>,
+{
+  type Error = IntoColorError;
+
+  fn try_from([red, green, blue]: [T; 3]) -> Result<Self, Self::Error> {
+    Color::try_from((red, green, blue))
+  }
+}
+
+// Slices: the length is only known at runtime. The standard `TryFrom<&[T]> for [T; 3]` checks it, and the array
+// impl does the rest.
+impl<T: Copy> TryFrom<&[T]> for Color
+where
+  u8: TryFrom<T>,
+{
+  type Error = IntoColorError;
+
+  fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+    let array: [T; 3] = slice.try_into().map_err(|_| IntoColorError::BadLen(slice.len()))?;
+    Color::try_from(array)
+  }
+}
+
+// -----------------------------------------------
+// ## Generic Helpers for Arrays, Slices and Tuples
+//
+// The same three shapes come up for any element type, so they can be written once, generically. A const generic
+// `N` lets one function handle arrays of every length.
+
+#[derive(Debug, PartialEq)]
+pub enum ElementError<E> {
+  // The slice does not have the expected length.
+  Length { expected: usize, found: usize },
+  // The element at `index` failed to convert.
+  Element { index: usize, source: E },
+}
+
+impl<E: fmt::Display> fmt::Display for ElementError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ElementError::Length { expected, found } => write!(f, "expected {expected} elements, found {found}"),
+      ElementError::Element { index, source } => write!(f, "element {index}: {source}"),
+    }
+  }
+}
+
+impl<E: Error + 'static> Error for ElementError<E> {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ElementError::Length { .. } => None,
+      ElementError::Element { source, .. } => Some(source),
+    }
+  }
+}
+
+// Converts every element of an array. (The output is collected into a `Vec` first: an array cannot be built
+// element by element without `unsafe`, and `Vec<U>` converts to `[U; N]` when its length is `N`, which it is.)
+pub fn try_map_array<T, U: TryFrom<T>, const N: usize>(array: [T; N]) -> Result<[U; N], ElementError<U::Error>> {
+  let converted: Vec<U> = array
+    .into_iter()
+    .enumerate()
+    .map(|(index, element)| U::try_from(element).map_err(|source| ElementError::Element { index, source }))
+    .collect::<Result<Vec<U>, _>>()?;
+  Ok(converted.try_into().unwrap_or_else(|_| unreachable!("one element was converted per element of the array")))
+}
+
+// Checks a slice's length, then converts every element.
+pub fn try_from_slice<T: Copy, U: TryFrom<T>, const N: usize>(slice: &[T]) -> Result<[U; N], ElementError<U::Error>> {
+  let array: [T; N] =
+    slice.try_into().map_err(|_| ElementError::Length { expected: N, found: slice.len() })?;
+  try_map_array(array)
+}
+
+// Tuples cannot be iterated (each element may have a different type), so a trait implemented per tuple length by
+// a macro is the usual way to convert them all: this is also how the standard library implements `Debug`,
+// `PartialEq`, ... for tuples of up to 12 elements.
+pub trait TryConvertTuple<Target> {
+  type Error;
+  fn try_convert(self) -> Result<Target, Self::Error>;
+}
+
+macro_rules! try_convert_tuple {
+  ($(($source:ident, $target:ident, $index:tt)),+) => {
+    impl<$($source, $target),+> TryConvertTuple<($($target,)+)> for ($($source,)+)
+    where
+      $($target: TryFrom<$source>, $target::Error: Error + 'static),+
+    {
+      // All elements' errors are boxed into one type, with the index of the element that failed.
+      type Error = (usize, Box<dyn Error>);
+
+      fn try_convert(self) -> Result<($($target,)+), Self::Error> {
+        Ok(($($target::try_from(self.$index).map_err(|e| ($index, Box::new(e) as Box<dyn Error>))?,)+))
+      }
+    }
+  };
+}
+
+try_convert_tuple!((A, TA, 0));
+try_convert_tuple!((A, TA, 0), (B, TB, 1));
+try_convert_tuple!((A, TA, 0), (B, TB, 1), (C, TC, 2));
+try_convert_tuple!((A, TA, 0), (B, TB, 1), (C, TC, 2), (D, TD, 3));
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::num::TryFromIntError;
+  use IntoColorError::*;
+
+  fn rust_orange() -> Color {
+    Color { red: 183, green: 65, blue: 14 }
+  }
+
+  #[test]
+  fn test_color_from_tuple() {
+    assert_eq!(Color::try_from((183_i16, 65, 14)), Ok(rust_orange()));
+    assert_eq!(Color::try_from((256_i16, 1000, 10000)), Err(IntConversion(0)));
+    assert_eq!(Color::try_from((-1_i16, -10, -256)), Err(IntConversion(0)));
+    assert_eq!(Color::try_from((1_i16, 255, 256)), Err(IntConversion(2)));
+    let c: Result<Color, _> = (183_u64, 65_u64, 14_u64).try_into();
+    assert_eq!(c, Ok(rust_orange()));
+  }
+
+  #[test]
+  fn test_color_from_array() {
+    let c: Result<Color, _> = [183_i16, 65, 14].try_into();
+    assert_eq!(c, Ok(rust_orange()));
+    let c: Result<Color, _> = [1000_i16, 10000, 256].try_into();
+    assert_eq!(c, Err(IntConversion(0)));
+    let c: Result<Color, _> = [10_i32, 20, -1].try_into();
+    assert_eq!(c, Err(IntConversion(2)));
+  }
+
+  #[test]
+  fn test_color_from_slice() {
+    let v: Vec<i16> = vec![183, 65, 14];
+    assert_eq!(Color::try_from(&v[..]), Ok(rust_orange()));
+    let c: Result<Color, _> = (&v[..]).try_into();
+    assert_eq!(c, Ok(rust_orange()));
+    assert_eq!(Color::try_from(&[-1_i16, 255, 255][..]), Err(IntConversion(0)));
+    assert_eq!(Color::try_from(&[1_i16, 2, 3, 4][..]), Err(BadLen(4)));
+    assert_eq!(Color::try_from(&[1_i16, 2][..]), Err(BadLen(2)));
+    assert_eq!(Color::try_from(&[] as &[i16]).unwrap_err().to_string(), "expected 3 components, found 0");
+  }
+
+  #[test]
+  fn test_try_map_array() {
+    assert_eq!(try_map_array::<i32, u8, 4>([1, 2, 3, 4]), Ok([1, 2, 3, 4]));
+    let err: ElementError<TryFromIntError> = try_map_array::<i32, u8, 3>([1, -2, 300]).unwrap_err();
+    assert_eq!(err.to_string(), "element 1: out of range integral type conversion attempted");
+    assert_eq!(try_map_array::<u8, char, 2>([b'o', b'k']), Ok(['o', 'k']));
+    assert_eq!(try_map_array::<i32, u8, 0>([]), Ok([]));
+  }
+
+  #[test]
+  fn test_try_from_slice() {
+    assert_eq!(try_from_slice::<u32, u16, 2>(&[1, 2]), Ok([1, 2]));
+    assert_eq!(try_from_slice::<u32, u16, 2>(&[1, 2, 3]), Err(ElementError::Length { expected: 2, found: 3 }));
+    assert!(matches!(try_from_slice::<u32, u16, 1>(&[70000]), Err(ElementError::Element { index: 0, .. })));
+  }
+
+  #[test]
+  fn test_try_convert_tuple() {
+    let converted: Result<(u8, char, i8), _> = (200_i32, 0x1F980_u32, -5_i64).try_convert();
+    assert_eq!(converted.unwrap(), (200, '🦀', -5));
+    let failed: Result<(u8, char), _> = (1_u16, 0xD800_u32).try_convert();
+    let (index, error) = failed.unwrap_err();
+    assert_eq!(index, 1);
+    assert_eq!(error.to_string(), "converted integer out of range for `char`");
+    let single: Result<(u8,), _> = (256_u32,).try_convert();
+    assert_eq!(single.unwrap_err().0, 0);
+  }
+}
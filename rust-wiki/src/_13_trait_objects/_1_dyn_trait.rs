@@ -0,0 +1,191 @@
+// -----------------------------------------------
+// # TRAIT OBJECTS
+//
+// Generics (see _4_generics) are resolved at compile time: `fn total_area<S: Shape>(shapes: &[S])` is compiled once
+// per type `S` it is called with ("monomorphisation"), and each copy calls that type's methods directly. This is
+// STATIC DISPATCH. Its limit: one `Vec<S>` holds one type `S`, so circles and squares cannot share a vector.
+//
+// A TRAIT OBJECT, `dyn Shape`, is "some value of a type implementing `Shape`", with the type only known at runtime.
+// Its size is unknown, so it only exists behind a pointer: `&dyn Shape`, `Box<dyn Shape>`, `Rc<dyn Shape>`, ...
+// Calling a method on it looks the method up in a table of function pointers (the VTABLE) at runtime. This is
+// DYNAMIC DISPATCH.
+//
+// This chapter covers:
+//   - `dyn Trait`, and choosing between it and generics (here).
+//   - which traits can be made into objects (_2_object_safety.rs).
+//   - what a `&dyn Trait` is made of: a data pointer and a vtable pointer (_3_fat_pointers.rs).
+//   - recovering the concrete type with `Any` (_4_any.rs).
+//   - a plugin registry combining all of the above (_5_plugin_registry.rs).
+
+use std::fmt::Debug;
+
+use super::_5_plugin_registry::Licensed;
+
+pub trait Shape {
+  fn area(&self) -> f64;
+  fn name(&self) -> String;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+  pub radius: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Square {
+  pub side: f64,
+}
+
+impl Shape for Circle {
+  fn area(&self) -> f64 {
+    std::f64::consts::PI * self.radius * self.radius
+  }
+
+  fn name(&self) -> String {
+    format!("circle of radius {}", self.radius)
+  }
+}
+
+impl Shape for Square {
+  fn area(&self) -> f64 {
+    self.side * self.side
+  }
+
+  fn name(&self) -> String {
+    format!("square of side {}", self.side)
+  }
+}
+
+// -----------------------------------------------
+// ## Static Dispatch
+//
+// One copy of this function is compiled for `Circle`, another for `Square`: each can inline `area`. But every
+// element of `shapes` must be of the same type.
+pub fn total_area_static<S: Shape>(shapes: &[S]) -> f64 {
+  shapes.iter().map(Shape::area).sum()
+}
+
+// -----------------------------------------------
+// ## Dynamic Dispatch
+//
+// A `Box<dyn Shape>` can hold a `Circle` or a `Square`, so a vector of them can mix both. One copy of the function
+// is compiled, and each `area` call goes through the vtable of the element's type.
+//
+//    Vec<Box<dyn Shape>>
+//    +----------------------+----------------------+
+//    | data ptr | vtable ptr| data ptr | vtable ptr|
+//    +----------------------+----------------------+
+//         |          |           |          |
+//         v          v           v          v
+//    Circle {..}  [Circle's   Square {..}  [Square's
+//                  drop, size,              drop, size,
+//                  align, area, name]       align, area, name]
+pub fn total_area_dynamic(shapes: &[Box<dyn Shape>]) -> f64 {
+  shapes.iter().map(|shape| shape.area()).sum()
+}
+
+pub fn mixed_shapes() -> Vec<Box<dyn Shape>> {
+  // Each `Box<Circle>` / `Box<Square>` is coerced to `Box<dyn Shape>` ("unsizing"), which attaches the vtable.
+  vec![Box::new(Circle { radius: 1.0 }), Box::new(Square { side: 2.0 }), Box::new(Circle { radius: 0.5 })]
+}
+
+// A borrowed trait object works just as well, and needs no allocation.
+pub fn describe(shape: &dyn Shape) -> String {
+  format!("{} has area {:.2}", shape.name(), shape.area())
+}
+
+// -----------------------------------------------
+// ## Returning Different Types
+//
+// `impl Trait` in return position is still static: the function returns *one* hidden concrete type. Returning a
+// `Circle` from one branch and a `Square` from another does not compile:
+//
+//    fn parse_shape(s: &str) -> impl Shape {
+//      if ... { Circle { .. } } else { Square { .. } }    // Error! `if` and `else` have incompatible types
+//    }
+//
+// A trait object erases the difference:
+pub fn parse_shape(s: &str) -> Option<Box<dyn Shape>> {
+  let (kind, size) = s.split_once(' ')?;
+  let size: f64 = size.parse().ok()?;
+  match kind {
+    "circle" => Some(Box::new(Circle { radius: size })),
+    "square" => Some(Box::new(Square { side: size })),
+    _ => None,
+  }
+}
+
+// -----------------------------------------------
+// ## Several Traits
+//
+// Only one non-auto trait may appear in a `dyn` type, plus any auto traits (`Send`, `Sync`, `Unpin`, ...) and a
+// lifetime. `dyn Shape + Debug` is not allowed; a trait combining both is:
+pub trait DebugShape: Shape + Debug {}
+
+impl<T: Shape + Debug> DebugShape for T {}
+
+pub fn debug_shapes(shapes: &[Box<dyn DebugShape + Send>]) -> Vec<String> {
+  shapes.iter().map(|shape| format!("{shape:?}: {:.1}", shape.area())).collect()
+}
+
+// -----------------------------------------------
+// ## Generics or Trait Objects?
+//
+//                             generics / impl Trait                  dyn Trait
+//   -----------------------  -------------------------------------  ------------------------------------------
+//   types                    one type per call site, fixed at       any mix of types, chosen at runtime
+//                            compile time
+//   call cost                direct call, can be inlined            indirect call through the vtable
+//   code size                one copy per type                      one copy
+//   usable with              every trait                            dyn-compatible traits only
+//                                                                   (_2_object_safety.rs)
+//
+// Prefer generics by default; use `dyn` for heterogeneous collections, for plugins and callbacks chosen at
+// runtime, or to keep compile times and binary size down when a function is generic over many types.
+//
+// `rustlings/exercises/15_traits/traits4.rs` asks for exactly that choice: `compare_license_types` accepts two
+// values of possibly different types implementing `Licensed` (defined in _5_plugin_registry.rs). With generics, each
+// parameter gets its own type parameter (`impl Licensed` is shorthand for that):
+pub fn compare_license_types(software1: impl Licensed, software2: impl Licensed) -> bool {
+  software1.licensing_info() == software2.licensing_info()
+}
+
+// With trait objects, both are `&dyn Licensed`, and the function is compiled once.
+pub fn compare_license_objects(software1: &dyn Licensed, software2: &dyn Licensed) -> bool {
+  software1.licensing_info() == software2.licensing_info()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_13_trait_objects::_5_plugin_registry::{OtherSoftware, SomeSoftware};
+
+  #[test]
+  fn test_static_and_dynamic_dispatch() {
+    assert_eq!(total_area_static(&[Square { side: 1.0 }, Square { side: 2.0 }]), 5.0);
+    let total: f64 = total_area_dynamic(&mixed_shapes());
+    assert!((total - (std::f64::consts::PI * 1.25 + 4.0)).abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_describe_and_parse() {
+    assert_eq!(describe(&Square { side: 3.0 }), "square of side 3 has area 9.00");
+    assert_eq!(parse_shape("circle 2").unwrap().name(), "circle of radius 2");
+    assert_eq!(parse_shape("square 2").unwrap().area(), 4.0);
+    assert!(parse_shape("triangle 2").is_none());
+    assert!(parse_shape("square two").is_none());
+  }
+
+  #[test]
+  fn test_combined_trait() {
+    let shapes: Vec<Box<dyn DebugShape + Send>> = vec![Box::new(Square { side: 1.5 })];
+    assert_eq!(debug_shapes(&shapes), vec!["Square { side: 1.5 }: 2.2"]);
+  }
+
+  #[test]
+  fn test_compare_license_types() {
+    assert!(compare_license_types(SomeSoftware, OtherSoftware));
+    assert!(compare_license_types(OtherSoftware, SomeSoftware));
+    assert!(compare_license_objects(&SomeSoftware, &OtherSoftware));
+  }
+}
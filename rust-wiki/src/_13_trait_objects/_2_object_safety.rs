@@ -0,0 +1,188 @@
+// -----------------------------------------------
+// # OBJECT SAFETY (DYN COMPATIBILITY)
+//
+// A `dyn Trait` value must be usable without knowing its concrete type: every method is called through the
+// vtable, and the vtable can only hold one function pointer per method. A trait is therefore only
+// "object safe" (officially: DYN COMPATIBLE) if:
+//   1. It does not require `Self: Sized` (a trait object is unsized).
+//   2. It has no associated consts, and no generic associated types.
+//   3. Every method is dispatchable, i.e. has a `self` receiver (`&self`, `&mut self`, `self: Box<Self>`, ...),
+//      has no type parameters, and does not use `Self` except in the receiver.
+//        - `fn new() -> Self`:              no receiver; which type's `new` would be called?
+//        - `fn clone(&self) -> Self`:       the caller cannot hold a `Self` of unknown size.
+//        - `fn map<T>(&self, t: T)`:        one function per `T`, so infinitely many vtable entries.
+//   4. Its supertraits are object safe too.
+//
+// A method breaking rule 3 can be opted out with `where Self: Sized`: it stays callable on concrete types, and is
+// simply left out of the vtable. Associated types are allowed, but must then be fixed in the `dyn` type:
+// `dyn Iterator<Item = u32>`.
+//
+// Using a trait that is not object safe as `dyn Trait` is error E0038.
+
+use std::fmt::Debug;
+
+use crate::_3_datatypes_and_traits::_4_traits::{Show, User};
+
+// -----------------------------------------------
+// ## Why `Show` Is Not Object Safe
+//
+// `Show` (_3_datatypes_and_traits/_4_traits.rs) has two obstacles:
+//
+//    pub trait Show {
+//      type AltShowType;                                   // (a)
+//      fn alt_show(s : String) -> Self::AltShowType;       // (b)
+//      fn show(&self) -> String;
+//      fn show_twice(&self) -> String { ... }
+//    }
+//
+//   (a) The associated type alone would be fine, but each implementor picks its own, so it has to be fixed in the
+//       object's type: `dyn Show<AltShowType = Vec<char>>` only holds types whose `AltShowType` is `Vec<char>`.
+//       Plain `dyn Show` would not name a complete type.
+//   (b) `alt_show` has no `self` receiver, so it cannot be called through a vtable: this breaks rule 3, and is what
+//       actually makes `Show` not object safe. Even with the associated type fixed:
+//
+//    let shown: &dyn Show<AltShowType = Vec<char>> = &user;   // Error! the trait `Show` is not dyn compatible
+//
+// Two ways out, without changing the trait's users:
+//   - add `where Self: Sized` to `alt_show`, removing it from the vtable (then `dyn Show<AltShowType = ..>` works).
+//   - define an object-safe companion trait with only the dispatchable methods, implemented for every `Show` type
+//     by a blanket impl. This is the usual pattern when the original trait cannot be changed (e.g. `Clone`, or a
+//     trait from another crate).
+pub trait DynShow {
+  fn show(&self) -> String;
+  fn show_twice(&self) -> String;
+}
+
+impl<T: Show> DynShow for T {
+  fn show(&self) -> String {
+    Show::show(self)
+  }
+
+  fn show_twice(&self) -> String {
+    Show::show_twice(self)
+  }
+}
+
+// `User` and the derive(Show) types of _10_macros have different `AltShowType`s, yet share one vector.
+pub fn show_all(values: &[&dyn DynShow]) -> Vec<String> {
+  values.iter().map(|value| value.show()).collect()
+}
+
+pub fn show_example() -> Vec<String> {
+  use crate::_10_macros::_2_derive_macros::Rgb;
+
+  let user: User = User { active: true, sign_in_count: 1, username: String::from("ferris") };
+  let colour: Rgb = Rgb(255, 0, 128);
+  show_all(&[&user, &colour])
+}
+
+// -----------------------------------------------
+// ## Opting Methods Out With `where Self: Sized`
+//
+// `Shape` has an object-safe core, plus a constructor and a method returning `Self`, which are only available on
+// concrete types.
+pub trait Shape: Debug {
+  fn area(&self) -> f64;
+
+  fn unit() -> Self
+  where
+    Self: Sized;
+
+  fn scaled(&self, factor: f64) -> Self
+  where
+    Self: Sized;
+
+  // A generic method can be opted out the same way.
+  fn area_in<U: From<f64>>(&self) -> U
+  where
+    Self: Sized,
+  {
+    U::from(self.area())
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Square(pub f64);
+
+impl Shape for Square {
+  fn area(&self) -> f64 {
+    self.0 * self.0
+  }
+
+  fn unit() -> Self {
+    Square(1.0)
+  }
+
+  fn scaled(&self, factor: f64) -> Self {
+    Square(self.0 * factor)
+  }
+}
+
+pub fn sized_methods_example() -> (f64, f64) {
+  let square: Square = Square::unit().scaled(3.0);
+  let shape: &dyn Shape = &square;
+  // `shape.scaled(2.0)` would not compile: `scaled` is not in the vtable. (Error: "the `scaled` method cannot be
+  // invoked on a trait object".)
+  (shape.area(), square.area_in::<f64>())
+}
+
+// -----------------------------------------------
+// ## Cloning a Trait Object
+//
+// `Clone` returns `Self`, so `dyn Clone` does not exist, and `Box<dyn Shape>` cannot derive `Clone`. The fix is a
+// method returning a `Box<dyn Shape>` instead: its return type no longer mentions `Self`, so it is dispatchable.
+// A blanket impl writes it once for every cloneable shape.
+pub trait CloneShape: Shape {
+  fn clone_box(&self) -> Box<dyn CloneShape>;
+}
+
+impl<T: Shape + Clone + 'static> CloneShape for T {
+  fn clone_box(&self) -> Box<dyn CloneShape> {
+    Box::new(self.clone())
+  }
+}
+
+impl Clone for Box<dyn CloneShape> {
+  fn clone(&self) -> Self {
+    // `**self` is the `dyn CloneShape` inside the box, so this dispatches to the concrete type's `clone_box`.
+    (**self).clone_box()
+  }
+}
+
+pub type Shapes = Vec<Box<dyn CloneShape>>;
+
+// `Vec<T>: Clone` needs `T: Clone`, which the impl above provides for the boxes.
+pub fn clone_boxed_shapes() -> (Shapes, Shapes) {
+  let shapes: Shapes = vec![Box::new(Square(1.0)), Box::new(Square(2.0))];
+  let copies: Shapes = shapes.clone();
+  (shapes, copies)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_dyn_show() {
+    assert_eq!(show_example(), vec!["ferris", "Rgb(255, 0, 128)"]);
+    let user: User = User { active: true, sign_in_count: 1, username: String::from("hi") };
+    let shown: &dyn DynShow = &user;
+    assert_eq!(shown.show_twice(), "hiworld");
+  }
+
+  #[test]
+  fn test_sized_methods() {
+    assert_eq!(sized_methods_example(), (9.0, 9.0));
+  }
+
+  #[test]
+  fn test_clone_box() {
+    let (shapes, copies) = clone_boxed_shapes();
+    let areas = |shapes: &[Box<dyn CloneShape>]| shapes.iter().map(|shape| shape.area()).collect::<Vec<f64>>();
+    assert_eq!(areas(&shapes), areas(&copies));
+    // Real copies, in different allocations.
+    let original: *const dyn CloneShape = &*shapes[0];
+    let copy: *const dyn CloneShape = &*copies[0];
+    assert!(!std::ptr::addr_eq(original, copy));
+  }
+}
@@ -0,0 +1,264 @@
+// -----------------------------------------------
+// # FAT POINTERS AND VTABLES
+//
+// A pointer to a sized type is one address: `&T`, `Box<T>` and `*const T` are all `usize`-sized.
+// A pointer to an UNSIZED type carries extra METADATA, making it two words wide (a "fat" pointer):
+//
+//   pointer               data               metadata
+//   --------------------  -----------------  ------------------------------------------------------
+//   &[T], &str            first element      length (number of elements / bytes)
+//   &dyn Trait            the value          pointer to the vtable of the value's type for `Trait`
+//
+// A VTABLE is a static table generated by the compiler for each (concrete type, trait) pair that is turned into a
+// trait object. In the current rustc it is laid out as:
+//
+//    vtable of `Circle` for `Shape`
+//    +-------------------------------+
+//    | drop_in_place::<Circle>       |   how to drop the value (or null, if it needs no drop)
+//    | size_of::<Circle>()           |   `size_of_val` on a trait object reads this
+//    | align_of::<Circle>()          |   `align_of_val` reads this
+//    | <Circle as Shape>::area       |   then one function pointer per method, in declaration order
+//    | <Circle as Shape>::name       |
+//    +-------------------------------+
+//
+// Neither the order of a fat pointer's two words nor the vtable layout is guaranteed by the language: the stable
+// way to read the metadata, `std::ptr::metadata`, is still unstable. So `split_fat_pointer` below checks its
+// assumptions at runtime rather than trusting them: it finds the data word by comparing with the thin pointer, and
+// the tests compare the vtable's size and alignment with `size_of_val` and `align_of_val`. It is a tool to look
+// at the representation, not something to build on.
+
+use std::fmt;
+use std::mem;
+
+// The two words of a fat pointer, identified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FatPointer {
+  pub data: usize,
+  pub metadata: usize,
+}
+
+// Splits any two-word pointer into its data address and its metadata.
+// Returns `None` for thin pointers (to sized types).
+pub fn split_fat_pointer<T: ?Sized>(pointer: &T) -> Option<FatPointer> {
+  if mem::size_of::<&T>() != 2 * mem::size_of::<usize>() {
+    return None;
+  }
+  // SAFETY: `&T` is exactly two `usize`s (checked above), and every bit pattern is a valid `[usize; 2]`.
+  let words: [usize; 2] = unsafe { mem::transmute_copy::<&T, [usize; 2]>(&pointer) };
+  // Casting to a thin pointer keeps the data address and discards the metadata: whichever word matches is the data.
+  let data: usize = pointer as *const T as *const () as usize;
+  match words {
+    [first, second] if first == data => Some(FatPointer { data, metadata: second }),
+    [first, second] if second == data => Some(FatPointer { data, metadata: first }),
+    _ => None,
+  }
+}
+
+// -----------------------------------------------
+// ## Reading a Vtable
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VtableHeader {
+  // Address of the `drop_in_place` function, or 0 if the type needs no drop glue.
+  pub drop_in_place: usize,
+  pub size: usize,
+  pub align: usize,
+}
+
+/// Reads the first three entries of the vtable of a trait object.
+///
+/// # Safety
+///
+/// `object` must be a trait object (`&dyn Trait`): for a slice or `str`, the metadata is a length, and would be
+/// dereferenced as an address. Relies on the current rustc vtable layout (see above).
+pub unsafe fn vtable_header<T: ?Sized>(object: &T) -> Option<VtableHeader> {
+  let FatPointer { metadata, .. } = split_fat_pointer(object)?;
+  let vtable: *const usize = metadata as *const usize;
+  // SAFETY: the caller guarantees that `metadata` points to a vtable, which starts with three `usize` entries.
+  let [drop_in_place, size, align] = unsafe { [*vtable, *vtable.add(1), *vtable.add(2)] };
+  Some(VtableHeader { drop_in_place, size, align })
+}
+
+// -----------------------------------------------
+// ## Inspecting Pointers
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointerKind {
+  Thin,
+  Slice { len: usize },
+  TraitObject { vtable: usize, header: VtableHeader },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerReport {
+  pub label: String,
+  // `size_of::<&T>()`: the size of the pointer itself.
+  pub pointer_size: usize,
+  // `size_of_val(value)`: the size of what it points to.
+  pub pointee_size: usize,
+  pub data: usize,
+  pub kind: PointerKind,
+}
+
+impl fmt::Display for PointerReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:<22} pointer {:>2} bytes -> {:>3} bytes at {:#x}", self.label, self.pointer_size, self.pointee_size, self.data)?;
+    match &self.kind {
+      PointerKind::Thin => Ok(()),
+      PointerKind::Slice { len } => write!(f, ", len {len}"),
+      PointerKind::TraitObject { vtable, header } => write!(
+        f,
+        ", vtable {vtable:#x} [drop {}, size {}, align {}]",
+        if header.drop_in_place == 0 { "none".to_string() } else { format!("{:#x}", header.drop_in_place) },
+        header.size,
+        header.align
+      ),
+    }
+  }
+}
+
+fn report<T: ?Sized>(label: &str, pointer: &T, kind: PointerKind) -> PointerReport {
+  PointerReport {
+    label: label.to_string(),
+    pointer_size: mem::size_of::<&T>(),
+    pointee_size: mem::size_of_val(pointer),
+    data: pointer as *const T as *const () as usize,
+    kind,
+  }
+}
+
+pub fn inspect_thin<T>(label: &str, pointer: &T) -> PointerReport {
+  report(label, pointer, PointerKind::Thin)
+}
+
+pub fn inspect_slice<T>(label: &str, slice: &[T]) -> PointerReport {
+  let len: usize = split_fat_pointer(slice).expect("a slice reference is a fat pointer").metadata;
+  report(label, slice, PointerKind::Slice { len })
+}
+
+pub fn inspect_str(label: &str, s: &str) -> PointerReport {
+  let len: usize = split_fat_pointer(s).expect("a str reference is a fat pointer").metadata;
+  report(label, s, PointerKind::Slice { len })
+}
+
+// A macro, because a function generic over "any trait object type" cannot be written: a trait is not a type
+// parameter. `inspect_dyn!("label", &value, dyn Trait)` coerces `&value` to `&dyn Trait`, which also guarantees that
+// `vtable_header` is given a trait object.
+#[macro_export]
+macro_rules! inspect_dyn {
+  ($label:expr, $object:expr, dyn $trait:path) => {{
+    let object: &dyn $trait = $object;
+    let vtable: usize = $crate::_13_trait_objects::_3_fat_pointers::split_fat_pointer(object)
+      .expect("a trait object reference is a fat pointer")
+      .metadata;
+    // SAFETY: `object` is a `&dyn Trait`.
+    let header = unsafe { $crate::_13_trait_objects::_3_fat_pointers::vtable_header(object) }.expect("a vtable");
+    $crate::_13_trait_objects::_3_fat_pointers::PointerReport {
+      label: ::std::string::ToString::to_string($label),
+      pointer_size: ::std::mem::size_of::<&dyn $trait>(),
+      pointee_size: ::std::mem::size_of_val(object),
+      data: object as *const dyn $trait as *const () as usize,
+      kind: $crate::_13_trait_objects::_3_fat_pointers::PointerKind::TraitObject { vtable, header },
+    }
+  }};
+}
+
+pub fn fat_pointers_example() -> Vec<PointerReport> {
+  use super::_1_dyn_trait::{Circle, Shape, Square};
+  use std::fmt::Debug;
+
+  let number: u64 = 7;
+  let array: [u16; 5] = [1, 2, 3, 4, 5];
+  let text: &str = "héllo";
+  let circle: Circle = Circle { radius: 1.0 };
+  let square: Square = Square { side: 2.0 };
+  let name: String = String::from("a String needs drop glue");
+
+  let reports: Vec<PointerReport> = vec![
+    inspect_thin("&u64", &number),
+    inspect_thin("&[u16; 5]", &array),
+    inspect_slice("&[u16]", &array[1..]),
+    inspect_str("&str", text),
+    inspect_dyn!("&dyn Shape (Circle)", &circle, dyn Shape),
+    inspect_dyn!("&dyn Shape (Square)", &square, dyn Shape),
+    inspect_dyn!("&dyn Debug (u64)", &number, dyn Debug),
+    inspect_dyn!("&dyn Debug (String)", &name, dyn Debug),
+  ];
+  for report in &reports {
+    crate::debug!("{report}");
+  }
+  reports
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_13_trait_objects::_1_dyn_trait::{Circle, Shape, Square};
+  use std::fmt::Debug;
+
+  #[test]
+  fn test_pointer_sizes() {
+    let word: usize = mem::size_of::<usize>();
+    assert_eq!(mem::size_of::<&u8>(), word);
+    assert_eq!(mem::size_of::<Box<[u8; 100]>>(), word);
+    assert_eq!(mem::size_of::<&[u8]>(), 2 * word);
+    assert_eq!(mem::size_of::<&str>(), 2 * word);
+    assert_eq!(mem::size_of::<&dyn Shape>(), 2 * word);
+    assert_eq!(mem::size_of::<Box<dyn Shape>>(), 2 * word);
+    // The metadata lives in the pointer, not in the value: `Option` still fits in the pointer's niche.
+    assert_eq!(mem::size_of::<Option<Box<dyn Shape>>>(), 2 * word);
+  }
+
+  #[test]
+  fn test_split_slice_pointer() {
+    let array: [u32; 4] = [1, 2, 3, 4];
+    let split: FatPointer = split_fat_pointer(&array[1..]).unwrap();
+    assert_eq!(split.data, &array[1] as *const u32 as usize);
+    assert_eq!(split.metadata, 3);
+    assert_eq!(split_fat_pointer(&array), None);
+    assert_eq!(split_fat_pointer("héllo").unwrap().metadata, 6);
+  }
+
+  #[test]
+  fn test_vtable_matches_size_and_align() {
+    let circle: Circle = Circle { radius: 1.0 };
+    let text: String = String::from("x");
+    let byte: u8 = 1;
+    // SAFETY (all `vtable_header` calls below): the arguments are `&dyn Debug`.
+    let header = |object: &dyn Debug| unsafe { vtable_header(object) }.unwrap();
+    for object in [&circle as &dyn Debug, &text, &byte] {
+      let header: VtableHeader = header(object);
+      assert_eq!(header.size, mem::size_of_val(object));
+      assert_eq!(header.align, mem::align_of_val(object));
+    }
+    // A `String` owns a heap buffer, so dropping it must run code; a `u8` needs no drop glue.
+    assert_ne!(header(&text).drop_in_place, 0);
+    assert_eq!(header(&byte).drop_in_place, 0);
+  }
+
+  #[test]
+  fn test_vtables_are_per_type() {
+    let (a, b) = (Circle { radius: 1.0 }, Circle { radius: 2.0 });
+    let square: Square = Square { side: 1.0 };
+    let vtable = |shape: &dyn Shape| split_fat_pointer(shape).unwrap().metadata;
+    assert_eq!(vtable(&a), vtable(&b));
+    assert_ne!(vtable(&a), vtable(&square));
+    // The same value behind two traits has two vtables, and the same data pointer.
+    let as_shape = split_fat_pointer(&a as &dyn Shape).unwrap();
+    let as_debug = split_fat_pointer(&a as &dyn Debug).unwrap();
+    assert_eq!(as_shape.data, as_debug.data);
+    assert_ne!(as_shape.metadata, as_debug.metadata);
+  }
+
+  #[test]
+  fn test_fat_pointers_example() {
+    let reports: Vec<PointerReport> = fat_pointers_example();
+    assert_eq!(reports.len(), 8);
+    assert_eq!(reports[1].pointee_size, 10);
+    assert_eq!(reports[2].kind, PointerKind::Slice { len: 4 });
+    assert_eq!(reports[2].pointee_size, 8);
+    assert!(matches!(reports[4].kind, PointerKind::TraitObject { header: VtableHeader { size: 8, align: 8, .. }, .. }));
+    assert!(reports[4].to_string().starts_with("&dyn Shape (Circle)    pointer 16 bytes ->   8 bytes at 0x"));
+    assert!(reports[6].to_string().ends_with("[drop none, size 8, align 8]"));
+  }
+}
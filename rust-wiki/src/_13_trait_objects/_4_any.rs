@@ -0,0 +1,199 @@
+// -----------------------------------------------
+// # DOWNCASTING WITH Any
+//
+// A trait object forgets its concrete type: a `&dyn Shape` only offers `Shape`'s methods. `std::any::Any` is the
+// trait that remembers it. It is implemented for every `'static` type, and has one method, `type_id()`, returning a
+// `TypeId`: a unique identifier of the type, compared at runtime. On top of it:
+//   - `<dyn Any>::is::<T>()`:            is the value a `T`?
+//   - `<dyn Any>::downcast_ref::<T>()`:  `Some(&T)` if it is, else `None` (also `downcast_mut`).
+//   - `Box<dyn Any>::downcast::<T>()`:   `Ok(Box<T>)`, or gives the box back as `Err`.
+//
+// `Any` requires `'static`: a type containing references could otherwise be downcast to the same type with a
+// longer lifetime, and lifetimes do not exist at runtime to be checked.
+//
+// Downcasting is a runtime type check, so it is a last resort: an enum, or a method on the trait, is usually better.
+// It is the right tool when the set of types is open-ended (plugins, type maps, panic payloads).
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+// -----------------------------------------------
+// ## Downcasting `dyn Any`
+pub fn describe_value(value: &dyn Any) -> String {
+  if let Some(n) = value.downcast_ref::<i32>() {
+    format!("an i32: {n}")
+  } else if let Some(s) = value.downcast_ref::<String>() {
+    format!("a String of {} bytes", s.len())
+  } else if let Some(s) = value.downcast_ref::<&str>() {
+    format!("a &str: {s:?}")
+  } else if value.is::<()>() {
+    String::from("the unit value")
+  } else {
+    String::from("something else")
+  }
+}
+
+// Downcasting only ever matches the exact type: a `u8` is not an `i32`, and a `Box<i32>` is not an `i32` either.
+// Beware the latter: with `value: Box<dyn Any>`, `describe_value(&value)` coerces the *box* itself to `&dyn Any`.
+// `&*value` is the `dyn Any` inside it.
+pub fn boxed_values_example() -> Vec<String> {
+  let values: Vec<Box<dyn Any>> = vec![Box::new(42_i32), Box::new(String::from("héllo")), Box::new("hi"), Box::new(()), Box::new(42_u8)];
+  let mut descriptions: Vec<String> = values.iter().map(|value| describe_value(&**value)).collect();
+  descriptions.push(describe_value(&values[0]));
+  descriptions
+}
+
+// Taking ownership back: `downcast` moves the value out of the `Box<dyn Any>`.
+pub fn take_string(value: Box<dyn Any>) -> Result<String, Box<dyn Any>> {
+  value.downcast::<String>().map(|string| *string)
+}
+
+// -----------------------------------------------
+// ## Downcasting Your Own Trait Objects
+//
+// A `&dyn Shape` cannot call `downcast_ref`, as that is a method of `dyn Any`. Making `Any` a supertrait fixes that:
+// since Rust 1.86, a `&dyn Component` can then be UPCAST (coerced) to `&dyn Any`, the supertrait's object.
+pub trait Component: Any + Debug {
+  fn name(&self) -> &'static str;
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Position {
+  pub x: i32,
+  pub y: i32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Health(pub u32);
+
+impl Component for Position {
+  fn name(&self) -> &'static str {
+    "position"
+  }
+}
+
+impl Component for Health {
+  fn name(&self) -> &'static str {
+    "health"
+  }
+}
+
+pub fn find_component<T: Component>(components: &[Box<dyn Component>]) -> Option<&T> {
+  components.iter().find_map(|component| {
+    let any: &dyn Any = &**component;
+    any.downcast_ref::<T>()
+  })
+}
+
+pub fn heal_all(components: &mut [Box<dyn Component>], amount: u32) -> usize {
+  let mut healed: usize = 0;
+  for component in components.iter_mut() {
+    let any: &mut dyn Any = &mut **component;
+    if let Some(Health(health)) = any.downcast_mut::<Health>() {
+      *health += amount;
+      healed += 1;
+    }
+  }
+  healed
+}
+
+// -----------------------------------------------
+// ## A Type Map
+//
+// A map keyed by `TypeId` holds at most one value of each type, and returns it with its real type: the `TypeId`
+// key guarantees that the downcast succeeds. (This is how e.g. web frameworks store per-request "extensions".)
+#[derive(Default)]
+pub struct TypeMap {
+  values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl TypeMap {
+  pub fn new() -> TypeMap {
+    TypeMap::default()
+  }
+
+  // Returns the previous value of that type, if any.
+  pub fn insert<T: 'static>(&mut self, value: T) -> Option<T> {
+    self.values.insert(TypeId::of::<T>(), Box::new(value)).map(|old| *old.downcast::<T>().expect("keyed by TypeId"))
+  }
+
+  pub fn get<T: 'static>(&self) -> Option<&T> {
+    self.values.get(&TypeId::of::<T>()).map(|value| value.downcast_ref::<T>().expect("keyed by TypeId"))
+  }
+
+  pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+    self.values.get_mut(&TypeId::of::<T>()).map(|value| value.downcast_mut::<T>().expect("keyed by TypeId"))
+  }
+
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+}
+
+// -----------------------------------------------
+// ## Panic Payloads
+//
+// `panic!` can carry any value, so `catch_unwind` and `JoinHandle::join` return it as a `Box<dyn Any + Send>`.
+// `panic!("literal")` carries a `&'static str`, and `panic!("{x}")` a `String`: a panic message is recovered by
+// trying both (see also _9_error_handling/_3_panics_and_exit_codes.rs).
+pub fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+  payload.downcast_ref::<&'static str>().copied().or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_describe_values() {
+    assert_eq!(
+      boxed_values_example(),
+      vec!["an i32: 42", "a String of 6 bytes", "a &str: \"hi\"", "the unit value", "something else", "something else"]
+    );
+  }
+
+  #[test]
+  fn test_take_string() {
+    assert_eq!(take_string(Box::new(String::from("mine"))).unwrap(), "mine");
+    let not_a_string: Box<dyn Any> = take_string(Box::new(7_i64)).unwrap_err();
+    assert_eq!(not_a_string.downcast_ref::<i64>(), Some(&7));
+  }
+
+  #[test]
+  fn test_components() {
+    let mut components: Vec<Box<dyn Component>> = vec![Box::new(Position { x: 1, y: 2 }), Box::new(Health(10))];
+    assert_eq!(find_component::<Position>(&components), Some(&Position { x: 1, y: 2 }));
+    assert_eq!(heal_all(&mut components, 5), 1);
+    assert_eq!(find_component::<Health>(&components), Some(&Health(15)));
+    assert_eq!(components.iter().map(|component| component.name()).collect::<Vec<_>>(), vec!["position", "health"]);
+  }
+
+  #[test]
+  fn test_type_map() {
+    let mut map: TypeMap = TypeMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(5_u32), None);
+    map.insert(String::from("hello"));
+    assert_eq!(map.insert(6_u32), Some(5));
+    *map.get_mut::<String>().unwrap() += " world";
+    assert_eq!(map.get::<String>().map(String::as_str), Some("hello world"));
+    assert_eq!(map.get::<u32>(), Some(&6));
+    assert_eq!(map.get::<i32>(), None);
+    assert_eq!(map.len(), 2);
+  }
+
+  #[test]
+  fn test_panic_message() {
+    let literal = std::panic::catch_unwind(|| panic!("literal")).unwrap_err();
+    let formatted = std::panic::catch_unwind(|| panic!("formatted {}", 42)).unwrap_err();
+    let other = std::panic::catch_unwind(|| std::panic::panic_any(42_u8)).unwrap_err();
+    assert_eq!(panic_message(&*literal), Some("literal"));
+    assert_eq!(panic_message(&*formatted), Some("formatted 42"));
+    assert_eq!(panic_message(&*other), None);
+  }
+}
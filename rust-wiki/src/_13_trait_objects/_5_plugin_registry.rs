@@ -0,0 +1,275 @@
+// -----------------------------------------------
+// # EXAMPLE: A PLUGIN REGISTRY
+//
+// An application that loads plugins cannot know their types when it is compiled, only the trait they implement:
+// the registry stores them as `Box<dyn Plugin>`. This combines the chapter:
+//   - trait objects in a heterogeneous collection (_1_dyn_trait.rs),
+//   - an object-safe trait, with a `where Self: Sized` constructor (_2_object_safety.rs),
+//   - a supertrait with a default method, `Licensed`, from `rustlings/exercises/15_traits`,
+//   - downcasting a plugin back to its concrete type through `Any` (_4_any.rs),
+//   - factories stored as plain function pointers, to create plugins by name from a configuration.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+// -----------------------------------------------
+// ## Licensed
+//
+// `rustlings/exercises/15_traits/traits3.rs` and `traits4.rs`: a trait whose only method has a default
+// implementation, so implementing it takes an empty `impl` block.
+pub trait Licensed {
+  fn licensing_info(&self) -> String {
+    String::from("Default license")
+  }
+}
+
+pub struct SomeSoftware;
+pub struct OtherSoftware;
+
+impl Licensed for SomeSoftware {}
+impl Licensed for OtherSoftware {}
+
+// -----------------------------------------------
+// ## The Plugin Trait
+//
+// Every plugin is `Licensed` (a supertrait: `Plugin` methods can call `licensing_info`, and a `dyn Plugin`'s vtable
+// includes it) and `Any` (so the registry can hand out the concrete type).
+pub trait Plugin: Licensed + Any {
+  fn name(&self) -> &str;
+
+  // Transforms a line of text.
+  fn run(&mut self, input: &str) -> String;
+
+  // Not in the vtable: called through the factory, where the type is known.
+  fn create() -> Self
+  where
+    Self: Sized;
+}
+
+pub struct Uppercase;
+
+impl Licensed for Uppercase {}
+
+impl Plugin for Uppercase {
+  fn name(&self) -> &str {
+    "uppercase"
+  }
+
+  fn run(&mut self, input: &str) -> String {
+    input.to_uppercase()
+  }
+
+  fn create() -> Self {
+    Uppercase
+  }
+}
+
+pub struct Reverse;
+
+impl Licensed for Reverse {
+  fn licensing_info(&self) -> String {
+    String::from("MIT")
+  }
+}
+
+impl Plugin for Reverse {
+  fn name(&self) -> &str {
+    "reverse"
+  }
+
+  fn run(&mut self, input: &str) -> String {
+    input.chars().rev().collect()
+  }
+
+  fn create() -> Self {
+    Reverse
+  }
+}
+
+// A plugin with state: it counts the lines it has seen.
+#[derive(Default)]
+pub struct LineNumbers {
+  pub lines_seen: usize,
+}
+
+impl Licensed for LineNumbers {
+  fn licensing_info(&self) -> String {
+    String::from("Apache-2.0")
+  }
+}
+
+impl Plugin for LineNumbers {
+  fn name(&self) -> &str {
+    "line-numbers"
+  }
+
+  fn run(&mut self, input: &str) -> String {
+    self.lines_seen += 1;
+    format!("{:>3} {input}", self.lines_seen)
+  }
+
+  fn create() -> Self {
+    LineNumbers::default()
+  }
+}
+
+// -----------------------------------------------
+// ## The Registry
+
+#[derive(Debug, PartialEq)]
+pub enum RegistryError {
+  AlreadyRegistered(String),
+  UnknownPlugin(String),
+}
+
+impl fmt::Display for RegistryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RegistryError::AlreadyRegistered(name) => write!(f, "a plugin named {name:?} is already registered"),
+      RegistryError::UnknownPlugin(name) => write!(f, "no plugin named {name:?}"),
+    }
+  }
+}
+
+impl Error for RegistryError {}
+
+// A factory is a plain `fn` pointer: `Uppercase::create` is not a closure, it captures nothing. Wrapping it in a
+// generic function that boxes the result turns each `fn() -> T` into the same type, `fn() -> Box<dyn Plugin>`.
+pub type Factory = fn() -> Box<dyn Plugin>;
+
+fn factory<P: Plugin>() -> Box<dyn Plugin> {
+  Box::new(P::create())
+}
+
+// The built-in plugins, by name.
+pub fn builtin_factories() -> BTreeMap<&'static str, Factory> {
+  BTreeMap::from([
+    ("uppercase", factory::<Uppercase> as Factory),
+    ("reverse", factory::<Reverse>),
+    ("line-numbers", factory::<LineNumbers>),
+  ])
+}
+
+#[derive(Default)]
+pub struct Registry {
+  // In registration order, which is the order `run_all` applies them in.
+  plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl Registry {
+  pub fn new() -> Registry {
+    Registry::default()
+  }
+
+  pub fn register(&mut self, plugin: Box<dyn Plugin>) -> Result<(), RegistryError> {
+    if self.plugins.iter().any(|registered| registered.name() == plugin.name()) {
+      return Err(RegistryError::AlreadyRegistered(plugin.name().to_string()));
+    }
+    self.plugins.push(plugin);
+    Ok(())
+  }
+
+  // Creates the plugins listed in a configuration, e.g. "reverse, uppercase".
+  pub fn from_config(config: &str, factories: &BTreeMap<&'static str, Factory>) -> Result<Registry, RegistryError> {
+    let mut registry: Registry = Registry::new();
+    for name in config.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+      let create: &Factory = factories.get(name).ok_or_else(|| RegistryError::UnknownPlugin(name.to_string()))?;
+      registry.register(create())?;
+    }
+    Ok(registry)
+  }
+
+  pub fn names(&self) -> Vec<&str> {
+    self.plugins.iter().map(|plugin| plugin.name()).collect()
+  }
+
+  // Pipes the input through every plugin, in order.
+  pub fn run_all(&mut self, input: &str) -> String {
+    self.plugins.iter_mut().fold(input.to_string(), |text, plugin| plugin.run(&text))
+  }
+
+  pub fn run(&mut self, name: &str, input: &str) -> Result<String, RegistryError> {
+    let plugin: &mut Box<dyn Plugin> = self
+      .plugins
+      .iter_mut()
+      .find(|plugin| plugin.name() == name)
+      .ok_or_else(|| RegistryError::UnknownPlugin(name.to_string()))?;
+    Ok(plugin.run(input))
+  }
+
+  // "name: license" for every plugin: `licensing_info` is called through the `dyn Plugin` vtable, which includes
+  // the supertrait's methods.
+  pub fn licenses(&self) -> Vec<String> {
+    self.plugins.iter().map(|plugin| format!("{}: {}", plugin.name(), plugin.licensing_info())).collect()
+  }
+
+  // The first plugin of type `P`, with its concrete type (and so its fields) back.
+  pub fn get<P: Plugin>(&self) -> Option<&P> {
+    self.plugins.iter().find_map(|plugin| {
+      let any: &dyn Any = &**plugin;
+      any.downcast_ref::<P>()
+    })
+  }
+
+  pub fn unregister(&mut self, name: &str) -> Option<Box<dyn Plugin>> {
+    let index: usize = self.plugins.iter().position(|plugin| plugin.name() == name)?;
+    Some(self.plugins.remove(index))
+  }
+}
+
+pub fn plugin_registry_example() -> Result<Vec<String>, RegistryError> {
+  let mut registry: Registry = Registry::from_config("reverse, uppercase, line-numbers", &builtin_factories())?;
+  let mut output: Vec<String> = ["abc", "rust"].iter().map(|line| registry.run_all(line)).collect();
+  output.extend(registry.licenses());
+  Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_licensing_info() {
+    assert_eq!(SomeSoftware.licensing_info(), "Default license");
+    assert_eq!(OtherSoftware.licensing_info(), "Default license");
+  }
+
+  #[test]
+  fn test_plugin_registry_example() {
+    assert_eq!(
+      plugin_registry_example().unwrap(),
+      vec!["  1 CBA", "  2 TSUR", "reverse: MIT", "uppercase: Default license", "line-numbers: Apache-2.0"]
+    );
+  }
+
+  #[test]
+  fn test_register_errors() {
+    let mut registry: Registry = Registry::new();
+    registry.register(Box::new(Reverse)).unwrap();
+    assert_eq!(registry.register(Box::new(Reverse)), Err(RegistryError::AlreadyRegistered("reverse".to_string())));
+    assert_eq!(
+      Registry::from_config("reverse, rot13", &builtin_factories()).err(),
+      Some(RegistryError::UnknownPlugin("rot13".to_string()))
+    );
+    assert_eq!(registry.run("rot13", "x").unwrap_err().to_string(), "no plugin named \"rot13\"");
+  }
+
+  #[test]
+  fn test_downcast_plugin_state() {
+    let mut registry: Registry = Registry::from_config("line-numbers", &builtin_factories()).unwrap();
+    registry.run("line-numbers", "a").unwrap();
+    registry.run("line-numbers", "b").unwrap();
+    assert_eq!(registry.get::<LineNumbers>().map(|plugin| plugin.lines_seen), Some(2));
+    assert!(registry.get::<Reverse>().is_none());
+  }
+
+  #[test]
+  fn test_unregister() {
+    let mut registry: Registry = Registry::from_config("uppercase,reverse", &builtin_factories()).unwrap();
+    assert_eq!(registry.unregister("uppercase").map(|plugin| plugin.name().to_string()), Some("uppercase".to_string()));
+    assert_eq!(registry.names(), vec!["reverse"]);
+    assert_eq!(registry.run_all("ab"), "ba");
+  }
+}
@@ -0,0 +1,247 @@
+// -----------------------------------------------
+// # ADVANCED TRAITS
+//
+// This chapter picks up where _3_datatypes_and_traits/_4_traits.rs and _4_generics/_1_generic_types.rs stop:
+//   - overloading operators with the `std::ops` traits (here).
+//   - blanket impls: implementing a trait for every type satisfying a bound (_2_blanket_impls.rs).
+//   - associated consts (_3_associated_consts.rs).
+//   - supertraits, and calling methods with the same name (_4_supertraits.rs).
+//   - the newtype pattern, to get around the orphan rule and to give units to numbers (_5_newtype.rs).
+//   - generic associated types (GATs) (_6_generic_associated_types.rs).
+//
+// -----------------------------------------------
+// ## Operator Overloading
+//
+// An operator is sugar for a method of a trait in `std::ops`: `a + b` is `Add::add(a, b)`, `-a` is `Neg::neg(a)`,
+// `a += b` is `AddAssign::add_assign(&mut a, b)`, and `a[i]` is `*Index::index(&a, i)`. Implementing the trait for
+// a type makes the operator work on it. Only the operators listed in `std::ops` (and the comparisons of
+// `std::cmp`) can be overloaded: no new operators, and no `&&`, `||` or `=`.
+//
+// The binary operator traits are generic over the right-hand side, with a DEFAULT TYPE PARAMETER:
+//
+//    pub trait Add<Rhs = Self> {
+//      type Output;
+//      fn add(self, rhs: Rhs) -> Self::Output;
+//    }
+//
+// `impl Add for Point` means `impl Add<Point> for Point`, while `impl Add<(T, U)> for Point` adds a tuple to a
+// point. `Output` is an associated type rather than a parameter: a given `Self + Rhs` has one result type.
+//
+// Here `Point<T, U>` is the generic struct of _4_generics/_1_generic_types.rs. Each impl requires of `T` and `U`
+// exactly the operator it uses on them, so `Point<i32, f64>` can be added but only `Point<T, T>` can be indexed.
+
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub};
+
+use crate::_4_generics::_1_generic_types::Point;
+
+// `p + q`: componentwise. `self` and `rhs` are taken by value, so both points are moved (or copied, if `Copy`).
+impl<T: Add<Output = T>, U: Add<Output = U>> Add for Point<T, U> {
+  type Output = Point<T, U>;
+
+  fn add(self, rhs: Point<T, U>) -> Point<T, U> {
+    Point { x: self.x + rhs.x, y: self.y + rhs.y }
+  }
+}
+
+// `p + (dx, dy)`: a different `Rhs`, so a second, non-overlapping impl of `Add`.
+impl<T: Add<Output = T>, U: Add<Output = U>> Add<(T, U)> for Point<T, U> {
+  type Output = Point<T, U>;
+
+  fn add(self, (dx, dy): (T, U)) -> Point<T, U> {
+    Point { x: self.x + dx, y: self.y + dy }
+  }
+}
+
+impl<T: Sub<Output = T>, U: Sub<Output = U>> Sub for Point<T, U> {
+  type Output = Point<T, U>;
+
+  fn sub(self, rhs: Point<T, U>) -> Point<T, U> {
+    Point { x: self.x - rhs.x, y: self.y - rhs.y }
+  }
+}
+
+// `-p`. A unary operator has no `Rhs`.
+impl<T: Neg<Output = T>, U: Neg<Output = U>> Neg for Point<T, U> {
+  type Output = Point<T, U>;
+
+  fn neg(self) -> Point<T, U> {
+    Point { x: -self.x, y: -self.y }
+  }
+}
+
+// `p * k`: scaling by a scalar, so `Rhs` is `T` and both coordinates must have that type.
+// (`k * p` would be `impl Mul<Point<i32, i32>> for i32`: allowed, as `Point` is local, but it has to be written
+// for each concrete scalar type, since `impl<T> Mul<Point<T, T>> for T` implements a foreign trait for any `T`.)
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T, T> {
+  type Output = Point<T, T>;
+
+  fn mul(self, k: T) -> Point<T, T> {
+    Point { x: self.x * k, y: self.y * k }
+  }
+}
+
+// `p += q`: takes `&mut self` and returns nothing, so nothing is moved out of `p`.
+impl<T: AddAssign, U: AddAssign> AddAssign for Point<T, U> {
+  fn add_assign(&mut self, rhs: Point<T, U>) {
+    self.x += rhs.x;
+    self.y += rhs.y;
+  }
+}
+
+// Operators on references. `p + q` consumes both points, which matters when they are not `Copy` (e.g.
+// `Point<String, String>`). std implements the arithmetic traits for `&i32 + &i32` etc.; the same can be done here:
+// `&p + &q` borrows both and builds a new point, at the cost of requiring `&T: Add`.
+impl<'a, T, U> Add for &'a Point<T, U>
+where
+  &'a T: Add<Output = T>,
+  &'a U: Add<Output = U>,
+{
+  type Output = Point<T, U>;
+
+  fn add(self, rhs: &'a Point<T, U>) -> Point<T, U> {
+    Point { x: &self.x + &rhs.x, y: &self.y + &rhs.y }
+  }
+}
+
+// -----------------------------------------------
+// ## Indexing
+//
+// `Index::index` returns a reference, `&Self::Output`, and `p[i]` dereferences it: `p[0]` is a place, not a copy.
+// `IndexMut` (which requires `Index`) makes `p[0] = 5` work. The index type is a parameter, so a `Point` can be
+// indexed both by position and by `Axis`. An out-of-range index panics, as it does for slices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+  X,
+  Y,
+}
+
+impl<T> Index<Axis> for Point<T, T> {
+  type Output = T;
+
+  fn index(&self, axis: Axis) -> &T {
+    match axis {
+      Axis::X => &self.x,
+      Axis::Y => &self.y,
+    }
+  }
+}
+
+impl<T> IndexMut<Axis> for Point<T, T> {
+  fn index_mut(&mut self, axis: Axis) -> &mut T {
+    match axis {
+      Axis::X => &mut self.x,
+      Axis::Y => &mut self.y,
+    }
+  }
+}
+
+impl<T> Index<usize> for Point<T, T> {
+  type Output = T;
+
+  fn index(&self, index: usize) -> &T {
+    match index {
+      0 => &self.x,
+      1 => &self.y,
+      _ => panic!("index out of bounds: a point has 2 coordinates but the index is {index}"),
+    }
+  }
+}
+
+impl<T> IndexMut<usize> for Point<T, T> {
+  fn index_mut(&mut self, index: usize) -> &mut T {
+    match index {
+      0 => &mut self.x,
+      1 => &mut self.y,
+      _ => panic!("index out of bounds: a point has 2 coordinates but the index is {index}"),
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Writing Generic Code Over Operators
+//
+// With the operators implemented, `Point` works in any code written against the `std::ops` traits, like the
+// `using_generic_enums2<T: Add<Output = T>>` of _4_generics/_1_generic_types.rs.
+pub fn sum_all<T: Add<Output = T>>(first: T, rest: impl IntoIterator<Item = T>) -> T {
+  rest.into_iter().fold(first, |total, item| total + item)
+}
+
+// The centroid of a polygon's vertices.
+pub fn centroid(points: &[Point<f64, f64>]) -> Option<Point<f64, f64>> {
+  let (&first, rest) = points.split_first()?;
+  Some(sum_all(first, rest.iter().copied()) * (1.0 / points.len() as f64))
+}
+
+pub fn operator_overloading_example() -> Vec<Point<i32, i32>> {
+  let p: Point<i32, i32> = Point { x: 1, y: 2 };
+  let q: Point<i32, i32> = Point { x: 10, y: 20 };
+
+  let mut r: Point<i32, i32> = p + q;
+  r += Point { x: 100, y: 100 };
+  r[Axis::Y] *= 2;
+  r[0] -= 1;
+
+  vec![p + q, p - q, -p, p * 3, p + (5, 5), r]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_operator_overloading_example() {
+    assert_eq!(
+      operator_overloading_example(),
+      vec![
+        Point { x: 11, y: 22 },
+        Point { x: -9, y: -18 },
+        Point { x: -1, y: -2 },
+        Point { x: 3, y: 6 },
+        Point { x: 6, y: 7 },
+        Point { x: 110, y: 244 },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_mixed_coordinate_types() {
+    let p: Point<i32, f64> = Point { x: 1, y: 0.5 } + Point { x: 2, y: 0.25 };
+    assert_eq!(p, Point { x: 3, y: 0.75 });
+    assert_eq!(-p, Point { x: -3, y: -0.75 });
+  }
+
+  #[test]
+  // Clippy suggests `a + b`, since `u8` is `Copy`: the references are what is being tested.
+  #[allow(clippy::op_ref)]
+  fn test_add_by_reference() {
+    // `&String + &String` is not implemented, so `&p + &p` does not compile for string points; integers are fine.
+    let (a, b) = (Point { x: 1_u8, y: 2_u8 }, Point { x: 3_u8, y: 4_u8 });
+    assert_eq!(&a + &b, Point { x: 4, y: 6 });
+    assert_eq!(a, Point { x: 1, y: 2 });
+  }
+
+  #[test]
+  fn test_index() {
+    let mut p: Point<char, char> = Point { x: 'a', y: 'b' };
+    assert_eq!((p[0], p[1]), ('a', 'b'));
+    assert_eq!((p[Axis::X], p[Axis::Y]), ('a', 'b'));
+    p[1] = 'z';
+    assert_eq!(p.y, 'z');
+  }
+
+  #[test]
+  #[should_panic(expected = "index out of bounds: a point has 2 coordinates but the index is 2")]
+  fn test_index_out_of_bounds() {
+    let p: Point<u8, u8> = Point { x: 0, y: 0 };
+    let _ = p[2];
+  }
+
+  #[test]
+  fn test_centroid() {
+    let square: [Point<f64, f64>; 4] =
+      [Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }, Point { x: 2.0, y: 2.0 }, Point { x: 0.0, y: 2.0 }];
+    assert_eq!(centroid(&square), Some(Point { x: 1.0, y: 1.0 }));
+    assert_eq!(centroid(&[]), None);
+    assert_eq!(sum_all(1, [2, 3]), 6);
+  }
+}
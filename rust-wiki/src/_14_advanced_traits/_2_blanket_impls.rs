@@ -0,0 +1,150 @@
+// -----------------------------------------------
+// # BLANKET IMPLEMENTATIONS
+//
+// A BLANKET IMPL implements a trait for every type meeting a bound, instead of for one named type:
+//
+//    impl<T: Display> Show for T { ... }
+//
+// std is built on them:
+//   - `impl<T: Display + ?Sized> ToString for T`:      every `Display` type gets `.to_string()`.
+//   - `impl<T, U: From<T>> Into<U> for T`:             implementing `From` is enough to get `Into`.
+//   - `impl<I: Iterator> IntoIterator for I`:          every iterator can be used in a `for` loop.
+//   - `impl<T: ?Sized> Borrow<T> for T`, `impl<T: Any + ?Sized> Any for T`, ...
+//
+// The wiki has already used a few: `DebugShape` (_13_trait_objects/_1_dyn_trait.rs), `DynShow` and `CloneShape`
+// (_13_trait_objects/_2_object_safety.rs).
+//
+// ## Coherence
+//
+// Rust requires that at most one impl applies to any type ("coherence"), and checks it when the impls are
+// written, not when they are used. A blanket impl therefore claims every type that could ever meet its bound, and
+// forbids any other impl that might overlap with it:
+//
+//    impl<T: Display> Show for T { ... }
+//    impl Show for i32 { .. }    // Error! conflicting implementations of trait `Show` for type `i32`
+//
+// This is also why the blanket impl below is for a `Show` trait of its own, rather than for the `Show` of
+// _3_datatypes_and_traits/_4_traits.rs: that one has an associated type and a constructor (`alt_show`) which no
+// single impl could define for every `Display` type, and types like `User` already implement it by hand.
+//
+// The ORPHAN RULE limits who can write an impl: a crate may only `impl Trait for Type` if the trait or the type is
+// its own. For a blanket impl `impl<T> Trait for T`, that means the trait must be local: `impl<T: Display> Debug
+// for T` is an error in any crate but std (E0210). See _5_newtype.rs for the way around it.
+
+use std::fmt::{Debug, Display};
+
+// -----------------------------------------------
+// ## A Blanket Impl over `Display`
+pub trait Show {
+  fn show(&self) -> String;
+
+  // Provided methods come along with the blanket impl, as with any impl.
+  fn show_twice(&self) -> String {
+    format!("{}{}", self.show(), self.show())
+  }
+}
+
+// `?Sized` so that `str` is included too: a type parameter is implicitly `Sized` unless it opts out.
+impl<T: Display + ?Sized> Show for T {
+  fn show(&self) -> String {
+    format!("<{self}>")
+  }
+}
+
+// A function bounded by `Show` now accepts every `Display` type, including those of other crates.
+pub fn show_all<T: Show + ?Sized>(values: &[&T]) -> Vec<String> {
+  values.iter().map(|value| value.show()).collect()
+}
+
+// -----------------------------------------------
+// ## Extension Traits
+//
+// A blanket impl is also how methods are added to types of other crates (including std): define a trait with the
+// methods, all provided, and implement it for every type with the needed bound. Bringing the trait into scope with
+// `use` makes the methods appear. `itertools::Itertools` is built this way on top of `Iterator`.
+pub trait DebugExt: Debug {
+  fn debug_string(&self) -> String {
+    format!("{self:?}")
+  }
+
+  // The `{:#?}` output, on one line per field.
+  fn debug_lines(&self) -> Vec<String> {
+    format!("{self:#?}").lines().map(str::to_string).collect()
+  }
+}
+
+impl<T: Debug + ?Sized> DebugExt for T {}
+
+// -----------------------------------------------
+// ## Conditional Implementations
+//
+// A blanket impl can also be restricted to some instantiations of a generic type: `Pair<T>` only has `largest` when
+// `T: PartialOrd`, and only implements `Show` (through the blanket impl) when `T: Display`, through the `Display`
+// impl below. This is how `Vec<T>: Clone` only holds when `T: Clone`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair<T> {
+  pub first: T,
+  pub second: T,
+}
+
+impl<T: PartialOrd> Pair<T> {
+  pub fn largest(&self) -> &T {
+    if self.second > self.first {
+      &self.second
+    } else {
+      &self.first
+    }
+  }
+}
+
+impl<T: Display> Display for Pair<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "({}, {})", self.first, self.second)
+  }
+}
+
+pub fn blanket_impls_example() -> Vec<String> {
+  let pair: Pair<u8> = Pair { first: 3, second: 7 };
+  vec![
+    42.show(),
+    "text".show(),
+    'c'.show_twice(),
+    pair.show(),
+    pair.largest().show(),
+    // `Pair<Vec<u8>>` is `Debug` but not `Display`: it gets `DebugExt`, but no `show`.
+    Pair { first: vec![1_u8], second: vec![] }.debug_string(),
+  ]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_blanket_impls_example() {
+    assert_eq!(
+      blanket_impls_example(),
+      vec!["<42>", "<text>", "<c><c>", "<(3, 7)>", "<7>", "Pair { first: [1], second: [] }"]
+    );
+  }
+
+  #[test]
+  fn test_show_all_unsized() {
+    let words: [&str; 2] = ["a", "b"];
+    assert_eq!(show_all::<str>(&words), vec!["<a>", "<b>"]);
+    assert_eq!(show_all(&[&1.5, &2.0]), vec!["<1.5>", "<2>"]);
+  }
+
+  #[test]
+  fn test_std_blanket_impls() {
+    // `ToString` through `Display`, `Into` through `From`.
+    assert_eq!(Pair { first: 'a', second: 'b' }.to_string(), "(a, b)");
+    let widened: i64 = 7_i32.into();
+    assert_eq!(widened, 7);
+  }
+
+  #[test]
+  fn test_debug_lines() {
+    assert_eq!(Pair { first: 1, second: 2 }.debug_lines(), vec!["Pair {", "    first: 1,", "    second: 2,", "}"]);
+  }
+}
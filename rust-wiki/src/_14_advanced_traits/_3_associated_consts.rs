@@ -0,0 +1,175 @@
+// -----------------------------------------------
+// # ASSOCIATED CONSTS
+//
+// Besides methods and types, traits and impls can hold constants:
+//
+//    trait Polygon {
+//      const SIDES: u32;                      // each implementor must define it...
+//      const NAME: &'static str = "polygon";  // ...or may override a default
+//    }
+//
+// They are read through the type, not a value: `Triangle::SIDES`, or `P::SIDES` for a type parameter `P: Polygon`.
+// Unlike a method returning a number, an associated const is evaluated at compile time, so it can be used where a
+// constant is required: in `const` items, array lengths of concrete types, `match` patterns.
+//
+// Two limits:
+//   - a trait with associated consts is not dyn compatible (_13_trait_objects/_2_object_safety.rs): a const is not
+//     a method, so it has no slot in the vtable. Expose it through a method as well if it is needed on `dyn Trait`.
+//   - in generic code, `[u8; P::SIDES]` is not (yet) allowed: an array length may not depend on a type parameter.
+//
+// Inherent impls can also have associated consts: `u32::MAX`, `f64::EPSILON`, `Point::ORIGIN` below.
+
+use crate::_4_generics::_1_generic_types::Point;
+
+pub trait Polygon {
+  const SIDES: u32;
+  const NAME: &'static str = "polygon";
+
+  fn side(&self) -> f64;
+
+  fn perimeter(&self) -> f64 {
+    f64::from(Self::SIDES) * self.side()
+  }
+}
+
+pub struct Triangle {
+  pub side: f64,
+}
+
+pub struct Square {
+  pub side: f64,
+}
+
+pub struct Hexagon {
+  pub side: f64,
+}
+
+impl Polygon for Triangle {
+  const SIDES: u32 = 3;
+  const NAME: &'static str = "triangle";
+
+  fn side(&self) -> f64 {
+    self.side
+  }
+}
+
+impl Polygon for Square {
+  const SIDES: u32 = 4;
+  const NAME: &'static str = "square";
+
+  fn side(&self) -> f64 {
+    self.side
+  }
+}
+
+// Keeps the default `NAME`.
+impl Polygon for Hexagon {
+  const SIDES: u32 = 6;
+
+  fn side(&self) -> f64 {
+    self.side
+  }
+}
+
+// Generic code reads the consts of its type parameter; a `const fn` can compute with them at compile time.
+pub const fn interior_angle_sum<P: Polygon>() -> u32 {
+  (P::SIDES - 2) * 180
+}
+
+pub fn describe<P: Polygon>(polygon: &P) -> String {
+  format!("{} with {} sides, perimeter {}", P::NAME, P::SIDES, polygon.perimeter())
+}
+
+// Evaluated by the compiler: a wrong const here fails the build, not a test.
+const _: () = assert!(interior_angle_sum::<Square>() == 360);
+
+// With a concrete type, an associated const is an ordinary constant, usable as an array length.
+pub fn square_corners() -> [Point<i32, i32>; Square::SIDES as usize] {
+  [Point { x: 0, y: 0 }, Point { x: 1, y: 0 }, Point { x: 1, y: 1 }, Point { x: 0, y: 1 }]
+}
+
+// -----------------------------------------------
+// ## Consts on Inherent Impls
+impl Point<i32, i32> {
+  pub const ORIGIN: Point<i32, i32> = Point { x: 0, y: 0 };
+}
+
+// Associated consts can be used as patterns.
+pub fn classify(point: Point<i32, i32>) -> &'static str {
+  match point {
+    Point::<i32, i32>::ORIGIN => "origin",
+    Point { x: 0, .. } | Point { y: 0, .. } => "on an axis",
+    _ => "elsewhere",
+  }
+}
+
+// -----------------------------------------------
+// ## A Trait of Constants
+//
+// A trait can consist of consts only, to describe a type to generic code. This is the shape of `num_traits::Bounded`
+// or `Zero`: `i32::MAX` is an inherent const, so generic code cannot name it, but `T::MAX` through a trait it can.
+pub trait Bounded: Copy + PartialOrd {
+  const MIN: Self;
+  const MAX: Self;
+}
+
+macro_rules! impl_bounded {
+  ($($t:ty),*) => {
+    $(impl Bounded for $t {
+      const MIN: Self = <$t>::MIN;
+      const MAX: Self = <$t>::MAX;
+    })*
+  };
+}
+
+impl_bounded!(u8, i8, u32, i32, f64);
+
+pub fn clamp_to<T: Bounded>(value: T, low: Option<T>, high: Option<T>) -> T {
+  let (low, high) = (low.unwrap_or(T::MIN), high.unwrap_or(T::MAX));
+  if value < low {
+    low
+  } else if value > high {
+    high
+  } else {
+    value
+  }
+}
+
+// The smallest and largest of a slice, starting from the opposite bounds (so an empty slice gives `(MAX, MIN)`).
+pub fn min_max<T: Bounded>(values: &[T]) -> (T, T) {
+  values.iter().fold((T::MAX, T::MIN), |(min, max), &value| {
+    (if value < min { value } else { min }, if value > max { value } else { max })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_polygon_consts() {
+    assert_eq!(describe(&Triangle { side: 2.0 }), "triangle with 3 sides, perimeter 6");
+    assert_eq!(describe(&Hexagon { side: 1.5 }), "polygon with 6 sides, perimeter 9");
+    assert_eq!(interior_angle_sum::<Triangle>(), 180);
+    assert_eq!(interior_angle_sum::<Hexagon>(), 720);
+    assert_eq!(Square { side: 0.5 }.perimeter(), 2.0);
+    assert_eq!(square_corners().len(), 4);
+  }
+
+  #[test]
+  fn test_const_patterns() {
+    assert_eq!(classify(Point::<i32, i32>::ORIGIN), "origin");
+    assert_eq!(classify(Point { x: 0, y: 5 }), "on an axis");
+    assert_eq!(classify(Point { x: 2, y: 5 }), "elsewhere");
+  }
+
+  #[test]
+  fn test_bounded() {
+    assert_eq!(clamp_to(300_i32, None, Some(255)), 255);
+    assert_eq!(clamp_to(-3_i8, Some(0), None), 0);
+    assert_eq!(clamp_to(7_u8, None, None), 7);
+    assert_eq!(min_max(&[3_u32, 9, 1]), (1, 9));
+    assert_eq!(min_max::<i8>(&[]), (i8::MAX, i8::MIN));
+    assert_eq!(min_max(&[0.5, -1.0]), (-1.0, 0.5));
+  }
+}
@@ -0,0 +1,174 @@
+// -----------------------------------------------
+// # SUPERTRAITS
+//
+// `trait OutlinePrint: Display` declares that every `OutlinePrint` type must also implement `Display`: `Display` is a
+// SUPERTRAIT of `OutlinePrint`. In return, `OutlinePrint`'s methods may use `Display`'s (here, `to_string`), and so
+// may any code bounded by `T: OutlinePrint` alone. Implementing `OutlinePrint` for a type that is not `Display` is an
+// error (E0277), even with an empty `impl` block.
+//
+// It is a requirement, not inheritance: the supertrait's methods are still implemented in their own `impl` block,
+// and nothing is overridden. Several supertraits are combined with `+`: `trait Plugin: Licensed + Any`
+// (_13_trait_objects/_5_plugin_registry.rs) and `trait Component: Any + Debug` (_13_trait_objects/_4_any.rs).
+
+use std::fmt;
+
+use crate::_4_generics::_1_generic_types::Point;
+
+pub trait OutlinePrint: fmt::Display {
+  fn outline(&self) -> String {
+    let text: String = self.to_string();
+    let width: usize = text.chars().count();
+    let border: String = "*".repeat(width + 4);
+    format!("{border}\n* {text} *\n{border}")
+  }
+}
+
+impl<T: fmt::Display, U: fmt::Display> fmt::Display for Point<T, U> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "({}, {})", self.x, self.y)
+  }
+}
+
+// Without the `Display` impl above, this line would not compile.
+impl<T: fmt::Display, U: fmt::Display> OutlinePrint for Point<T, U> {}
+
+// -----------------------------------------------
+// ## Supertraits in Generic Code
+//
+// A bound on the subtrait implies its supertraits: `T: Entity` is enough to call `id` and `Debug`-format the value,
+// and `&dyn Entity` can be used as a `&dyn Named` (trait upcasting, stable since Rust 1.86).
+pub trait Named {
+  fn name(&self) -> String;
+}
+
+pub trait Entity: Named + fmt::Debug {
+  fn id(&self) -> u32;
+
+  // Provided methods can combine the supertraits' methods.
+  fn label(&self) -> String {
+    format!("#{} {}", self.id(), self.name())
+  }
+}
+
+#[derive(Debug)]
+pub struct Employee {
+  pub id: u32,
+  pub first_name: String,
+  pub last_name: String,
+}
+
+impl Named for Employee {
+  fn name(&self) -> String {
+    format!("{} {}", self.first_name, self.last_name)
+  }
+}
+
+impl Entity for Employee {
+  fn id(&self) -> u32 {
+    self.id
+  }
+}
+
+pub fn labels<T: Entity>(entities: &[T]) -> Vec<String> {
+  entities.iter().map(Entity::label).collect()
+}
+
+pub fn as_named(entity: &dyn Entity) -> &dyn Named {
+  entity
+}
+
+// -----------------------------------------------
+// ## Methods With the Same Name
+//
+// Nothing stops two traits, or a trait and the type itself, from having methods of the same name. `value.fly()`
+// picks the inherent method first; the others are called with the trait's name:
+//
+//    Pilot::fly(&person)                  // method with a receiver: the type is inferred from the argument
+//    <Human as Wizard>::fly(&person)      // FULLY QUALIFIED SYNTAX: names both the type and the trait
+//
+// The fully qualified form is the only one that works for associated functions without `self`, as there is no
+// argument to infer the type from:
+//
+//    Animal::baby_name()                  // Error! cannot call associated function on trait without specifying the corresponding `impl` type
+//    <Dog as Animal>::baby_name()         // "puppy"
+pub trait Pilot {
+  fn fly(&self) -> String;
+}
+
+pub trait Wizard {
+  fn fly(&self) -> String;
+}
+
+pub struct Human;
+
+impl Pilot for Human {
+  fn fly(&self) -> String {
+    String::from("This is your captain speaking.")
+  }
+}
+
+impl Wizard for Human {
+  fn fly(&self) -> String {
+    String::from("Up!")
+  }
+}
+
+impl Human {
+  pub fn fly(&self) -> String {
+    String::from("*waving arms furiously*")
+  }
+}
+
+pub trait Animal {
+  fn baby_name() -> String;
+}
+
+pub struct Dog;
+
+impl Dog {
+  pub fn baby_name() -> String {
+    String::from("Spot")
+  }
+}
+
+impl Animal for Dog {
+  fn baby_name() -> String {
+    String::from("puppy")
+  }
+}
+
+pub fn disambiguation_example() -> Vec<String> {
+  let person: Human = Human;
+  vec![person.fly(), Pilot::fly(&person), <Human as Wizard>::fly(&person), Dog::baby_name(), <Dog as Animal>::baby_name()]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_outline_print() {
+    let point: Point<i32, f64> = Point { x: 1, y: 3.5 };
+    assert_eq!(point.to_string(), "(1, 3.5)");
+    assert_eq!(point.outline(), "************\n* (1, 3.5) *\n************");
+  }
+
+  #[test]
+  fn test_entity() {
+    let employees: [Employee; 2] = [
+      Employee { id: 1, first_name: String::from("Ada"), last_name: String::from("Lovelace") },
+      Employee { id: 2, first_name: String::from("Alan"), last_name: String::from("Turing") },
+    ];
+    assert_eq!(labels(&employees), vec!["#1 Ada Lovelace", "#2 Alan Turing"]);
+    assert_eq!(as_named(&employees[1]).name(), "Alan Turing");
+    assert!(format!("{:?}", employees[0]).starts_with("Employee { id: 1"));
+  }
+
+  #[test]
+  fn test_disambiguation() {
+    assert_eq!(
+      disambiguation_example(),
+      vec!["*waving arms furiously*", "This is your captain speaking.", "Up!", "Spot", "puppy"]
+    );
+  }
+}
@@ -0,0 +1,176 @@
+// -----------------------------------------------
+// # THE NEWTYPE PATTERN
+//
+// A NEWTYPE is a tuple struct with a single field, `struct Meters(f64);`. It costs nothing at runtime (it has the
+// same size and layout as the field) but is a distinct type to the compiler. It has three uses:
+//   1. Getting around the ORPHAN RULE (_2_blanket_impls.rs): `impl Display for Vec<String>` is forbidden, since
+//      neither the trait nor the type is local, but `impl Display for Wrapper` is allowed.
+//   2. Type safety: `Meters` and `Millimeters` are both `f64`s, but cannot be mixed up.
+//   3. Hiding or restricting an API: the wrapper only exposes what it chooses to, and can uphold an invariant that
+//      the inner type does not (`SortedVec` below).
+//
+// The price is that the wrapper has none of the inner type's methods. Implementing `Deref` forwards all the `&self`
+// ones (convenient for 1., wrong for 3., where it would bypass the invariant); otherwise each is forwarded by hand.
+
+use std::fmt;
+use std::ops::{Add, Deref};
+
+// -----------------------------------------------
+// ## 1. Implementing a Foreign Trait on a Foreign Type
+pub struct Wrapper(pub Vec<String>);
+
+impl fmt::Display for Wrapper {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[{}]", self.0.join(", "))
+  }
+}
+
+// `Wrapper` can be used wherever a `&Vec<String>` (and so a `&[String]`) is expected: `wrapper.len()`, `wrapper.iter()`.
+impl Deref for Wrapper {
+  type Target = Vec<String>;
+
+  fn deref(&self) -> &Vec<String> {
+    &self.0
+  }
+}
+
+// -----------------------------------------------
+// ## 2. Units
+//
+// The Mars Climate Orbiter was lost in 1999 because one program produced pound-force seconds and another read them
+// as newton seconds. With newtypes, the conversion is explicit, and forgetting it is a type error:
+//
+//    let total = Meters(1.0) + 500.0;    // Error! mismatched types: expected `Meters`, found floating-point number
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Meters(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Millimeters(pub f64);
+
+impl From<Millimeters> for Meters {
+  fn from(Millimeters(mm): Millimeters) -> Meters {
+    Meters(mm / 1000.0)
+  }
+}
+
+impl Add for Meters {
+  type Output = Meters;
+
+  fn add(self, rhs: Meters) -> Meters {
+    Meters(self.0 + rhs.0)
+  }
+}
+
+// `Add`'s `Rhs` parameter again: meters plus millimeters, converted on the way.
+impl Add<Millimeters> for Meters {
+  type Output = Meters;
+
+  fn add(self, rhs: Millimeters) -> Meters {
+    self + Meters::from(rhs)
+  }
+}
+
+impl fmt::Display for Meters {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} m", self.0)
+  }
+}
+
+// Two ids of the same representation, which a function cannot confuse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderId(pub u32);
+
+pub fn order_owner(orders: &[(OrderId, UserId)], order: OrderId) -> Option<UserId> {
+  orders.iter().find(|(id, _)| *id == order).map(|&(_, user)| user)
+}
+
+// -----------------------------------------------
+// ## 3. Upholding an Invariant
+//
+// A `SortedVec` is always sorted: its field is private, so the only way to build or change one is through methods
+// that keep it sorted. It deliberately does not implement `DerefMut` (which would allow `v[0] = 99`), and only
+// exposes the read-only operations that benefit from the order, like `contains` in O(log n).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SortedVec<T>(Vec<T>);
+
+impl<T: Ord> SortedVec<T> {
+  pub fn new() -> SortedVec<T> {
+    SortedVec(Vec::new())
+  }
+
+  pub fn insert(&mut self, value: T) {
+    let index: usize = self.0.partition_point(|existing| existing <= &value);
+    self.0.insert(index, value);
+  }
+
+  pub fn contains(&self, value: &T) -> bool {
+    self.0.binary_search(value).is_ok()
+  }
+
+  pub fn as_slice(&self) -> &[T] {
+    &self.0
+  }
+
+  pub fn into_inner(self) -> Vec<T> {
+    self.0
+  }
+}
+
+impl<T: Ord> From<Vec<T>> for SortedVec<T> {
+  fn from(mut values: Vec<T>) -> SortedVec<T> {
+    values.sort();
+    SortedVec(values)
+  }
+}
+
+impl<T: Ord> FromIterator<T> for SortedVec<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> SortedVec<T> {
+    SortedVec::from(iter.into_iter().collect::<Vec<T>>())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_wrapper_display_and_deref() {
+    let wrapper: Wrapper = Wrapper(vec![String::from("hello"), String::from("world")]);
+    assert_eq!(wrapper.to_string(), "[hello, world]");
+    assert_eq!(wrapper.len(), 2);
+    assert_eq!(wrapper.first().map(String::as_str), Some("hello"));
+  }
+
+  #[test]
+  fn test_units() {
+    let total: Meters = Meters(1.0) + Millimeters(500.0) + Meters(0.25);
+    assert_eq!(total, Meters(1.75));
+    assert_eq!(total.to_string(), "1.75 m");
+    assert!(Meters(1.0) > Meters::from(Millimeters(999.0)));
+    // Zero-cost: the wrapper is exactly the size of its field.
+    assert_eq!(std::mem::size_of::<Meters>(), std::mem::size_of::<f64>());
+  }
+
+  #[test]
+  fn test_ids() {
+    let orders: [(OrderId, UserId); 2] = [(OrderId(1), UserId(10)), (OrderId(2), UserId(20))];
+    assert_eq!(order_owner(&orders, OrderId(2)), Some(UserId(20)));
+    assert_eq!(order_owner(&orders, OrderId(3)), None);
+  }
+
+  #[test]
+  fn test_sorted_vec() {
+    let mut sorted: SortedVec<i32> = vec![5, 1, 4].into();
+    sorted.insert(3);
+    sorted.insert(9);
+    sorted.insert(1);
+    assert_eq!(sorted.as_slice(), &[1, 1, 3, 4, 5, 9]);
+    assert!(sorted.contains(&4));
+    assert!(!sorted.contains(&2));
+    let collected: SortedVec<char> = "rust".chars().collect();
+    assert_eq!(collected.into_inner(), vec!['r', 's', 't', 'u']);
+  }
+}
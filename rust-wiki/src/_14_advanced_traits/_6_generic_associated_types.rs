@@ -0,0 +1,240 @@
+// -----------------------------------------------
+// # GENERIC ASSOCIATED TYPES (GATs)
+//
+// An associated type names one type per impl: `Iterator::Item` is `u32`, or `&'a str`, fixed for the whole impl.
+// A GENERIC associated type takes parameters of its own, lifetimes or types, so the impl defines a type FAMILY:
+//
+//    trait LendingIterator {
+//      type Item<'a> where Self: 'a;
+//      fn next(&mut self) -> Option<Self::Item<'_>>;
+//    }
+//
+// GATs are stable since Rust 1.65. Like associated consts, they make a trait not dyn compatible.
+//
+// -----------------------------------------------
+// ## Lending Iterators
+//
+// `Iterator::next(&mut self) -> Option<Self::Item>`: the item's type cannot mention the lifetime of that `&mut self`
+// borrow, so an item can never borrow from the iterator itself. Every item must be able to outlive the call to
+// `next`, and all items can be held at once (`collect` relies on it).
+//
+// That rules out an iterator over overlapping mutable windows of a slice: two windows `[a, b]` and `[b, c]` alive at
+// once would be two `&mut` to `b`. std has `windows` (shared) and `chunks_mut` (mutable, but disjoint), but no
+// `windows_mut`. With a GAT, `Item<'a>` can borrow from the `&'a mut self` of `next`, so the borrow checker only
+// allows one window to exist at a time: a LENDING (or "streaming") iterator.
+//
+// The `where Self: 'a` clause says the item may only be used while the iterator itself is valid; the compiler
+// requires it here.
+
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub trait LendingIterator {
+  type Item<'a>
+  where
+    Self: 'a;
+
+  fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+// There is no `collect`, and even a `for_each(self, f: impl FnMut(Self::Item<'_>))` adaptor runs into a current
+// limitation: the closure bound means "for every lifetime `'a`", and with the `where Self: 'a` clause, that is
+// read as requiring `Self: 'static`. Lending iterators are consumed with `while let` instead.
+
+pub struct WindowsMut<'s, T> {
+  slice: &'s mut [T],
+  size: usize,
+  start: usize,
+}
+
+pub fn windows_mut<T>(slice: &mut [T], size: usize) -> WindowsMut<'_, T> {
+  assert!(size > 0, "window size must be non-zero");
+  WindowsMut { slice, size, start: 0 }
+}
+
+impl<'s, T> LendingIterator for WindowsMut<'s, T> {
+  type Item<'a>
+    = &'a mut [T]
+  where
+    Self: 'a;
+
+  fn next(&mut self) -> Option<&mut [T]> {
+    let window: &mut [T] = self.slice.get_mut(self.start..self.start + self.size)?;
+    self.start += 1;
+    Some(window)
+  }
+}
+
+// Each window sees the changes made through the previous one: a running prefix sum, in place.
+pub fn prefix_sums(values: &mut [i64]) {
+  let mut windows: WindowsMut<'_, i64> = windows_mut(values, 2);
+  while let Some(window) = windows.next() {
+    window[1] += window[0];
+  }
+}
+
+// A lending iterator over the lines of a reader, reusing one buffer rather than allocating a `String` per line
+// (`BufRead::lines` allocates, because `Iterator` cannot lend its buffer).
+pub struct BufferedLines<R> {
+  reader: R,
+  buffer: String,
+}
+
+impl<R: std::io::BufRead> BufferedLines<R> {
+  pub fn new(reader: R) -> BufferedLines<R> {
+    BufferedLines { reader, buffer: String::new() }
+  }
+}
+
+impl<R: std::io::BufRead> LendingIterator for BufferedLines<R> {
+  type Item<'a>
+    = &'a str
+  where
+    Self: 'a;
+
+  fn next(&mut self) -> Option<&str> {
+    self.buffer.clear();
+    match self.reader.read_line(&mut self.buffer) {
+      Ok(0) | Err(_) => None,
+      Ok(_) => Some(self.buffer.trim_end_matches(['\n', '\r'])),
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Type Families
+//
+// A GAT with a type parameter lets generic code be generic over a type CONSTRUCTOR (such as `Rc` or `Arc`),
+// rather than over a type (such as `Rc<String>`): `P::Pointer<T>` is "the pointer of family `P` to a `T`", for any
+// `T`. Without GATs, `Stack<T, P>` below could not be written: a parameter `P = Rc<Node>` names one pointer type.
+pub trait PointerFamily {
+  type Pointer<T>: Deref<Target = T> + Clone;
+
+  fn new<T>(value: T) -> Self::Pointer<T>;
+}
+
+pub struct RcFamily;
+pub struct ArcFamily;
+
+impl PointerFamily for RcFamily {
+  type Pointer<T> = Rc<T>;
+
+  fn new<T>(value: T) -> Rc<T> {
+    Rc::new(value)
+  }
+}
+
+impl PointerFamily for ArcFamily {
+  type Pointer<T> = Arc<T>;
+
+  fn new<T>(value: T) -> Arc<T> {
+    Arc::new(value)
+  }
+}
+
+// A persistent (immutable, structurally shared) stack, usable with `Rc` on one thread or `Arc` across threads.
+// Pushing returns a new stack sharing the old one's nodes; the old one stays valid.
+pub struct Stack<T, P: PointerFamily> {
+  head: Option<P::Pointer<Node<T, P>>>,
+}
+
+pub struct Node<T, P: PointerFamily> {
+  value: T,
+  next: Option<P::Pointer<Node<T, P>>>,
+}
+
+impl<T, P: PointerFamily> Clone for Stack<T, P> {
+  fn clone(&self) -> Self {
+    Stack { head: self.head.clone() }
+  }
+}
+
+impl<T, P: PointerFamily> Stack<T, P> {
+  pub fn new() -> Stack<T, P> {
+    Stack { head: None }
+  }
+
+  pub fn push(&self, value: T) -> Stack<T, P> {
+    Stack { head: Some(P::new(Node { value, next: self.head.clone() })) }
+  }
+
+  pub fn peek(&self) -> Option<&T> {
+    self.head.as_deref().map(|node| &node.value)
+  }
+
+  pub fn pop(&self) -> Option<Stack<T, P>> {
+    self.head.as_deref().map(|node| Stack { head: node.next.clone() })
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &T> {
+    std::iter::successors(self.head.as_deref(), |node| node.next.as_deref()).map(|node| &node.value)
+  }
+}
+
+impl<T, P: PointerFamily> Default for Stack<T, P> {
+  fn default() -> Self {
+    Stack::new()
+  }
+}
+
+pub type RcStack<T> = Stack<T, RcFamily>;
+pub type ArcStack<T> = Stack<T, ArcFamily>;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_windows_mut() {
+    let mut values: [i64; 5] = [1, 2, 3, 4, 5];
+    prefix_sums(&mut values);
+    assert_eq!(values, [1, 3, 6, 10, 15]);
+
+    let mut letters: Vec<char> = "abcd".chars().collect();
+    let mut windows: WindowsMut<'_, char> = windows_mut(&mut letters, 3);
+    windows.next().unwrap().reverse();
+    windows.next().unwrap().reverse();
+    assert!(windows.next().is_none());
+    assert_eq!(letters, vec!['c', 'd', 'a', 'b']);
+  }
+
+  #[test]
+  fn test_window_larger_than_slice() {
+    let mut values: [u8; 2] = [1, 2];
+    assert!(windows_mut(&mut values, 3).next().is_none());
+  }
+
+  #[test]
+  fn test_buffered_lines() {
+    let mut lines: BufferedLines<&[u8]> = BufferedLines::new("first\r\nsecond\n\nlast".as_bytes());
+    let mut seen: Vec<String> = Vec::new();
+    while let Some(line) = lines.next() {
+      seen.push(line.to_uppercase());
+    }
+    assert_eq!(seen, vec!["FIRST", "SECOND", "", "LAST"]);
+  }
+
+  #[test]
+  fn test_rc_stack_is_shared() {
+    let empty: RcStack<u32> = RcStack::new();
+    let one: RcStack<u32> = empty.push(1);
+    let two: RcStack<u32> = one.push(2);
+    let other: RcStack<u32> = one.push(3);
+    assert_eq!(two.iter().copied().collect::<Vec<u32>>(), vec![2, 1]);
+    assert_eq!(other.iter().copied().collect::<Vec<u32>>(), vec![3, 1]);
+    assert_eq!(two.pop().unwrap().peek(), Some(&1));
+    assert!(empty.peek().is_none());
+    // `one`'s node is shared by `one`, `two` and `other`.
+    assert_eq!(Rc::strong_count(one.head.as_ref().unwrap()), 3);
+  }
+
+  #[test]
+  fn test_arc_stack_crosses_threads() {
+    let stack: ArcStack<String> = ArcStack::default().push(String::from("a")).push(String::from("b"));
+    let copy: ArcStack<String> = stack.clone();
+    let joined: String = std::thread::spawn(move || copy.iter().cloned().collect()).join().unwrap();
+    assert_eq!(joined, "ba");
+    assert_eq!(stack.peek().map(String::as_str), Some("b"));
+  }
+}
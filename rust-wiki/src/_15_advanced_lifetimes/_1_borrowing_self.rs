@@ -0,0 +1,140 @@
+// -----------------------------------------------
+// # ADVANCED LIFETIMES
+//
+// _4_generics/_2_lifetime_annotations.rs introduces lifetime parameters in functions, structs and traits. This
+// chapter covers what goes wrong once they are combined:
+//   - which lifetime a method's `self` borrow and its result should have (here).
+//   - variance: when a reference with one lifetime can be used as one with another (_2_variance.rs).
+//   - higher-ranked trait bounds, `for<'a>`, for closures taking references (_3_higher_ranked_bounds.rs).
+//   - `T: 'static` versus `&'static T` (_4_static.rs).
+//   - reborrowing, and why a `&mut` sometimes seems to be moved (_5_reborrowing.rs).
+//
+// Each "Error!" line below is checked by a case of tests/compile_fail/.
+//
+// -----------------------------------------------
+// ## Borrowing `self` for the Struct's Lifetime
+//
+// A struct holding references, `Tokens<'a>`, has two lifetimes in play in each method: the lifetime `'a` of the
+// data it borrows, and the (usually much shorter) lifetime of the borrow of `self` for the call. A method's
+// signature decides which one its arguments and result are tied to:
+//
+//   signature                                          result borrows      `self` is borrowed
+//   -------------------------------------------------  ------------------  ------------------------------------
+//   fn next_token(&mut self) -> Option<&'a str>        the input           for the call only
+//   fn next_token_elided(&mut self) -> Option<&str>    `self`              for as long as the result is used
+//   fn next_token_locked(&'a mut self) -> ..           the input           for all of 'a: until `self` is dead
+//
+// The third is the `NamedBorrowed::add_one(&'a mut self)` trap of _4_generics/_2_lifetime_annotations.rs. The
+// borrow of `self` must last for 'a, the lifetime of the borrowed data, which outlives the struct. And it cannot be
+// shortened: `&'x mut T` is INVARIANT in `T` (see _2_variance.rs), so `&'a mut Tokens<'a>` cannot be treated as
+// `&'short mut Tokens<'short>`. The struct is mutably borrowed for the rest of its life, after a single call.
+//
+// With a shared `&'a self`, the same signature is mostly harmless: `&'x T` is covariant in `T`, so the compiler can
+// shrink `'a` to the call, and shared borrows can overlap anyway. It is still needlessly strict.
+//
+// Rule of thumb: never write `&'a self` or `&'a mut self` with the struct's own parameter. Use plain `&self` /
+// `&mut self`, and name `'a` only in the result, when the result borrows from the struct's data rather than from the
+// struct.
+
+// Splits its input into whitespace-separated tokens, returning them as slices of the input.
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+  rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+  pub fn new(input: &'a str) -> Tokens<'a> {
+    Tokens { rest: input }
+  }
+
+  // The right signature: the token borrows from the input, so it stays valid after the next call, and even after
+  // the `Tokens` is dropped.
+  pub fn next_token(&mut self) -> Option<&'a str> {
+    let trimmed: &'a str = self.rest.trim_start();
+    if trimmed.is_empty() {
+      return None;
+    }
+    let end: usize = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+    let (token, rest) = trimmed.split_at(end);
+    self.rest = rest;
+    Some(token)
+  }
+
+  // The elision rules tie the result to `&mut self`: while the token is in use, `self` stays mutably borrowed.
+  //
+  //    let first = tokens.next_token_elided();
+  //    let second = tokens.next_token_elided();   // Error! cannot borrow `tokens` as mutable more than once at a time
+  //    println!("{first:?} {second:?}");
+  pub fn next_token_elided(&mut self) -> Option<&str> {
+    self.next_token()
+  }
+
+  // The trap: `self` is borrowed for 'a, so even without keeping the result, there is no second call.
+  //
+  //    tokens.next_token_locked();
+  //    tokens.next_token_locked();                // Error! cannot borrow `tokens` as mutable more than once at a time
+  pub fn next_token_locked(&'a mut self) -> Option<&'a str> {
+    self.next_token()
+  }
+
+  pub fn remaining(&self) -> &'a str {
+    self.rest
+  }
+}
+
+// With `next_token`, the tokens can be collected while the tokenizer keeps going, and returned after it is dropped.
+pub fn tokenize(input: &str) -> Vec<&str> {
+  let mut tokens: Tokens = Tokens::new(input);
+  let mut collected: Vec<&str> = Vec::new();
+  while let Some(token) = tokens.next_token() {
+    collected.push(token);
+  }
+  collected
+}
+
+// With `next_token_elided`, each token must be used (here, copied into a `String`) before the next call.
+pub fn tokenize_owned(input: &str) -> Vec<String> {
+  let mut tokens: Tokens = Tokens::new(input);
+  let mut collected: Vec<String> = Vec::new();
+  while let Some(token) = tokens.next_token_elided() {
+    collected.push(token.to_string());
+  }
+  collected
+}
+
+// `next_token_locked` can be called exactly once per `Tokens`; a fresh clone is needed for each call.
+pub fn first_token_locked(tokens: &Tokens<'_>) -> Option<String> {
+  let mut copy: Tokens = tokens.clone();
+  copy.next_token_locked().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tokenize() {
+    assert_eq!(tokenize("  let x =\t42; "), vec!["let", "x", "=", "42;"]);
+    assert_eq!(tokenize_owned("héllo wörld"), vec!["héllo", "wörld"]);
+    assert!(tokenize(" \n ").is_empty());
+  }
+
+  #[test]
+  fn test_tokens_outlive_tokenizer() {
+    let input: String = String::from("a b c");
+    let first: &str;
+    {
+      let mut tokens: Tokens = Tokens::new(&input);
+      first = tokens.next_token().unwrap();
+      assert_eq!(tokens.remaining(), " b c");
+    }
+    assert_eq!(first, "a");
+  }
+
+  #[test]
+  fn test_locked_needs_a_copy() {
+    let tokens: Tokens = Tokens::new("one two");
+    assert_eq!(first_token_locked(&tokens), Some(String::from("one")));
+    assert_eq!(first_token_locked(&tokens), Some(String::from("one")));
+  }
+}
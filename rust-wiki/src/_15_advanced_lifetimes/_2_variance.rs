@@ -0,0 +1,198 @@
+// -----------------------------------------------
+// # VARIANCE
+//
+// Lifetimes have a SUBTYPING relation: if `'long: 'short` ("'long outlives 'short"), then `&'long T` can be used
+// where a `&'short T` is expected. A `&'static str` can be passed to any function taking a `&'a str`.
+//
+// VARIANCE says how that relation carries over to types built from a lifetime or a type parameter, `F<'a>` or `F<T>`:
+//   - COVARIANT:      `F<'long>` can be used as `F<'short>`.                         (the natural direction)
+//   - CONTRAVARIANT:  `F<'short>` can be used as `F<'long>`.                         (the reverse)
+//   - INVARIANT:      neither; the lifetime or type must match exactly.
+//
+//   type                       in 'a            in T
+//   -------------------------  ---------------  -------------
+//   &'a T                      covariant        covariant
+//   &'a mut T                  covariant        INVARIANT
+//   Box<T>, Vec<T>, Option<T>                   covariant
+//   Cell<T>, RefCell<T>                         INVARIANT
+//   fn(T) -> U                                  CONTRAVARIANT in T, covariant in U
+//   *const T / *mut T                           covariant / invariant
+//
+// A struct has the variance of its fields, combined: `Tokens<'a> { rest: &'a str }` is covariant in 'a. Variance is
+// never written down; the compiler infers it, and it only shows up in which programs are accepted.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+// -----------------------------------------------
+// ## Covariance: Shortening Lifetimes
+//
+// Both arguments get the same 'a, and the compiler picks the shorter of the two actual lifetimes: the `&'static str`
+// is shortened to it.
+pub fn longest<'a>(a: &'a str, b: &'a str) -> &'a str {
+  if b.len() > a.len() {
+    b
+  } else {
+    a
+  }
+}
+
+pub fn shorten<'a>(s: &'static str) -> &'a str {
+  s
+}
+
+// Covariance goes through containers: a `Vec<&'static str>` is a `Vec<&'a str>`.
+pub fn count_longer_than<'a>(words: Vec<&'a str>, reference: &'a str) -> usize {
+  words.iter().filter(|word| word.len() > reference.len()).count()
+}
+
+// -----------------------------------------------
+// ## Invariance: Why `&mut T` Must Be Exact
+//
+// If `&mut &'static str` could be used as a `&mut &'a str`, a function could store a short-lived `&'a str` through
+// it, leaving the caller's `&'static str` variable pointing at freed memory:
+//
+//    let mut greeting: &'static str = "hello";
+//    {
+//      let local: String = String::from("bye");
+//      overwrite(&mut greeting, &local);    // Error! `local` does not live long enough
+//    }
+//    println!("{greeting}");
+//
+// Because `&mut T` is invariant in `T`, `'a` must be exactly `'static` here, and `&local` cannot be that long. The
+// same goes for anything with interior mutability (`Cell<&'a str>`), which is a `&mut` in disguise.
+pub fn overwrite<'a>(target: &mut &'a str, value: &'a str) {
+  *target = value;
+}
+
+// Accepted: the variable's type is inferred with the shorter lifetime from the start, so nothing is overwritten by
+// a shorter-lived reference than its type says.
+pub fn overwrite_example() -> String {
+  let local: String = String::from("bye");
+  let mut greeting: &str = "hello";
+  overwrite(&mut greeting, &local);
+  greeting.to_string()
+}
+
+// The same rule, through a `Cell`: `Cell::set` takes `&self`, so it is as dangerous as `&mut`.
+pub fn replace_in_cell<'a>(cell: &Cell<&'a str>, value: &'a str) -> &'a str {
+  cell.replace(value)
+}
+
+// -----------------------------------------------
+// ## Contravariance: Function Arguments
+//
+// A function accepting a `&'a str` for ANY 'a (`fn(&str)`) can stand in for one that will only ever be given
+// `&'static str`s: it accepts more than is required. So `fn(&'short str)` can be used as `fn(&'static str)`: the
+// reverse direction.
+pub type StaticStrHandler = fn(&'static str) -> usize;
+
+pub fn count_chars(s: &str) -> usize {
+  s.chars().count()
+}
+
+pub fn run_handlers(handlers: &[StaticStrHandler], input: &'static str) -> Vec<usize> {
+  handlers.iter().map(|handler| handler(input)).collect()
+}
+
+pub fn contravariance_example() -> Vec<usize> {
+  // `str::len` and `count_chars` accept any `&str`: their type, `for<'a> fn(&'a str) -> usize`, is a subtype of
+  // `fn(&'static str) -> usize`.
+  // (Each is coerced on its own: in an array literal, the elements are first unified with each other.)
+  let handlers: [StaticStrHandler; 2] = [str::len as StaticStrHandler, count_chars as StaticStrHandler];
+  run_handlers(&handlers, "héllo")
+}
+
+// -----------------------------------------------
+// ## Choosing the Variance of Your Own Types: `PhantomData`
+//
+// A type parameter that no field uses is an error (E0392): the compiler could not infer its variance, nor whether
+// the type owns a `T` (for drop checking) or is `Send`. `PhantomData<X>` is a zero-sized field that makes the type
+// behave as if it contained an `X`:
+//   - `PhantomData<T>`:            owns a `T`; covariant.
+//   - `PhantomData<&'a T>`:        borrows a `T` for 'a; covariant (raw-pointer types use this to get a lifetime).
+//   - `PhantomData<fn() -> T>`:    produces `T`s; covariant, and `Send`/`Sync` whatever `T` is.
+//   - `PhantomData<fn(T)>`:        consumes `T`s; contravariant.
+//   - `PhantomData<Cell<T>>` or `PhantomData<fn(T) -> T>`: invariant.
+//
+// A typed id: an `Id<User>` and an `Id<Order>` are both a `u32`, but cannot be confused. `fn() -> T` is chosen over
+// `T` because the id does not own a `T`: `Id<Rc<u8>>` is still `Send`, and dropping an id drops no `T`.
+pub struct Id<T> {
+  raw: u32,
+  _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
+  pub fn new(raw: u32) -> Id<T> {
+    Id { raw, _marker: PhantomData }
+  }
+
+  pub fn raw(&self) -> u32 {
+    self.raw
+  }
+}
+
+// Derives would require `T: Clone`, `T: PartialEq`, ... which an id does not need: implemented by hand.
+impl<T> Clone for Id<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.raw == other.raw
+  }
+}
+
+impl<T> std::fmt::Debug for Id<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Id<{}>({})", std::any::type_name::<T>().rsplit("::").next().unwrap_or("?"), self.raw)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_covariance() {
+    let local: String = String::from("a local string");
+    // "static" is a `&'static str`, shortened to the lifetime of `&local`.
+    assert_eq!(longest("static", &local), "a local string");
+    let short: &str = shorten("literal");
+    assert_eq!(short, "literal");
+    let words: Vec<&'static str> = vec!["a", "abc", "abcdef"];
+    assert_eq!(count_longer_than(words, &local[..2]), 2);
+  }
+
+  #[test]
+  fn test_invariance() {
+    assert_eq!(overwrite_example(), "bye");
+    let local: String = String::from("new");
+    let cell: Cell<&str> = Cell::new("old");
+    assert_eq!(replace_in_cell(&cell, &local), "old");
+    assert_eq!(cell.get(), "new");
+  }
+
+  #[test]
+  fn test_contravariance() {
+    assert_eq!(contravariance_example(), vec![6, 5]);
+  }
+
+  #[test]
+  fn test_phantom_id() {
+    struct User;
+    let id: Id<User> = Id::new(7);
+    let copy: Id<User> = id;
+    assert_eq!(id, copy);
+    assert_eq!(copy.raw(), 7);
+    assert_eq!(format!("{id:?}"), "Id<User>(7)");
+    assert_eq!(std::mem::size_of::<Id<User>>(), 4);
+    // `Rc` is not `Send`, but an id of one is.
+    fn assert_send<T: Send>(_: &T) {}
+    assert_send(&Id::<std::rc::Rc<u8>>::new(1));
+  }
+}
@@ -0,0 +1,132 @@
+// -----------------------------------------------
+// # HIGHER-RANKED TRAIT BOUNDS (HRTB)
+//
+// A lifetime parameter of a function is chosen by the CALLER: in `fn apply<'a, F: Fn(&'a str) -> &'a str>`, the
+// caller picks one 'a, and `f` only has to work for that one. That is not enough if the function calls `f` with a
+// reference to its own local variable: no lifetime the caller can name is that short.
+//
+// A HIGHER-RANKED bound says "for every lifetime": `F: for<'a> Fn(&'a str) -> &'a str`. The closure must then work
+// for whichever lifetime the function gives it, including one that only exists inside the function.
+//
+// They are rarely written out, because lifetime elision in `Fn` bounds produces them: `F: Fn(&str) -> &str` means
+// `F: for<'a> Fn(&'a str) -> &'a str`, and `Box<dyn Fn(&str) -> &str>` is `Box<dyn for<'a> Fn(&'a str) -> &'a str>`.
+// The explicit form is needed when the elided one is not what is meant, and in bounds that are not on an `Fn` trait.
+
+use std::collections::BTreeSet;
+
+// -----------------------------------------------
+// ## Closures Called With Local References
+//
+// The caller cannot know the lifetime of `line`, so the bound has to cover all of them.
+//
+//    fn apply_to_lines<'a, F: Fn(&'a str) -> &'a str>(text: &str, f: F) -> Vec<String> {
+//      let line: String = ..;
+//      f(&line).to_string()           // Error! `line` does not live long enough
+//    }
+pub fn apply_to_lines<F>(text: &str, f: F) -> Vec<String>
+where
+  F: for<'a> Fn(&'a str) -> &'a str,
+{
+  text
+    .lines()
+    .map(|line| {
+      // A fresh local `String` for each line: `f` gets a reference that dies at the end of the closure.
+      let line: String = line.replace('\t', " ");
+      f(&line).to_string()
+    })
+    .collect()
+}
+
+pub fn first_word(s: &str) -> &str {
+  s.split_whitespace().next().unwrap_or("")
+}
+
+// -----------------------------------------------
+// ## Storing Higher-Ranked Closures
+//
+// A pipeline of text transformations, each taking and returning a slice of its input. The elided
+// `dyn Fn(&str) -> &str` is higher-ranked: each stage works on whatever the previous one returned.
+pub type Stage = Box<dyn for<'a> Fn(&'a str) -> &'a str>;
+
+#[derive(Default)]
+pub struct Pipeline {
+  stages: Vec<Stage>,
+}
+
+impl Pipeline {
+  pub fn new() -> Pipeline {
+    Pipeline::default()
+  }
+
+  pub fn stage(mut self, stage: impl Fn(&str) -> &str + 'static) -> Pipeline {
+    self.stages.push(Box::new(stage));
+    self
+  }
+
+  pub fn run<'a>(&self, input: &'a str) -> &'a str {
+    self.stages.iter().fold(input, |text, stage| stage(text))
+  }
+}
+
+// A closure is only inferred to be higher-ranked when it is written where an `Fn(&str) -> &str` bound is expected.
+// Bound to a `let` first, its input and output lifetimes are inferred as two unrelated lifetimes, and
+// `let strip = |s: &str| -> &str { s.trim() };` is rejected ("lifetime may not live long enough"). Passing it through
+// an identity function with the bound fixes the inference:
+pub fn higher_ranked<F: Fn(&str) -> &str>(f: F) -> F {
+  f
+}
+
+pub fn pipeline_example() -> Vec<String> {
+  let strip_comment = higher_ranked(|s: &str| s.split('#').next().unwrap_or(""));
+  let pipeline: Pipeline = Pipeline::new().stage(strip_comment).stage(str::trim).stage(|s| s.strip_prefix("> ").unwrap_or(s));
+  ["  > quoted # comment", "plain", "#only a comment"].iter().map(|line| pipeline.run(line).to_string()).collect()
+}
+
+// -----------------------------------------------
+// ## Higher-Ranked Bounds on Other Traits
+//
+// To accept "any collection that can be iterated by reference", the bound must hold for the borrow made inside the
+// function, which the caller cannot name: `for<'a> &'a C: IntoIterator<Item = &'a T>`. It is satisfied by `Vec<T>`,
+// `[T; N]`, `BTreeSet<T>`, `VecDeque<T>`, ...: all types for which `for x in &collection` works.
+pub fn min_and_max<C, T>(collection: &C) -> Option<(T, T)>
+where
+  for<'a> &'a C: IntoIterator<Item = &'a T>,
+  T: Ord + Copy,
+{
+  // Two separate borrows of `collection`, each with its own lifetime.
+  let min: T = collection.into_iter().min().copied()?;
+  let max: T = collection.into_iter().max().copied()?;
+  Some((min, max))
+}
+
+pub fn min_and_max_example() -> [Option<(i32, i32)>; 3] {
+  [min_and_max(&vec![3, -1, 7]), min_and_max(&[5, 5]), min_and_max(&BTreeSet::<i32>::new())]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_apply_to_lines() {
+    assert_eq!(apply_to_lines("hello world\n\tindented line\n", first_word), vec!["hello", "indented"]);
+    assert_eq!(apply_to_lines("a\nb", |line| line), vec!["a", "b"]);
+  }
+
+  #[test]
+  fn test_pipeline() {
+    assert_eq!(pipeline_example(), vec!["quoted", "plain", ""]);
+    // The result borrows from the input, not from the pipeline.
+    let input: String = String::from("  kept  ");
+    let kept: &str = Pipeline::new().stage(str::trim).run(&input);
+    assert_eq!(kept, "kept");
+    assert!(std::ptr::eq(kept.as_ptr(), input[2..].as_ptr()));
+  }
+
+  #[test]
+  fn test_min_and_max() {
+    assert_eq!(min_and_max_example(), [Some((-1, 7)), Some((5, 5)), None]);
+    let words: std::collections::VecDeque<&str> = ["pear", "apple", "fig"].into();
+    assert_eq!(min_and_max(&words), Some(("apple", "pear")));
+  }
+}
@@ -0,0 +1,112 @@
+// -----------------------------------------------
+// # `'static`: REFERENCES VERSUS BOUNDS
+//
+// `'static` appears in two places, with different meanings:
+//
+//   `&'static T`   a REFERENCE valid until the end of the program. The data it points to is never freed: string
+//                  literals, `static` items, memory leaked with `Box::leak`.
+//
+//   `T: 'static`   a BOUND on a type: `T` contains no references shorter than `'static`. A value of such a type can
+//                  be kept for as long as its owner wants, because it borrows nothing that could go away. Every
+//                  owned type without lifetime parameters qualifies (`i32`, `String`, `Vec<u8>`), although the
+//                  value itself may well be dropped a moment later.
+//
+// So `T: 'static` does NOT mean "lives forever": it means "could live forever". A `String` satisfies it; a
+// `&'a str` only if `'a` is `'static`.
+//
+// The bound shows up wherever a value is kept for an unknown time:
+//   - `std::thread::spawn<F: FnOnce() -> T + Send + 'static>`: the thread may outlive the caller's stack frame.
+//   - `Box<dyn Trait>`, which means `Box<dyn Trait + 'static>` (the default "object lifetime bound").
+//   - `Any` (_13_trait_objects/_4_any.rs), whose `TypeId` cannot tell lifetimes apart.
+
+use std::fmt::Display;
+use std::thread;
+
+// -----------------------------------------------
+// ## `&'static T`
+pub static GREETING: &str = "hello";
+
+// A `&'static str` argument only accepts data that is never freed: literals and statics, not a borrowed `String`.
+pub fn log_prefix(prefix: &'static str) -> impl Fn(&str) -> String {
+  move |message| format!("[{prefix}] {message}")
+}
+
+// `Box::leak` turns an owned value into a `&'static` reference by never freeing it. It is for data that is needed
+// until the program exits anyway, like a configuration parsed at startup; in a loop, it is a memory leak.
+pub fn leak_config(config: String) -> &'static str {
+  Box::leak(config.into_boxed_str())
+}
+
+// -----------------------------------------------
+// ## `T: 'static`
+//
+// `value` is moved into the thread, so the thread owns it: it must not borrow from the caller's stack, which may be
+// gone by the time the thread runs.
+//
+//    let local: String = String::from("borrowed");
+//    describe_in_thread(&local).join();     // Error! `local` does not live long enough
+pub fn describe_in_thread<T: Display + Send + 'static>(value: T) -> thread::JoinHandle<String> {
+  thread::spawn(move || format!("thread got {value}"))
+}
+
+// A compile-time check of the bound, to show which types have it.
+pub fn is_static<T: 'static>(_: &T) -> bool {
+  true
+}
+
+// When the caller does need to lend data, `thread::scope` (_8_concurrency/_1_threads.rs) guarantees the threads end
+// before the scope does, so borrowing is allowed and no `'static` bound is needed.
+pub fn describe_in_scoped_thread<T: Display + Sync>(value: &T) -> String {
+  thread::scope(|scope| scope.spawn(|| format!("scoped thread got {value}")).join().expect("the thread does not panic"))
+}
+
+// -----------------------------------------------
+// ## The Default Object Lifetime Bound
+//
+// `Box<dyn Display>` is short for `Box<dyn Display + 'static>`, so it cannot hold a borrowed `&'a str` (nor a struct
+// containing one). To box borrowed data, the bound must be relaxed explicitly: `Box<dyn Display + 'a>`.
+// (For references, the default follows the reference: `&'a dyn Display` is `&'a (dyn Display + 'a)`.)
+pub fn boxed_owned(value: String) -> Box<dyn Display> {
+  Box::new(value)
+}
+
+pub fn boxed_borrowed<'a>(value: &'a str) -> Box<dyn Display + 'a> {
+  Box::new(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_static_references() {
+    let log = log_prefix(GREETING);
+    assert_eq!(log("world"), "[hello] world");
+    let config: &'static str = leak_config(String::from("verbose=true"));
+    assert_eq!(log_prefix(config)("x"), "[verbose=true] x");
+  }
+
+  #[test]
+  fn test_static_bound() {
+    // An owned `String` is `'static`, even though it is a local dropped at the end of the test.
+    let owned: String = String::from("owned");
+    assert!(is_static(&owned));
+    assert!(is_static(&"literal"));
+    assert_eq!(describe_in_thread(owned).join().unwrap(), "thread got owned");
+    assert_eq!(describe_in_thread(42).join().unwrap(), "thread got 42");
+  }
+
+  #[test]
+  fn test_scoped_thread_borrows() {
+    let local: String = String::from("borrowed");
+    assert_eq!(describe_in_scoped_thread(&local), "scoped thread got borrowed");
+    assert_eq!(local, "borrowed");
+  }
+
+  #[test]
+  fn test_object_lifetime_bounds() {
+    let local: String = String::from("local");
+    let boxes: Vec<Box<dyn Display + '_>> = vec![boxed_owned(String::from("owned")), boxed_borrowed(&local)];
+    assert_eq!(boxes.iter().map(|value| value.to_string()).collect::<Vec<_>>(), vec!["owned", "local"]);
+  }
+}
@@ -0,0 +1,176 @@
+// -----------------------------------------------
+// # REBORROWING
+//
+// `&mut T` is not `Copy`: copying it would make two usable mutable references. Yet this works:
+//
+//    fn push_one(v: &mut Vec<i32>) { v.push(1); }
+//    let r: &mut Vec<i32> = &mut vec;
+//    push_one(r);
+//    push_one(r);      // `r` was not moved into the first call?
+//
+// It was not: the compiler passed `&mut *r`, a new, shorter mutable borrow of the same place, derived from `r`. This
+// is a REBORROW. While it is alive, `r` is unusable (it is "frozen"); when it ends, `r` becomes usable again.
+// Reborrows also happen implicitly on method calls (`r.push(1)` is `Vec::push(&mut *r, 1)`), and can be shared:
+// `&*r` gives a `&T`. A shared reborrow only blocks writes: while it is alive, `r` can still be read through
+// (`r.len()`), but not written through (`r.push(1)`).
+//
+// -----------------------------------------------
+// ## When the Compiler Does Not Reborrow
+//
+// The implicit reborrow only happens when the expected type is known to be a `&mut` at that point. Passed to a
+// generic `T`, a `&mut` is moved:
+//
+//    take(r);
+//    r.push(1);        // Error! borrow of moved value: `r`
+//
+// The fix is to reborrow explicitly: `take(&mut *r)`.
+
+use std::collections::HashMap;
+
+pub fn push_one(v: &mut Vec<i32>) {
+  v.push(1);
+}
+
+// Generic over any `T`: it could be a `&mut`, but the compiler does not know that when checking the call.
+pub fn take<T>(value: T) -> T {
+  value
+}
+
+pub fn reborrow_example() -> Vec<i32> {
+  let mut numbers: Vec<i32> = Vec::new();
+  let r: &mut Vec<i32> = &mut numbers;
+  push_one(r);
+  push_one(r);
+  // An explicit reborrow into a generic function, so that `r` is not moved.
+  take(&mut *r).push(2);
+  // A shared reborrow: while `view` is in use, `r` can still be read through (`r.len()`), but `r.push(..)` would
+  // be rejected.
+  let view: &Vec<i32> = &*r;
+  let len: usize = r.len().min(view.len());
+  r.push(len as i32);
+  numbers
+}
+
+// `Option<&mut T>` is not `Copy` either, and is not reborrowed implicitly: `as_deref_mut()` reborrows the reference
+// inside, so the option can be used again.
+pub fn bump(counter: Option<&mut u32>) {
+  if let Some(counter) = counter {
+    *counter += 1;
+  }
+}
+
+pub fn bump_twice(mut counter: Option<&mut u32>) {
+  bump(counter.as_deref_mut());
+  bump(counter);
+}
+
+// -----------------------------------------------
+// ## Moving the Cursor: Reborrowing in a Loop
+//
+// Walking down a linked list with a `&mut` cursor: each step replaces the cursor with a reborrow of the next link,
+// derived from the current one. The old cursor is never used again, so the borrows do not conflict.
+#[derive(Debug, PartialEq)]
+pub struct Node {
+  pub value: i32,
+  pub next: Option<Box<Node>>,
+}
+
+pub fn push_back(head: &mut Option<Box<Node>>, value: i32) {
+  let mut cursor: &mut Option<Box<Node>> = head;
+  while let Some(node) = cursor {
+    cursor = &mut node.next;
+  }
+  *cursor = Some(Box::new(Node { value, next: None }));
+}
+
+pub fn list_values(head: &Option<Box<Node>>) -> Vec<i32> {
+  std::iter::successors(head.as_deref(), |node| node.next.as_deref()).map(|node| node.value).collect()
+}
+
+// -----------------------------------------------
+// ## A Mutable Borrow That Outlives the Call
+//
+// A `&T` returned by a `&mut self` method keeps `self` MUTABLY borrowed for as long as the `&T` is used: the result
+// is derived from the `&mut self` reborrow, which cannot be downgraded to a shared one.
+//
+//    let value = cache.get_or_compute(10);
+//    let size = cache.len();         // Error! cannot borrow `cache` as immutable because it is also borrowed as mutable
+//    println!("{value} {size}");
+//
+// The usual fixes: copy the value out (`let value = *cache.get_or_compute(10);`), or split the method in two: a
+// `&mut self` one that fills the cache, then a `&self` one that reads from it.
+#[derive(Default)]
+pub struct Cache {
+  values: HashMap<u64, u64>,
+  computed: usize,
+}
+
+impl Cache {
+  // The sum 1 + 2 + ... + n, computed once per `n`.
+  pub fn get_or_compute(&mut self, n: u64) -> &u64 {
+    let computed: &mut usize = &mut self.computed;
+    self.values.entry(n).or_insert_with(|| {
+      *computed += 1;
+      (1..=n).sum()
+    })
+  }
+
+  pub fn fill(&mut self, n: u64) {
+    self.get_or_compute(n);
+  }
+
+  pub fn get(&self, n: u64) -> Option<&u64> {
+    self.values.get(&n)
+  }
+
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+}
+
+pub fn cache_example() -> (u64, usize, usize) {
+  let mut cache: Cache = Cache::default();
+  // Copied out: the mutable borrow ends with the statement.
+  let first: u64 = *cache.get_or_compute(10);
+  cache.fill(10);
+  cache.fill(100);
+  // Shared borrows only: both can be alive together.
+  let (hundred, size) = (cache.get(100), cache.len());
+  (first + hundred.copied().unwrap_or(0), size, cache.computed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_reborrow_example() {
+    assert_eq!(reborrow_example(), vec![1, 1, 2, 3]);
+  }
+
+  #[test]
+  fn test_bump_twice() {
+    let mut counter: u32 = 0;
+    bump_twice(Some(&mut counter));
+    bump_twice(None);
+    assert_eq!(counter, 2);
+  }
+
+  #[test]
+  fn test_push_back() {
+    let mut head: Option<Box<Node>> = None;
+    for value in [1, 2, 3] {
+      push_back(&mut head, value);
+    }
+    assert_eq!(list_values(&head), vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn test_cache() {
+    assert_eq!(cache_example(), (55 + 5050, 2, 2));
+  }
+}
@@ -0,0 +1,213 @@
+// -----------------------------------------------
+// # INTEGER OVERFLOW
+//
+// The `Fibonacci` iterator of _5_functional_features/_2_iterators.rs computes `current + self.next` in `u32`. The
+// 48th Fibonacci number, F(47) = 2_971_215_073, is the last to fit (`u32::MAX` is 4_294_967_295), and since the
+// iterator computes two terms ahead, the addition overflows on the 47th call to `next`. What happens then depends on
+// the build:
+//   - with `overflow-checks` on (the default in the `dev` and `test` profiles): a panic,
+//     "attempt to add with overflow".
+//   - with them off (the default in `release`): the result silently WRAPS around, modulo 2^32, and the sequence
+//     continues with wrong numbers.
+//
+// (`[profile.release] overflow-checks = true` in Cargo.toml turns the checks on in release builds too.)
+//
+// Neither is what a caller wants. The integer types have explicit methods for each behaviour instead:
+//
+//   method                  result of `u32::MAX + 1`        use when
+//   ----------------------  ------------------------------  ---------------------------------------------------
+//   checked_add             None                            overflow is an error to handle
+//   wrapping_add            0                               modular arithmetic is intended (hashes, checksums)
+//   saturating_add          u32::MAX                        clamping at the bound is acceptable (counters, audio)
+//   overflowing_add         (0, true)                       both the wrapped result and the flag are needed
+//
+// (`std::num::Wrapping<u32>` and `Saturating<u32>` wrap a number to make `+` itself behave that way.)
+//
+// And when the numbers must simply be right, at any size, an arbitrary-precision integer is needed:
+// `BigUint` (_2_big_uint.rs), used by the generic `Fibonacci<T>` of _3_generic_fibonacci.rs.
+
+// The number of Fibonacci numbers, from F(0), that fit in a `u32`.
+pub const U32_FIBONACCI_TERMS: usize = 48;
+
+// -----------------------------------------------
+// ## Checked: Ending the Sequence
+//
+// The iterator ends instead of producing a wrong number: a term that does not fit is `None`, and so is the end of
+// the iteration. It yields every Fibonacci number representable in a `u32`, and nothing else.
+pub struct CheckedFibonacci {
+  curr: Option<u32>,
+  next: Option<u32>,
+}
+
+impl CheckedFibonacci {
+  pub fn new() -> CheckedFibonacci {
+    CheckedFibonacci { curr: Some(0), next: Some(1) }
+  }
+}
+
+impl Default for CheckedFibonacci {
+  fn default() -> Self {
+    CheckedFibonacci::new()
+  }
+}
+
+impl Iterator for CheckedFibonacci {
+  type Item = u32;
+
+  fn next(&mut self) -> Option<u32> {
+    let current: u32 = self.curr?;
+    self.curr = self.next;
+    self.next = self.next.and_then(|next| current.checked_add(next));
+    Some(current)
+  }
+}
+
+// Once `curr` is `None`, it stays `None`.
+impl std::iter::FusedIterator for CheckedFibonacci {}
+
+// -----------------------------------------------
+// ## Wrapping: Arithmetic Modulo 2^32
+//
+// Never panics, in any build: the release-mode behaviour of the original, made explicit. The terms are the
+// Fibonacci numbers modulo 2^32, which is occasionally exactly what is wanted (Pisano periods, hashing), and
+// otherwise silently wrong.
+pub struct WrappingFibonacci {
+  curr: u32,
+  next: u32,
+}
+
+impl WrappingFibonacci {
+  pub fn new() -> WrappingFibonacci {
+    WrappingFibonacci { curr: 0, next: 1 }
+  }
+}
+
+impl Default for WrappingFibonacci {
+  fn default() -> Self {
+    WrappingFibonacci::new()
+  }
+}
+
+impl Iterator for WrappingFibonacci {
+  type Item = u32;
+
+  fn next(&mut self) -> Option<u32> {
+    let current: u32 = self.curr;
+    self.curr = self.next;
+    self.next = current.wrapping_add(self.next);
+    Some(current)
+  }
+}
+
+// -----------------------------------------------
+// ## Saturating: Clamping at `u32::MAX`
+//
+// Every term past F(47) is `u32::MAX`: "at least this much". Wrong, but monotonic and never small.
+pub struct SaturatingFibonacci {
+  curr: u32,
+  next: u32,
+}
+
+impl SaturatingFibonacci {
+  pub fn new() -> SaturatingFibonacci {
+    SaturatingFibonacci { curr: 0, next: 1 }
+  }
+}
+
+impl Default for SaturatingFibonacci {
+  fn default() -> Self {
+    SaturatingFibonacci::new()
+  }
+}
+
+impl Iterator for SaturatingFibonacci {
+  type Item = u32;
+
+  fn next(&mut self) -> Option<u32> {
+    let current: u32 = self.curr;
+    self.curr = self.next;
+    self.next = current.saturating_add(self.next);
+    Some(current)
+  }
+}
+
+// -----------------------------------------------
+// ## Overflowing: Detecting Where It Went Wrong
+//
+// `overflowing_add` returns the wrapped sum and whether it wrapped: here, the index of the first term that does not
+// fit in a `u32`.
+pub fn first_overflowing_term() -> usize {
+  let (mut curr, mut next): (u32, u32) = (0, 1);
+  let mut index: usize = 0;
+  loop {
+    let (sum, overflowed) = curr.overflowing_add(next);
+    // `sum` is F(index + 2).
+    if overflowed {
+      return index + 2;
+    }
+    (curr, next) = (next, sum);
+    index += 1;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_5_functional_features::_2_iterators::Fibonacci;
+
+  const F47: u32 = 2_971_215_073;
+
+  #[test]
+  fn test_original_overflows() {
+    // The 46 first terms are computed without overflow; the 47th call computes F(48).
+    assert_eq!(Fibonacci { curr: 0, next: 1 }.take(46).count(), 46);
+    let result = std::panic::catch_unwind(|| Fibonacci { curr: 0, next: 1 }.take(47).count());
+    // `cargo test` uses the `test` profile, which inherits `dev`'s overflow checks; `--release` does not.
+    if cfg!(debug_assertions) {
+      assert!(result.is_err());
+    } else {
+      assert_eq!(result.ok(), Some(47));
+    }
+  }
+
+  #[test]
+  fn test_checked_fibonacci_ends() {
+    let terms: Vec<u32> = CheckedFibonacci::new().collect();
+    assert_eq!(terms.len(), U32_FIBONACCI_TERMS);
+    assert_eq!(terms.last(), Some(&F47));
+    assert_eq!(terms[..10], [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    let mut fused: CheckedFibonacci = CheckedFibonacci::new();
+    fused.by_ref().for_each(drop);
+    assert_eq!(fused.next(), None);
+  }
+
+  #[test]
+  fn test_wrapping_fibonacci() {
+    let terms: Vec<u32> = WrappingFibonacci::new().take(50).collect();
+    assert_eq!(terms[47], F47);
+    // F(48) = 4_807_526_976, minus 2^32.
+    assert_eq!(terms[48], 512_559_680);
+    assert_eq!(terms[49], F47.wrapping_add(512_559_680));
+  }
+
+  #[test]
+  fn test_saturating_fibonacci() {
+    let terms: Vec<u32> = SaturatingFibonacci::new().take(60).collect();
+    assert_eq!(terms[47], F47);
+    assert!(terms[48..].iter().all(|&term| term == u32::MAX));
+  }
+
+  #[test]
+  fn test_first_overflowing_term() {
+    assert_eq!(first_overflowing_term(), U32_FIBONACCI_TERMS);
+  }
+
+  #[test]
+  fn test_overflow_methods() {
+    assert_eq!(u32::MAX.checked_add(1), None);
+    assert_eq!(u32::MAX.wrapping_add(1), 0);
+    assert_eq!(u32::MAX.saturating_add(1), u32::MAX);
+    assert_eq!(u32::MAX.overflowing_add(1), (0, true));
+    assert_eq!((std::num::Wrapping(u32::MAX) + std::num::Wrapping(2)).0, 1);
+  }
+}
@@ -0,0 +1,542 @@
+// -----------------------------------------------
+// # AN ARBITRARY-PRECISION UNSIGNED INTEGER
+//
+// A `BigUint` stores its value as a vector of base-2^32 digits ("limbs"), least significant first:
+//
+//    value = limbs[0] + limbs[1] * 2^32 + limbs[2] * 2^64 + ...
+//
+//    12_345_678_901_234_567_890 = 0xAB54A98C_EB1F0AD2
+//                     limbs     = [0xEB1F0AD2, 0xAB54A98C]
+//
+// The representation is kept NORMALISED: no zero limbs at the end, so zero is the empty vector and each value has
+// exactly one representation (which makes the derived `PartialEq` and `Hash` correct).
+//
+// Each operation is the pen-and-paper algorithm, with 2^32 in place of 10: a limb times a limb fits in a `u64`, and
+// so does a carry. Addition is O(n), schoolbook multiplication O(n * m), and division here is binary long division,
+// O(bits * n): simple rather than fast. Real libraries (`num-bigint`, `rug`) use Knuth's algorithm D for division,
+// and Karatsuba or FFT multiplication for large operands.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Rem, Sub};
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+  // Least significant limb first, with no trailing zeros.
+  limbs: Vec<u32>,
+}
+
+const LIMB_BITS: u32 = u32::BITS;
+
+// The largest power of ten that fits in a limb, used to convert to and from decimal nine digits at a time.
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+impl BigUint {
+  pub fn zero() -> BigUint {
+    BigUint { limbs: Vec::new() }
+  }
+
+  pub fn one() -> BigUint {
+    BigUint { limbs: vec![1] }
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.limbs.is_empty()
+  }
+
+  fn from_limbs(limbs: Vec<u32>) -> BigUint {
+    let mut number: BigUint = BigUint { limbs };
+    number.normalize();
+    number
+  }
+
+  fn normalize(&mut self) {
+    while self.limbs.last() == Some(&0) {
+      self.limbs.pop();
+    }
+  }
+
+  // The number of significant bits: 0 for zero, 1 for one, 33 for 2^32.
+  pub fn bits(&self) -> u64 {
+    match self.limbs.last() {
+      None => 0,
+      Some(top) => (self.limbs.len() as u64 - 1) * u64::from(LIMB_BITS) + u64::from(LIMB_BITS - top.leading_zeros()),
+    }
+  }
+
+  fn bit(&self, index: u64) -> bool {
+    let limb: usize = (index / u64::from(LIMB_BITS)) as usize;
+    self.limbs.get(limb).is_some_and(|limb| limb >> (index % u64::from(LIMB_BITS)) & 1 == 1)
+  }
+
+  // -----------------------------------------------
+  // ## Arithmetic With a Single Limb
+  //
+  // The building blocks of the decimal conversions.
+
+  // self = self * factor + addend
+  fn mul_add_small(&mut self, factor: u32, addend: u32) {
+    let mut carry: u64 = u64::from(addend);
+    for limb in self.limbs.iter_mut() {
+      let product: u64 = u64::from(*limb) * u64::from(factor) + carry;
+      *limb = product as u32;
+      carry = product >> LIMB_BITS;
+    }
+    if carry > 0 {
+      self.limbs.push(carry as u32);
+    }
+    self.normalize();
+  }
+
+  // Short division, from the most significant limb down: (quotient, remainder).
+  fn divmod_small(&self, divisor: u32) -> (BigUint, u32) {
+    assert!(divisor != 0, "attempt to divide by zero");
+    let mut quotient: Vec<u32> = vec![0; self.limbs.len()];
+    let mut remainder: u64 = 0;
+    for (index, &limb) in self.limbs.iter().enumerate().rev() {
+      let current: u64 = (remainder << LIMB_BITS) | u64::from(limb);
+      quotient[index] = (current / u64::from(divisor)) as u32;
+      remainder = current % u64::from(divisor);
+    }
+    (BigUint::from_limbs(quotient), remainder as u32)
+  }
+
+  // -----------------------------------------------
+  // ## Subtraction and Division
+
+  // `None` if `other > self`, like `u32::checked_sub`: there are no negative `BigUint`s.
+  pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+    if *self < *other {
+      return None;
+    }
+    let mut limbs: Vec<u32> = self.limbs.clone();
+    let mut borrow: bool = false;
+    for (index, limb) in limbs.iter_mut().enumerate() {
+      let subtrahend: u32 = other.limbs.get(index).copied().unwrap_or(0);
+      if subtrahend == 0 && !borrow {
+        if index >= other.limbs.len() {
+          break;
+        }
+        continue;
+      }
+      let (difference, borrowed_1) = limb.overflowing_sub(subtrahend);
+      let (difference, borrowed_2) = difference.overflowing_sub(u32::from(borrow));
+      *limb = difference;
+      borrow = borrowed_1 || borrowed_2;
+    }
+    debug_assert!(!borrow, "self >= other, so the subtraction cannot underflow");
+    Some(BigUint::from_limbs(limbs))
+  }
+
+  // Binary long division: bring down one bit of the dividend at a time, and subtract the divisor whenever the running
+  // remainder is at least as large, setting that bit of the quotient.
+  //
+  // Returns `(self / divisor, self % divisor)`, and panics on a zero divisor, like the primitive integers.
+  pub fn divmod(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+    assert!(!divisor.is_zero(), "attempt to divide by zero");
+    if self < divisor {
+      return (BigUint::zero(), self.clone());
+    }
+    if let [small] = divisor.limbs[..] {
+      let (quotient, remainder) = self.divmod_small(small);
+      return (quotient, BigUint::from(remainder));
+    }
+    let mut quotient: Vec<u32> = vec![0; self.limbs.len()];
+    let mut remainder: BigUint = BigUint::zero();
+    for index in (0..self.bits()).rev() {
+      remainder = &remainder + &remainder;
+      if self.bit(index) {
+        remainder += &BigUint::one();
+      }
+      if remainder >= *divisor {
+        remainder = remainder.checked_sub(divisor).expect("remainder >= divisor");
+        quotient[(index / u64::from(LIMB_BITS)) as usize] |= 1 << (index % u64::from(LIMB_BITS));
+      }
+    }
+    (BigUint::from_limbs(quotient), remainder)
+  }
+
+  // Exponentiation by squaring: O(log exp) multiplications.
+  pub fn pow(&self, mut exp: u32) -> BigUint {
+    let mut base: BigUint = self.clone();
+    let mut result: BigUint = BigUint::one();
+    while exp > 0 {
+      if exp & 1 == 1 {
+        result = &result * &base;
+      }
+      exp >>= 1;
+      if exp > 0 {
+        base = &base * &base;
+      }
+    }
+    result
+  }
+}
+
+// -----------------------------------------------
+// ## Conversions From and To Primitive Integers
+macro_rules! impl_from_unsigned {
+  ($($t:ty),*) => {
+    $(impl From<$t> for BigUint {
+      fn from(value: $t) -> BigUint {
+        // Split into 32-bit limbs through the widest type; `from_limbs` drops the leading zeros.
+        let value: u128 = value as u128;
+        BigUint::from_limbs((0..128 / LIMB_BITS).map(|i| (value >> (i * LIMB_BITS)) as u32).collect())
+      }
+    })*
+  };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+// Fails when the value needs more than 128 bits.
+impl TryFrom<&BigUint> for u128 {
+  type Error = TooLargeError;
+
+  fn try_from(value: &BigUint) -> Result<u128, TooLargeError> {
+    if value.bits() > 128 {
+      return Err(TooLargeError { bits: value.bits() });
+    }
+    Ok(value.limbs.iter().rev().fold(0, |total, &limb| (total << LIMB_BITS) | u128::from(limb)))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TooLargeError {
+  pub bits: u64,
+}
+
+impl fmt::Display for TooLargeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "a {}-bit number does not fit in a u128", self.bits)
+  }
+}
+
+impl Error for TooLargeError {}
+
+// -----------------------------------------------
+// ## Ordering
+//
+// With no leading zeros, a number with more limbs is larger; with as many, the most significant differing limb
+// decides. The derived `Ord` would compare the vectors lexicographically from the LEAST significant limb: wrong.
+impl Ord for BigUint {
+  fn cmp(&self, other: &BigUint) -> Ordering {
+    self.limbs.len().cmp(&other.limbs.len()).then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+  }
+}
+
+impl PartialOrd for BigUint {
+  fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+// -----------------------------------------------
+// ## Operators
+//
+// The arithmetic is implemented on references, `&a + &b`, so that neither operand has to be given up (or cloned);
+// the by-value forms `a + b` forward to them. (The same pattern as std's `&i32 + &i32`, and
+// _14_advanced_traits/_1_operator_overloading.rs.)
+impl Add<&BigUint> for &BigUint {
+  type Output = BigUint;
+
+  fn add(self, other: &BigUint) -> BigUint {
+    let (long, short) = if self.limbs.len() >= other.limbs.len() { (self, other) } else { (other, self) };
+    let mut limbs: Vec<u32> = Vec::with_capacity(long.limbs.len() + 1);
+    let mut carry: u64 = 0;
+    for (index, &limb) in long.limbs.iter().enumerate() {
+      let sum: u64 = u64::from(limb) + u64::from(short.limbs.get(index).copied().unwrap_or(0)) + carry;
+      limbs.push(sum as u32);
+      carry = sum >> LIMB_BITS;
+    }
+    if carry > 0 {
+      limbs.push(carry as u32);
+    }
+    BigUint { limbs }
+  }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+  fn add_assign(&mut self, other: &BigUint) {
+    *self = &*self + other;
+  }
+}
+
+// Schoolbook multiplication: every limb of one operand times every limb of the other, added at the right offset.
+impl Mul<&BigUint> for &BigUint {
+  type Output = BigUint;
+
+  fn mul(self, other: &BigUint) -> BigUint {
+    if self.is_zero() || other.is_zero() {
+      return BigUint::zero();
+    }
+    let mut limbs: Vec<u32> = vec![0; self.limbs.len() + other.limbs.len()];
+    for (i, &a) in self.limbs.iter().enumerate() {
+      let mut carry: u64 = 0;
+      for (j, &b) in other.limbs.iter().enumerate() {
+        // At most (2^32 - 1)^2 + 2 * (2^32 - 1) = 2^64 - 1: it always fits.
+        let product: u64 = u64::from(a) * u64::from(b) + u64::from(limbs[i + j]) + carry;
+        limbs[i + j] = product as u32;
+        carry = product >> LIMB_BITS;
+      }
+      limbs[i + other.limbs.len()] = carry as u32;
+    }
+    BigUint::from_limbs(limbs)
+  }
+}
+
+impl MulAssign<&BigUint> for BigUint {
+  fn mul_assign(&mut self, other: &BigUint) {
+    *self = &*self * other;
+  }
+}
+
+// Panics on underflow, like `u32` subtraction in a debug build; `checked_sub` is the non-panicking form.
+impl Sub<&BigUint> for &BigUint {
+  type Output = BigUint;
+
+  fn sub(self, other: &BigUint) -> BigUint {
+    self.checked_sub(other).expect("attempt to subtract with overflow")
+  }
+}
+
+impl Div<&BigUint> for &BigUint {
+  type Output = BigUint;
+
+  fn div(self, other: &BigUint) -> BigUint {
+    self.divmod(other).0
+  }
+}
+
+impl Rem<&BigUint> for &BigUint {
+  type Output = BigUint;
+
+  fn rem(self, other: &BigUint) -> BigUint {
+    self.divmod(other).1
+  }
+}
+
+macro_rules! forward_by_value {
+  ($($trait:ident::$method:ident),*) => {
+    $(impl $trait for BigUint {
+      type Output = BigUint;
+
+      fn $method(self, other: BigUint) -> BigUint {
+        (&self).$method(&other)
+      }
+    })*
+  };
+}
+
+forward_by_value!(Add::add, Mul::mul, Sub::sub, Div::div, Rem::rem);
+
+impl std::iter::Sum for BigUint {
+  fn sum<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+    iter.fold(BigUint::zero(), |total, value| &total + &value)
+  }
+}
+
+impl std::iter::Product for BigUint {
+  fn product<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+    iter.fold(BigUint::one(), |total, value| &total * &value)
+  }
+}
+
+// -----------------------------------------------
+// ## Decimal Formatting and Parsing
+//
+// Converting to decimal divides by 10^9 repeatedly, collecting 9-digit groups from the least significant up; all
+// but the most significant group are zero-padded. Parsing does the reverse: for each group of up to 9 digits,
+// `value = value * 10^len + group`.
+impl fmt::Display for BigUint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut groups: Vec<u32> = Vec::new();
+    let mut rest: BigUint = self.clone();
+    while !rest.is_zero() {
+      let (quotient, group) = rest.divmod_small(DECIMAL_BASE);
+      groups.push(group);
+      rest = quotient;
+    }
+    let mut digits: String = match groups.pop() {
+      None => String::from("0"),
+      Some(most_significant) => most_significant.to_string(),
+    };
+    for group in groups.iter().rev() {
+      digits.push_str(&format!("{group:0width$}", width = DECIMAL_DIGITS));
+    }
+    // `pad_integral` applies the width, fill and alignment flags, as for the primitive integers: `{:>30}`, `{:030}`.
+    f.pad_integral(true, "", &digits)
+  }
+}
+
+// Printed as its decimal value, so that `assert_eq!` failures are readable.
+impl fmt::Debug for BigUint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "BigUint({self})")
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigUintError {
+  Empty,
+  InvalidDigit { index: usize, found: char },
+}
+
+impl fmt::Display for ParseBigUintError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseBigUintError::Empty => write!(f, "cannot parse a number from an empty string"),
+      ParseBigUintError::InvalidDigit { index, found } => write!(f, "invalid digit {found:?} at byte {index}"),
+    }
+  }
+}
+
+impl Error for ParseBigUintError {}
+
+// Accepts decimal digits only, with an optional leading `+`, like `u32::from_str`.
+impl FromStr for BigUint {
+  type Err = ParseBigUintError;
+
+  fn from_str(s: &str) -> Result<BigUint, ParseBigUintError> {
+    let (offset, digits) = match s.strip_prefix('+') {
+      Some(digits) => (1, digits),
+      None => (0, s),
+    };
+    if digits.is_empty() {
+      return Err(ParseBigUintError::Empty);
+    }
+    if let Some((index, found)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+      return Err(ParseBigUintError::InvalidDigit { index: index + offset, found });
+    }
+    // Groups of 9 digits, aligned on the right: the first group takes the leftover digits.
+    let first_len: usize = match digits.len() % DECIMAL_DIGITS {
+      0 => DECIMAL_DIGITS,
+      leftover => leftover,
+    };
+    let mut value: BigUint = BigUint::zero();
+    let mut start: usize = 0;
+    let mut len: usize = first_len;
+    while start < digits.len() {
+      let group: u32 = digits[start..start + len].parse().expect("at most 9 ASCII digits");
+      value.mul_add_small(10_u32.pow(len as u32), group);
+      start += len;
+      len = DECIMAL_DIGITS;
+    }
+    Ok(value)
+  }
+}
+
+pub fn factorial(n: u32) -> BigUint {
+  (1..=n).map(BigUint::from).product()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_11_collections::_5_benchmarks::shuffled_keys;
+
+  fn big(s: &str) -> BigUint {
+    s.parse().unwrap()
+  }
+
+  // Pairs of random `u128`s of assorted sizes, whose results can be checked with `u128` arithmetic.
+  fn random_pairs() -> Vec<(u128, u128)> {
+    let keys: Vec<u64> = shuffled_keys(2000, 42);
+    keys
+      .chunks_exact(4)
+      .map(|k| {
+        let a: u128 = (u128::from(k[0]) << 64 | u128::from(k[1])) >> (k[2] % 128);
+        let b: u128 = u128::from(k[2]) >> (k[3] % 64);
+        (a, b)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_limbs_are_normalized() {
+    assert_eq!(BigUint::from(0_u32), BigUint::zero());
+    assert_eq!(BigUint::from(0_u32).limbs, Vec::<u32>::new());
+    assert_eq!(BigUint::from(12_345_678_901_234_567_890_u64).limbs, vec![0xEB1F_0AD2, 0xAB54_A98C]);
+    assert_eq!(big("000123"), BigUint::from(123_u8));
+    let difference: BigUint = &big("18446744073709551616") - &big("1");
+    assert_eq!(difference.limbs, vec![u32::MAX, u32::MAX]);
+    assert_eq!((&big("4294967296") - &big("4294967295")).limbs, vec![1]);
+  }
+
+  #[test]
+  fn test_matches_u128_arithmetic() {
+    for (a, b) in random_pairs() {
+      let (big_a, big_b) = (BigUint::from(a), BigUint::from(b));
+      assert_eq!(big_a.to_string(), a.to_string());
+      assert_eq!(big(&a.to_string()), big_a);
+      assert_eq!(big_a.cmp(&big_b), a.cmp(&b));
+      if let Some(sum) = a.checked_add(b) {
+        assert_eq!(&big_a + &big_b, BigUint::from(sum));
+      }
+      if let Some(product) = a.checked_mul(b) {
+        assert_eq!(&big_a * &big_b, BigUint::from(product));
+      }
+      assert_eq!(big_a.checked_sub(&big_b), a.checked_sub(b).map(BigUint::from));
+      if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+        assert_eq!(big_a.divmod(&big_b), (BigUint::from(quotient), BigUint::from(remainder)));
+      }
+      assert_eq!(big_a.bits(), u64::from(128 - a.leading_zeros()));
+    }
+  }
+
+  #[test]
+  fn test_beyond_u128() {
+    let two_to_128: BigUint = BigUint::from(2_u8).pow(128);
+    assert_eq!(two_to_128.to_string(), "340282366920938463463374607431768211456");
+    assert_eq!(&two_to_128 - &BigUint::one(), BigUint::from(u128::MAX));
+    assert_eq!(u128::try_from(&two_to_128), Err(TooLargeError { bits: 129 }));
+    assert_eq!(u128::try_from(&BigUint::from(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(factorial(30).to_string(), "265252859812191058636308480000000");
+    assert_eq!(factorial(0), BigUint::one());
+  }
+
+  #[test]
+  fn test_divmod_identity() {
+    // For large operands, check a = q * b + r with r < b.
+    let a: BigUint = factorial(60) + big("123456789123456789");
+    for b in [factorial(25), big("340282366920938463463374607431768211457"), big("7"), a.clone()] {
+      let (q, r) = a.divmod(&b);
+      assert!(r < b);
+      assert_eq!(&(&q * &b) + &r, a);
+    }
+    assert_eq!(&factorial(30) / &factorial(28), BigUint::from(30_u32 * 29));
+    assert_eq!(&factorial(30) % &BigUint::from(31_u8), BigUint::from(30_u8));
+  }
+
+  #[test]
+  #[should_panic(expected = "attempt to divide by zero")]
+  fn test_divide_by_zero() {
+    let _ = BigUint::one().divmod(&BigUint::zero());
+  }
+
+  #[test]
+  #[should_panic(expected = "attempt to subtract with overflow")]
+  fn test_subtract_overflow() {
+    let _ = BigUint::one() - BigUint::from(2_u8);
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    assert_eq!("".parse::<BigUint>(), Err(ParseBigUintError::Empty));
+    assert_eq!("+".parse::<BigUint>(), Err(ParseBigUintError::Empty));
+    assert_eq!("12a4".parse::<BigUint>(), Err(ParseBigUintError::InvalidDigit { index: 2, found: 'a' }));
+    assert_eq!("-1".parse::<BigUint>().unwrap_err().to_string(), "invalid digit '-' at byte 0");
+    assert_eq!(big("+1000000000"), BigUint::from(1_000_000_000_u32));
+  }
+
+  #[test]
+  fn test_formatting_flags() {
+    let n: BigUint = big("1000000000000000000000");
+    assert_eq!(format!("{n:>25}"), "   1000000000000000000000");
+    assert_eq!(format!("{:05}", BigUint::from(42_u8)), "00042");
+    assert_eq!(format!("{:?}", BigUint::zero()), "BigUint(0)");
+  }
+}
@@ -0,0 +1,85 @@
+// -----------------------------------------------
+// # A GENERIC FIBONACCI ITERATOR
+//
+// The `u32` iterator of _5_functional_features/_2_iterators.rs, generic over the number type. With `u64` it yields
+// 92 terms before overflowing, with `u128` 185, and with `BigUint` as many as there is memory for.
+//
+// What `T` must support:
+//   - `From<u8>`, to build the starting values 0 and 1 (every integer type, and `BigUint`, implement it).
+//   - `&T + &T -> T`: an addition that BORROWS both operands. Adding by value would need `T: Copy` (ruling out
+//     `BigUint`) or a `clone()` of both terms on every step. The bound is higher-ranked, `for<'a>`, because the
+//     references are to the iterator's own fields, whose lifetime cannot be named in the `impl` header (see
+//     _15_advanced_lifetimes/_3_higher_ranked_bounds.rs).
+
+use std::mem;
+use std::ops::Add;
+
+pub struct Fibonacci<T> {
+  curr: T,
+  next: T,
+}
+
+impl<T: From<u8>> Fibonacci<T> {
+  pub fn new() -> Fibonacci<T> {
+    Fibonacci { curr: T::from(0), next: T::from(1) }
+  }
+}
+
+impl<T: From<u8>> Default for Fibonacci<T> {
+  fn default() -> Self {
+    Fibonacci::new()
+  }
+}
+
+impl<T> Iterator for Fibonacci<T>
+where
+  for<'a> &'a T: Add<&'a T, Output = T>,
+{
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    // (curr, next) becomes (next, curr + next), and the old `curr` is returned: moved out with `mem::replace`, so no
+    // term is ever cloned.
+    let sum: T = &self.curr + &self.next;
+    let next: T = mem::replace(&mut self.next, sum);
+    Some(mem::replace(&mut self.curr, next))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_16_big_numbers::_2_big_uint::BigUint;
+
+  #[test]
+  fn test_agrees_with_primitive_integers() {
+    // The iterator computes two terms ahead: yielding F(92) would compute F(94), which overflows a `u64`.
+    let small: Vec<u64> = Fibonacci::<u64>::new().take(92).collect();
+    let wide: Vec<u128> = Fibonacci::<u128>::new().take(150).collect();
+    let big: Vec<BigUint> = Fibonacci::<BigUint>::new().take(150).collect();
+    assert_eq!(small[..10], [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    for (index, term) in small.iter().enumerate() {
+      assert_eq!(u128::from(*term), wide[index]);
+    }
+    for (index, term) in big.iter().enumerate() {
+      assert_eq!(*term, BigUint::from(wide[index]));
+    }
+  }
+
+  #[test]
+  fn test_big_terms() {
+    let f100: BigUint = Fibonacci::<BigUint>::new().nth(100).unwrap();
+    assert_eq!(f100.to_string(), "354224848179261915075");
+    let f300: BigUint = Fibonacci::<BigUint>::new().nth(300).unwrap();
+    assert_eq!(f300.to_string(), "222232244629420445529739893461909967206666939096499764990979600");
+  }
+
+  #[test]
+  fn test_fibonacci_identity() {
+    // F(2n) = F(n) * (2 * F(n + 1) - F(n)), far beyond what any primitive type holds.
+    let terms: Vec<BigUint> = Fibonacci::<BigUint>::new().take(401).collect();
+    let n: usize = 200;
+    let two: BigUint = BigUint::from(2_u8);
+    assert_eq!(terms[2 * n], &terms[n] * &(&(&two * &terms[n + 1]) - &terms[n]));
+  }
+}
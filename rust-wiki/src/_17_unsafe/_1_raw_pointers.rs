@@ -0,0 +1,188 @@
+// -----------------------------------------------
+// # UNSAFE RUST AND RAW POINTERS
+//
+// The borrow checker proves that safe code cannot cause undefined behaviour (UB): no dangling or misaligned
+// references, no data races, no reads of uninitialised memory. Some correct programs are beyond what it can prove,
+// and some (allocators, collections, FFI) are made of exactly the operations it cannot check. `unsafe` unlocks five
+// of them:
+//   1. dereferencing a raw pointer,
+//   2. calling an `unsafe fn` (including foreign functions),
+//   3. accessing or modifying a `static mut`,
+//   4. implementing an `unsafe trait` (`Send`, `Sync`, `GlobalAlloc`),
+//   5. accessing a field of a `union`.
+// Nothing else changes: borrows inside an `unsafe` block are checked as usual. What changes is who is responsible.
+// The compiler no longer checks those operations, so the programmer must, and writes down why each is sound:
+//   - an `unsafe fn` documents its contract in a `# Safety` section: what the CALLER must guarantee.
+//   - an `unsafe { .. }` block carries a `// SAFETY:` comment: why the contract holds at this call.
+// The goal is a SAFE API around a small unsafe core, like `Vec` (see `MyVec` in _2_my_vec.rs): no sequence of calls
+// to the safe functions can cause UB, whatever the caller does.
+//
+// -----------------------------------------------
+// ## Raw Pointers
+//
+// `*const T` and `*mut T` are addresses with none of the guarantees of `&T` and `&mut T`: they may be null,
+// dangling, misaligned or aliased, and carry no lifetime. CREATING one is safe; only DEREFERENCING it is unsafe:
+//
+//    let x: i32 = 5;
+//    let p: *const i32 = &x;      // fine: `&x` coerces to `*const i32`
+//    let y: i32 = *p;             // Error! dereference of raw pointer is unsafe and requires unsafe function or block
+//
+// Dereferencing is only sound when the pointer is NON-NULL, ALIGNED, points into a live ALLOCATION with an
+// INITIALISED `T` (for reads), and no `&mut` to the same place is in use (aliasing).
+
+use std::mem::MaybeUninit;
+use std::ptr;
+
+pub fn raw_pointer_example() -> (i32, i32) {
+  let mut x: i32 = 5;
+  // A `*mut` and a `*const` to the same place: allowed for raw pointers, never for references. Both are derived from
+  // one `&raw mut x`: taking a fresh `&mut x` for the second would invalidate the first.
+  let write: *mut i32 = &raw mut x;
+  let read: *const i32 = write;
+  // SAFETY: both point to `x`, which is alive, aligned and initialised; no reference to `x` is in use meanwhile.
+  unsafe {
+    *write += 1;
+    (*read, *write * 10)
+  }
+}
+
+// -----------------------------------------------
+// ## Pointer Arithmetic
+//
+// `p.add(n)` is the address `n` ELEMENTS after `p` (n * size_of::<T>() bytes). It is unsafe itself: the result must
+// stay within the same allocation (or one past its end), even if it is never dereferenced.
+pub fn sum_with_pointers(values: &[i32]) -> i32 {
+  let start: *const i32 = values.as_ptr();
+  let mut total: i32 = 0;
+  for i in 0..values.len() {
+    // SAFETY: `i < values.len()`, so `start.add(i)` is in bounds of the slice, and points to an initialised `i32`.
+    total += unsafe { *start.add(i) };
+  }
+  total
+}
+
+// -----------------------------------------------
+// ## `ptr::read`, `ptr::write` and `ptr::swap`
+//
+// `*p = value` DROPS the old value at `*p` before writing: wrong if there is no old value (uninitialised memory).
+// `let value = *p` MOVES out, which is not allowed for non-`Copy` types through a pointer. Their raw forms:
+//   - `ptr::write(p, value)`:   writes without dropping (or reading) what was there.
+//   - `ptr::read(p)`:           a bitwise copy of `*p`, as an owned value. The original is now "moved from": it must
+//                               not be dropped or read again, unless `T: Copy`.
+//   - `ptr::swap(a, b)`, `ptr::copy(src, dst, n)` (memmove) and `ptr::copy_nonoverlapping` (memcpy).
+
+// Initialises an array element by element, through `MaybeUninit`: the type for memory that may not hold a valid `T`
+// yet. (`[String; 3]` cannot be created empty, and `mem::zeroed` would be UB: a null pointer is not a valid `String`.)
+pub fn init_strings() -> [String; 3] {
+  let mut slots: [MaybeUninit<String>; 3] = [const { MaybeUninit::uninit() }; 3];
+  for (i, slot) in slots.iter_mut().enumerate() {
+    // `MaybeUninit::write` is a safe `ptr::write`: it never drops the (nonexistent) old value.
+    slot.write(format!("slot {i}"));
+  }
+  // SAFETY: every element was initialised by the loop; `MaybeUninit<String>` has the same layout as `String`.
+  slots.map(|slot| unsafe { slot.assume_init() })
+}
+
+// Moves the first element out and the last one into its place, without cloning: what `Vec::swap_remove` does.
+pub fn take_first_replace_with_last(values: &mut Vec<String>) -> Option<String> {
+  let len: usize = values.len();
+  if len == 0 {
+    return None;
+  }
+  let base: *mut String = values.as_mut_ptr();
+  // SAFETY: `len > 0`, so indices 0 and `len - 1` are in bounds. `read` copies the first string out; the slot is
+  // then overwritten by a bitwise copy of the last one, without dropping the first (now owned by `first`). `set_len`
+  // forgets the last slot, now a duplicate, so that its string is not dropped twice.
+  unsafe {
+    let first: String = ptr::read(base);
+    ptr::copy(base.add(len - 1), base, 1);
+    values.set_len(len - 1);
+    Some(first)
+  }
+}
+
+// -----------------------------------------------
+// ## Unsafe Functions
+//
+/// Returns the element at `index`, without bounds checking.
+///
+/// # Safety
+///
+/// `index` must be less than `values.len()`.
+pub unsafe fn get_unchecked_example<T>(values: &[T], index: usize) -> &T {
+  debug_assert!(index < values.len(), "get_unchecked_example: index {index} out of bounds");
+  // SAFETY: the caller guarantees `index < values.len()`.
+  unsafe { &*values.as_ptr().add(index) }
+}
+
+// A safe wrapper: the check is done once, and the unsafe call relies on it.
+pub fn last_two<T>(values: &[T]) -> Option<(&T, &T)> {
+  let len: usize = values.len();
+  if len < 2 {
+    return None;
+  }
+  // SAFETY: `len >= 2`, so `len - 2` and `len - 1` are in bounds.
+  unsafe { Some((get_unchecked_example(values, len - 2), get_unchecked_example(values, len - 1))) }
+}
+
+// -----------------------------------------------
+// ## Splitting a Borrow: Safe API, Unsafe Inside
+//
+// `split_at_mut` returns two `&mut` into the same slice. The borrow checker cannot see that they do not overlap:
+//
+//    let (left, right) = (&mut values[..mid], &mut values[mid..]);   // Error! cannot borrow `*values` as mutable more than once at a time
+//
+// Raw pointers make the two halves by hand; the function is safe because `mid <= len` is checked first.
+pub fn split_at_mut_example<T>(values: &mut [T], mid: usize) -> (&mut [T], &mut [T]) {
+  let len: usize = values.len();
+  assert!(mid <= len, "mid {mid} > len {len}");
+  let base: *mut T = values.as_mut_ptr();
+  // SAFETY: `[0, mid)` and `[mid, len)` are in bounds and disjoint, so the two `&mut` never alias, and both borrow
+  // from `values`, so they cannot outlive it.
+  unsafe { (std::slice::from_raw_parts_mut(base, mid), std::slice::from_raw_parts_mut(base.add(mid), len - mid)) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_raw_pointers() {
+    assert_eq!(raw_pointer_example(), (6, 60));
+    assert_eq!(sum_with_pointers(&[1, 2, 3, 4]), 10);
+    assert_eq!(sum_with_pointers(&[]), 0);
+    // Creating a null or dangling pointer is fine, as long as it is not dereferenced.
+    let null: *const i32 = ptr::null();
+    assert!(null.is_null());
+    assert_eq!(ptr::NonNull::<u64>::dangling().as_ptr() as usize, std::mem::align_of::<u64>());
+  }
+
+  #[test]
+  fn test_read_and_write() {
+    assert_eq!(init_strings(), [String::from("slot 0"), String::from("slot 1"), String::from("slot 2")]);
+    let mut values: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+    assert_eq!(take_first_replace_with_last(&mut values), Some(String::from("a")));
+    assert_eq!(values, vec!["c", "b"]);
+    assert_eq!(take_first_replace_with_last(&mut values), Some(String::from("c")));
+    assert_eq!(take_first_replace_with_last(&mut values), Some(String::from("b")));
+    assert_eq!(take_first_replace_with_last(&mut values), None);
+  }
+
+  #[test]
+  fn test_unsafe_fn_behind_safe_api() {
+    assert_eq!(last_two(&[1, 2, 3]), Some((&2, &3)));
+    assert_eq!(last_two(&[1]), None);
+    let mut values: [i32; 5] = [1, 2, 3, 4, 5];
+    let (left, right) = split_at_mut_example(&mut values, 2);
+    left[0] = 10;
+    right[0] = 30;
+    assert_eq!(values, [10, 2, 30, 4, 5]);
+    assert_eq!(split_at_mut_example(&mut values, 5).1, &mut []);
+  }
+
+  #[test]
+  #[should_panic(expected = "mid 6 > len 5")]
+  fn test_split_at_mut_checks_bounds() {
+    split_at_mut_example(&mut [0; 5], 6);
+  }
+}
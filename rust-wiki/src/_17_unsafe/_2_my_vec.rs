@@ -0,0 +1,569 @@
+// -----------------------------------------------
+// # A HAND-ROLLED `Vec<T>`
+//
+// The ptr/len/capacity triple drawn in _2_ownership/_4_slices.rs, built by hand:
+//
+//    MyVec { ptr, cap: 4, len: 2 }
+//             |
+//             v
+//    heap:  [ T | T | uninit | uninit ]
+//            <-len-->
+//            <------- cap ----------->
+//
+// The INVARIANTS, which every method relies on and must restore before returning (`check_invariants` asserts them in
+// debug builds):
+//   1. `len <= cap`.
+//   2. The first `len` slots hold initialised `T`s; the others are uninitialised, and never read or dropped.
+//   3. If `cap == 0`, or `T` is zero-sized, nothing is allocated and `ptr` is `NonNull::dangling()` (non-null and
+//      aligned, as slices require, but pointing at nothing). Otherwise `ptr` came from the global allocator, with the
+//      layout of `[T; cap]`.
+//   4. Zero-sized types (`()`, empty structs) need no memory at all: `cap` is `usize::MAX` from the start.
+//
+// The type is safe to use: every `unsafe` block below relies only on those invariants and on checks made in the
+// same function, never on the caller.
+
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+use std::slice;
+
+pub struct MyVec<T> {
+  ptr: NonNull<T>,
+  cap: usize,
+  len: usize,
+  // `NonNull<T>` does not say that the vector OWNS `T`s; `PhantomData<T>` does, so that the drop checker knows that
+  // dropping a `MyVec<T>` may drop `T`s.
+  _owns: PhantomData<T>,
+}
+
+// Raw pointers are neither `Send` nor `Sync`, which would make `MyVec` neither. It owns its `T`s like a `Box<[T]>`
+// does, so it can follow the same rules. (These are `unsafe impl`s: the compiler takes our word for it.)
+unsafe impl<T: Send> Send for MyVec<T> {}
+unsafe impl<T: Sync> Sync for MyVec<T> {}
+
+impl<T> MyVec<T> {
+  const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+  pub fn new() -> MyVec<T> {
+    let cap: usize = if Self::IS_ZST { usize::MAX } else { 0 };
+    MyVec { ptr: NonNull::dangling(), cap, len: 0, _owns: PhantomData }
+  }
+
+  pub fn with_capacity(capacity: usize) -> MyVec<T> {
+    let mut vec: MyVec<T> = MyVec::new();
+    if capacity > vec.cap {
+      vec.grow_to(capacity);
+    }
+    vec
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.cap
+  }
+
+  pub fn as_ptr(&self) -> *const T {
+    self.ptr.as_ptr()
+  }
+
+  // Compiled to nothing in release builds, like every `debug_assert!`.
+  fn check_invariants(&self) {
+    debug_assert!(self.len <= self.cap, "len {} > cap {}", self.len, self.cap);
+    if Self::IS_ZST {
+      debug_assert_eq!(self.cap, usize::MAX, "a vector of zero-sized types never allocates");
+    }
+    if self.cap == 0 || Self::IS_ZST {
+      debug_assert_eq!(self.ptr, NonNull::dangling(), "nothing allocated, so the pointer must be dangling");
+    } else {
+      debug_assert!(Layout::array::<T>(self.cap).is_ok(), "the allocation must fit in isize::MAX bytes");
+    }
+  }
+
+  // -----------------------------------------------
+  // ## Allocation
+  //
+  // `Layout::array::<T>(n)` is the size and alignment of `[T; n]`, and fails when that exceeds `isize::MAX` bytes,
+  // the most any allocation may hold. `alloc` returns null when memory is exhausted, reported by
+  // `handle_alloc_error` (which aborts, like `Vec`).
+
+  fn grow(&mut self) {
+    // Doubling makes `push` amortised O(1): each element is moved O(1) times on average.
+    let new_cap: usize = if self.cap == 0 { 4 } else { self.cap.checked_mul(2).expect("capacity overflow") };
+    self.grow_to(new_cap);
+  }
+
+  fn grow_to(&mut self, new_cap: usize) {
+    // A ZST vector is born with `cap == usize::MAX`: it only gets here if that is not enough.
+    assert!(!Self::IS_ZST, "capacity overflow");
+    debug_assert!(new_cap > self.cap);
+    let new_layout: Layout = Layout::array::<T>(new_cap).expect("capacity overflow");
+    let new_ptr: *mut u8 = if self.cap == 0 {
+      // SAFETY: `new_layout` has a non-zero size: `T` is not zero-sized, and `new_cap > 0`.
+      unsafe { alloc::alloc(new_layout) }
+    } else {
+      let old_layout: Layout = Layout::array::<T>(self.cap).expect("checked when it was allocated");
+      // SAFETY: `ptr` was allocated with `old_layout` (invariant 3), and the new size is non-zero and, checked by
+      // `Layout::array`, at most `isize::MAX`. `realloc` moves the `len` initialised elements if needed.
+      unsafe { alloc::realloc(self.ptr.as_ptr().cast::<u8>(), old_layout, new_layout.size()) }
+    };
+    self.ptr = match NonNull::new(new_ptr.cast::<T>()) {
+      Some(ptr) => ptr,
+      None => alloc::handle_alloc_error(new_layout),
+    };
+    self.cap = new_cap;
+    self.check_invariants();
+  }
+
+  pub fn reserve(&mut self, additional: usize) {
+    let required: usize = self.len.checked_add(additional).expect("capacity overflow");
+    if required > self.cap {
+      self.grow_to(required.max(self.cap.saturating_mul(2)));
+    }
+  }
+
+  // -----------------------------------------------
+  // ## Adding and Removing Elements
+  //
+  // `ptr::write` stores into uninitialised slots, which must not be dropped; `ptr::read` moves a value out, after
+  // which its slot counts as uninitialised again; `ptr::copy` shifts a range (overlap allowed, like `memmove`).
+
+  pub fn push(&mut self, value: T) {
+    if self.len == self.cap {
+      self.grow();
+    }
+    // SAFETY: `len < cap` now, so slot `len` is allocated, and uninitialised (invariant 2).
+    unsafe { ptr::write(self.ptr.as_ptr().add(self.len), value) };
+    self.len += 1;
+    self.check_invariants();
+  }
+
+  pub fn pop(&mut self) -> Option<T> {
+    if self.len == 0 {
+      return None;
+    }
+    self.len -= 1;
+    self.check_invariants();
+    // SAFETY: slot `len` (the old last one) is initialised, and is now outside `0..len`, so nothing else will read or
+    // drop it.
+    Some(unsafe { ptr::read(self.ptr.as_ptr().add(self.len)) })
+  }
+
+  pub fn insert(&mut self, index: usize, value: T) {
+    assert!(index <= self.len, "insertion index (is {index}) should be <= len (is {})", self.len);
+    if self.len == self.cap {
+      self.grow();
+    }
+    // SAFETY: `len < cap`, so shifting `index..len` right by one stays within the allocation; slot `index` is then a
+    // bitwise duplicate, overwritten without being dropped.
+    unsafe {
+      let slot: *mut T = self.ptr.as_ptr().add(index);
+      ptr::copy(slot, slot.add(1), self.len - index);
+      ptr::write(slot, value);
+    }
+    self.len += 1;
+    self.check_invariants();
+  }
+
+  pub fn remove(&mut self, index: usize) -> T {
+    assert!(index < self.len, "removal index (is {index}) should be < len (is {})", self.len);
+    // SAFETY: `index < len`, so the slot is initialised. After the read it is moved from, and the shift overwrites
+    // it with `index + 1..len`; the old last slot is left as a duplicate, outside the new `0..len`.
+    let value: T = unsafe {
+      let slot: *mut T = self.ptr.as_ptr().add(index);
+      let value: T = ptr::read(slot);
+      ptr::copy(slot.add(1), slot, self.len - index - 1);
+      value
+    };
+    self.len -= 1;
+    self.check_invariants();
+    value
+  }
+
+  // Drops the elements from `new_len` on. The length is updated FIRST: if a `T::drop` panics, the vector no longer
+  // claims the elements being dropped, so none is dropped twice (at worst, some are leaked, which is safe).
+  pub fn truncate(&mut self, new_len: usize) {
+    if new_len >= self.len {
+      return;
+    }
+    let tail: *mut [T] = ptr::slice_from_raw_parts_mut(
+      // SAFETY: `new_len < len <= cap`: in bounds.
+      unsafe { self.ptr.as_ptr().add(new_len) },
+      self.len - new_len,
+    );
+    self.len = new_len;
+    // SAFETY: the tail's elements are initialised, and no longer part of the vector.
+    unsafe { ptr::drop_in_place(tail) };
+    self.check_invariants();
+  }
+
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+}
+
+impl<T> Default for MyVec<T> {
+  fn default() -> Self {
+    MyVec::new()
+  }
+}
+
+// -----------------------------------------------
+// ## Drop
+//
+// First the elements (only the `len` initialised ones), then the memory, with the layout it was allocated with.
+impl<T> Drop for MyVec<T> {
+  fn drop(&mut self) {
+    self.check_invariants();
+    // SAFETY: the first `len` slots are initialised (invariant 2), and are dropped exactly once, here.
+    unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len)) };
+    if self.cap != 0 && !Self::IS_ZST {
+      let layout: Layout = Layout::array::<T>(self.cap).expect("checked when it was allocated");
+      // SAFETY: `ptr` was allocated with this layout (invariant 3).
+      unsafe { alloc::dealloc(self.ptr.as_ptr().cast::<u8>(), layout) };
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Deref to a Slice
+//
+// All of a slice's methods (indexing, `iter`, `sort`, `contains`, `len`...) come for free through `Deref`. A slice
+// needs a non-null, aligned pointer even when empty: `NonNull::dangling()` is exactly that.
+impl<T> Deref for MyVec<T> {
+  type Target = [T];
+
+  fn deref(&self) -> &[T] {
+    // SAFETY: `ptr` is non-null and aligned, and the first `len` elements are initialised (invariants 2, 3).
+    unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+  }
+}
+
+impl<T> DerefMut for MyVec<T> {
+  fn deref_mut(&mut self) -> &mut [T] {
+    // SAFETY: as for `deref`; `&mut self` guarantees that the slice is the only reference.
+    unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+  }
+}
+
+// -----------------------------------------------
+// ## An Owning Iterator
+//
+// `into_iter` takes the buffer over: `ManuallyDrop` stops `MyVec::drop` from running, so the iterator becomes
+// responsible for the elements it has not yielded yet, and for the memory. Elements are moved out of the front
+// (`start`) or the back (`end`); those in `start..end` are still owned.
+pub struct IntoIter<T> {
+  buf: NonNull<T>,
+  cap: usize,
+  start: usize,
+  end: usize,
+  _owns: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for IntoIter<T> {}
+unsafe impl<T: Sync> Sync for IntoIter<T> {}
+
+impl<T> IntoIterator for MyVec<T> {
+  type Item = T;
+  type IntoIter = IntoIter<T>;
+
+  fn into_iter(self) -> IntoIter<T> {
+    let vec: ManuallyDrop<MyVec<T>> = ManuallyDrop::new(self);
+    IntoIter { buf: vec.ptr, cap: vec.cap, start: 0, end: vec.len, _owns: PhantomData }
+  }
+}
+
+impl<T> Iterator for IntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    if self.start == self.end {
+      return None;
+    }
+    // SAFETY: `start < end`, so the slot is still owned and initialised; advancing `start` gives it up.
+    let value: T = unsafe { ptr::read(self.buf.as_ptr().add(self.start)) };
+    self.start += 1;
+    Some(value)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining: usize = self.end - self.start;
+    (remaining, Some(remaining))
+  }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+  fn next_back(&mut self) -> Option<T> {
+    if self.start == self.end {
+      return None;
+    }
+    self.end -= 1;
+    // SAFETY: the old `end - 1` slot was still owned and initialised, and is now outside `start..end`.
+    Some(unsafe { ptr::read(self.buf.as_ptr().add(self.end)) })
+  }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> std::iter::FusedIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+  fn drop(&mut self) {
+    // Rebuilding a `MyVec` over the remaining elements reuses its `Drop`. First move them to the front (a no-op if
+    // nothing was taken from the front), so that they are its `0..len`.
+    let remaining: usize = self.end - self.start;
+    // SAFETY: `start..end` are the initialised elements still owned; `copy` handles the overlap.
+    unsafe { ptr::copy(self.buf.as_ptr().add(self.start), self.buf.as_ptr(), remaining) };
+    drop(MyVec { ptr: self.buf, cap: self.cap, len: remaining, _owns: PhantomData });
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MyVec<T> {
+  type Item = &'a T;
+  type IntoIter = slice::Iter<'a, T>;
+
+  fn into_iter(self) -> slice::Iter<'a, T> {
+    self.iter()
+  }
+}
+
+// -----------------------------------------------
+// ## The Usual Traits, Through the Slice
+
+impl<T> Extend<T> for MyVec<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+    for value in iter {
+      self.push(value);
+    }
+  }
+}
+
+impl<T> FromIterator<T> for MyVec<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> MyVec<T> {
+    let mut vec: MyVec<T> = MyVec::new();
+    vec.extend(iter);
+    vec
+  }
+}
+
+// If a `clone` panics halfway, the new vector holds the clones made so far, and drops them: nothing leaks.
+impl<T: Clone> Clone for MyVec<T> {
+  fn clone(&self) -> MyVec<T> {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MyVec<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<MyVec<U>> for MyVec<T> {
+  fn eq(&self, other: &MyVec<U>) -> bool {
+    self[..] == other[..]
+  }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for MyVec<T> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    self[..] == other[..]
+  }
+}
+
+#[macro_export]
+macro_rules! my_vec {
+  ($($value:expr),* $(,)?) => {{
+    let mut vec = $crate::_17_unsafe::_2_my_vec::MyVec::new();
+    $(vec.push($value);)*
+    vec
+  }};
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_11_collections::_5_benchmarks::shuffled_keys;
+  use std::cell::Cell;
+  use std::rc::Rc;
+
+  // Counts its drops in a shared counter: each value must be dropped exactly once.
+  #[derive(Debug, Clone)]
+  struct DropCounter {
+    id: u32,
+    drops: Rc<Cell<usize>>,
+  }
+
+  impl Drop for DropCounter {
+    fn drop(&mut self) {
+      self.drops.set(self.drops.get() + 1);
+    }
+  }
+
+  fn counters(count: u32, drops: &Rc<Cell<usize>>) -> MyVec<DropCounter> {
+    (0..count).map(|id| DropCounter { id, drops: Rc::clone(drops) }).collect()
+  }
+
+  #[test]
+  fn test_push_pop_and_growth() {
+    let mut vec: MyVec<i32> = MyVec::new();
+    assert_eq!((vec.len(), vec.capacity()), (0, 0));
+    let mut capacities: Vec<usize> = Vec::new();
+    for i in 0..20 {
+      vec.push(i);
+      capacities.push(vec.capacity());
+    }
+    capacities.dedup();
+    assert_eq!(capacities, vec![4, 8, 16, 32]);
+    assert_eq!(vec.pop(), Some(19));
+    assert_eq!(vec.len(), 19);
+    assert_eq!(vec[3], 3);
+    while vec.pop().is_some() {}
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), 32);
+  }
+
+  #[test]
+  fn test_insert_and_remove() {
+    let mut vec: MyVec<String> = ["b", "d"].into_iter().map(String::from).collect();
+    vec.insert(0, String::from("a"));
+    vec.insert(2, String::from("c"));
+    vec.insert(4, String::from("e"));
+    assert_eq!(vec, ["a", "b", "c", "d", "e"]);
+    assert_eq!(vec.remove(1), "b");
+    assert_eq!(vec.remove(3), "e");
+    assert_eq!(vec, ["a", "c", "d"]);
+  }
+
+  #[test]
+  fn test_matches_vec_on_random_operations() {
+    let keys: Vec<u64> = shuffled_keys(3000, 7);
+    let (mut mine, mut reference): (MyVec<u64>, Vec<u64>) = (MyVec::new(), Vec::new());
+    for key in keys {
+      match key % 5 {
+        0 | 1 => {
+          mine.push(key);
+          reference.push(key);
+        }
+        2 => assert_eq!(mine.pop(), reference.pop()),
+        3 => {
+          let index: usize = key as usize % (reference.len() + 1);
+          mine.insert(index, key);
+          reference.insert(index, key);
+        }
+        _ if !reference.is_empty() => {
+          let index: usize = key as usize % reference.len();
+          assert_eq!(mine.remove(index), reference.remove(index));
+        }
+        _ => {}
+      }
+      assert_eq!(mine[..], reference[..]);
+    }
+  }
+
+  #[test]
+  fn test_slice_methods_through_deref() {
+    let mut vec: MyVec<i32> = my_vec![5, 3, 9, 1];
+    vec.sort();
+    assert_eq!(vec, [1, 3, 5, 9]);
+    assert!(vec.contains(&9));
+    assert_eq!(vec.iter().sum::<i32>(), 18);
+    assert_eq!(vec.first(), Some(&1));
+    vec[0] = 7;
+    assert_eq!(format!("{vec:?}"), "[7, 3, 5, 9]");
+    let empty: MyVec<i32> = MyVec::new();
+    assert_eq!(empty[..], []);
+  }
+
+  #[test]
+  fn test_every_element_dropped_once() {
+    let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let mut vec: MyVec<DropCounter> = counters(10, &drops);
+    drop(vec.pop());
+    drop(vec.remove(0));
+    assert_eq!(drops.get(), 2);
+    vec.truncate(5);
+    assert_eq!(drops.get(), 5);
+    let copy: MyVec<DropCounter> = vec.clone();
+    drop(vec);
+    assert_eq!(drops.get(), 10);
+    drop(copy);
+    assert_eq!(drops.get(), 15);
+  }
+
+  #[test]
+  fn test_into_iter() {
+    let drops: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let mut iter: IntoIter<DropCounter> = counters(6, &drops).into_iter();
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.next().map(|counter| counter.id), Some(0));
+    assert_eq!(iter.next_back().map(|counter| counter.id), Some(5));
+    assert_eq!(iter.len(), 4);
+    assert_eq!(drops.get(), 2);
+    // The 4 elements left are dropped with the iterator.
+    drop(iter);
+    assert_eq!(drops.get(), 6);
+    let words: MyVec<String> = ["x", "y", "z"].into_iter().map(String::from).collect();
+    assert_eq!(words.into_iter().rev().collect::<Vec<String>>(), vec!["z", "y", "x"]);
+    let borrowed: MyVec<i32> = my_vec![1, 2];
+    assert_eq!((&borrowed).into_iter().count(), 2);
+  }
+
+  #[test]
+  fn test_zero_sized_types() {
+    let mut units: MyVec<()> = MyVec::new();
+    assert_eq!(units.capacity(), usize::MAX);
+    for _ in 0..1000 {
+      units.push(());
+    }
+    assert_eq!(units.len(), 1000);
+    // Nothing was allocated.
+    assert_eq!(units.as_ptr(), NonNull::<()>::dangling().as_ptr());
+    assert_eq!(units.pop(), Some(()));
+    assert_eq!(units.into_iter().count(), 999);
+  }
+
+  #[test]
+  fn test_with_capacity_and_reserve() {
+    let mut vec: MyVec<u8> = MyVec::with_capacity(10);
+    assert_eq!(vec.capacity(), 10);
+    vec.extend(0..10);
+    assert_eq!(vec.capacity(), 10);
+    vec.reserve(1);
+    assert_eq!(vec.capacity(), 20);
+    assert_eq!(MyVec::<u8>::with_capacity(0).capacity(), 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "capacity overflow")]
+  fn test_capacity_overflow() {
+    let _ = MyVec::<u64>::with_capacity(usize::MAX / 4);
+  }
+
+  #[test]
+  #[should_panic(expected = "removal index (is 3) should be < len (is 3)")]
+  fn test_remove_out_of_bounds() {
+    my_vec![1, 2, 3].remove(3);
+  }
+
+  #[test]
+  fn test_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<MyVec<String>>();
+    assert_send_sync::<IntoIter<String>>();
+    let vec: MyVec<u32> = my_vec![1, 2, 3];
+    let total: u32 = std::thread::spawn(move || vec.iter().sum()).join().unwrap();
+    assert_eq!(total, 6);
+  }
+}
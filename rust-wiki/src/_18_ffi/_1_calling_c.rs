@@ -0,0 +1,154 @@
+// -----------------------------------------------
+// # CALLING C FROM RUST
+//
+// A FOREIGN FUNCTION INTERFACE lets code in one language call functions compiled from another. C's is the lingua
+// franca: every language and OS can call, and be called through, C's calling convention (the "C ABI": which
+// registers and stack slots hold the arguments, and who cleans up).
+//
+// An `extern "C"` block DECLARES functions that exist elsewhere, with Rust types matching the C ones; the linker
+// finds their definitions. Here, in the C standard library (libc), which every Rust program on Linux or macOS already
+// links to:
+//
+//    size_t strlen(const char *s);
+//    void qsort(void *base, size_t count, size_t size, int (*compare)(const void *, const void *));
+//    pid_t getpid(void);
+//
+// Calling them is `unsafe`: the compiler cannot check that the declaration matches the real function, nor what the C
+// code does with the pointers it is given. Each call therefore has the usual `// SAFETY:` comment, and the module
+// wraps each function in a safe Rust one.
+//
+// ## Types Across the Boundary
+//
+//   C                         Rust
+//   ------------------------  -----------------------------------------------------------------
+//   int, long, size_t         std::ffi::c_int, c_long, usize (widths vary by platform: use the aliases)
+//   char *  (NUL-terminated)  *const c_char; borrowed as `&CStr`, owned as `CString`
+//   void *                    *mut c_void
+//   struct point { ... }      #[repr(C)] struct Point { ... }   (Rust's default layout is unspecified)
+//   int (*f)(int)             extern "C" fn(c_int) -> c_int   (or Option<...> if it may be NULL)
+//
+// Rust's `&str` is NOT a C string: it is not NUL-terminated, and may contain NUL bytes. `CString::new` adds the
+// terminator (and rejects interior NULs); `CStr` reads one back.
+
+use std::cmp::Ordering;
+use std::ffi::{c_char, c_int, c_void, CStr, CString, NulError};
+
+extern "C" {
+  fn strlen(s: *const c_char) -> usize;
+  fn qsort(base: *mut c_void, count: usize, size: usize, compare: extern "C" fn(*const c_void, *const c_void) -> c_int);
+}
+
+// `getpid` is POSIX, not standard C: only declared on Unix.
+#[cfg(unix)]
+extern "C" {
+  fn getpid() -> c_int;
+}
+
+// -----------------------------------------------
+// ## `strlen`: Passing a C String
+//
+// `c"..."` literals are `&'static CStr`s, NUL-terminated at compile time. A runtime string goes through `CString`,
+// which allocates a copy with the terminator; the pointer is only valid while the `CString` is alive.
+pub fn c_strlen(s: &CStr) -> usize {
+  // SAFETY: `s.as_ptr()` points to a NUL-terminated string that outlives the call; `strlen` only reads up to the NUL.
+  unsafe { strlen(s.as_ptr()) }
+}
+
+// Fails if `s` contains a NUL byte: C would see the string end there.
+pub fn c_strlen_of(s: &str) -> Result<usize, NulError> {
+  let owned: CString = CString::new(s)?;
+  Ok(c_strlen(&owned))
+}
+
+// A classic bug: `CString::new(s)?.as_ptr()` in one expression. The temporary `CString` is dropped at the end of the
+// statement, and the pointer dangles. Bind the `CString` to a variable first, as above.
+
+// -----------------------------------------------
+// ## `qsort`: C Calling Back Into Rust
+//
+// `qsort` takes a function pointer, which it calls with pointers to two elements. The comparison is written in Rust,
+// with the C ABI: `extern "C" fn`. It can be generic: each `T` gets its own instantiation, hence its own address.
+//
+// Two rules for callbacks:
+//   - A panic must not unwind into C code. An `extern "C" fn` that panics ABORTS the process instead (since Rust
+//     1.81); a callback that can fail should catch the panic with `catch_unwind`, or not panic.
+//   - `qsort`'s callback has no "user data" argument, so it cannot capture anything: no closures, only functions.
+//     (APIs that take a `void *context` alongside the function can be given a closure through it.)
+extern "C" fn compare_ord<T: Ord>(a: *const c_void, b: *const c_void) -> c_int {
+  // SAFETY: `qsort` passes pointers to two elements of the slice given to `sort_with_qsort`, which are valid `T`s.
+  let (a, b): (&T, &T) = unsafe { (&*a.cast::<T>(), &*b.cast::<T>()) };
+  match a.cmp(b) {
+    Ordering::Less => -1,
+    Ordering::Equal => 0,
+    Ordering::Greater => 1,
+  }
+}
+
+// Sorts any slice with libc's `qsort`. `qsort` moves elements by copying their bytes, which is a valid move for any
+// Rust type: Rust values never depend on their own address (unless pinned).
+pub fn sort_with_qsort<T: Ord>(values: &mut [T]) {
+  // SAFETY: the pointer, count and element size describe exactly the slice, which is borrowed mutably for the call;
+  // `compare_ord::<T>` reads its arguments as `T`s, which they are.
+  unsafe { qsort(values.as_mut_ptr().cast::<c_void>(), values.len(), std::mem::size_of::<T>(), compare_ord::<T>) }
+}
+
+// A hand-written comparison, in reverse order, for `i32`s only.
+extern "C" fn compare_i32_descending(a: *const c_void, b: *const c_void) -> c_int {
+  // SAFETY: only passed to `qsort` over a slice of `i32`s.
+  let (a, b): (i32, i32) = unsafe { (*a.cast::<i32>(), *b.cast::<i32>()) };
+  b.cmp(&a) as c_int
+}
+
+pub fn sort_descending_with_qsort(values: &mut [i32]) {
+  // SAFETY: as in `sort_with_qsort`, with `i32` elements.
+  unsafe { qsort(values.as_mut_ptr().cast::<c_void>(), values.len(), std::mem::size_of::<i32>(), compare_i32_descending) }
+}
+
+// -----------------------------------------------
+// ## `getpid`: No Arguments, Still `unsafe`
+//
+// Harmless as it is, the call is unsafe because the declaration is unchecked: had it been written `-> u64` or with
+// an argument, the program would read garbage. (std's own `std::process::id()` makes the same call.)
+#[cfg(unix)]
+pub fn process_id() -> u32 {
+  // SAFETY: `getpid` takes no arguments, cannot fail, and returns the `pid_t` (an `int` on Unix systems) declared.
+  let pid: c_int = unsafe { getpid() };
+  pid as u32
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_strlen() {
+    assert_eq!(c_strlen(c"hello"), 5);
+    // Bytes, not chars: "é" is two.
+    assert_eq!(c_strlen_of("héllo"), Ok(6));
+    assert_eq!(c_strlen_of(""), Ok(0));
+    assert!(c_strlen_of("nul\0inside").is_err());
+    // A C string can be cut short by an interior NUL.
+    assert_eq!(c_strlen(CStr::from_bytes_until_nul(b"ab\0cd\0").unwrap()), 2);
+  }
+
+  #[test]
+  fn test_qsort() {
+    let mut numbers: Vec<i32> = vec![5, -2, 9, 0, 3, 3];
+    sort_with_qsort(&mut numbers);
+    assert_eq!(numbers, vec![-2, 0, 3, 3, 5, 9]);
+    sort_descending_with_qsort(&mut numbers);
+    assert_eq!(numbers, vec![9, 5, 3, 3, 0, -2]);
+    // Owned, heap-allocated elements are moved around by C, byte by byte, and still dropped exactly once.
+    let mut words: Vec<String> = ["pear", "apple", "fig"].map(String::from).to_vec();
+    sort_with_qsort(&mut words);
+    assert_eq!(words, vec!["apple", "fig", "pear"]);
+    let mut empty: [u8; 0] = [];
+    sort_with_qsort(&mut empty);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_getpid() {
+    assert_eq!(process_id(), std::process::id());
+  }
+}
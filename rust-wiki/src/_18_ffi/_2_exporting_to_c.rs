@@ -0,0 +1,300 @@
+// -----------------------------------------------
+// # EXPOSING A C API FROM RUST
+//
+// The other direction: Rust functions that C code can call. Each needs:
+//   - `extern "C"`, to use the C calling convention instead of Rust's (unspecified) one.
+//   - `#[no_mangle]`, to keep its symbol name as written. Rust normally MANGLES names (adds the crate, module path
+//     and a hash) so that `a::f` and `b::f` do not clash; C looks a function up by its plain name.
+//   - argument and return types that C understands (see the table in _1_calling_c.rs).
+//
+// The crate is built as a `cdylib` (a C-compatible dynamic library, `librust_wiki.so`) as well as the usual `rlib`:
+// `crate-type = ["rlib", "cdylib"]` in Cargo.toml. The declarations for C are in ffi/wiki.h, and ffi/harness.c
+// exercises them from C (see tests/ffi.rs, and build.rs, which compiles the harness with the system `cc`).
+//
+// -----------------------------------------------
+// ## Who Frees What
+//
+// Rust and C do not share an allocator: memory allocated by Rust (`Box`, `String`, `Vec`) must be freed by Rust,
+// and memory allocated by C (`malloc`) by C's `free`. So an API that hands out memory also hands out the function
+// that frees it, and the header says which owns what:
+//   - BORROWED arguments (`const char *name`): C keeps ownership; Rust reads them during the call only.
+//   - RETURNED allocations (`wiki_greeting`, `wiki_fibonacci`): ownership moves to C, which must give them back to
+//     the matching `wiki_*_free` exactly once. Rust "forgets" them with `into_raw`, and takes them back with
+//     `from_raw`.
+//   - CALLER-PROVIDED buffers (`wiki_reverse_into`): C owns the memory; Rust only writes into it, within the size C
+//     said it has. No allocation crosses the boundary: the safest shape, used throughout the C standard library.
+//   - OPAQUE HANDLES (`WikiCounter`): C holds a pointer to a Rust struct it cannot see inside, and manipulates it
+//     through functions only.
+//
+// Functions that dereference pointers from C are `unsafe extern "C" fn`: C cannot be made to uphold a contract, but
+// Rust callers (and the tests) can, and the `# Safety` sections are that contract, mirrored in wiki.h.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+// -----------------------------------------------
+// ## Plain Values
+//
+// No pointers, no ownership: a safe function, callable from Rust as usual.
+#[no_mangle]
+pub extern "C" fn wiki_add(a: i32, b: i32) -> i32 {
+  a.wrapping_add(b)
+}
+
+// -----------------------------------------------
+// ## Strings: Returned to C, and Given Back
+
+/// Returns a newly allocated "Hello, <name>!", or null if `name` is null or not UTF-8. The result must be freed
+/// with `wiki_string_free`.
+///
+/// # Safety
+///
+/// `name` must be null, or point to a NUL-terminated string that stays valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_greeting(name: *const c_char) -> *mut c_char {
+  if name.is_null() {
+    return ptr::null_mut();
+  }
+  // SAFETY: non-null, and NUL-terminated and valid for the call by the contract.
+  let name: &CStr = unsafe { CStr::from_ptr(name) };
+  let Ok(name) = name.to_str() else {
+    return ptr::null_mut();
+  };
+  match CString::new(format!("Hello, {name}!")) {
+    // Ownership goes to the caller: the `CString` is not dropped here.
+    Ok(greeting) => greeting.into_raw(),
+    Err(_) => ptr::null_mut(),
+  }
+}
+
+/// Frees a string returned by `wiki_greeting`. Does nothing on null.
+///
+/// # Safety
+///
+/// `s` must be null, or a pointer returned by `wiki_greeting` that has not been freed yet. It must not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_string_free(s: *mut c_char) {
+  if !s.is_null() {
+    // SAFETY: `s` came from `CString::into_raw` (by the contract); `from_raw` takes ownership back, and dropping the
+    // `CString` frees it with the allocator it came from.
+    drop(unsafe { CString::from_raw(s) });
+  }
+}
+
+// -----------------------------------------------
+// ## Buffers: a Boxed Slice and Its Length
+//
+// A `Box<[u64]>` is a pointer and a length. C gets both, separately, and must give both back: freeing needs the
+// length to rebuild the box. (A `Vec` would also need its capacity: `into_boxed_slice` drops the spare capacity.)
+
+/// Returns the first `count` Fibonacci numbers (wrapping on overflow), writing the length to `*len`. Returns null,
+/// with `*len` set to 0, when `count` is 0. The buffer must be freed with `wiki_fibonacci_free`.
+///
+/// # Safety
+///
+/// `len` must point to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_fibonacci(count: usize, len: *mut usize) -> *mut u64 {
+  let mut numbers: Vec<u64> = Vec::with_capacity(count);
+  let (mut a, mut b): (u64, u64) = (0, 1);
+  for _ in 0..count {
+    numbers.push(a);
+    (a, b) = (b, a.wrapping_add(b));
+  }
+  // SAFETY: `len` is writable by the contract.
+  unsafe { len.write(numbers.len()) };
+  if numbers.is_empty() {
+    return ptr::null_mut();
+  }
+  Box::into_raw(numbers.into_boxed_slice()).cast::<u64>()
+}
+
+/// Frees a buffer returned by `wiki_fibonacci`. Does nothing on null.
+///
+/// # Safety
+///
+/// `buffer` must be null, or a pointer returned by `wiki_fibonacci`, with the length it reported, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_fibonacci_free(buffer: *mut u64, len: usize) {
+  if !buffer.is_null() {
+    // SAFETY: by the contract, `buffer` and `len` are the parts of a `Box<[u64]>` given away by `wiki_fibonacci`.
+    drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buffer, len)) });
+  }
+}
+
+// -----------------------------------------------
+// ## Caller-Provided Buffers
+//
+// The `snprintf` convention: write at most `capacity` bytes, NUL included, and return the length the full result
+// needs, so the caller can retry with a larger buffer. Truncation never splits a UTF-8 character.
+
+/// Writes `input` with its characters reversed into `output` (NUL-terminated, truncated to fit `capacity` bytes),
+/// and returns the length in bytes of the full reversed string, or -1 if `input` is null or not UTF-8.
+///
+/// # Safety
+///
+/// `input` must be null or a valid NUL-terminated string; `output` must be valid for writes of `capacity` bytes
+/// (it may be null if `capacity` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn wiki_reverse_into(input: *const c_char, output: *mut c_char, capacity: usize) -> isize {
+  if input.is_null() {
+    return -1;
+  }
+  // SAFETY: non-null and NUL-terminated by the contract.
+  let Ok(input) = unsafe { CStr::from_ptr(input) }.to_str() else {
+    return -1;
+  };
+  let reversed: String = input.chars().rev().collect();
+  if capacity > 0 {
+    let mut fits: usize = reversed.len().min(capacity - 1);
+    while !reversed.is_char_boundary(fits) {
+      fits -= 1;
+    }
+    // SAFETY: `fits + 1 <= capacity` bytes are written, which `output` has room for by the contract; the source is
+    // a Rust string, which cannot overlap memory C owns.
+    unsafe {
+      ptr::copy_nonoverlapping(reversed.as_ptr(), output.cast::<u8>(), fits);
+      output.add(fits).write(0);
+    }
+  }
+  reversed.len() as isize
+}
+
+// -----------------------------------------------
+// ## Opaque Handles
+//
+// C sees `typedef struct WikiCounter WikiCounter;`: a type it can point to but not look into, so the Rust struct can
+// hold anything (here a `Vec`, which has no C layout and needs no `#[repr(C)]`).
+pub struct WikiCounter {
+  counts: Vec<u64>,
+}
+
+#[no_mangle]
+pub extern "C" fn wiki_counter_new(slots: usize) -> *mut WikiCounter {
+  Box::into_raw(Box::new(WikiCounter { counts: vec![0; slots] }))
+}
+
+/// Increments slot `slot`, returning its new value, or 0 if the slot does not exist.
+///
+/// # Safety
+///
+/// `counter` must be a live pointer returned by `wiki_counter_new`, not used by another thread during the call.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_counter_increment(counter: *mut WikiCounter, slot: usize) -> u64 {
+  // SAFETY: live and exclusively ours for the call, by the contract.
+  let counter: &mut WikiCounter = unsafe { &mut *counter };
+  match counter.counts.get_mut(slot) {
+    Some(count) => {
+      *count += 1;
+      *count
+    }
+    None => 0,
+  }
+}
+
+/// The sum of all slots.
+///
+/// # Safety
+///
+/// `counter` must be a live pointer returned by `wiki_counter_new`.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_counter_total(counter: *const WikiCounter) -> u64 {
+  // SAFETY: live by the contract.
+  unsafe { &*counter }.counts.iter().sum()
+}
+
+/// Frees a counter. Does nothing on null.
+///
+/// # Safety
+///
+/// `counter` must be null, or a pointer returned by `wiki_counter_new` and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wiki_counter_free(counter: *mut WikiCounter) {
+  if !counter.is_null() {
+    // SAFETY: the box given away by `wiki_counter_new`, taken back once.
+    drop(unsafe { Box::from_raw(counter) });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  #[cfg(unix)]
+  use std::ffi::c_int;
+
+  // ffi/harness.c, compiled by build.rs and linked into this crate: C calling the functions above. It returns the
+  // number of failed checks, and describes the first one in `message`. Only built for Unix targets, see build.rs.
+  #[cfg(unix)]
+  extern "C" {
+    fn wiki_ffi_harness(message: *mut c_char, capacity: usize) -> c_int;
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_c_harness() {
+    let mut message: [c_char; 256] = [0; 256];
+    // SAFETY: `message` is writable for its whole length.
+    let failures: c_int = unsafe { wiki_ffi_harness(message.as_mut_ptr(), message.len()) };
+    // SAFETY: the harness always NUL-terminates `message`.
+    let message: &CStr = unsafe { CStr::from_ptr(message.as_ptr()) };
+    assert_eq!(failures, 0, "{}", message.to_string_lossy());
+  }
+
+  #[test]
+  fn test_strings_round_trip() {
+    // SAFETY: valid C strings in, and each result freed once.
+    unsafe {
+      let greeting: *mut c_char = wiki_greeting(c"Ferris".as_ptr());
+      assert_eq!(CStr::from_ptr(greeting), c"Hello, Ferris!");
+      wiki_string_free(greeting);
+      assert!(wiki_greeting(ptr::null()).is_null());
+      // Invalid UTF-8.
+      assert!(wiki_greeting(c"\xff".as_ptr()).is_null());
+      wiki_string_free(ptr::null_mut());
+    }
+  }
+
+  #[test]
+  fn test_buffers() {
+    let mut len: usize = usize::MAX;
+    // SAFETY: `len` is writable; the buffer is read within its length, then freed once.
+    unsafe {
+      let buffer: *mut u64 = wiki_fibonacci(10, &mut len);
+      assert_eq!(std::slice::from_raw_parts(buffer, len), [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+      wiki_fibonacci_free(buffer, len);
+      assert!(wiki_fibonacci(0, &mut len).is_null());
+      assert_eq!(len, 0);
+    }
+  }
+
+  #[test]
+  fn test_reverse_into() {
+    let mut output: [c_char; 8] = [0; 8];
+    // SAFETY: `output` has room for `output.len()` bytes.
+    unsafe {
+      assert_eq!(wiki_reverse_into(c"héllo".as_ptr(), output.as_mut_ptr(), output.len()), 6);
+      assert_eq!(CStr::from_ptr(output.as_ptr()), c"olléh");
+      // Room for 4 bytes and the NUL: the 4th byte is the first half of the 'é', so it backs off to "oll".
+      assert_eq!(wiki_reverse_into(c"héllo".as_ptr(), output.as_mut_ptr(), 5), 6);
+      assert_eq!(CStr::from_ptr(output.as_ptr()), c"oll");
+      assert_eq!(wiki_reverse_into(c"abc".as_ptr(), ptr::null_mut(), 0), 3);
+      assert_eq!(wiki_reverse_into(ptr::null(), output.as_mut_ptr(), output.len()), -1);
+    }
+  }
+
+  #[test]
+  fn test_opaque_counter() {
+    let counter: *mut WikiCounter = wiki_counter_new(2);
+    // SAFETY: `counter` is live until freed, and only used by this thread.
+    unsafe {
+      assert_eq!(wiki_counter_increment(counter, 0), 1);
+      assert_eq!(wiki_counter_increment(counter, 0), 2);
+      assert_eq!(wiki_counter_increment(counter, 1), 1);
+      assert_eq!(wiki_counter_increment(counter, 7), 0);
+      assert_eq!(wiki_counter_total(counter), 3);
+      wiki_counter_free(counter);
+    }
+    assert_eq!(wiki_add(i32::MAX, 1), i32::MIN);
+  }
+}
@@ -0,0 +1,150 @@
+// -----------------------------------------------
+// # INTERIOR MUTABILITY: THE BORROWING RULES AT RUNTIME
+//
+// _2_ownership/_3_references_and_lifetimes.rs states the rule behind every reference (`shared_reference_example`
+// and `mut_reference_example`): at any time, a value has either
+//   - any number of shared references `&T`, which can only read, or
+//   - exactly one mutable reference `&mut T`, which can read and write.
+// In short, ALIASING XOR MUTATION. The borrow checker proves it at compile time, so it costs nothing at runtime.
+//
+// Some programs need to mutate a value that is shared: a cache behind `&self`, a graph whose nodes point to each
+// other through `Rc`, a counter updated by several threads. The INTERIOR MUTABILITY types allow mutation through a
+// `&` to them, and keep the rule in a different way, some of them by checking it at runtime:
+//
+//   type            how the rule is kept                               if broken                thread-safe
+//   --------------  -------------------------------------------------  -----------------------  -----------
+//   Cell<T>         never hands out a reference to its inside:          cannot be broken         no
+//                   values are copied or moved in and out
+//   RefCell<T>      counts the `borrow`s and `borrow_mut`s alive        panic (BorrowMutError)   no
+//   Mutex<T>        one guard at a time; other threads wait             blocks (deadlock if the  yes
+//                                                                       same thread re-locks)
+//   RwLock<T>       many read guards, or one write guard                blocks                   yes
+//   Atomic*         every operation is indivisible                      cannot be broken         yes
+//
+// _7_smart_pointers/_3_cells.rs covers `Cell`, `RefCell` and `OnceCell`, and _8_concurrency/_3_locks.rs the locks.
+// This chapter puts them side by side, and _2_tracked_ref_cell.rs shows `RefCell`'s bookkeeping as it happens.
+//
+// The rule itself never changes: a `RefCell` still never lets a `&mut T` coexist with another reference to the
+// same `T`. What moves is WHEN it is checked, and what a violation costs: a compile error becomes a panic, or a
+// thread waiting.
+
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::sync::{Mutex, TryLockError};
+
+// -----------------------------------------------
+// ## The Same Rule, Three Ways
+//
+// `shared_reference_example` cannot change `s` while `immut_ref_s1` and `immut_ref_s2` are alive. With a `Cell`,
+// two shared references can both write: they never borrow the value inside, so there is nothing to invalidate.
+pub fn shared_mutation_with_cell() -> i32 {
+  let counter: Cell<i32> = Cell::new(0);
+  let (ref_1, ref_2): (&Cell<i32>, &Cell<i32>) = (&counter, &counter);
+  ref_1.set(ref_1.get() + 1);
+  ref_2.set(ref_2.get() + 10);
+  counter.get()
+}
+
+// `mut_reference_example` with a `RefCell`: the lines commented out there compile here, and are checked when they
+// run instead. `try_borrow_mut` reports the violation as an `Err`; `borrow_mut` would panic.
+pub fn mutation_with_ref_cell() -> (bool, String) {
+  let s: RefCell<String> = RefCell::new(String::from("hello"));
+
+  let reader = s.borrow();
+  // A shared borrow is alive: no mutable one.
+  let refused: bool = s.try_borrow_mut().is_err();
+  drop(reader);
+
+  // No borrow is alive: a mutable one is fine, and ends with the statement.
+  s.borrow_mut().push('s');
+  (refused, s.into_inner())
+}
+
+// The thread-safe version: a `Mutex` enforces "one `&mut` at a time" by making everyone else wait for the guard.
+// `try_lock` reports that instead of blocking. Calling `lock` here, while `guard` is alive, would never return.
+pub fn mutation_with_mutex() -> (bool, String) {
+  let s: Mutex<String> = Mutex::new(String::from("hello"));
+
+  let mut guard = s.lock().unwrap();
+  guard.push('s');
+  let refused: bool = matches!(s.try_lock(), Err(TryLockError::WouldBlock));
+  drop(guard);
+
+  s.lock().unwrap().push('!');
+  (refused, s.into_inner().unwrap())
+}
+
+// -----------------------------------------------
+// ## UnsafeCell: the Primitive Underneath
+//
+// The compiler assumes that the value behind a `&T` does not change while the reference is alive, and optimises
+// with that assumption: writing through a `&T` (e.g. by casting it to `*mut T`) is undefined behaviour. The single
+// exception is `UnsafeCell<T>`: the compiler treats its contents as possibly changing, and `get(&self)` returns a
+// `*mut T`. Every type above is a safe API around an `UnsafeCell`, in the way `MyVec` is around raw pointers
+// (_17_unsafe/_2_my_vec.rs): the unsafe code relies on a rule that its API makes impossible to break.
+//
+// `MyCell` rebuilds `Cell`. Its rule: no reference to the inside ever escapes. Values only go in and out by copy or
+// by move, so a `set` can never invalidate a reference that someone still holds. A method such as
+//
+//    pub fn get_ref(&self) -> &T
+//
+// would break it: `let r = cell.get_ref(); cell.set(other);` would change (and drop) the value `r` points to.
+// `UnsafeCell` is not `Sync`, so neither is `MyCell`: two threads can never call `set` at once.
+pub struct MyCell<T> {
+  value: UnsafeCell<T>,
+}
+
+impl<T> MyCell<T> {
+  pub fn new(value: T) -> MyCell<T> {
+    MyCell { value: UnsafeCell::new(value) }
+  }
+
+  pub fn set(&self, value: T) {
+    drop(self.replace(value));
+  }
+
+  pub fn replace(&self, value: T) -> T {
+    // SAFETY: no reference to the inside exists (none is ever handed out), and `MyCell` is not `Sync`, so this is
+    // the only access to the value while it runs.
+    unsafe { std::mem::replace(&mut *self.value.get(), value) }
+  }
+
+  pub fn into_inner(self) -> T {
+    self.value.into_inner()
+  }
+}
+
+impl<T: Copy> MyCell<T> {
+  // Only for `Copy` types: returning the value by copy leaves a valid value behind, and no reference escapes.
+  pub fn get(&self) -> T {
+    // SAFETY: as in `replace`; the value is copied out before the reference ends.
+    unsafe { *self.value.get() }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_the_rule_three_ways() {
+    assert_eq!(shared_mutation_with_cell(), 11);
+    assert_eq!(mutation_with_ref_cell(), (true, String::from("hellos")));
+    assert_eq!(mutation_with_mutex(), (true, String::from("hellos!")));
+  }
+
+  #[test]
+  fn test_my_cell() {
+    let cell: MyCell<i32> = MyCell::new(1);
+    let (a, b): (&MyCell<i32>, &MyCell<i32>) = (&cell, &cell);
+    a.set(a.get() + 1);
+    assert_eq!(b.replace(10), 2);
+    assert_eq!(cell.into_inner(), 10);
+
+    // Non-`Copy` values can be swapped in and out, but not looked at in place.
+    let words: MyCell<Vec<&str>> = MyCell::new(vec!["a"]);
+    let mut taken: Vec<&str> = words.replace(Vec::new());
+    taken.push("b");
+    words.set(taken);
+    assert_eq!(words.into_inner(), vec!["a", "b"]);
+  }
+}
@@ -0,0 +1,432 @@
+// -----------------------------------------------
+// # WATCHING A REFCELL: TrackedRefCell
+//
+// A `RefCell<T>` keeps one counter next to the value:
+//   - 0 when it is not borrowed,
+//   - n > 0 while n `Ref`s (from `borrow()`) are alive,
+//   - -1 while a `RefMut` (from `borrow_mut()`) is alive.
+// `borrow()` fails when the counter is -1, `borrow_mut()` when it is anything but 0, and dropping a guard moves it
+// back. The counter is private, so `TrackedRefCell` keeps its own copy, and records every step in a log:
+//
+//    #1 borrow               1 reader
+//    #2 borrow               2 readers
+//    #3 borrow_mut FAILED    2 readers
+//    #4 release borrow       1 reader
+//
+// It wraps a real `RefCell`, and its guards wrap the real `Ref` and `RefMut`, so the rules are RefCell's own: the
+// wrapper only watches. Failed borrows are logged first, then panic as `RefCell` does, with its error type in the
+// message ("already borrowed: BorrowMutError").
+
+use std::cell::{BorrowError, BorrowMutError, Cell, Ref, RefCell, RefMut};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorrowState {
+  Unborrowed,
+  Reading(usize),
+  Writing,
+}
+
+impl fmt::Display for BorrowState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BorrowState::Unborrowed => write!(f, "unborrowed"),
+      BorrowState::Reading(1) => write!(f, "1 reader"),
+      BorrowState::Reading(n) => write!(f, "{n} readers"),
+      BorrowState::Writing => write!(f, "1 writer"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorrowAction {
+  Borrow,
+  BorrowMut,
+  ReleaseBorrow,
+  ReleaseBorrowMut,
+  BorrowFailed,
+  BorrowMutFailed,
+}
+
+impl fmt::Display for BorrowAction {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let text: &str = match self {
+      BorrowAction::Borrow => "borrow",
+      BorrowAction::BorrowMut => "borrow_mut",
+      BorrowAction::ReleaseBorrow => "release borrow",
+      BorrowAction::ReleaseBorrowMut => "release borrow_mut",
+      BorrowAction::BorrowFailed => "borrow FAILED",
+      BorrowAction::BorrowMutFailed => "borrow_mut FAILED",
+    };
+    // `pad` applies the caller's width and alignment, as in `{:<20}`.
+    f.pad(text)
+  }
+}
+
+// One step of the log: what happened, and the state of the cell right after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorrowEvent {
+  pub action: BorrowAction,
+  pub state: BorrowState,
+}
+
+pub struct TrackedRefCell<T> {
+  value: RefCell<T>,
+  // The active borrows, mirroring RefCell's private counter. Both are `Cell`s: they change through `&self`.
+  readers: Cell<usize>,
+  writing: Cell<bool>,
+  log: RefCell<Vec<BorrowEvent>>,
+}
+
+impl<T> TrackedRefCell<T> {
+  pub fn new(value: T) -> TrackedRefCell<T> {
+    TrackedRefCell { value: RefCell::new(value), readers: Cell::new(0), writing: Cell::new(false), log: RefCell::new(Vec::new()) }
+  }
+
+  pub fn state(&self) -> BorrowState {
+    match (self.writing.get(), self.readers.get()) {
+      (true, _) => BorrowState::Writing,
+      (false, 0) => BorrowState::Unborrowed,
+      (false, n) => BorrowState::Reading(n),
+    }
+  }
+
+  pub fn active_borrows(&self) -> usize {
+    self.readers.get() + usize::from(self.writing.get())
+  }
+
+  fn record(&self, action: BorrowAction) {
+    // The log's own `RefCell` is also borrowed by `log` and `render_log`, but only while they copy or format it, and
+    // no guard is created or dropped during that. So no other borrow of the log is alive when `record` runs, and this
+    // `borrow_mut` can never fail.
+    self.log.borrow_mut().push(BorrowEvent { action, state: self.state() });
+  }
+
+  pub fn try_borrow(&self) -> Result<TrackedRef<'_, T>, BorrowError> {
+    match self.value.try_borrow() {
+      Ok(inner) => {
+        self.readers.set(self.readers.get() + 1);
+        self.record(BorrowAction::Borrow);
+        Ok(TrackedRef { inner, cell: self })
+      }
+      Err(error) => {
+        self.record(BorrowAction::BorrowFailed);
+        Err(error)
+      }
+    }
+  }
+
+  pub fn try_borrow_mut(&self) -> Result<TrackedRefMut<'_, T>, BorrowMutError> {
+    match self.value.try_borrow_mut() {
+      Ok(inner) => {
+        self.writing.set(true);
+        self.record(BorrowAction::BorrowMut);
+        Ok(TrackedRefMut { inner, cell: self })
+      }
+      Err(error) => {
+        self.record(BorrowAction::BorrowMutFailed);
+        Err(error)
+      }
+    }
+  }
+
+  // Panics if the value is mutably borrowed.
+  pub fn borrow(&self) -> TrackedRef<'_, T> {
+    match self.try_borrow() {
+      Ok(guard) => guard,
+      Err(error) => panic!("already mutably borrowed: {error:?}"),
+    }
+  }
+
+  // Panics if the value is borrowed at all.
+  pub fn borrow_mut(&self) -> TrackedRefMut<'_, T> {
+    match self.try_borrow_mut() {
+      Ok(guard) => guard,
+      Err(error) => panic!("already borrowed: {error:?}"),
+    }
+  }
+
+  pub fn log(&self) -> Vec<BorrowEvent> {
+    self.log.borrow().clone()
+  }
+
+  // The log as a numbered table, one line per event.
+  pub fn render_log(&self) -> String {
+    self
+      .log
+      .borrow()
+      .iter()
+      .enumerate()
+      .map(|(i, event)| format!("#{} {:<20} {}\n", i + 1, event.action, event.state))
+      .collect()
+  }
+
+  pub fn into_inner(self) -> T {
+    self.value.into_inner()
+  }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TrackedRefCell<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // Like `RefCell`'s `Debug`, shows `<borrowed>` rather than panicking when the value is mutably borrowed. It
+    // borrows the inner `RefCell` directly, so printing the cell does not add to its log.
+    let mut debug = f.debug_struct("TrackedRefCell");
+    match self.value.try_borrow() {
+      Ok(value) => debug.field("value", &*value),
+      Err(_) => debug.field("value", &format_args!("<borrowed>")),
+    };
+    debug.field("state", &self.state()).finish()
+  }
+}
+
+// -----------------------------------------------
+// ## The Guards
+//
+// They deref to the value like `Ref` and `RefMut`, and update the count and the log when dropped: the borrow ends
+// when the guard does, not when the variable holding it is last used (unlike a plain reference).
+pub struct TrackedRef<'a, T> {
+  inner: Ref<'a, T>,
+  cell: &'a TrackedRefCell<T>,
+}
+
+impl<T> Deref for TrackedRef<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.inner
+  }
+}
+
+impl<T> Drop for TrackedRef<'_, T> {
+  fn drop(&mut self) {
+    // `inner` is only dropped after this, so for a moment RefCell still counts this borrow; the log does not.
+    self.cell.readers.set(self.cell.readers.get() - 1);
+    self.cell.record(BorrowAction::ReleaseBorrow);
+  }
+}
+
+pub struct TrackedRefMut<'a, T> {
+  inner: RefMut<'a, T>,
+  cell: &'a TrackedRefCell<T>,
+}
+
+impl<T> Deref for TrackedRefMut<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.inner
+  }
+}
+
+impl<T> DerefMut for TrackedRefMut<'_, T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.inner
+  }
+}
+
+impl<T> Drop for TrackedRefMut<'_, T> {
+  fn drop(&mut self) {
+    self.cell.writing.set(false);
+    self.cell.record(BorrowAction::ReleaseBorrowMut);
+  }
+}
+
+// -----------------------------------------------
+// ## Runtime Borrow Errors in Practice
+//
+// The borrow checker rejects two `&mut` to the same value at compile time. Behind `RefCell`s, it cannot tell whether
+// two cells are the same one, so this compiles, and works until `from` and `to` are the same account:
+pub struct Account {
+  pub name: String,
+  pub balance: TrackedRefCell<i64>,
+}
+
+impl Account {
+  pub fn new(name: &str, balance: i64) -> Account {
+    Account { name: name.to_string(), balance: TrackedRefCell::new(balance) }
+  }
+}
+
+pub fn transfer(from: &Account, to: &Account, amount: i64) {
+  let mut source = from.balance.borrow_mut();
+  let mut target = to.balance.borrow_mut();   // panics if `to` is `from`: already borrowed: BorrowMutError
+  *source -= amount;
+  *target += amount;
+}
+
+// The fix: rule out the aliasing case before borrowing, and keep each borrow as short as a statement.
+pub fn transfer_checked(from: &Account, to: &Account, amount: i64) {
+  if std::ptr::eq(from, to) {
+    return;
+  }
+  *from.balance.borrow_mut() -= amount;
+  *to.balance.borrow_mut() += amount;
+}
+
+// The other classic: a shared borrow held across a call that wants to mutate. `total` borrows `entries` for the
+// whole loop, and `record` is called inside it.
+pub struct Ledger {
+  entries: TrackedRefCell<Vec<i64>>,
+}
+
+impl Ledger {
+  pub fn new(entries: Vec<i64>) -> Ledger {
+    Ledger { entries: TrackedRefCell::new(entries) }
+  }
+
+  pub fn record(&self, amount: i64) {
+    self.entries.borrow_mut().push(amount);
+  }
+
+  // Adds a 1% fee entry for each large entry, while still iterating over the entries: panics.
+  pub fn add_fees(&self) {
+    for &amount in self.entries.borrow().iter() {
+      if amount >= 100 {
+        self.record(-amount / 100);
+      }
+    }
+  }
+
+  // The fix: finish reading (the borrow ends with the statement) before writing.
+  pub fn add_fees_checked(&self) {
+    let fees: Vec<i64> = self.entries.borrow().iter().filter(|&&amount| amount >= 100).map(|amount| -amount / 100).collect();
+    for fee in fees {
+      self.record(fee);
+    }
+  }
+
+  pub fn entries(&self) -> Vec<i64> {
+    self.entries.borrow().clone()
+  }
+
+  pub fn render_log(&self) -> String {
+    self.entries.render_log()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::panic::{self, AssertUnwindSafe};
+
+  #[test]
+  fn test_counts_shared_borrows() {
+    let cell: TrackedRefCell<Vec<i32>> = TrackedRefCell::new(vec![1, 2, 3]);
+    assert_eq!(cell.state(), BorrowState::Unborrowed);
+    {
+      let first = cell.borrow();
+      let second = cell.borrow();
+      assert_eq!(cell.state(), BorrowState::Reading(2));
+      assert_eq!(cell.active_borrows(), 2);
+      assert_eq!(first.len() + second.len(), 6);
+      assert!(cell.try_borrow_mut().is_err());
+      drop(first);
+      assert_eq!(cell.state(), BorrowState::Reading(1));
+    }
+    assert_eq!(cell.state(), BorrowState::Unborrowed);
+    assert_eq!(
+      cell.render_log(),
+      "#1 borrow               1 reader\n\
+       #2 borrow               2 readers\n\
+       #3 borrow_mut FAILED    2 readers\n\
+       #4 release borrow       1 reader\n\
+       #5 release borrow       unborrowed\n"
+    );
+  }
+
+  #[test]
+  fn test_mutable_borrow_is_exclusive() {
+    let cell: TrackedRefCell<String> = TrackedRefCell::new(String::from("a"));
+    {
+      let mut writer = cell.borrow_mut();
+      writer.push('b');
+      assert_eq!(cell.state(), BorrowState::Writing);
+      assert!(cell.try_borrow().is_err());
+      assert!(cell.try_borrow_mut().is_err());
+      assert_eq!(format!("{cell:?}"), "TrackedRefCell { value: <borrowed>, state: Writing }");
+    }
+    assert_eq!(format!("{cell:?}"), "TrackedRefCell { value: \"ab\", state: Unborrowed }");
+    let actions: Vec<BorrowAction> = cell.log().iter().map(|event| event.action).collect();
+    assert_eq!(
+      actions,
+      vec![
+        BorrowAction::BorrowMut,
+        BorrowAction::BorrowFailed,
+        BorrowAction::BorrowMutFailed,
+        // `Debug` peeks at the value without being logged.
+        BorrowAction::ReleaseBorrowMut,
+      ]
+    );
+    assert_eq!(cell.into_inner(), "ab");
+  }
+
+  #[test]
+  fn test_failed_borrow_is_logged_before_panicking() {
+    let cell: TrackedRefCell<i32> = TrackedRefCell::new(0);
+    let reader = cell.borrow();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+      *cell.borrow_mut() += 1;
+    }));
+    assert!(result.is_err());
+    drop(reader);
+    assert_eq!(cell.log().last(), Some(&BorrowEvent { action: BorrowAction::ReleaseBorrow, state: BorrowState::Unborrowed }));
+    assert_eq!(cell.log()[1], BorrowEvent { action: BorrowAction::BorrowMutFailed, state: BorrowState::Reading(1) });
+  }
+
+  #[test]
+  #[should_panic(expected = "already borrowed: BorrowMutError")]
+  fn test_borrow_mut_while_mutably_borrowed_panics() {
+    let cell: TrackedRefCell<i32> = TrackedRefCell::new(0);
+    let _first = cell.borrow_mut();
+    let _second = cell.borrow_mut();
+  }
+
+  #[test]
+  #[should_panic(expected = "already borrowed: BorrowMutError")]
+  fn test_borrow_mut_while_borrowed_panics() {
+    let cell: TrackedRefCell<i32> = TrackedRefCell::new(0);
+    let _reader = cell.borrow();
+    let _writer = cell.borrow_mut();
+  }
+
+  #[test]
+  #[should_panic(expected = "already mutably borrowed: BorrowError")]
+  fn test_borrow_while_mutably_borrowed_panics() {
+    let cell: TrackedRefCell<i32> = TrackedRefCell::new(0);
+    let _writer = cell.borrow_mut();
+    let _reader = cell.borrow();
+  }
+
+  #[test]
+  fn test_transfer() {
+    let (alice, bob): (Account, Account) = (Account::new("alice", 100), Account::new("bob", 0));
+    transfer(&alice, &bob, 30);
+    transfer_checked(&bob, &alice, 10);
+    transfer_checked(&alice, &alice, 50);
+    assert_eq!((*alice.balance.borrow(), *bob.balance.borrow()), (80, 20));
+    assert_eq!(alice.name, "alice");
+  }
+
+  #[test]
+  #[should_panic(expected = "already borrowed: BorrowMutError")]
+  fn test_transfer_to_self_panics() {
+    let alice: Account = Account::new("alice", 100);
+    transfer(&alice, &alice, 10);
+  }
+
+  #[test]
+  #[should_panic(expected = "already borrowed: BorrowMutError")]
+  fn test_mutating_while_iterating_panics() {
+    let ledger: Ledger = Ledger::new(vec![50, 200]);
+    ledger.add_fees();
+  }
+
+  #[test]
+  fn test_mutating_after_iterating() {
+    let ledger: Ledger = Ledger::new(vec![50, 200, 300]);
+    ledger.add_fees_checked();
+    assert_eq!(ledger.entries(), vec![50, 200, 300, -2, -3]);
+    assert!(ledger.render_log().starts_with("#1 borrow               1 reader\n#2 release borrow       unborrowed\n#3 borrow_mut"));
+  }
+}
@@ -12,7 +12,7 @@ use std::io;
 // Constants are declared using `const .. = ..;` and exactly the same as immutable variables, except:
 //   1. They can be declared in sany scope including the global scope
 //   2. They can only be set to constant expressions and not the result of a value that is only computable at runtime.
-fn immutable_variables(){
+pub fn immutable_variables(){
 
     let x : i32 = 5; // x is immutable
     // x = 6;        // not allowed
@@ -32,8 +32,23 @@ fn immutable_variables(){
 // The One Rule of Mutability:
 //  * Mutability is inherited in Rust: whether a value is mutable or not is decided by the variable that **owns** it.
 
-fn mutable_variables(){
+pub fn mutable_variables(){
     let mut x : i32 = 5;  // x is mutable
     x = 6;    // allowed
     println!("The value of x is: {x}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_immutable_variables() {
+        immutable_variables();
+    }
+
+    #[test]
+    fn test_mutable_variables() {
+        mutable_variables();
+    }
+}
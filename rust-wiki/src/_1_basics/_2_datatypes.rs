@@ -3,7 +3,7 @@ use std::io;
 // # Data Types
 // Every value in Rust is of a certain data type, where type annotations are specified with `:`.
 // There are two data type subsets: scalar and compound.
-fn data_types() {
+pub fn data_types() {
 
   // ## Scalar Types
   // A scalar type represents a single value. Rust has four primary scalar types: integers, floats, Booleans, and characters.
@@ -68,6 +68,8 @@ fn data_types() {
 
   // We can access elements in a string slice by indexing:
   let first_char = &str_version[0..1]; // Slices the first character, returns "h"
+  // Careful: the range is in BYTES, so this is the first character only when it is ASCII. On "éa" it panics, as
+  // byte 1 is inside the two-byte 'é'. See _2_ownership/_5_utf8_text.rs for slicing that is UTF-8 correct.
 
   // #### Strings (`String`, essentially a byte vector `Vec<u8>` that guarantees valid UTF-8 characters)
   // A `String` has ownership over a growable mutable string stored on the heap.
@@ -86,8 +88,15 @@ fn data_types() {
   string_version.push('!'); // Adds a single character to the end
 
 
-  // ### Invalid Element Acccess
-  // Rust protects you against invalid element access by immediately exiting instead of allowing the memory access and continuing.
+  // ### Mutable data
+  // In Rust, mutability is always inherited. There is no intrinsic notion of mutability in the definitions of datatypes.
+  // Hence, all components of a datatype inherit the mutability of the whole data type, which is decided by the owner that creates that data.
+}
+
+// ### Invalid Element Acccess
+// Rust protects you against invalid element access by immediately exiting instead of allowing the memory access and continuing.
+pub fn invalid_element_access() {
+  let arr: [i32; 5] = [3; 5];
   let mut index = String::new();
   io::stdin()
       .read_line(&mut index)
@@ -96,10 +105,31 @@ fn data_types() {
       .trim()
       .parse()
       .expect("Index entered was not a number");
-  let element = arr[index];                                       // terminates program eearly if index > arr.leng().
-  println!("The value of the element at index {index} is: {element}"); // does not print if index > arr.len()
-
-  // ### Mutable data
-  // In Rust, mutability is always inherited. There is no intrinsic notion of mutability in the definitions of datatypes.
-  // Hence, all components of a datatype inherit the mutability of the whole data type, which is decided by the owner that creates that data.
-}
\ No newline at end of file
+  let element = element_at(&arr, index);
+  println!("The value of the element at index {index} is: {element}"); // does not print if index >= arr.len()
+}
+
+pub fn element_at(arr: &[i32; 5], index: usize) -> i32 {
+  arr[index]                                                      // terminates program early if index >= arr.len().
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_data_types() {
+    data_types();
+  }
+
+  #[test]
+  fn test_element_at_in_bounds() {
+    assert_eq!(element_at(&[1, 2, 3, 4, 5], 4), 5);
+  }
+
+  #[test]
+  #[should_panic(expected = "index out of bounds")]
+  fn test_element_at_out_of_bounds() {
+    element_at(&[1, 2, 3, 4, 5], 5);
+  }
+}
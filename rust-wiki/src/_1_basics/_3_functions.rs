@@ -1,6 +1,6 @@
 
 // # Functions
-fn functions() -> () {
+pub fn functions() -> () {
   // ## Statements and expressions
   // Statements are instructions that perform some action and do not return a value, and are separated by ';'.
   // Expressions evaluate to a resultant value, and do not finish with a ';'
@@ -21,4 +21,14 @@ fn functions() -> () {
       };
       n + 2 // return normally
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_functions() {
+    assert_eq!(functions(), ());
+  }
+}
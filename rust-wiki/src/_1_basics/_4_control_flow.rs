@@ -1,6 +1,6 @@
 
 // # Control flow
-fn control_flow() -> () {
+pub fn control_flow() -> () {
   // ## Explicit (Early) Return: Statement-Based
   // Using `return` as a statement will return a value from the current function, exiting early.
   return ();
@@ -67,3 +67,13 @@ fn control_flow() -> () {
   // Ending a function with an expression i.e. without a semicolon will return it.
   ()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_control_flow_returns_early() {
+    assert_eq!(control_flow(), ());
+  }
+}
@@ -1,30 +1,277 @@
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
 /* StdOut and StdErr
 
 On most operating systems, a program can write to two output streams, stdout and stderr. stdout is for the program’s actual output, while stderr allows errors and other messages to be kept separate from stdout. That way, output can be stored to a file or piped to another program while errors are shown to the user.
 
 In Rust this is achieved with println! and eprintln!, the former printing to stdout and the latter to stderr.
 */
-println!("Hello World");
+pub fn stdout_and_stderr() {
+    println!("Hello World");
 
-eprintln!("This is an error! :(");
+    eprintln!("This is an error! :(");
+}
 
 /* Logging
 
 Logging is the same as using println!, except that you can specify the importance of a message. The levels you can usually use are error, warn, info, debug, and trace (error has the highest priority, trace the lowest).
 
+The usual approach is the `log` crate for the macros plus a backend such as `env_logger`:
+
+  use log::{info, warn};
+
+  fn main() {
+      env_logger::init();
+      info!("starting up");
+      warn!("oops, nothing implemented!");
+  }
+
+This wiki has no dependencies, so below builds the same thing by hand:
+  1. `Level` is the importance of a message.
+  2. `Filter` decides which levels are enabled, parsed from the `RUST_LOG` environment variable.
+  3. `Logger` formats a message and routes it: errors and warnings go to stderr, everything else to stdout.
+  4. `init()` installs a global `Logger`, which the `error!`, `warn!`, `info!`, `debug!` and `trace!` macros write to.
+
+  use rust_wiki::{info, warn};
+
+  fn main() {
+      rust_wiki::_1_basics::_5_io_and_logging::init();
+      info!("starting up");
+      warn!("oops, nothing implemented!");
+  }
+
+To enable different levels of logging when executing a rust project
+  > RUST_LOG=<log-level> cargo run
+  e.g.
+  > RUST_LOG=info cargo run
+  > RUST_LOG=warn,rust_wiki::_2_ownership=trace cargo run
+
+The chapters log their traces and reports (drop timelines, reference counts, fat pointer layouts, ...) with `debug!`,
+and warnings with `warn!`, so they stay quiet unless asked for. `src/main.rs` installs the logger, then runs an example that logs its trace:
+  > RUST_LOG=rust_wiki::_7_smart_pointers=debug cargo run
 */
-use log::{info, warn};
 
-fn main() {
-    env_logger::init();
-    info!("starting up");
-    warn!("oops, nothing implemented!");
-  /*
-  To enable different levels of logging when executing a rust project
-    > RUST_LOG=<log-level> cargo run
-    e.g.
-    > RUST_LOG=info cargo run
-  */
+// Levels are ordered by verbosity, so `Error < Warn < Info < Debug < Trace`, and a filter of `Info` enables every
+// level that is `<= Info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name: &str = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        // `pad` rather than `write_str`, so that width specifiers like `{:<5}` line up the levels.
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseLevelError(pub String);
+
+impl fmt::Display for ParseLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown log level `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseLevelError {}
+
+// `None` is the level "off", which disables logging entirely.
+fn parse_level(s: &str) -> Result<Option<Level>, ParseLevelError> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "off" => Ok(None),
+        "error" => Ok(Some(Level::Error)),
+        "warn" => Ok(Some(Level::Warn)),
+        "info" => Ok(Some(Level::Info)),
+        "debug" => Ok(Some(Level::Debug)),
+        "trace" => Ok(Some(Level::Trace)),
+        _ => Err(ParseLevelError(s.to_string())),
+    }
+}
+
+impl FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_level(s)?.ok_or_else(|| ParseLevelError(s.to_string()))
+    }
+}
+
+// ## Filtering: RUST_LOG
+//
+// `RUST_LOG` is a comma-separated list of directives, each either `level` or `module::path=level`:
+//    RUST_LOG=info                               // everything at info or above
+//    RUST_LOG=warn,rust_wiki::_2_ownership=trace // warnings everywhere, but everything from the ownership chapter
+// A message is checked against the directive with the longest module path that prefixes the message's module.
+// A directive without a module path applies to every module, and without any directive only errors are logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    directives: Vec<(Option<String>, Option<Level>)>,
+}
+
+impl Filter {
+    pub fn from_env() -> Filter {
+        match std::env::var("RUST_LOG") {
+            // An invalid RUST_LOG shouldn't stop the program, so fall back to the default.
+            Ok(spec) => spec.parse().unwrap_or_default(),
+            Err(_) => Filter::default(),
+        }
+    }
+
+    pub fn enabled(&self, level: Level, module: &str) -> bool {
+        let is_prefix = |path: &str| {
+            module == path || (module.starts_with(path) && module[path.len()..].starts_with("::"))
+        };
+        let max_level: Option<Level> = self
+            .directives
+            .iter()
+            .filter(|(path, _)| path.as_deref().is_none_or(is_prefix))
+            .max_by_key(|(path, _)| path.as_ref().map_or(0, String::len))
+            .map_or(Some(Level::Error), |(_, level)| *level);
+        max_level.is_some_and(|max_level| level <= max_level)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter { directives: vec![(None, Some(Level::Error))] }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = ParseLevelError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut directives: Vec<(Option<String>, Option<Level>)> = Vec::new();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((path, level)) => directives.push((Some(path.trim().to_string()), parse_level(level)?)),
+                // A bare module path (e.g. `RUST_LOG=rust_wiki`) enables everything from that module.
+                None => match parse_level(directive) {
+                    Ok(level) => directives.push((None, level)),
+                    Err(_) => directives.push((Some(directive.to_string()), Some(Level::Trace))),
+                },
+            }
+        }
+        Ok(Filter { directives })
+    }
+}
+
+// ## The Logger: Formatting and Routing
+//
+// A `Logger` owns two writers: `out` for info, debug and trace messages, and `err` for errors and warnings.
+// These are `Stdout` and `Stderr` for the global logger, but can be any `Write`r (e.g. a `Vec<u8>` in tests).
+// The writers sit behind a `Mutex` so that `log(&self, ...)` can be shared between threads.
+pub struct Logger<O: Write, E: Write> {
+    filter: Filter,
+    writers: Mutex<(O, E)>,
+}
+
+impl Logger<io::Stdout, io::Stderr> {
+    pub fn from_env() -> Self {
+        Logger::new(Filter::from_env(), io::stdout(), io::stderr())
+    }
+}
+
+impl<O: Write, E: Write> Logger<O, E> {
+    pub fn new(filter: Filter, out: O, err: E) -> Self {
+        Logger { filter, writers: Mutex::new((out, err)) }
+    }
+
+    pub fn enabled(&self, level: Level, module: &str) -> bool {
+        self.filter.enabled(level, module)
+    }
+
+    pub fn log(&self, level: Level, module: &str, args: fmt::Arguments<'_>) {
+        if !self.enabled(level, module) {
+            return;
+        }
+        // A panic while holding the lock poisons it, but the writers themselves are still usable.
+        let mut writers = self.writers.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (out, err) = &mut *writers;
+        let target: &mut dyn Write = if level <= Level::Warn { err } else { out };
+        // Logging is best-effort: a closed pipe shouldn't crash the program.
+        let _ = writeln!(target, "[{level:<5} {module}] {args}");
+    }
+
+    pub fn into_writers(self) -> (O, E) {
+        self.writers.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+static LOGGER: OnceLock<Logger<io::Stdout, io::Stderr>> = OnceLock::new();
+
+// Installs the global logger, configured from `RUST_LOG`. Like `env_logger::init()`, nothing is logged before this is
+// called; unlike it, calling it again is harmless and keeps the first configuration.
+pub fn init() {
+    LOGGER.get_or_init(Logger::from_env);
+}
+
+// The entry point used by the logging macros.
+pub fn log(level: Level, module: &str, args: fmt::Arguments<'_>) {
+    if let Some(logger) = LOGGER.get() {
+        logger.log(level, module, args);
+    }
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::_1_basics::_5_io_and_logging::log(
+            $crate::_1_basics::_5_io_and_logging::Level::Error, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        $crate::_1_basics::_5_io_and_logging::log(
+            $crate::_1_basics::_5_io_and_logging::Level::Warn, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        $crate::_1_basics::_5_io_and_logging::log(
+            $crate::_1_basics::_5_io_and_logging::Level::Info, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::_1_basics::_5_io_and_logging::log(
+            $crate::_1_basics::_5_io_and_logging::Level::Debug, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::_1_basics::_5_io_and_logging::log(
+            $crate::_1_basics::_5_io_and_logging::Level::Trace, module_path!(), format_args!($($arg)+))
+    };
+}
+
+pub fn logging_example() {
+    init();
+    crate::info!("starting up");
+    crate::warn!("oops, nothing implemented!");
 }
 
 /* Progress bar
@@ -32,10 +279,148 @@ fn main() {
 Some CLI applications run less than a second, others take minutes or hours. If you are writing one of the latter types of programs, you might want to show the user that something is happening. For this, you should try to print useful status updates, ideally in a form that can be easily consumed.
 
 Using the indicatif crate, you can add progress bars and little spinners to your program. Here’s a quick example:
+
+  fn main() {
+      let pb = indicatif::ProgressBar::new(100);
+      for i in 0..100 {
+          do_hard_work();
+          pb.println(format!("[+] finished #{}", i));
+          pb.inc(1);
+      }
+      pb.finish_with_message("done");
+  }
+
+Below builds the same `ProgressBar` (and a `Spinner`) by hand.
+Both redraw their single line in place by starting it with a carriage return `\r`, which moves the cursor back to the
+start of the line without moving down. Like indicatif, they draw to stderr, and draw nothing if stderr isn't a terminal
+(e.g. when it is redirected to a file), so that the output isn't filled with partial redraws.
 */
 
-fn main() {
-    let pb = indicatif::ProgressBar::new(100);
+pub struct ProgressBar<W: Write> {
+    len: u64,
+    pos: u64,
+    width: usize,
+    target: Option<W>,
+}
+
+impl ProgressBar<io::Stderr> {
+    pub fn new(len: u64) -> Self {
+        let stderr: io::Stderr = io::stderr();
+        let target: Option<io::Stderr> = if stderr.is_terminal() { Some(stderr) } else { None };
+        ProgressBar { len, pos: 0, width: 40, target }
+    }
+}
+
+impl<W: Write> ProgressBar<W> {
+    pub fn with_target(len: u64, target: W) -> Self {
+        ProgressBar { len, pos: 0, width: 40, target: Some(target) }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    // e.g. `[================>                       ] 42/100`
+    pub fn render(&self) -> String {
+        // An empty bar has nothing left to do, so it is drawn full.
+        // Computed in `u128`, so that `pos * width` cannot overflow even for a `len` near `u64::MAX`.
+        let filled: usize =
+            (u128::from(self.pos) * self.width as u128).checked_div(u128::from(self.len)).map_or(self.width, |f| f as usize);
+        let bar: String = if filled >= self.width {
+            "=".repeat(self.width)
+        } else {
+            format!("{}>{}", "=".repeat(filled), " ".repeat(self.width - filled - 1))
+        };
+        format!("[{bar}] {}/{}", self.pos, self.len)
+    }
+
+    pub fn inc(&mut self, delta: u64) {
+        self.pos = self.pos.saturating_add(delta).min(self.len);
+        self.draw();
+    }
+
+    // Prints a line above the bar, then redraws the bar below it.
+    pub fn println(&mut self, msg: impl fmt::Display) {
+        self.write_line(&format!("\r\x1b[2K{msg}\n"));
+        self.draw();
+    }
+
+    pub fn finish_with_message(&mut self, msg: impl fmt::Display) {
+        self.pos = self.len;
+        let line: String = format!("\r{} {msg}\n", self.render());
+        self.write_line(&line);
+    }
+
+    pub fn into_target(self) -> Option<W> {
+        self.target
+    }
+
+    fn draw(&mut self) {
+        let line: String = format!("\r{}", self.render());
+        self.write_line(&line);
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if let Some(target) = &mut self.target {
+            let _ = target.write_all(line.as_bytes());
+            let _ = target.flush();
+        }
+    }
+}
+
+// A spinner is a progress bar for work of unknown length: each tick draws the next frame.
+pub struct Spinner<W: Write> {
+    frame: usize,
+    message: String,
+    target: Option<W>,
+}
+
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+impl Spinner<io::Stderr> {
+    pub fn new(message: impl Into<String>) -> Self {
+        let stderr: io::Stderr = io::stderr();
+        let target: Option<io::Stderr> = if stderr.is_terminal() { Some(stderr) } else { None };
+        Spinner { frame: 0, message: message.into(), target }
+    }
+}
+
+impl<W: Write> Spinner<W> {
+    pub fn with_target(message: impl Into<String>, target: W) -> Self {
+        Spinner { frame: 0, message: message.into(), target: Some(target) }
+    }
+
+    pub fn render(&self) -> String {
+        format!("{} {}", SPINNER_FRAMES[self.frame % SPINNER_FRAMES.len()], self.message)
+    }
+
+    pub fn tick(&mut self) {
+        self.frame += 1;
+        let line: String = format!("\r{}", self.render());
+        self.write_line(&line);
+    }
+
+    pub fn finish_with_message(&mut self, msg: impl fmt::Display) {
+        self.write_line(&format!("\r\x1b[2K{msg}\n"));
+    }
+
+    pub fn into_target(self) -> Option<W> {
+        self.target
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if let Some(target) = &mut self.target {
+            let _ = target.write_all(line.as_bytes());
+            let _ = target.flush();
+        }
+    }
+}
+
+pub fn progress_bar_example() {
+    fn do_hard_work() {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let mut pb: ProgressBar<io::Stderr> = ProgressBar::new(100);
     for i in 0..100 {
         do_hard_work();
         pb.println(format!("[+] finished #{}", i));
@@ -44,3 +429,163 @@ fn main() {
     pb.finish_with_message("done");
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logged(filter: &str, messages: &[(Level, &str, &str)]) -> (String, String) {
+        let logger: Logger<Vec<u8>, Vec<u8>> = Logger::new(filter.parse().unwrap(), Vec::new(), Vec::new());
+        for (level, module, msg) in messages {
+            logger.log(*level, module, format_args!("{msg}"));
+        }
+        let (out, err) = logger.into_writers();
+        (String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+    }
+
+    #[test]
+    fn test_stdout_and_stderr() {
+        stdout_and_stderr();
+    }
+
+    #[test]
+    fn test_level_order_and_parsing() {
+        assert!(Level::Error < Level::Warn && Level::Warn < Level::Info);
+        assert!(Level::Info < Level::Debug && Level::Debug < Level::Trace);
+        assert_eq!("WARN".parse(), Ok(Level::Warn));
+        assert_eq!(" trace ".parse(), Ok(Level::Trace));
+        assert_eq!("loud".parse::<Level>(), Err(ParseLevelError(String::from("loud"))));
+        assert_eq!(format!("[{:<5}]", Level::Info), "[INFO ]");
+    }
+
+    #[test]
+    fn test_default_filter_only_enables_errors() {
+        let filter: Filter = Filter::default();
+        assert!(filter.enabled(Level::Error, "rust_wiki"));
+        assert!(!filter.enabled(Level::Warn, "rust_wiki"));
+        assert!(!"".parse::<Filter>().unwrap().enabled(Level::Warn, "rust_wiki"));
+    }
+
+    #[test]
+    fn test_filter_levels() {
+        let filter: Filter = "info".parse().unwrap();
+        assert!(filter.enabled(Level::Warn, "rust_wiki"));
+        assert!(filter.enabled(Level::Info, "rust_wiki"));
+        assert!(!filter.enabled(Level::Debug, "rust_wiki"));
+
+        let off: Filter = "off".parse().unwrap();
+        assert!(!off.enabled(Level::Error, "rust_wiki"));
+    }
+
+    #[test]
+    fn test_filter_most_specific_module_wins() {
+        let filter: Filter = "warn,rust_wiki::_2_ownership=trace,rust_wiki::_2_ownership::_4_slices=off".parse().unwrap();
+        assert!(!filter.enabled(Level::Info, "rust_wiki::_1_basics"));
+        assert!(filter.enabled(Level::Trace, "rust_wiki::_2_ownership::_2_owners_and_scope"));
+        assert!(!filter.enabled(Level::Error, "rust_wiki::_2_ownership::_4_slices"));
+        // A module path only matches whole path segments.
+        assert!(!filter.enabled(Level::Trace, "rust_wiki::_2_ownershipx"));
+    }
+
+    #[test]
+    fn test_filter_bare_module_enables_everything() {
+        let filter: Filter = "rust_wiki::_1_basics".parse().unwrap();
+        assert!(filter.enabled(Level::Trace, "rust_wiki::_1_basics::_5_io_and_logging"));
+        assert!(filter.enabled(Level::Error, "rust_wiki::_2_ownership"));
+        assert!(!filter.enabled(Level::Warn, "rust_wiki::_2_ownership"));
+    }
+
+    #[test]
+    fn test_filter_rejects_unknown_levels() {
+        assert!("rust_wiki=loud".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_logger_routes_errors_and_warnings_to_stderr() {
+        let (out, err) = logged(
+            "trace",
+            &[
+                (Level::Error, "m", "e"),
+                (Level::Warn, "m", "w"),
+                (Level::Info, "m", "i"),
+                (Level::Debug, "m", "d"),
+                (Level::Trace, "m", "t"),
+            ],
+        );
+        assert_eq!(err, "[ERROR m] e\n[WARN  m] w\n");
+        assert_eq!(out, "[INFO  m] i\n[DEBUG m] d\n[TRACE m] t\n");
+    }
+
+    #[test]
+    fn test_logger_drops_filtered_messages() {
+        let (out, err) = logged("warn", &[(Level::Warn, "m", "kept"), (Level::Info, "m", "dropped")]);
+        assert_eq!(err, "[WARN  m] kept\n");
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn test_macros_without_init_are_harmless() {
+        crate::error!("not initialised: {}", 1);
+        crate::trace!("not initialised");
+    }
+
+    #[test]
+    fn test_logging_example() {
+        logging_example();
+        crate::debug!("after init: {}", 2);
+    }
+
+    #[test]
+    fn test_progress_bar_render() {
+        let mut pb: ProgressBar<Vec<u8>> = ProgressBar::with_target(4, Vec::new());
+        pb.width = 8;
+        assert_eq!(pb.render(), "[>       ] 0/4");
+        pb.inc(1);
+        assert_eq!(pb.render(), "[==>     ] 1/4");
+        pb.inc(10);
+        assert_eq!(pb.position(), 4);
+        assert_eq!(pb.render(), "[========] 4/4");
+    }
+
+    #[test]
+    fn test_progress_bar_output() {
+        let mut pb: ProgressBar<Vec<u8>> = ProgressBar::with_target(2, Vec::new());
+        pb.width = 4;
+        pb.inc(1);
+        pb.println("[+] finished #0");
+        pb.finish_with_message("done");
+        let output: String = String::from_utf8(pb.into_target().unwrap()).unwrap();
+        assert_eq!(
+            output,
+            "\r[==> ] 1/2\r\x1b[2K[+] finished #0\n\r[==> ] 1/2\r[====] 2/2 done\n"
+        );
+    }
+
+    #[test]
+    fn test_progress_bar_huge_lengths() {
+        let mut pb: ProgressBar<Vec<u8>> = ProgressBar::with_target(u64::MAX, Vec::new());
+        pb.width = 4;
+        pb.inc(u64::MAX / 2 + 1);
+        assert_eq!(pb.render(), format!("[==> ] {}/{}", u64::MAX / 2 + 1, u64::MAX));
+        pb.inc(u64::MAX);
+        assert_eq!(pb.position(), u64::MAX);
+        assert_eq!(pb.render(), format!("[====] {0}/{0}", u64::MAX));
+    }
+
+    #[test]
+    fn test_empty_progress_bar_is_full() {
+        let pb: ProgressBar<Vec<u8>> = ProgressBar::with_target(0, Vec::new());
+        assert_eq!(pb.render(), format!("[{}] 0/0", "=".repeat(40)));
+    }
+
+    #[test]
+    fn test_spinner_cycles_frames() {
+        let mut spinner: Spinner<Vec<u8>> = Spinner::with_target("working", Vec::new());
+        assert_eq!(spinner.render(), "| working");
+        let frames: Vec<String> = (0..4).map(|_| { spinner.tick(); spinner.render() }).collect();
+        assert_eq!(frames, vec!["/ working", "- working", "\\ working", "| working"]);
+        spinner.finish_with_message("done");
+        let output: String = String::from_utf8(spinner.into_target().unwrap()).unwrap();
+        assert!(output.starts_with("\r/ working\r- working"));
+        assert!(output.ends_with("\r\x1b[2Kdone\n"));
+    }
+}
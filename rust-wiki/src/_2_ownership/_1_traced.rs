@@ -0,0 +1,305 @@
+// -----------------------------------------------
+// # TRACING OWNERSHIP
+//
+// Moves, clones and drops are invisible: nothing is printed when a value changes owner or is freed.
+// `Traced<T>` wraps a value and records those events on a timeline, so that the examples of _2_owners_and_scope.rs
+// can show what happens, and in what order, rather than only describe it in comments.
+//
+//    start_timeline();
+//    {
+//      let s1 = Traced::new("s1", String::from("hello"));   // create s1
+//      let s2 = s1.moved_to("s2");                          // move s1 -> s2
+//    }                                                      // drop s2
+//    print_timeline(&take_timeline());
+//
+// Recorded events:
+//   - create:  `Traced::new`.
+//   - clone:   `.clone()` or `.clone_as(name)`.
+//   - move:    `.moved_to(name)`. A move is a plain memcpy that runs no code, so it cannot be detected; instead, a
+//              move site is labelled by passing the value through `moved_to`, which returns it unchanged.
+//   - drop:    `Drop::drop`, which Rust calls exactly once, when the value's last owner goes out of scope.
+//   - enter/exit: a `Scope` guard, used to show which block or function a value is dropped in. Events are indented
+//              by how many scopes are open.
+//
+// (A `Traced<T>` can never be `Copy`, even if `T` is: a type that implements `Drop` cannot implement `Copy`.)
+//
+// The timeline is thread-local, so that tests running in parallel each see only their own events.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventKind {
+  Create,
+  Clone { from: String },
+  Move { from: String },
+  Drop,
+  Enter,
+  Exit,
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+  // Time since `start_timeline`.
+  pub at: Duration,
+  // Number of scopes open when the event happened.
+  pub depth: usize,
+  pub kind: EventKind,
+  // The value's name (its current owner), or the scope's label.
+  pub name: String,
+}
+
+impl Event {
+  // The event without its timestamp and depth, e.g. "move s1 -> s2".
+  pub fn describe(&self) -> String {
+    match &self.kind {
+      EventKind::Create => format!("create {}", self.name),
+      EventKind::Clone { from } => format!("clone {from} -> {}", self.name),
+      EventKind::Move { from } => format!("move {from} -> {}", self.name),
+      EventKind::Drop => format!("drop {}", self.name),
+      EventKind::Enter => format!("enter {}", self.name),
+      EventKind::Exit => format!("exit {}", self.name),
+    }
+  }
+}
+
+impl fmt::Display for Event {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[{:>9.3}ms] {}{}", self.at.as_secs_f64() * 1000.0, "  ".repeat(self.depth), self.describe())
+  }
+}
+
+struct Timeline {
+  start: Instant,
+  depth: usize,
+  events: Vec<Event>,
+}
+
+thread_local! {
+  static TIMELINE: RefCell<Timeline> = RefCell::new(Timeline { start: Instant::now(), depth: 0, events: Vec::new() });
+}
+
+fn record(kind: EventKind, name: &str) {
+  TIMELINE.with(|timeline| {
+    let mut timeline = timeline.borrow_mut();
+    if kind == EventKind::Exit {
+      // Saturating: a `Scope` still open when `start_timeline` reset the depth exits at depth 0.
+      timeline.depth = timeline.depth.saturating_sub(1);
+    }
+    let event = Event { at: timeline.start.elapsed(), depth: timeline.depth, kind: kind.clone(), name: name.to_string() };
+    timeline.events.push(event);
+    if kind == EventKind::Enter {
+      timeline.depth += 1;
+    }
+  });
+}
+
+// Clears this thread's timeline and restarts its clock.
+pub fn start_timeline() {
+  TIMELINE.with(|timeline| {
+    let mut timeline = timeline.borrow_mut();
+    timeline.start = Instant::now();
+    timeline.depth = 0;
+    timeline.events.clear();
+  });
+}
+
+// Removes and returns the events recorded on this thread since `start_timeline`.
+pub fn take_timeline() -> Vec<Event> {
+  TIMELINE.with(|timeline| std::mem::take(&mut timeline.borrow_mut().events))
+}
+
+// Logged at the debug level: `RUST_LOG=rust_wiki::_2_ownership=debug` shows the timelines.
+pub fn print_timeline(events: &[Event]) {
+  for event in events {
+    crate::debug!("{event}");
+  }
+}
+
+pub fn describe_all(events: &[Event]) -> Vec<String> {
+  events.iter().map(Event::describe).collect()
+}
+
+// The names of the values dropped, in the order they were dropped.
+pub fn drop_order(events: &[Event]) -> Vec<String> {
+  events.iter().filter(|event| event.kind == EventKind::Drop).map(|event| event.name.clone()).collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+// ## Scopes
+
+// Records "enter" when created and "exit" when dropped, so it should be bound to a named variable (`_scope`, not `_`,
+// which would drop it immediately) declared first in its block: locals are dropped in reverse order of declaration,
+// so the guard is dropped after every other local of the block.
+pub struct Scope {
+  label: String,
+}
+
+pub fn enter(label: &str) -> Scope {
+  record(EventKind::Enter, label);
+  Scope { label: label.to_string() }
+}
+
+impl Drop for Scope {
+  fn drop(&mut self) {
+    record(EventKind::Exit, &self.label);
+  }
+}
+
+// Runs `f` in a scope. Used around function calls: a function's parameters are dropped when the function returns,
+// so values moved into `f` are dropped inside the scope.
+pub fn in_scope<R>(label: &str, f: impl FnOnce() -> R) -> R {
+  let _scope: Scope = enter(label);
+  f()
+}
+
+// -------------------------------------------------------------------------------------------------
+// ## Traced<T>
+
+pub struct Traced<T> {
+  name: String,
+  value: T,
+}
+
+impl<T> Traced<T> {
+  pub fn new(name: &str, value: T) -> Traced<T> {
+    record(EventKind::Create, name);
+    Traced { name: name.to_string(), value }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  // Labels a move site: `let s2 = s1.moved_to("s2");` moves `s1` into `moved_to` and back out to `s2`.
+  // The value itself is not touched, and no drop happens.
+  pub fn moved_to(mut self, name: &str) -> Traced<T> {
+    record(EventKind::Move { from: self.name.clone() }, name);
+    self.name = name.to_string();
+    self
+  }
+}
+
+impl<T: Clone> Traced<T> {
+  pub fn clone_as(&self, name: &str) -> Traced<T> {
+    record(EventKind::Clone { from: self.name.clone() }, name);
+    Traced { name: name.to_string(), value: self.value.clone() }
+  }
+}
+
+impl<T: Clone> Clone for Traced<T> {
+  fn clone(&self) -> Self {
+    self.clone_as(&format!("{}.clone()", self.name))
+  }
+}
+
+impl<T> Drop for Traced<T> {
+  fn drop(&mut self) {
+    record(EventKind::Drop, &self.name);
+  }
+}
+
+impl<T> Deref for Traced<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl<T> DerefMut for Traced<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.value
+  }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Traced<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {:?}", self.name, self.value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_locals_drop_in_reverse_order() {
+    start_timeline();
+    {
+      let _scope = enter("block");
+      let _a = Traced::new("a", 1);
+      let _b = Traced::new("b", 2);
+      let _c = Traced::new("c", 3);
+    }
+    let events = take_timeline();
+    assert_eq!(drop_order(&events), vec!["c", "b", "a"]);
+    assert_eq!(events.last().unwrap().describe(), "exit block");
+  }
+
+  #[test]
+  fn test_fields_and_elements_drop_in_order() {
+    struct Pair {
+      _first: Traced<i32>,
+      _second: Traced<i32>,
+    }
+    start_timeline();
+    // Unlike locals, struct fields and vector elements are dropped first to last.
+    drop(Pair { _first: Traced::new("first", 1), _second: Traced::new("second", 2) });
+    drop(vec![Traced::new("v[0]", 0), Traced::new("v[1]", 1)]);
+    assert_eq!(drop_order(&take_timeline()), vec!["first", "second", "v[0]", "v[1]"]);
+  }
+
+  #[test]
+  fn test_move_and_clone_events() {
+    start_timeline();
+    let s1 = Traced::new("s1", String::from("hi"));
+    let s2 = s1.clone();
+    let s3 = s1.moved_to("s3");
+    assert_eq!(s3.name(), "s3");
+    assert_eq!(*s2, "hi");
+    drop(s3);
+    drop(s2);
+    assert_eq!(describe_all(&take_timeline()), vec!["create s1", "clone s1 -> s1.clone()", "move s1 -> s3", "drop s3", "drop s1.clone()"]);
+  }
+
+  #[test]
+  fn test_depth_and_timestamps() {
+    start_timeline();
+    in_scope("outer", || {
+      let _inner = enter("inner");
+      Traced::new("x", ());
+    });
+    let events = take_timeline();
+    let depths: Vec<usize> = events.iter().map(|event| event.depth).collect();
+    // enter outer, enter inner, create x, drop x, exit inner, exit outer
+    assert_eq!(depths, vec![0, 1, 2, 2, 1, 0]);
+    assert!(events.windows(2).all(|pair| pair[0].at <= pair[1].at));
+    assert!(events[2].to_string().ends_with("    create x"));
+  }
+
+  #[test]
+  fn test_scope_open_across_restart() {
+    start_timeline();
+    let stale: Scope = enter("stale");
+    start_timeline();
+    drop(stale);
+    let _scope = enter("fresh");
+    let events = take_timeline();
+    assert_eq!(describe_all(&events), vec!["exit stale", "enter fresh"]);
+    assert_eq!(events.iter().map(|event| event.depth).collect::<Vec<usize>>(), vec![0, 0]);
+  }
+
+  #[test]
+  fn test_deref_to_the_traced_value() {
+    start_timeline();
+    let mut v = Traced::new("v", vec![1, 2]);
+    v.push(3);
+    assert_eq!(*v, vec![1, 2, 3]);
+    assert_eq!(format!("{v:?}"), "v: [1, 2, 3]");
+    drop(v);
+    assert_eq!(describe_all(&take_timeline()), vec!["create v", "drop v"]);
+  }
+}
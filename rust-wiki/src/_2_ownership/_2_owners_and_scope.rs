@@ -1,6 +1,9 @@
 // -----------------------------------------------
 // # OWNERS
 //
+// The examples marked (traced) record their moves, clones and drops with `Traced<T>` (see _1_traced.rs), and return
+// the timeline of events so the order can be checked.
+//
 // A variable (i.e. its value) that is an owner of some data means it manages the data.
 // This either means:
 //   1) It is that data on the stack, and hence trivially manages itself.
@@ -20,7 +23,9 @@
 //  4. The Owner is the Sole Decider of The Mutability of its Value.
 //       This refers back Rust's rule of mutability i.e. that mutability is inherited in Rust.
 
-fn owner_example() {
+use super::_1_traced::{enter, in_scope, print_timeline, start_timeline, take_timeline, Event, Scope, Traced};
+
+pub fn owner_example() {
   // x1('s value) trivially owns 42 on the stack
   let x1 = 42;
   // xmut('s value) mutably owns an i32 on the stack, which is *copied* from another i32 (as i32 implements copy)
@@ -41,7 +46,7 @@ fn owner_example() {
   }
   // vec2('s value) is a Vec owns a vector on the heap that was moved from vec1 to vecmut and modified
   let vec2: Vec<i32> = add_ones(vec1);
-  print!("{0}", vec2[4]);
+  print!("{0}", vec2[3]);
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -59,17 +64,33 @@ fn owner_example() {
 //    (Note: The scope of a reference can confusingly be called the value's lifetime)
 //    (Note: The lifetime of a reference can confusingly be called the reference's lifetime, and has a different meaning)
 
-fn ownership_scope_example(){
-
-    { // s is a String object that owns a string allocated on the heap
-      let s: String = String::from("hello"); // <<-- s is valid from this point forward
+// (traced)
+pub fn ownership_scope_example() -> Vec<Event> {
+    start_timeline();
+    { let _scope: Scope = enter("block 1");
+      // s is a String object that owns a string allocated on the heap
+      let s: Traced<String> = Traced::new("s", String::from("hello")); // <<-- s is valid from this point forward
     } // <<-- s goes out of scope so is no longer valid.
       //      hence it is deallocated from the stack, and the data it manages is dropped from the heap.
 
-    { // x is an int (trivially owning itself) on the stack
-      let x : u32 = 5;        // <<-- x is valid from this point forward
+    { let _scope: Scope = enter("block 2");
+      // x is an int (trivially owning itself) on the stack.
+      // (A plain u32 has no drop code at all: it is simply gone when the stack frame is. `Traced` adds a drop to
+      // show *when* that happens.)
+      let x: Traced<u32> = Traced::new("x", 5);        // <<-- x is valid from this point forward
     } // <<-- x goes out of scope so is no longer valid.
       //      hence it is deallocated from the stack.
+    let events: Vec<Event> = take_timeline();
+    print_timeline(&events);
+    // [    0.001ms] enter block 1
+    // [    0.002ms]   create s
+    // [    0.002ms]   drop s
+    // [    0.003ms] exit block 1
+    // [    0.003ms] enter block 2
+    // [    0.004ms]   create x
+    // [    0.004ms]   drop x
+    // [    0.005ms] exit block 2
+    events
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -84,14 +105,25 @@ fn ownership_scope_example(){
 //        - The value stored in the original variable is "moved" to the new variable.
 //        - If the value involves managing other data (usually on the heap), that data is not duplicated.
 //        - The original variable is invalidated, and the new variable owns the data.
-fn move_data(){
+//      (traced)
+pub fn move_data() -> Vec<Event> {
+  start_timeline();
   {
+      let _scope: Scope = enter("block");
       // s1('s value), a String object, manages a string on the heap
-      let s1: String = String::from("hello"); // <<-- s1 is valid hereon.
+      let s1: Traced<String> = Traced::new("s1", String::from("hello")); // <<-- s1 is valid hereon.
       // s2('s value) manages a string on the heap whose ownership was moved from s1.
-      let s2: String = s1; // <<-- s1 is no longer valid, and s2 is valid hereon
+      let s2: Traced<String> = s1.moved_to("s2"); // <<-- s1 is no longer valid, and s2 is valid hereon
   } // <<-- Both s1 and s2 are out of scope (with s2 no longer valid).
     //      Only s2's String is dropped, as s1 does not manage any data.
+  let events: Vec<Event> = take_timeline();
+  print_timeline(&events);
+  // [    0.001ms] enter block
+  // [    0.002ms]   create s1
+  // [    0.003ms]   move s1 -> s2
+  // [    0.003ms]   drop s2
+  // [    0.004ms] exit block
+  events
 }
 
 //   b. A COPY happens if the assigned value implements the Copy trait.
@@ -99,7 +131,7 @@ fn move_data(){
 //      This can be understood as:
 //        - The value stored in the original variable is "copied" to the new variable.
 //        - Both the original and new variables remain valid and own independent copies of the same value.
-fn copy_data(){
+pub fn copy_data(){
   {
       // x('s value) trivially manages 5 on the stack
       let x: i32 = 5;      // <<-- x is valid hereon
@@ -115,25 +147,40 @@ fn copy_data(){
 //         but points to a newly allocated memory region.
 //       - The data that the value pointed to is "cloned" to the new region.
 //       - Both the original and new variables remain valid and own independent copies of the same data.
-fn clone_data(){
+//      (traced)
+pub fn clone_data() -> Vec<Event> {
+  start_timeline();
   {
+      let _scope: Scope = enter("block");
       // s1('s value) manages a string on the heap
-      let s1: String = String::from("hello");   // <<-- s1 is valid hereon
+      let s1: Traced<String> = Traced::new("s1", String::from("hello"));   // <<-- s1 is valid hereon
       // s2('s value) manages a different string on the heap cloned from s1
-      let s2: String = s1.clone();              // <<-- s2 is valid hereon
+      let s2: Traced<String> = s1.clone_as("s2");                          // <<-- s2 is valid hereon
   } // <<-- Both s1 and s2 are out of scope (and no longer valid).
-    //      Both of their managed data is dropped.
+    //      Both of their managed data is dropped, in reverse order of declaration.
+  let events: Vec<Event> = take_timeline();
+  print_timeline(&events);
+  // [    0.001ms] enter block
+  // [    0.002ms]   create s1
+  // [    0.003ms]   clone s1 -> s2
+  // [    0.003ms]   drop s2
+  // [    0.004ms]   drop s1
+  // [    0.004ms] exit block
+  events
 }
 
 // -------------------------------------------------------------------------------------------------
 // ## Ownership Transfer: Function Calls
 //
 // Passing a value as a function argument will transfer ownership exactly like when a value is assigned to a variable.
-fn ownership_in_function_calls() {
+// (traced)
+pub fn ownership_in_function_calls() -> Vec<Event> {
+  start_timeline();
+  let _scope: Scope = enter("ownership_in_function_calls");
   // s1 manages a string on the heap
-  let s: String = String::from("hello"); // <<-- s is valid hereon
+  let s: Traced<String> = Traced::new("s", String::from("hello")); // <<-- s is valid hereon
   // some_string manages the string whose ownership was moved from s.
-  takes_ownership(s);       // <<-- some_string is valid and s is invalid hereon
+  in_scope("takes_ownership", || takes_ownership(s.moved_to("some_string")));  // <<-- some_string is valid and s is invalid hereon
   // <<-- some_string is out of scope and no longer valid; it's data is dropped,
   // The following line causes a compile-time error because s is no longer valid.
   // print!(s); // ERROR: `s` is invalid here because ownership was moved.
@@ -146,39 +193,73 @@ fn ownership_in_function_calls() {
 
   // The following line works fine as x never became invalid.
   println!("{}", x);
+
+  drop(_scope);
+  let events: Vec<Event> = take_timeline();
+  print_timeline(&events);
+  // [    0.001ms] enter ownership_in_function_calls
+  // [    0.002ms]   create s
+  // [    0.003ms]   enter takes_ownership
+  // [    0.003ms]     move s -> some_string
+  // [    0.010ms]     drop some_string             <<-- dropped inside the call, not at the end of the caller
+  // [    0.011ms]   exit takes_ownership
+  // [    0.020ms] exit ownership_in_function_calls
+  events
 } // <<-- Both x and s are out of scope (and invalid).
 //      Because s's ownership was already moved, there is nothing to drop.
 
-fn takes_ownership(some_string: String) {   // <<-- some_string is valid hereon
-println!("{some_string}");
+pub fn takes_ownership(some_string: Traced<String>) {   // <<-- some_string is valid hereon
+println!("{}", *some_string);
 } // <<-- some_string goes out of scope (and is no longer valid).
 //      Its managed data is dropped.
 
-fn makes_copy(some_integer: i32) {          // <<-- some_integer is valid hereon
+pub fn makes_copy(some_integer: i32) {          // <<-- some_integer is valid hereon
 println!("{some_integer}");
 } // <<-- some_integer goes out of scope (and is no longer valid).
 
 // #### Ownership Transfer in Function Returns
 // Returning a value from a function can also transfer ownership.
-fn ownership_in_function_returns() {
-  // s1 manages a string on the heap
-  let s1 = gives_ownership();         // <<-- s1 is valid hereon
-  // s2 manages a string on the heap
-  let s2 = String::from("hello");     // <<-- s2 is valid hereon
-  // s3 manages a string on the heap whose ownership was moved from s2.
-  let s3 = takes_and_gives_back(s2); // <<-- s3 is valid and s2 is invalid hereon.
-} // <<-- s3, s2, s1 go out of scope (and become invalid), and only s3's and s1's data are dropped.
-//      Because s2's ownership was moved, there is nothing to drop.
-
-fn gives_ownership() -> String {
+// (traced)
+pub fn ownership_in_function_returns() -> Vec<Event> {
+  start_timeline();
+  {
+    let _scope: Scope = enter("ownership_in_function_returns");
+    // s1 manages a string on the heap, whose ownership was moved out of gives_ownership.
+    let s1: Traced<String> = in_scope("gives_ownership", gives_ownership).moved_to("s1");         // <<-- s1 is valid hereon
+    // s2 manages a string on the heap
+    let s2: Traced<String> = Traced::new("s2", String::from("hello"));     // <<-- s2 is valid hereon
+    // s3 manages a string on the heap whose ownership was moved from s2, into the function and back out.
+    let s3: Traced<String> =
+      in_scope("takes_and_gives_back", || takes_and_gives_back(s2.moved_to("a_string"))).moved_to("s3"); // <<-- s3 is valid and s2 is invalid hereon.
+  } // <<-- s3, s2, s1 go out of scope (and become invalid), and only s3's and s1's data are dropped.
+  //      Because s2's ownership was moved, there is nothing to drop.
+  let events: Vec<Event> = take_timeline();
+  print_timeline(&events);
+  // [    0.001ms] enter ownership_in_function_returns
+  // [    0.001ms]   enter gives_ownership
+  // [    0.002ms]     create some_string
+  // [    0.002ms]   exit gives_ownership                <<-- returned, not dropped
+  // [    0.003ms]   move some_string -> s1
+  // [    0.003ms]   create s2
+  // [    0.004ms]   enter takes_and_gives_back
+  // [    0.004ms]     move s2 -> a_string
+  // [    0.005ms]   exit takes_and_gives_back
+  // [    0.005ms]   move a_string -> s3
+  // [    0.006ms]   drop s3
+  // [    0.006ms]   drop s1
+  // [    0.007ms] exit ownership_in_function_returns
+  events
+}
+
+pub fn gives_ownership() -> Traced<String> {
   // some_string manages a string on the heap
-  let some_string = String::from("yours"); // <<-- some_string is valid hereon
+  let some_string: Traced<String> = Traced::new("some_string", String::from("yours")); // <<-- some_string is valid hereon
   // some_string is returned and its ownership is moved
   some_string
 } // <<-- some_string goes out of scope (and is no longer valid).
 //      Because its ownership was already moved, there is nothing to drop.
 
-fn takes_and_gives_back(a_string: String) -> String { // <<-- a_string is valid hereon
+pub fn takes_and_gives_back(a_string: Traced<String>) -> Traced<String> { // <<-- a_string is valid hereon
   // a_string is returned and its ownership is moved
   a_string
 }  // <<-- a_string goes out of scope (and is no longer valid).
@@ -189,7 +270,7 @@ fn takes_and_gives_back(a_string: String) -> String { // <<-- a_string is valid
 //
 // As the owner decides the mutability of its value, mutability of data can be changed when ownership is transferred.
 //
-fn ownership_and_mutability() {
+pub fn ownership_and_mutability() {
   let immutable_box: Box<u32> = Box::new(5u32);
 
   println!("immutable_box contains {}", immutable_box);
@@ -232,41 +313,184 @@ fn ownership_and_mutability() {
 // the Borrow) can still be used.
 //
 
-fn partial_move_copy_clone_reference() {
+// (traced)
+pub fn partial_move_copy_clone_reference() -> Vec<Event> {
   #[derive(Debug)]
   struct Person {
-      first_name: String,
-      last_name: String,
+      first_name: Traced<String>,
+      last_name: Traced<String>,
       age: u8,
-      id: Box<u8>
+      id: Traced<Box<u8>>
   }
 
-  let person = Person {
-      first_name: String::from("Alice"),
-      last_name: String::from("Smith"),
-      age: 20,
-      id: Box::new(0)
-  };
-
-  // `first_name` is referenced, `last_name` is moved, `age` is copied, and `id` is cloned.
-  let first_name: &String = &person.first_name;  // reference
-  let last_name: String = person.last_name;      // move
-  let age: u8 = person.age;                      // copy
-  let id: Box<u8>  = person.id.clone();          // clone
-
-  println!("The person's first_name is {}", first_name);
-  println!("The person's last_name is {}", last_name);
-  println!("The person's age is {}", age);
-  println!("The person's id is {}", id);
-
-  // `person` cannot be used but their first_name, age, and id, can be used as they are not moved
-  // println!("The person struct is {:?}", person); // Error! borrow of partially moved value.
-  println!("The person's first_name from person struct is {}", first_name);
-  println!("The person's age from person struct is {}", person.age);
-  println!("The person's od from person struct is {}", person.id);
+  start_timeline();
+  {
+    let _scope: Scope = enter("partial_move_copy_clone_reference");
+    let person = Person {
+        first_name: Traced::new("person.first_name", String::from("Alice")),
+        last_name: Traced::new("person.last_name", String::from("Smith")),
+        age: 20,
+        id: Traced::new("person.id", Box::new(0))
+    };
+
+    // `first_name` is referenced, `last_name` is moved, `age` is copied, and `id` is cloned.
+    let first_name: &String = &person.first_name;                         // reference
+    let last_name: Traced<String> = person.last_name.moved_to("last_name"); // move
+    let age: u8 = person.age;                                             // copy
+    let id: Traced<Box<u8>> = person.id.clone_as("id");                   // clone
+
+    println!("The person's first_name is {}", first_name);
+    println!("The person's last_name is {}", *last_name);
+    println!("The person's age is {}", age);
+    println!("The person's id is {}", **id);
+
+    // `person` cannot be used but their first_name, age, and id, can be used as they are not moved
+    // println!("The person struct is {:?}", person); // Error! borrow of partially moved value.
+    println!("The person's first_name from person struct is {}", first_name);
+    println!("The person's age from person struct is {}", person.age);
+    println!("The person's od from person struct is {}", **person.id);
+  } // <<-- Locals are dropped in reverse order: the clone `id`, then `last_name`, then what is left of `person`.
+    //      A partially moved struct drops only its remaining fields, in declaration order; `last_name` is not
+    //      dropped twice.
+  let events: Vec<Event> = take_timeline();
+  print_timeline(&events);
+  // [    0.001ms] enter partial_move_copy_clone_reference
+  // [    0.001ms]   create person.first_name
+  // [    0.002ms]   create person.last_name
+  // [    0.002ms]   create person.id
+  // [    0.003ms]   move person.last_name -> last_name
+  // [    0.003ms]   clone person.id -> id
+  // [    0.004ms]   drop id
+  // [    0.004ms]   drop last_name
+  // [    0.005ms]   drop person.first_name
+  // [    0.005ms]   drop person.id
+  // [    0.006ms] exit partial_move_copy_clone_reference
+  events
 }
 
 // -------------------------------------------------------------------------------------------------
 // ## Mental Model: Ownership Transfer in practice.
 //
-// Ownership transfer is an abstract concept, and it is not usually productive to think of how it happens in memory. That is, transferring ownership doesn't necessarily do anything in memory at all. Semantically, all MOVE, COPY, and CLONE perform a memcpy i.e. an actual copy in memory. In practice, a memcpy won't happen unless necessary, and the optimiser can do anything as long as it does not change the program's behaviour. (A MOVE may perform a copy in memory, and a COPY may not copy anything. These details are entirely up to the compiler.) Having a mental model where every copy is a new value stored is fine, as long as you don't use this mental model to reason about performance.
\ No newline at end of file
+// Ownership transfer is an abstract concept, and it is not usually productive to think of how it happens in memory. That is, transferring ownership doesn't necessarily do anything in memory at all. Semantically, all MOVE, COPY, and CLONE perform a memcpy i.e. an actual copy in memory. In practice, a memcpy won't happen unless necessary, and the optimiser can do anything as long as it does not change the program's behaviour. (A MOVE may perform a copy in memory, and a COPY may not copy anything. These details are entirely up to the compiler.) Having a mental model where every copy is a new value stored is fine, as long as you don't use this mental model to reason about performance.
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_2_ownership::_1_traced::{describe_all, drop_order, EventKind};
+
+  #[test]
+  fn test_owner_example() {
+    owner_example();
+  }
+
+  #[test]
+  fn test_ownership_scope_example_drops_at_the_end_of_each_block() {
+    let events: Vec<Event> = ownership_scope_example();
+    assert_eq!(
+      describe_all(&events),
+      vec!["enter block 1", "create s", "drop s", "exit block 1", "enter block 2", "create x", "drop x", "exit block 2"]
+    );
+  }
+
+  #[test]
+  fn test_copy_data() {
+    copy_data();
+  }
+
+  #[test]
+  fn test_move_data_drops_only_the_new_owner() {
+    let events: Vec<Event> = move_data();
+    assert_eq!(describe_all(&events), vec!["enter block", "create s1", "move s1 -> s2", "drop s2", "exit block"]);
+  }
+
+  #[test]
+  fn test_clone_data_drops_in_reverse_declaration_order() {
+    let events: Vec<Event> = clone_data();
+    assert_eq!(drop_order(&events), vec!["s2", "s1"]);
+    assert_eq!(events.last().unwrap().describe(), "exit block");
+  }
+
+  #[test]
+  fn test_ownership_in_function_calls_drops_inside_the_callee() {
+    let events: Vec<Event> = ownership_in_function_calls();
+    assert_eq!(
+      describe_all(&events),
+      vec![
+        "enter ownership_in_function_calls",
+        "create s",
+        "enter takes_ownership",
+        "move s -> some_string",
+        "drop some_string",
+        "exit takes_ownership",
+        "exit ownership_in_function_calls",
+      ]
+    );
+    let drop_event: &Event = events.iter().find(|event| event.kind == EventKind::Drop).unwrap();
+    assert_eq!(drop_event.depth, 2);
+    makes_copy(5);
+  }
+
+  #[test]
+  fn test_ownership_in_function_returns_moves_out_without_dropping() {
+    let events: Vec<Event> = ownership_in_function_returns();
+    assert_eq!(
+      describe_all(&events),
+      vec![
+        "enter ownership_in_function_returns",
+        "enter gives_ownership",
+        "create some_string",
+        "exit gives_ownership",
+        "move some_string -> s1",
+        "create s2",
+        "enter takes_and_gives_back",
+        "move s2 -> a_string",
+        "exit takes_and_gives_back",
+        "move a_string -> s3",
+        "drop s3",
+        "drop s1",
+        "exit ownership_in_function_returns",
+      ]
+    );
+    // Neither function dropped anything: both drops happen back in the caller's scope.
+    assert!(events.iter().filter(|event| event.kind == EventKind::Drop).all(|event| event.depth == 1));
+  }
+
+  #[test]
+  fn test_takes_and_gives_back_moves_without_reallocating() {
+    start_timeline();
+    let s: Traced<String> = Traced::new("s", String::from("hello"));
+    let ptr: *const u8 = s.as_ptr();
+    // Moving the String moves only its { ptr, len, capacity }, so the heap data stays where it is.
+    let s: Traced<String> = takes_and_gives_back(s);
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(*gives_ownership(), "yours");
+    drop(s);
+    assert_eq!(drop_order(&take_timeline()), vec!["some_string", "s"]);
+  }
+
+  #[test]
+  fn test_ownership_and_mutability() {
+    ownership_and_mutability();
+  }
+
+  #[test]
+  fn test_partial_move_drops_only_the_remaining_fields() {
+    let events: Vec<Event> = partial_move_copy_clone_reference();
+    assert_eq!(
+      describe_all(&events),
+      vec![
+        "enter partial_move_copy_clone_reference",
+        "create person.first_name",
+        "create person.last_name",
+        "create person.id",
+        "move person.last_name -> last_name",
+        "clone person.id -> id",
+        "drop id",
+        "drop last_name",
+        "drop person.first_name",
+        "drop person.id",
+        "exit partial_move_copy_clone_reference",
+      ]
+    );
+  }
+}
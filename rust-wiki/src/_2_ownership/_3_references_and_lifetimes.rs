@@ -16,9 +16,9 @@
 // A reference is represented in memory as just one part:
 //   - { ptr : 0x... } A pointer to another variable or value.
 //
-struct IntBox(i32);  // A datatype that does not implement Copy (unlike i32)
+pub struct IntBox(pub i32);  // A datatype that does not implement Copy (unlike i32)
 
-fn refs_vs_owners() -> (IntBox, String){
+pub fn refs_vs_owners() -> (IntBox, String){
     // x is (owns) IntBox(42)
     let x: IntBox = IntBox(42);
     // y is a reference to x
@@ -49,7 +49,7 @@ fn refs_vs_owners() -> (IntBox, String){
 //    Note that it is not always unambigious to the Rust compiler whether it should automatically deference a variable,
 //    and so sometimes, we need to explicitly dereference it ourself
 
-fn reference_deferencing_example() {
+pub fn reference_deferencing_example() {
     // x refers to a value 10
     let ref_x: &u32 = &10;
     // Explicitly deference and print out the value
@@ -71,7 +71,7 @@ fn reference_deferencing_example() {
 //   in which case this also requires generic parameters (not talked about here).
 
 // Below shows when reference lifetimes prevent dangling pointers.
-fn refs_lifetime_example(arg: &i32) -> &i32{
+pub fn refs_lifetime_example(arg: &i32) -> &i32{
     // x is (owns) 42
     let x: i32 = 42;
     // xref is a reference to x
@@ -87,7 +87,7 @@ fn refs_lifetime_example(arg: &i32) -> &i32{
 // Below elaborates on what reference lifetimes would refer to as regions of code.
 // Note: The syntax 'a { ...} are simply named regions (which lifetime names would coincide with).
 //       No explicit reference lifetimes (i.e. the generic parameter <'a> or the type &'a T) are actually used.
-fn refs_lifetime_elaborated(){
+pub fn refs_lifetime_elaborated(){
     // Example 1:
     // Each let statement implicitly introduces a scope.
     let x: i32 = 42;     // x is (owns) 42
@@ -128,7 +128,7 @@ fn refs_lifetime_elaborated(){
 }
 
 // Below shows some subtle cases about reference lifetimes.
-fn refs_lifetimes_subtleties(){
+pub fn refs_lifetimes_subtleties(){
 
     // Below is fine:
     let mut x: i32 = 10;
@@ -168,7 +168,7 @@ fn refs_lifetimes_subtleties(){
 //    a. a mutable reference cannot be declared
 //    b. the owner cannot change the referenced data
 //
-fn shared_reference_example() {
+pub fn shared_reference_example() {
     // s is a mutable variable that manages the string "hello" (on the heap)
     let mut s = String::from("hello");
 
@@ -200,7 +200,7 @@ fn shared_reference_example() {
 //     c. The owner cannot be used.
 //  In other words, only one variable may actively refer to a value while it is being mutated.
 //
-fn mut_reference_example() {
+pub fn mut_reference_example() {
     // s is a mutable owner of "hello" in memory
     let mut s = String::from("hello");
 
@@ -225,13 +225,16 @@ fn mut_reference_example() {
     // The owner s can be used again, as mut_ref_s is not used afterwards
     s.push_str("s");
 }
+//
+// Cell, RefCell and Mutex allow mutation through shared references, by checking these rules at runtime instead (see
+// _19_interior_mutability).
 
 // -------------------------------------------------------------------
 // ## (Im)mutable Variables that are (Im)mutable References
 //
 // As variables can be mutable, we can also have combinations of (im)mutable variables that are (im)mutable references.
 // In other words, we can both modify what a reference points to as well as modify what the referenced data.
-fn mutable_variables_and_references(){
+pub fn mutable_variables_and_references(){
     let mut x : i32 = 2;
 
     // r1 is a constant variable that is a shared reference to a (possibly mutable or immutable) i32 value.
@@ -273,3 +276,43 @@ fn mutable_variables_and_references(){
 //  2. Mutable references cannot be aliased.
 // Using pointers entails using unsafe Rust.
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refs_vs_owners() {
+        let (intbox, s): (IntBox, String) = refs_vs_owners();
+        assert_eq!(intbox.0, 42);
+        assert_eq!(s, "golly");
+    }
+
+    #[test]
+    fn test_reference_deferencing_example() {
+        reference_deferencing_example();
+    }
+
+    #[test]
+    fn test_refs_lifetime_example_returns_arg() {
+        let arg: i32 = 7;
+        assert!(std::ptr::eq(refs_lifetime_example(&arg), &arg));
+    }
+
+    #[test]
+    fn test_refs_lifetime_elaborated() {
+        refs_lifetime_elaborated();
+    }
+
+    #[test]
+    fn test_refs_lifetimes_subtleties() {
+        refs_lifetimes_subtleties();
+    }
+
+    #[test]
+    fn test_shared_and_mut_reference_examples() {
+        shared_reference_example();
+        mut_reference_example();
+        mutable_variables_and_references();
+    }
+}
@@ -20,7 +20,7 @@
 //    - If the slice references data stored in a heap-allocated structure like a Vec<T> or String, the data is on the heap, but the slice's metadata (pointer and length) is still on the stack.
 
 // An array [T; n] is a static sequence of elements of a known-size.
-fn arrslice_example() {
+pub fn arrslice_example() {
 
   // Not allowed: arr of type [i32] is an arbitrary-sized array slice
   // let arr: [i32] = [1, 2, 3, 4, 5]; // Error: all local variables must have a known size at compile-time
@@ -63,7 +63,7 @@ fn arrslice_example() {
     // +------------------------------------+
 
 // A Vec<T> is a dynamic representation of an array [T].
-fn vecslice_example() {
+pub fn vecslice_example() {
   // vec manages a vector on the heap
   let vec: Vec<i32> = vec![1, 2, 3, 4, 5];
 
@@ -108,10 +108,11 @@ fn vecslice_example() {
     // +------------------------------------------+ 0x60001234
     // | 1, 2, 3, 4, 5                            | 8 bytes x 5 (vec[0] ... vec[4])
     // +------------------------------------------+ 0x60001244
+    // (_17_unsafe/_2_my_vec.rs builds this ptr/len/capacity layout by hand, with raw pointers and `std::alloc`.)
 
 // A string slice `str` is shorthand an array type [u8] **without** a known size.
 // The `String` type is to [u8] what a Vec<T> is to [T].
-fn strslice_example(){
+pub fn strslice_example(){
   // s1('s value) is a reference to a string literal "hello" allocated on the stack
   // (which is hardcoded in and loaded from the read-only section of the executable binary).
   let s1: &str = "hello"; // i.e. let s1: &[u8; 5] = "hello";
@@ -135,7 +136,7 @@ fn strslice_example(){
 // The rules for mutable slice references are the same as for references.
 //  ~ While a mutable reference lives, no other references can live, and the owner cannot be used.
 // This is true even for two mutable slice references that refer to different parts of memory.
-fn mutable_slices() {
+pub fn mutable_slices() {
   // xs is an array on the stack.
   let mut xs:  [i32; 5] = [1, 2, 3, 4, 5];
 
@@ -144,7 +145,7 @@ fn mutable_slices() {
   println!("First element of the slice: {}", r1[0]);   // <<-- end of r1's lifetime
 
   // r2 is a mutable slice reference that borrows elements 5 onwards.
-  let r2 = &mut xs[5 .. ];                 // <<-- start of r2's lifetime
+  let r2 = &mut xs[4 .. ];                 // <<-- start of r2's lifetime
   println!("First element of the slice: {}", r2[0]);   // <<-- end of r2's lifetime
 
   // A mutable reference cannot live at the same time as another reference, so below is not allowed:
@@ -160,7 +161,17 @@ fn mutable_slices() {
 // A parameter of type &str can accept both String references (&String) and string slices (&str).
 // 1. If we choose to provide an argument of type &String, this is the same as a slice &str of the entire string.
 // 2. If we choose to provide an argument of type &str, this could be any slice of the entire string.
-fn get_first_word(s: &mut str) ->  &str {
+//
+// (`get_first_word` only counts b' ' as a space: tabs, newlines and Unicode spaces such as U+00A0 do not. See `first_word` in
+// _5_utf8_text.rs.)
+/// Example:
+/// ```
+/// use rust_wiki::_2_ownership::_4_slices::get_first_word;
+///
+/// let mut s: String = String::from("hello world");
+/// assert_eq!(get_first_word(&mut s), "hello");
+/// ```
+pub fn get_first_word(s: &mut str) ->  &str {
   let bytes: &[u8] = s.as_bytes();
 
   for (i, &item) in bytes.iter().enumerate() {
@@ -170,4 +181,49 @@ fn get_first_word(s: &mut str) ->  &str {
   }
 
   &s[..]
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_arrslice_example() {
+    arrslice_example();
+  }
+
+  #[test]
+  fn test_vecslice_example() {
+    vecslice_example();
+  }
+
+  #[test]
+  fn test_strslice_example() {
+    strslice_example();
+  }
+
+  #[test]
+  fn test_mutable_slices() {
+    mutable_slices();
+  }
+
+  #[test]
+  fn test_get_first_word() {
+    let mut s: String = String::from("hello world");
+    assert_eq!(get_first_word(&mut s), "hello");
+  }
+
+  #[test]
+  fn test_get_first_word_of_substring() {
+    let mut s: String = String::from("hello world");
+    assert_eq!(get_first_word(&mut s[6..]), "world");
+  }
+
+  #[test]
+  fn test_get_first_word_without_spaces() {
+    let mut s: String = String::from("hello");
+    assert_eq!(get_first_word(&mut s), "hello");
+    let mut empty: String = String::new();
+    assert_eq!(get_first_word(&mut empty), "");
+  }
+}
@@ -0,0 +1,341 @@
+// -----------------------------------------------
+// # UTF-8 TEXT: SLICING, WORDS, CASE AND COLUMNS
+//
+// A `str` is a sequence of BYTES guaranteed to be valid UTF-8, in which a character (`char`, a Unicode scalar value)
+// takes 1 to 4 bytes:
+//
+//    text     h     é         日              🦀
+//    bytes    68    C3 A9     E6 97 A5        F0 9F A6 80
+//    chars    1     1         1               1
+//    columns  1     1         2               2          (how wide it is drawn in a terminal)
+//
+// So there are three different "positions" in a string, and code written against ASCII input tends to confuse them:
+//   - a BYTE index is what `&s[a..b]`, `s.len()` and `find` use. Slicing panics if an end is not on a char boundary:
+//     `&"épée"[0..1]` panics, because byte 1 is inside the two-byte 'é'.
+//   - a CHAR index is what `s.chars().nth(i)` and `s.chars().count()` use. It costs O(n) to find: UTF-8 is not
+//     indexable by char.
+//   - a display COLUMN is what a terminal or an editor shows. Wide (CJK, most emoji) characters take two columns,
+//     combining accents none.
+// And a "character" as a reader sees it may be several `char`s: "é" can be written as 'e' + U+0301 (a combining
+// acute accent). Those GRAPHEME CLUSTERS need the Unicode segmentation rules, which std does not include (the
+// `unicode-segmentation` crate does); this module works in `char`s and says where that falls short.
+//
+// Two examples elsewhere in the wiki are only correct on ASCII input:
+//   - `&str_version[0..1]` (_1_basics/_2_datatypes.rs) is the first BYTE; it panics on "éa". See `first_char`.
+//   - `get_first_word` (_4_slices.rs) only splits on b' '. Its slicing is safe (a space is one byte, so it is always a
+//     boundary), but tabs, newlines and non-breaking spaces are not treated as spaces. See `first_word`.
+
+// -----------------------------------------------
+// ## Char-Boundary-Safe Slicing
+//
+// `str::get(range)` is the non-panicking form of `&s[range]`: `None` if an end is out of bounds or not on a char
+// boundary. When a cut must happen anyway, the index is first moved to the nearest boundary.
+
+// The largest char boundary at or before `index` (clamped to the string's length).
+pub fn floor_char_boundary(s: &str, index: usize) -> usize {
+  if index >= s.len() {
+    return s.len();
+  }
+  // A char is at most 4 bytes long: one of `index`, ..., `index - 3` is a boundary.
+  (index.saturating_sub(3)..=index).rev().find(|&i| s.is_char_boundary(i)).unwrap_or(0)
+}
+
+// The smallest char boundary at or after `index` (clamped to the string's length).
+pub fn ceil_char_boundary(s: &str, index: usize) -> usize {
+  if index >= s.len() {
+    return s.len();
+  }
+  (index..s.len()).find(|&i| s.is_char_boundary(i)).unwrap_or(s.len())
+}
+
+// At most `max_bytes` bytes of `s`, cut at a char boundary: for fixed-size buffers and database columns.
+pub fn truncate_bytes(s: &str, max_bytes: usize) -> &str {
+  &s[..floor_char_boundary(s, max_bytes)]
+}
+
+// The chars `start..end` (char indices, clamped to the string), as a slice of `s`.
+pub fn slice_chars(s: &str, start: usize, end: usize) -> &str {
+  let start_byte: usize = char_to_byte_index(s, start).unwrap_or(s.len());
+  let end_byte: usize = char_to_byte_index(s, end.max(start)).unwrap_or(s.len());
+  &s[start_byte..end_byte]
+}
+
+// The first char, as a slice: the UTF-8 correct `&s[0..1]`.
+pub fn first_char(s: &str) -> &str {
+  match s.chars().next() {
+    Some(c) => &s[..c.len_utf8()],
+    None => "",
+  }
+}
+
+// -----------------------------------------------
+// ## Splitting Words on Unicode Whitespace
+//
+// `char::is_whitespace` follows Unicode's White_Space property: besides ' ', '\t' and '\n', it includes the
+// no-break space U+00A0, the ideographic space U+3000 used in CJK text, and a dozen others. `str::split_whitespace`
+// uses it; `str::split_ascii_whitespace` and `b' '` comparisons do not. (The zero-width space U+200B is NOT
+// whitespace: it is a line-break opportunity, not a space.)
+
+// The `get_first_word` of _4_slices.rs, splitting on any Unicode whitespace, and skipping leading whitespace.
+pub fn first_word(s: &str) -> &str {
+  let s: &str = s.trim_start();
+  match s.char_indices().find(|(_, c)| c.is_whitespace()) {
+    // `index` comes from `char_indices`, so it is a boundary: the slice cannot panic.
+    Some((index, _)) => &s[..index],
+    None => s,
+  }
+}
+
+pub fn words(s: &str) -> impl Iterator<Item = &str> {
+  s.split_whitespace()
+}
+
+// Collapses every run of whitespace into one ASCII space, and trims the ends.
+pub fn normalize_whitespace(s: &str) -> String {
+  words(s).collect::<Vec<&str>>().join(" ")
+}
+
+// -----------------------------------------------
+// ## Case Mapping
+//
+// Changing the case of a char can change the NUMBER of chars: 'ß'.to_uppercase() is "SS", the ligature 'ﬁ' becomes
+// "FI", and 'ŉ' becomes "ʼN". That is why `char::to_uppercase` returns an iterator, not a `char`, and why
+// `to_ascii_uppercase` (which only maps a-z) is not a substitute. Strings can also change length in bytes:
+// 'ı' (2 bytes) uppercases to 'I' (1 byte).
+//
+// Whole-string conversions are context-sensitive where single chars cannot be: `str::to_lowercase` turns a
+// word-final 'Σ' into 'ς', but a standalone `char::to_lowercase` always gives 'σ'.
+
+// "hello" -> "Hello", "ßtraße" -> "SStraße": the rustlings `capitalize_first`, for any first char.
+pub fn capitalize_first(s: &str) -> String {
+  let mut chars = s.chars();
+  match chars.next() {
+    Some(first) => first.to_uppercase().chain(chars).collect(),
+    None => String::new(),
+  }
+}
+
+// Each word capitalized, the rest lowercased, and whitespace normalized.
+pub fn title_case(s: &str) -> String {
+  words(s).map(|word| capitalize_first(&word.to_lowercase())).collect::<Vec<String>>().join(" ")
+}
+
+// Case-insensitive comparison, by lowercasing both sides. (Full Unicode "case folding" has a few more rules, e.g.
+// 'ß' folds to "ss"; lowercasing leaves 'ß' alone, so "STRASSE" and "straße" compare different.)
+pub fn eq_ignore_case(a: &str, b: &str) -> bool {
+  a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
+}
+
+// -----------------------------------------------
+// ## Converting Between Byte, Char and Column Indices
+
+// The char index of the char starting at `byte`; `None` if `byte` is not a boundary. `s.len()` maps to the char
+// count, so that end positions convert too.
+pub fn byte_to_char_index(s: &str, byte: usize) -> Option<usize> {
+  if !s.is_char_boundary(byte) {
+    return None;
+  }
+  Some(s[..byte].chars().count())
+}
+
+// The byte index of char number `char_index`; the char count maps to `s.len()`.
+pub fn char_to_byte_index(s: &str, char_index: usize) -> Option<usize> {
+  s.char_indices().map(|(byte, _)| byte).chain(std::iter::once(s.len())).nth(char_index)
+}
+
+// How many terminal columns a char takes: 0 for combining marks and zero-width chars, 2 for wide East Asian chars
+// and emoji, 1 otherwise. A simplified form of Unicode's East Asian Width tables (the `unicode-width` crate has the
+// complete ones): enough for accents, CJK and common emoji.
+pub fn char_width(c: char) -> usize {
+  match c as u32 {
+    0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F => 0,
+    0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+    _ if c.is_control() => 0,
+    0x1100..=0x115F
+    | 0x2E80..=0x303E
+    | 0x3041..=0x33FF
+    | 0x3400..=0x4DBF
+    | 0x4E00..=0x9FFF
+    | 0xA000..=0xA4CF
+    | 0xAC00..=0xD7A3
+    | 0xF900..=0xFAFF
+    | 0xFE30..=0xFE4F
+    | 0xFF00..=0xFF60
+    | 0xFFE0..=0xFFE6
+    | 0x1F300..=0x1F64F
+    | 0x1F900..=0x1F9FF
+    | 0x20000..=0x3FFFD => 2,
+    _ => 1,
+  }
+}
+
+pub fn display_width(s: &str) -> usize {
+  s.chars().map(char_width).sum()
+}
+
+// The display column at which the char starting at `byte` is drawn; `None` if `byte` is not a boundary.
+pub fn byte_to_column(s: &str, byte: usize) -> Option<usize> {
+  s.get(..byte).map(display_width)
+}
+
+// The 1-based (line, column) of a byte offset into a multi-line text, as compilers report positions; the column
+// counts chars, as most editors do.
+pub fn line_and_column(text: &str, byte: usize) -> Option<(usize, usize)> {
+  let before: &str = text.get(..byte)?;
+  let line_start: usize = before.rfind('\n').map_or(0, |newline| newline + 1);
+  let line: usize = before.matches('\n').count() + 1;
+  Some((line, before[line_start..].chars().count() + 1))
+}
+
+// A line of `^` under the bytes `start..end` of a single line of text, aligned by display column:
+//
+//    let 日本 = "语";
+//        ^^^^
+//
+// A CJK char is 3 bytes wide in UTF-8 but 2 columns on screen: counting bytes would put the caret 1 column too far
+// per CJK char before it, and counting chars 1 column too short.
+pub fn underline(line: &str, start: usize, end: usize) -> Option<String> {
+  let indent: usize = byte_to_column(line, start)?;
+  let width: usize = display_width(line.get(start..end)?).max(1);
+  Some(format!("{}{}", " ".repeat(indent), "^".repeat(width)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Fixtures: accents, a multi-byte first char, CJK (3-byte, wide), an emoji (4-byte, wide), and a decomposed 'é'.
+  const FRENCH: &str = "épée à deux mains";
+  const JAPANESE: &str = "日本語のテキスト";
+  const MIXED: &str = "crab 🦀 ferris";
+  const DECOMPOSED: &str = "e\u{301}t\u{e9}";
+
+  #[test]
+  fn test_byte_slicing_panics_inside_a_char() {
+    assert!(std::panic::catch_unwind(|| FRENCH[0..1].len()).is_err());
+    assert_eq!(FRENCH.get(0..1), None);
+    assert_eq!(FRENCH.get(0..2), Some("é"));
+  }
+
+  #[test]
+  fn test_char_boundaries() {
+    // "日" is bytes 0..3.
+    assert_eq!((floor_char_boundary(JAPANESE, 2), ceil_char_boundary(JAPANESE, 2)), (0, 3));
+    assert_eq!((floor_char_boundary(JAPANESE, 3), ceil_char_boundary(JAPANESE, 3)), (3, 3));
+    assert_eq!(floor_char_boundary(MIXED, 100), MIXED.len());
+    // The crab is bytes 5..9.
+    assert_eq!((floor_char_boundary(MIXED, 7), ceil_char_boundary(MIXED, 7)), (5, 9));
+    assert_eq!(truncate_bytes(JAPANESE, 7), "日本");
+    assert_eq!(truncate_bytes(MIXED, 8), "crab ");
+    assert_eq!(truncate_bytes("", 3), "");
+  }
+
+  #[test]
+  fn test_slice_chars() {
+    assert_eq!(slice_chars(FRENCH, 0, 4), "épée");
+    assert_eq!(slice_chars(JAPANESE, 2, 4), "語の");
+    assert_eq!(slice_chars(MIXED, 5, 100), "🦀 ferris");
+    assert_eq!(slice_chars(MIXED, 50, 100), "");
+    assert_eq!(slice_chars(MIXED, 3, 1), "");
+  }
+
+  #[test]
+  fn test_first_char() {
+    assert_eq!(first_char(FRENCH), "é");
+    assert_eq!(first_char("🦀!"), "🦀");
+    assert_eq!(first_char("hello"), "h");
+    assert_eq!(first_char(""), "");
+    // One char, but not what a reader calls the first character: only the 'e' of "é".
+    assert_eq!(first_char(DECOMPOSED), "e");
+  }
+
+  #[test]
+  fn test_first_word_and_words() {
+    assert_eq!(first_word("épée\tà deux mains"), "épée");
+    assert_eq!(first_word("\u{3000}日本語\u{3000}テキスト"), "日本語");
+    assert_eq!(first_word("prix\u{a0}: 10\u{a0}€"), "prix");
+    assert_eq!(first_word("   "), "");
+    // The zero-width space does not split.
+    assert_eq!(first_word("a\u{200b}b c"), "a\u{200b}b");
+    let collected: Vec<&str> = words(" un\u{a0}deux\n\ttrois\u{2003}四 ").collect();
+    assert_eq!(collected, vec!["un", "deux", "trois", "四"]);
+    assert_eq!(normalize_whitespace("\u{3000} a \u{85}\u{2028} b  "), "a b");
+    // The byte-scanning version keeps the non-breaking space inside the word.
+    let mut s: String = String::from("prix\u{a0}: 10");
+    assert_eq!(crate::_2_ownership::_4_slices::get_first_word(&mut s), "prix\u{a0}:");
+  }
+
+  #[test]
+  fn test_case_mapping_can_change_length() {
+    assert_eq!(capitalize_first("ßtraße"), "SStraße");
+    assert_eq!(capitalize_first("ﬁle"), "FIle");
+    assert_eq!(capitalize_first("élan"), "Élan");
+    assert_eq!(capitalize_first(""), "");
+    assert_eq!("ß".to_uppercase(), "SS");
+    assert_eq!('ß'.to_ascii_uppercase(), 'ß');
+    assert_eq!("ı".len(), 2);
+    assert_eq!("ı".to_uppercase(), "I");
+    // Final sigma: context-sensitive on strings, not on chars.
+    assert_eq!("ΟΔΟΣ".to_lowercase(), "οδο\u{3c2}");
+    assert_eq!("ΟΔΟΣ".chars().flat_map(char::to_lowercase).collect::<String>(), "οδο\u{3c3}");
+  }
+
+  #[test]
+  fn test_title_case_and_comparison() {
+    assert_eq!(title_case("ÉPÉE  à\u{a0}DEUX mains"), "Épée À Deux Mains");
+    assert_eq!(title_case("日本語 テキスト"), "日本語 テキスト");
+    assert!(eq_ignore_case("ÉPÉE", "épée"));
+    assert!(eq_ignore_case("Straße", "STRAßE"));
+    assert!(!eq_ignore_case("STRASSE", "straße"));
+  }
+
+  #[test]
+  fn test_byte_and_char_indices() {
+    // "日本語のテキスト": every char is 3 bytes.
+    assert_eq!(byte_to_char_index(JAPANESE, 9), Some(3));
+    assert_eq!(byte_to_char_index(JAPANESE, 10), None);
+    assert_eq!(byte_to_char_index(JAPANESE, JAPANESE.len()), Some(8));
+    assert_eq!(char_to_byte_index(JAPANESE, 3), Some(9));
+    assert_eq!(char_to_byte_index(JAPANESE, 8), Some(JAPANESE.len()));
+    assert_eq!(char_to_byte_index(JAPANESE, 9), None);
+    // Round trip on every boundary of every fixture.
+    for text in [FRENCH, JAPANESE, MIXED, DECOMPOSED] {
+      for (char_index, (byte, _)) in text.char_indices().enumerate() {
+        assert_eq!(byte_to_char_index(text, byte), Some(char_index));
+        assert_eq!(char_to_byte_index(text, char_index), Some(byte));
+      }
+    }
+  }
+
+  #[test]
+  fn test_columns() {
+    assert_eq!(display_width("hello"), 5);
+    assert_eq!(display_width(JAPANESE), 16);
+    assert_eq!(display_width(MIXED), 14);
+    // Two chars, one column.
+    assert_eq!((DECOMPOSED.chars().count(), display_width(DECOMPOSED)), (4, 3));
+    assert_eq!(byte_to_column(MIXED, MIXED.find("ferris").unwrap()), Some(8));
+    assert_eq!(byte_to_column(MIXED, 6), None);
+  }
+
+  #[test]
+  fn test_line_and_column() {
+    let text: &str = "fn main() {\n  let 日本 = \"语\";\n}";
+    let byte: usize = text.find('=').unwrap();
+    assert_eq!(line_and_column(text, byte), Some((2, 10)));
+    assert_eq!(line_and_column(text, 0), Some((1, 1)));
+    assert_eq!(line_and_column(text, text.len()), Some((3, 2)));
+    // Inside "本".
+    assert_eq!(line_and_column(text, byte - 2), None);
+  }
+
+  #[test]
+  fn test_underline() {
+    let line: &str = "let 日本 = \"语\";";
+    let start: usize = line.find('日').unwrap();
+    assert_eq!(underline(line, start, start + "日本".len()), Some(String::from("    ^^^^")));
+    let quote: usize = line.find('"').unwrap();
+    assert_eq!(underline(line, quote, line.len() - 1), Some(String::from("           ^^^^")));
+    assert_eq!(underline(line, start + 1, start + 3), None);
+  }
+}
@@ -28,10 +28,10 @@
 //    struct StructName (field_type, ...)
 //
 
-struct User {
-  active: bool,              // active is a bool value
-  sign_in_count: u64,        // sign_in_count is an int value
-  username: String,          // username is a { ptr, len, cap } value that manages a string on the heap
+pub struct User {
+  pub active: bool,          // active is a bool value
+  pub sign_in_count: u64,    // sign_in_count is an int value
+  pub username: String,      // username is a { ptr, len, cap } value that manages a string on the heap
 }
 
     // Informal Mental Model: what *COULD* happen:
@@ -71,7 +71,7 @@ struct User {
 //  - Reusing parts of structs.
 //    We can specify new field values where desired, and then use `..` followed by an existing struct to reuse its field values
 
-fn using_structs(){
+pub fn using_structs(){
   // Specifying struct values
   let mut user1 = User {
     active: true,
@@ -109,7 +109,7 @@ fn using_structs(){
 //    Hence structs fields always inherit the mutability of the struct value, which is decided by the owner of that data, and
 //    cannot be defined as inherently mutable.
 
-fn mutability_in_structs(){
+pub fn mutability_in_structs(){
   // immut_user is an immutable struct, and so all its fields are immutable
   let immut_user: User = User {
     active: true,
@@ -149,7 +149,7 @@ fn mutability_in_structs(){
 // the Borrow) can still be used.
 //
 
-fn partial_move_copy_clone_reference() {
+pub fn partial_move_copy_clone_reference() {
   #[derive(Debug)]
   struct Person {
       first_name: String,
@@ -185,14 +185,14 @@ fn partial_move_copy_clone_reference() {
 
 // -------------------------------------------------------------------------------------------------
 // [MOVE]
-struct UserMove {
-  active: bool,              // copyable
-  sign_in_count: u64,        // copyable
-  username: String,          // only moveable/cloneable
-  email: String,             // only moveable/cloneable
+pub struct UserMove {
+  pub active: bool,          // copyable
+  pub sign_in_count: u64,    // copyable
+  pub username: String,      // only moveable/cloneable
+  pub email: String,         // only moveable/cloneable
 }
 
-fn move_struct(){
+pub fn move_struct(){
   let mut moveable_user = UserMove {
     active: true,
     sign_in_count: 1,
@@ -258,11 +258,11 @@ fn move_struct(){
 // -------------------------------------------------------------------------------------------------
 // [COPY]
 #[derive(Clone, Copy)]
-struct UserCopy {
-  active: bool,              // copyable
-  sign_in_count: u64,        // copyable
+pub struct UserCopy {
+  pub active: bool,          // copyable
+  pub sign_in_count: u64,    // copyable
 }
-fn copy_struct(){
+pub fn copy_struct(){
   let copyable_user = UserCopy {
     active: true,
     sign_in_count: 1,
@@ -287,12 +287,12 @@ fn copy_struct(){
 // -------------------------------------------------------------------------------------------------
 // [COPY]
 #[derive(Clone)]
-struct UserClone {
-  active: bool,              // copyable
-  sign_in_count: u64,        // copyable
-  email: String,             // only moveable/cloneable
+pub struct UserClone {
+  pub active: bool,          // copyable
+  pub sign_in_count: u64,    // copyable
+  pub email: String,         // only moveable/cloneable
 }
-fn clone_struct(){
+pub fn clone_struct(){
   let mut cloneable_user = UserClone {
     active: true,
     sign_in_count: 1,
@@ -315,4 +315,60 @@ fn clone_struct(){
   // ---- Partial clone of cloneable structs does not affect ownership.
   //      and have the same rules as for moveable structs, and does not affect ownership.
   let cloned_email = cloneable_user.email.clone();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_using_structs() {
+    using_structs();
+  }
+
+  #[test]
+  fn test_mutability_in_structs() {
+    mutability_in_structs();
+  }
+
+  #[test]
+  fn test_partial_move_copy_clone_reference() {
+    partial_move_copy_clone_reference();
+  }
+
+  #[test]
+  fn test_move_copy_clone_struct() {
+    move_struct();
+    copy_struct();
+    clone_struct();
+  }
+
+  #[test]
+  fn test_struct_update_syntax_moves_unnamed_fields() {
+    let user1 = User { active: false, sign_in_count: 3, username: String::from("someusername123") };
+    let user2 = User { username: String::from("anotherexample"), ..user1 };
+    assert!(!user2.active);
+    assert_eq!(user2.sign_in_count, 3);
+    assert_eq!(user2.username, "anotherexample");
+    // Only copyable fields were taken from user1, so its username is still valid.
+    assert_eq!(user1.username, "someusername123");
+  }
+
+  #[test]
+  fn test_copy_struct_is_independent() {
+    let copyable_user = UserCopy { active: true, sign_in_count: 1 };
+    let mut copied_user = copyable_user;
+    copied_user.sign_in_count += 1;
+    assert_eq!(copyable_user.sign_in_count, 1);
+    assert_eq!(copied_user.sign_in_count, 2);
+  }
+
+  #[test]
+  fn test_clone_struct_is_independent() {
+    let cloneable_user = UserClone { active: true, sign_in_count: 1, email: String::from("a@b.c") };
+    let mut cloned_user = cloneable_user.clone();
+    cloned_user.email.push('d');
+    assert_eq!(cloneable_user.email, "a@b.c");
+    assert_eq!(cloned_user.email, "a@b.cd");
+  }
+}
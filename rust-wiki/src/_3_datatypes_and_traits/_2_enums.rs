@@ -24,7 +24,7 @@
 //                  , ... }
 //
 
-enum Message {
+pub enum Message {
   Quit,                         //  Quit has no associated data
   Move { x: i32, y: i32 },      //  Move has named fields for two  i32 values
   Write(String),                //  Write contains a single { ptr, len, cap } value that manages a string on the heap
@@ -40,7 +40,7 @@ enum Message {
 //    let y = EnumName::Variant2Name{field_name: field_value, ...};
 //
 
-fn enums_usage(){
+pub fn enums_usage(){
   // Specifying enum values
   let quit_message: Message = Message::Quit;
   let move_message: Message = Message::Move { x: 0, y: 0 };
@@ -71,7 +71,7 @@ fn enums_usage(){
 //    * The `x@pattern => ...` syntax lets us set a variable name x that owns the value/pattern matched on.
 //    * The `_` syntax lets us match any value/pattern and not assign a variable to it.
 
-fn matching(n : i32) {
+pub fn matching(n : i32) {
   // Matching on basic values.
   let msg: Message = match n {
     // Matches on the i32 value 0
@@ -137,7 +137,7 @@ fn matching(n : i32) {
 //        case Just(x) =>     ... // x is a reference to a u8 value
 //      };
 
-fn matching_with_refs(msg : Message){
+pub fn matching_with_refs(msg : Message){
   // Matching a value without `ref`
   let msg1: Message = match msg {
     // x_val and y_val own the i32 values x and y copied from msg
@@ -195,7 +195,7 @@ fn matching_with_refs(msg : Message){
 // The `if let pattern = variable { code  }` lets us match a variable as a single pattern while ignoring the rest, and
 // only execute a block of code if that match was successful.
 
-fn matching_with_if_let(msg : Message){
+pub fn matching_with_if_let(msg : Message){
   // Matching a value using if-let (without `ref`)
   let msg1 : Message = if let Message::Write( s) = msg {
       print!("{s}");
@@ -220,3 +220,36 @@ fn matching_with_if_let(msg : Message){
     Message::Write(s.to_string())
   } else { Message::Quit };
 }
+
+// More patterns (`@` bindings, slice patterns, guards, binding modes, `let ... else`, `matches!` and
+// `#[non_exhaustive]`), and an expression evaluator that uses them all, are in _5_patterns.rs.
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_enums_usage() {
+    enums_usage();
+  }
+
+  #[test]
+  fn test_matching() {
+    matching(0);
+    matching(5);
+  }
+
+  #[test]
+  fn test_matching_with_refs() {
+    matching_with_refs(Message::Move { x: 1, y: 2 });
+    matching_with_refs(Message::Write(String::from("hello")));
+    matching_with_refs(Message::ChangeColor(0, 5, 7));
+    matching_with_refs(Message::Quit);
+  }
+
+  #[test]
+  fn test_matching_with_if_let() {
+    matching_with_if_let(Message::Write(String::from("hello")));
+    matching_with_if_let(Message::Quit);
+  }
+}
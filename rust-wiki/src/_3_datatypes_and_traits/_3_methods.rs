@@ -52,39 +52,46 @@
 //            2.  fn method_name(mut self: &Self, ...)
 //            3.  fn method_name(mut self: &mut Self, ...)
 #[derive(Clone, Copy)]
-struct Rectangle {        // Rectangle is copyable
-  width: u32,
-  height: u32,
+pub struct Rectangle {        // Rectangle is copyable
+  pub width: u32,
+  pub height: u32,
 }
 
 impl Rectangle {
   // Rectangle::new is an associated function that constructs and returns a new Rectangle struct.
-  fn new(width: u32, height: u32) -> Self {
+  pub fn new(width: u32, height: u32) -> Self {
       Rectangle{width, height}
   }
   // Rectangle::area_withselfval is a method that takes ownership of the receiver `self :: Rectangle`, which copies it
-  fn area_withselfval(self) -> u32 {
+  pub fn area_withselfval(self) -> u32 {
       return self.width * self.height
   }
   // Rectangle::area_withselfref is a method that creates a reference that borrows the receiver `self :: Rectangle`
-  fn area_withselfref(&self) -> u32 {
+  /// Example:
+  /// ```
+  /// use rust_wiki::_3_datatypes_and_traits::_3_methods::Rectangle;
+  ///
+  /// let r: Rectangle = Rectangle::new(3, 4);
+  /// assert_eq!(r.area_withselfref(), 12);
+  /// ```
+  pub fn area_withselfref(&self) -> u32 {
       return self.width * self.height
   }
 }
 
 #[derive(Clone)]
-enum Message {
+pub enum Message {
   Move{x: i32, y: i32},   //  Move has named fields for two  i32 values
   Write(String),          //  Write contains a single { ptr, len, cap } value that manages a string on the heap
 }
 
 impl Message {
   // Message::new is an associated function that constructs and returns a new Message::Move variant.
-  fn new(x: i32, y: i32) -> Self {
+  pub fn new(x: i32, y: i32) -> Self {
       Message::Move{x, y}
   }
   // Message::sum_withselfval is a method that takes ownership of the receiver `self :: Message`, which moves it
-  fn sum_withselfval(self) -> i32 {
+  pub fn sum_withselfval(self) -> i32 {
       match self {
         // x own the i32 values x and y moved from self : Self
         Message::Move{x, y} => x + y,
@@ -93,7 +100,7 @@ impl Message {
       }
   }
   // Message::sum_withselfref is a method that creates a reference that borrows the receiver `self :: Message`
-  fn sum_withselfref(&self) -> i32 {
+  pub fn sum_withselfref(&self) -> i32 {
       match self {
         // x_ref and y_ref are references to the i32 values x and y because self : &Self is a reference.
         Message::Move{x : x_ref, y: y_ref} => *x_ref + *y_ref,
@@ -122,7 +129,7 @@ impl Message {
 // Using an associated function that is not a method is done by StructName::fun_name() or EnumName::fun_name();
 //
 
-fn using_methods_example_1(){
+pub fn using_methods_example_1(){
   // r is a new rectangle created by calling the Rectangle associated function new().
   let r: Rectangle = Rectangle::new(5, 5);
   // area1 is the result of calling r's method area_withselfval, which involves copying `r`.
@@ -133,7 +140,7 @@ fn using_methods_example_1(){
 }
 
 
-fn using_methods_example_2(){
+pub fn using_methods_example_2(){
   // m is a new message created by calling the Message associated function new().
   let m: Message = Message::new(5, 5);
   // x1 is the result of calling m's method sum_withselfval, which involves moving `m`.
@@ -147,3 +154,30 @@ fn using_methods_example_2(){
   // We can reuse m because we only borrowed it as a reference when calling m.sum_withselfref();
   let m2: Message = m;
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rectangle_area() {
+    let r: Rectangle = Rectangle::new(3, 4);
+    assert_eq!(r.area_withselfval(), 12);
+    // r was copied into area_withselfval, so it can still be used.
+    assert_eq!(r.area_withselfref(), 12);
+  }
+
+  #[test]
+  fn test_message_sum() {
+    assert_eq!(Message::new(2, 3).sum_withselfval(), 5);
+    assert_eq!(Message::new(2, 3).sum_withselfref(), 5);
+    assert_eq!(Message::Write(String::from("hello")).sum_withselfval(), 0);
+    assert_eq!(Message::Write(String::from("hello")).sum_withselfref(), 0);
+  }
+
+  #[test]
+  fn test_using_methods_examples() {
+    using_methods_example_1();
+    using_methods_example_2();
+  }
+}
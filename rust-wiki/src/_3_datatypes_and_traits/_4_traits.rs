@@ -47,7 +47,7 @@
 //      }
 //    }
 //
-trait Show {
+pub trait Show {
   type AltShowType;
   fn alt_show(s : String) -> Self::AltShowType;
   // Method
@@ -82,10 +82,10 @@ trait Show {
 // Using Traits
 //
 //
-struct User {
-  active: bool,
-  sign_in_count: u64,
-  username: String,
+pub struct User {
+  pub active: bool,
+  pub sign_in_count: u64,
+  pub username: String,
 }
 
 impl Show for User {
@@ -112,4 +112,33 @@ pub fn using_traits_example(){
   print!("{s}");
   let v: Vec<char> = User::alt_show(user1.show());
   print!("{v:?}");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn user(username: &str) -> User {
+    User { active: true, sign_in_count: 0, username: String::from(username) }
+  }
+
+  #[test]
+  fn test_show() {
+    assert_eq!(user("hello").show(), "hello");
+  }
+
+  #[test]
+  fn test_show_twice_default_method() {
+    assert_eq!(user("hello").show_twice(), "helloworld");
+  }
+
+  #[test]
+  fn test_alt_show() {
+    assert_eq!(User::alt_show(String::from("hi")), vec!['h', 'i']);
+  }
+
+  #[test]
+  fn test_using_traits_example() {
+    using_traits_example();
+  }
+}
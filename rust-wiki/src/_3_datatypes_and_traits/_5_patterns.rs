@@ -0,0 +1,666 @@
+// -----------------------------------------------
+// # PATTERNS IN DEPTH
+//
+// _2_enums.rs introduced `match`, `ref` and `if let` over `Message`. Patterns go further: they can test ranges, bind
+// a value while testing it, look inside nested structs, tuples, boxes' contents and slices, and they appear not only
+// in `match` but in `let`, `if let`, `while let`, `let ... else`, `for` loops and function parameters.
+//
+//    literal          5, "go", 'a', true
+//    range            0..=9, 'a'..='z'
+//    binding          x, mut x, ref x, ref mut x
+//    @ binding        n @ 1..=9, msg @ Message::Quit
+//    wildcard / rest  _, ..
+//    or               A | B
+//    tuple / struct   (a, _, c), Point { x, y: 0, .. }, Message::Move { x, .. }
+//    slice            [], [first, .., last], [head, tail @ ..]
+//    reference        &(a, b)
+//
+// A pattern is REFUTABLE if some value of the type does not match it (`Some(x)`), and IRREFUTABLE if every value
+// does (`(a, b)`, `x`). `let` and function parameters need irrefutable patterns; `match` arms, `if let` and
+// `while let` take refutable ones:
+//
+//    let Some(n) = "42".parse::<i32>().ok();    // Error! refutable pattern in local binding
+//
+// (`let ... else`, below, is how to write that line.)
+
+use std::fmt;
+
+use super::_2_enums::Message;
+
+// -----------------------------------------------
+// ## `@` Bindings and Nested Destructuring
+//
+// `name @ pattern` tests the value against `pattern` AND binds the whole of it to `name`: useful with ranges and
+// or-patterns, whose matched value would otherwise be unnamed. Patterns nest, so a single arm can look several
+// levels deep, and literals inside it (`y: 0`) make the arm more specific. Arms are tried top to bottom.
+pub fn describe_message(msg: &Message) -> String {
+  match msg {
+    Message::Quit => String::from("quit"),
+    Message::Move { x: 0, y: 0 } => String::from("stay put"),
+    // An or-pattern must bind the same names, with the same types, in each alternative.
+    Message::Move { x: distance, y: 0 } | Message::Move { x: 0, y: distance } => format!("move {distance} along an axis"),
+    Message::Move { x: x @ -10..=10, y: y @ -10..=10 } => format!("small move to ({x}, {y})"),
+    Message::Move { x, y } => format!("move to ({x}, {y})"),
+    Message::Write(text) if text.is_empty() => String::from("write nothing"),
+    Message::Write(text) => format!("write {} chars", text.chars().count()),
+    Message::ChangeColor(level @ 0..=255, g, b) if level == g && g == b => format!("grey {level}"),
+    Message::ChangeColor(0..=255, 0..=255, 0..=255) => String::from("colour"),
+    // `..` skips the remaining fields (or tuple elements).
+    Message::ChangeColor(..) => String::from("invalid colour"),
+  }
+}
+
+// Irrefutable patterns destructure in `let`, in function parameters and in `for` loops.
+pub fn manhattan_distance(((x1, y1), (x2, y2)): ((i32, i32), (i32, i32))) -> i32 {
+  (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+pub fn best_score(scores: &[(&str, u32)]) -> Option<(usize, String)> {
+  let mut best: Option<(usize, &str, u32)> = None;
+  // `(index, &(name, score))`: the `&` pattern matches the reference the iterator yields, copying the fields out.
+  for (index, &(name, score)) in scores.iter().enumerate() {
+    match best {
+      Some((_, _, best_score)) if best_score >= score => {}
+      _ => best = Some((index, name, score)),
+    }
+  }
+  let (index, name, _) = best?;
+  Some((index, name.to_uppercase()))
+}
+
+// -----------------------------------------------
+// ## Slice Patterns
+//
+// Slices and arrays can be matched by their length and their elements. `..` stands for any number of elements (at
+// most once per pattern), and `rest @ ..` binds them as a sub-slice. Arrays have a fixed length, so `[r, g, b]` is
+// irrefutable for a `[u8; 3]`; slices need an arm for every length, often the empty one.
+pub fn describe_slice(values: &[i32]) -> String {
+  match values {
+    [] => String::from("empty"),
+    [one] => format!("just {one}"),
+    [first, second] => format!("{first} and {second}"),
+    [first, .., last] if first == last => format!("{} values from {first} back to {last}", values.len()),
+    [first, middle @ .., last] => format!("{first}, {} more, then {last}", middle.len()),
+  }
+}
+
+// Recursion on the head and the tail of a slice, as on a list in functional languages.
+pub fn sum_recursive(values: &[i32]) -> i32 {
+  match values {
+    [] => 0,
+    [head, tail @ ..] => head + sum_recursive(tail),
+  }
+}
+
+// Matching a list of words against command shapes: literals, bindings and `@` on sub-patterns together.
+pub fn run_command(words: &[&str]) -> String {
+  match words {
+    [] => String::from("say something"),
+    ["go", direction @ ("north" | "south" | "east" | "west")] => format!("you go {direction}"),
+    ["go", elsewhere] => format!("you cannot go {elsewhere}"),
+    ["take", item] | ["pick", "up", item] => format!("you take the {item}"),
+    ["say", message @ ..] if !message.is_empty() => message.join(" "),
+    [verb, ..] => format!("you do not know how to {verb}"),
+  }
+}
+
+pub fn brightness(rgb: [u8; 3]) -> u32 {
+  let [r, g, b] = rgb;
+  (u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000
+}
+
+// -----------------------------------------------
+// ## Match Guards
+//
+// `pattern if condition =>` adds a boolean test to an arm; if it fails, matching continues with the next arm.
+// Guards can use variables from outside the pattern, which patterns themselves cannot: a name in a pattern always
+// introduces a NEW binding, so `target => ...` matches anything, and shadows `target`. Compare with a guard:
+pub fn compare_to(guess: i32, target: i32) -> &'static str {
+  match guess {
+    g if g == target => "hit",
+    g if g.abs_diff(target) <= 2 => "close",
+    _ => "miss",
+  }
+}
+
+// A guard applies to the whole or-pattern before it: `'w' | 'y' if ...` is `('w' | 'y') if ...`.
+pub fn letter_kind(c: char, semivowels_are_vowels: bool) -> &'static str {
+  match c.to_ascii_lowercase() {
+    'a' | 'e' | 'i' | 'o' | 'u' => "vowel",
+    'w' | 'y' if semivowels_are_vowels => "vowel",
+    'a'..='z' => "consonant",
+    _ => "not a letter",
+  }
+}
+
+// The compiler checks exhaustiveness ignoring guards (it cannot evaluate them), hence the last arm here:
+pub fn sign(n: i64) -> &'static str {
+  match n {
+    n if n < 0 => "negative",
+    n if n > 0 => "positive",
+    _ => "zero",
+  }
+}
+
+// -----------------------------------------------
+// ## Binding Modes
+//
+// A binding in a pattern MOVES or copies the matched value, unless written `ref x` (binds `&T`) or `ref mut x`
+// (binds `&mut T`). When a non-reference pattern such as `Some(x)` matches a REFERENCE (`&Option<String>`), Rust
+// switches the DEFAULT BINDING MODE: it sees through the reference, and binds `x` by reference (`&String`). With
+// `&mut`, bindings become `&mut`. An explicit `&` pattern instead matches the reference itself, and would move out
+// of it:
+//
+//    if let &Some(s) = &name { }    // Error! cannot move out of a shared reference
+pub fn name_length(name: &Option<String>) -> usize {
+  match name {
+    // `name: &String`, by the default binding mode.
+    Some(name) => name.chars().count(),
+    None => 0,
+  }
+}
+
+// `ref` keeps the value where it is: `pair` is still whole afterwards, since `text` only borrowed it.
+pub fn ref_keyword() -> (String, usize) {
+  let pair: (String, usize) = (String::from("wiki"), 2);
+  let (ref text, count) = pair;
+  let total: usize = text.len() + count;
+  (pair.0, total)
+}
+
+// `ref mut` on an owned value, and the default binding mode through `&mut` in a loop.
+pub fn shout(mut msg: Message) -> Message {
+  if let Message::Write(ref mut text) = msg {
+    text.push('!');
+  }
+  msg
+}
+
+pub fn bump_scores(scores: &mut [(String, Option<u32>)]) {
+  for (name, score) in scores.iter_mut() {
+    // `name: &mut String`, `score: &mut Option<u32>`, and in the arm, `points: &mut u32`.
+    match score {
+      Some(points) => *points += 1,
+      None => *score = Some(0),
+    }
+    name.make_ascii_uppercase();
+  }
+}
+
+// -----------------------------------------------
+// ## `let ... else` and `matches!`
+//
+// `let PATTERN = value else { ... };` binds the pattern's variables in the rest of the block, or runs the `else`
+// block, which must leave it (`return`, `break`, `continue` or panic). It keeps the happy path unindented, where
+// `if let` would nest it, or `match` would repeat the bindings.
+pub fn parse_assignment(line: &str) -> Result<(&str, i64), String> {
+  let Some((name, value)) = line.split_once('=') else {
+    return Err(format!("missing '=' in {line:?}"));
+  };
+  let name: &str = name.trim();
+  if !is_identifier(name) {
+    return Err(format!("{name:?} is not a name"));
+  }
+  let Ok(value) = value.trim().parse::<i64>() else {
+    return Err(format!("{:?} is not a number", value.trim()));
+  };
+  Ok((name, value))
+}
+
+// `matches!(value, pattern)` is `match value { pattern => true, _ => false }`, guards included.
+pub fn is_identifier(s: &str) -> bool {
+  let mut chars = s.chars();
+  matches!(chars.next(), Some(c) if is_identifier_start(c)) && chars.all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_'))
+}
+
+fn is_identifier_start(c: char) -> bool {
+  matches!(c, 'a'..='z' | 'A'..='Z' | '_')
+}
+
+// -----------------------------------------------
+// ## Exhaustiveness and `#[non_exhaustive]`
+//
+// A `match` must cover every value: leave out a variant and the compiler lists what is missing:
+//
+//    match op { BinOp::Add => 1, BinOp::Sub => 2 }    // Error! non-exhaustive patterns: `BinOp::Mul` and `BinOp::Div` not covered
+//
+// That is what makes adding a variant safe: every `match` that must handle it stops compiling. For a library, it
+// also makes adding a variant a breaking change. `#[non_exhaustive]` on a public enum reserves the right to add
+// variants later: OTHER crates must then always have a wildcard arm, even when they list every current variant
+// (inside the defining crate, it has no effect). On a struct, it stops other crates from building it with a struct
+// literal or destructuring it without `..`, so that fields can be added too.
+//
+// `EvalError` below is marked so: tests/library_api.rs matches on it from outside the crate, with the required `_`.
+
+// -----------------------------------------------
+// ## Putting It Together: an Expression Evaluator
+//
+// Integer arithmetic with variables:
+//
+//    expr    := "let" NAME "=" expr "in" expr | sum
+//    sum     := product (("+" | "-") product)*
+//    product := unary (("*" | "/") unary)*
+//    unary   := "-" unary | atom
+//    atom    := NUMBER | NAME | "(" expr ")" | "let" ...
+//
+// The tokenizer matches characters with ranges and `while let`; the parser matches token slices with slice patterns
+// and `@`; `eval` and `simplify` match the tree with guards, nested patterns and default binding modes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+  Num(i64),
+  Name(String),
+  Plus,
+  Minus,
+  Star,
+  Slash,
+  LParen,
+  RParen,
+  Let,
+  Equals,
+  In,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+  Add,
+  Sub,
+  Mul,
+  Div,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+  Num(i64),
+  Var(String),
+  Neg(Box<Expr>),
+  Binary(BinOp, Box<Expr>, Box<Expr>),
+  // `let name = value in body`
+  Let(String, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EvalError {
+  UnexpectedChar(char),
+  UnexpectedToken(Token),
+  UnexpectedEnd,
+  UnknownVariable(String),
+  DivisionByZero,
+  Overflow,
+}
+
+impl fmt::Display for EvalError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      EvalError::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+      EvalError::UnexpectedToken(token) => write!(f, "unexpected {token:?}"),
+      EvalError::UnexpectedEnd => write!(f, "unexpected end of input"),
+      EvalError::UnknownVariable(name) => write!(f, "unknown variable {name:?}"),
+      EvalError::DivisionByZero => write!(f, "division by zero"),
+      EvalError::Overflow => write!(f, "integer overflow"),
+    }
+  }
+}
+
+impl std::error::Error for EvalError {}
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>, EvalError> {
+  let mut tokens: Vec<Token> = Vec::new();
+  let mut chars = source.char_indices().peekable();
+  while let Some((start, c)) = chars.next() {
+    let token: Token = match c {
+      c if c.is_whitespace() => continue,
+      '+' => Token::Plus,
+      '-' => Token::Minus,
+      '*' => Token::Star,
+      '/' => Token::Slash,
+      '(' => Token::LParen,
+      ')' => Token::RParen,
+      '=' => Token::Equals,
+      '0'..='9' => {
+        let mut end: usize = start + 1;
+        // `peek` gives `Option<&(usize, char)>`: the `&` pattern looks through the reference, and the range only
+        // accepts digits.
+        while let Some(&(i, '0'..='9')) = chars.peek() {
+          end = i + 1;
+          chars.next();
+        }
+        Token::Num(source[start..end].parse().map_err(|_| EvalError::Overflow)?)
+      }
+      c if is_identifier_start(c) => {
+        let mut end: usize = start + c.len_utf8();
+        while let Some(&(i, c)) = chars.peek() {
+          if !matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_') {
+            break;
+          }
+          end = i + c.len_utf8();
+          chars.next();
+        }
+        match &source[start..end] {
+          "let" => Token::Let,
+          "in" => Token::In,
+          name => Token::Name(name.to_string()),
+        }
+      }
+      other => return Err(EvalError::UnexpectedChar(other)),
+    };
+    tokens.push(token);
+  }
+  Ok(tokens)
+}
+
+// Each parsing function returns the expression it read and the tokens left after it.
+type Parsed<'t> = Result<(Expr, &'t [Token]), EvalError>;
+
+pub fn parse(tokens: &[Token]) -> Result<Expr, EvalError> {
+  match parse_expr(tokens)? {
+    (expr, []) => Ok(expr),
+    (_, rest) => Err(unexpected(rest)),
+  }
+}
+
+fn unexpected(rest: &[Token]) -> EvalError {
+  match rest {
+    [token, ..] => EvalError::UnexpectedToken(token.clone()),
+    [] => EvalError::UnexpectedEnd,
+  }
+}
+
+fn parse_expr(tokens: &[Token]) -> Parsed<'_> {
+  match tokens {
+    [Token::Let, Token::Name(name), Token::Equals, rest @ ..] => {
+      let (value, rest) = parse_expr(rest)?;
+      let [Token::In, rest @ ..] = rest else {
+        return Err(unexpected(rest));
+      };
+      let (body, rest) = parse_expr(rest)?;
+      Ok((Expr::Let(name.clone(), Box::new(value), Box::new(body)), rest))
+    }
+    _ => parse_sum(tokens),
+  }
+}
+
+fn parse_sum(tokens: &[Token]) -> Parsed<'_> {
+  let (mut lhs, mut rest) = parse_product(tokens)?;
+  // `op @ (A | B)`: the arm needs to know which operator it matched.
+  while let [op @ (Token::Plus | Token::Minus), tail @ ..] = rest {
+    let (rhs, tail) = parse_product(tail)?;
+    let op: BinOp = if *op == Token::Plus { BinOp::Add } else { BinOp::Sub };
+    lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+    rest = tail;
+  }
+  Ok((lhs, rest))
+}
+
+fn parse_product(tokens: &[Token]) -> Parsed<'_> {
+  let (mut lhs, mut rest) = parse_unary(tokens)?;
+  while let [op @ (Token::Star | Token::Slash), tail @ ..] = rest {
+    let (rhs, tail) = parse_unary(tail)?;
+    let op: BinOp = if *op == Token::Star { BinOp::Mul } else { BinOp::Div };
+    lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+    rest = tail;
+  }
+  Ok((lhs, rest))
+}
+
+fn parse_unary(tokens: &[Token]) -> Parsed<'_> {
+  match tokens {
+    [Token::Minus, rest @ ..] => {
+      let (operand, rest) = parse_unary(rest)?;
+      Ok((Expr::Neg(Box::new(operand)), rest))
+    }
+    _ => parse_atom(tokens),
+  }
+}
+
+fn parse_atom(tokens: &[Token]) -> Parsed<'_> {
+  match tokens {
+    // `n: &i64` and `name: &String`, by the default binding mode: `tokens` is a reference to a slice of `Token`s
+    // (`&[Token]`), so the patterns match through the reference and bind by reference.
+    [Token::Num(n), rest @ ..] => Ok((Expr::Num(*n), rest)),
+    [Token::Name(name), rest @ ..] => Ok((Expr::Var(name.clone()), rest)),
+    [Token::LParen, rest @ ..] => {
+      let (inner, rest) = parse_expr(rest)?;
+      match rest {
+        [Token::RParen, rest @ ..] => Ok((inner, rest)),
+        _ => Err(unexpected(rest)),
+      }
+    }
+    [Token::Let, ..] => parse_expr(tokens),
+    _ => Err(unexpected(tokens)),
+  }
+}
+
+pub fn apply(op: BinOp, a: i64, b: i64) -> Result<i64, EvalError> {
+  let result: Option<i64> = match op {
+    BinOp::Add => a.checked_add(b),
+    BinOp::Sub => a.checked_sub(b),
+    BinOp::Mul => a.checked_mul(b),
+    BinOp::Div if b == 0 => return Err(EvalError::DivisionByZero),
+    // `i64::MIN / -1` overflows.
+    BinOp::Div => a.checked_div(b),
+  };
+  result.ok_or(EvalError::Overflow)
+}
+
+// `scope` holds the variables bound by enclosing `let`s, innermost last.
+pub fn eval(expr: &Expr, scope: &mut Vec<(String, i64)>) -> Result<i64, EvalError> {
+  match expr {
+    Expr::Num(n) => Ok(*n),
+    Expr::Var(name) => {
+      let Some(&(_, value)) = scope.iter().rev().find(|(bound, _)| bound == name) else {
+        return Err(EvalError::UnknownVariable(name.clone()));
+      };
+      Ok(value)
+    }
+    Expr::Neg(operand) => eval(operand, scope)?.checked_neg().ok_or(EvalError::Overflow),
+    Expr::Binary(op, lhs, rhs) => {
+      let lhs: i64 = eval(lhs, scope)?;
+      let rhs: i64 = eval(rhs, scope)?;
+      apply(*op, lhs, rhs)
+    }
+    Expr::Let(name, value, body) => {
+      let value: i64 = eval(value, scope)?;
+      scope.push((name.clone(), value));
+      let result: Result<i64, EvalError> = eval(body, scope);
+      scope.pop();
+      result
+    }
+  }
+}
+
+pub fn evaluate(source: &str) -> Result<i64, EvalError> {
+  let tokens: Vec<Token> = tokenize(source)?;
+  let expr: Expr = parse(&tokens)?;
+  eval(&expr, &mut Vec::new())
+}
+
+// Rewrites an expression into a simpler one with the same value. It takes the tree by value, and matches the
+// simplified children as a tuple, which reaches two levels down in one pattern. (Patterns cannot look inside a `Box`
+// directly: `*lhs` moves the child out first.)
+pub fn simplify(expr: Expr) -> Expr {
+  match expr {
+    // `--e` is not rewritten to `e`: when `e` is `i64::MIN`, `-e` overflows, which `eval` reports. A known `Num` is
+    // folded instead, so `--5` still becomes `5`.
+    Expr::Neg(operand) => match simplify(*operand) {
+      Expr::Num(n) if n != i64::MIN => Expr::Num(-n),
+      operand => Expr::Neg(Box::new(operand)),
+    },
+    Expr::Binary(op, lhs, rhs) => match (op, simplify(*lhs), simplify(*rhs)) {
+      // Both sides known: fold, unless the operation fails, which is left for `eval` to report.
+      (op, lhs @ Expr::Num(a), rhs @ Expr::Num(b)) => match apply(op, a, b) {
+        Ok(n) => Expr::Num(n),
+        Err(_) => Expr::Binary(op, Box::new(lhs), Box::new(rhs)),
+      },
+      (BinOp::Add | BinOp::Sub, e, Expr::Num(0))
+      | (BinOp::Add, Expr::Num(0), e)
+      | (BinOp::Mul | BinOp::Div, e, Expr::Num(1))
+      | (BinOp::Mul, Expr::Num(1), e) => e,
+      (op, lhs, rhs) => Expr::Binary(op, Box::new(lhs), Box::new(rhs)),
+    },
+    Expr::Let(name, value, body) => Expr::Let(name, Box::new(simplify(*value)), Box::new(simplify(*body))),
+    leaf @ (Expr::Num(_) | Expr::Var(_)) => leaf,
+  }
+}
+
+// Renames a variable in place. Matching on `&mut Expr` binds every field as `&mut`, so `name` can be assigned.
+pub fn rename(expr: &mut Expr, from: &str, to: &str) {
+  match expr {
+    Expr::Num(_) => {}
+    Expr::Var(name) if name == from => *name = to.to_string(),
+    Expr::Var(_) => {}
+    Expr::Neg(operand) => rename(operand, from, to),
+    Expr::Binary(_, lhs, rhs) => {
+      rename(lhs, from, to);
+      rename(rhs, from, to);
+    }
+    Expr::Let(name, value, body) => {
+      if name == from {
+        *name = to.to_string();
+      }
+      rename(value, from, to);
+      rename(body, from, to);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_str(source: &str) -> Expr {
+    parse(&tokenize(source).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn test_at_bindings_and_nesting() {
+    assert_eq!(describe_message(&Message::Quit), "quit");
+    assert_eq!(describe_message(&Message::Move { x: 0, y: 0 }), "stay put");
+    assert_eq!(describe_message(&Message::Move { x: 0, y: -4 }), "move -4 along an axis");
+    assert_eq!(describe_message(&Message::Move { x: 3, y: -4 }), "small move to (3, -4)");
+    assert_eq!(describe_message(&Message::Move { x: 30, y: -4 }), "move to (30, -4)");
+    assert_eq!(describe_message(&Message::Write(String::new())), "write nothing");
+    assert_eq!(describe_message(&Message::Write(String::from("héllo"))), "write 5 chars");
+    assert_eq!(describe_message(&Message::ChangeColor(9, 9, 9)), "grey 9");
+    assert_eq!(describe_message(&Message::ChangeColor(300, 300, 300)), "invalid colour");
+    assert_eq!(describe_message(&Message::ChangeColor(0, 128, 255)), "colour");
+    assert_eq!(manhattan_distance(((1, 2), (4, -2))), 7);
+    assert_eq!(best_score(&[("ann", 3), ("bob", 7), ("cy", 7)]), Some((1, String::from("BOB"))));
+    assert_eq!(best_score(&[]), None);
+  }
+
+  #[test]
+  fn test_slice_patterns() {
+    assert_eq!(describe_slice(&[]), "empty");
+    assert_eq!(describe_slice(&[4]), "just 4");
+    assert_eq!(describe_slice(&[4, 5]), "4 and 5");
+    assert_eq!(describe_slice(&[1, 2, 1]), "3 values from 1 back to 1");
+    assert_eq!(describe_slice(&[1, 2, 3, 4]), "1, 2 more, then 4");
+    assert_eq!(sum_recursive(&[1, 2, 3, 4]), 10);
+    assert_eq!(run_command(&["go", "north"]), "you go north");
+    assert_eq!(run_command(&["go", "up"]), "you cannot go up");
+    assert_eq!(run_command(&["pick", "up", "lamp"]), "you take the lamp");
+    assert_eq!(run_command(&["say", "hello", "there"]), "hello there");
+    assert_eq!(run_command(&["say"]), "you do not know how to say");
+    assert_eq!(run_command(&[]), "say something");
+    assert_eq!(brightness([255, 255, 255]), 255);
+  }
+
+  #[test]
+  fn test_guards() {
+    assert_eq!([compare_to(7, 7), compare_to(9, 7), compare_to(3, 7)], ["hit", "close", "miss"]);
+    assert_eq!(letter_kind('E', false), "vowel");
+    assert_eq!(letter_kind('y', true), "vowel");
+    assert_eq!(letter_kind('y', false), "consonant");
+    assert_eq!(letter_kind('?', true), "not a letter");
+    assert_eq!([sign(-3), sign(0), sign(3)], ["negative", "zero", "positive"]);
+  }
+
+  #[test]
+  fn test_binding_modes() {
+    assert_eq!(name_length(&Some(String::from("Zoë"))), 3);
+    assert_eq!(name_length(&None), 0);
+    assert_eq!(ref_keyword(), (String::from("wiki"), 6));
+    assert!(matches!(shout(Message::Write(String::from("hi"))), Message::Write(text) if text == "hi!"));
+    assert!(matches!(shout(Message::Quit), Message::Quit));
+    let mut scores: Vec<(String, Option<u32>)> = vec![(String::from("ann"), Some(2)), (String::from("bob"), None)];
+    bump_scores(&mut scores);
+    assert_eq!(scores, vec![(String::from("ANN"), Some(3)), (String::from("BOB"), Some(0))]);
+  }
+
+  #[test]
+  fn test_let_else_and_matches() {
+    assert_eq!(parse_assignment("x = 42"), Ok(("x", 42)));
+    assert_eq!(parse_assignment("x 42"), Err(String::from("missing '=' in \"x 42\"")));
+    assert_eq!(parse_assignment("4x = 1"), Err(String::from("\"4x\" is not a name")));
+    assert_eq!(parse_assignment("x = y"), Err(String::from("\"y\" is not a number")));
+    assert!(is_identifier("_tmp1"));
+    assert!(!is_identifier(""));
+    assert!(!is_identifier("a-b"));
+  }
+
+  #[test]
+  fn test_tokenize() {
+    assert_eq!(
+      tokenize("let x1 = (2) in -x1").unwrap(),
+      vec![
+        Token::Let,
+        Token::Name(String::from("x1")),
+        Token::Equals,
+        Token::LParen,
+        Token::Num(2),
+        Token::RParen,
+        Token::In,
+        Token::Minus,
+        Token::Name(String::from("x1")),
+      ]
+    );
+    assert_eq!(tokenize("1 % 2"), Err(EvalError::UnexpectedChar('%')));
+    assert_eq!(tokenize("99999999999999999999"), Err(EvalError::Overflow));
+  }
+
+  #[test]
+  fn test_evaluate() {
+    assert_eq!(evaluate("1 + 2 * 3"), Ok(7));
+    assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+    assert_eq!(evaluate("10 - 4 - 3"), Ok(3));
+    assert_eq!(evaluate("--7 / 2"), Ok(3));
+    assert_eq!(evaluate("let x = 5 in let y = x * 2 in x + y"), Ok(15));
+    // The inner `x` shadows the outer one, only within its body.
+    assert_eq!(evaluate("let x = 1 in (let x = 10 in x) + x"), Ok(11));
+    assert_eq!(evaluate("1 + let x = 2 in x"), Ok(3));
+  }
+
+  #[test]
+  fn test_evaluate_errors() {
+    assert_eq!(evaluate("1 / (2 - 2)"), Err(EvalError::DivisionByZero));
+    assert_eq!(evaluate("9223372036854775807 + 1"), Err(EvalError::Overflow));
+    assert_eq!(evaluate("-9223372036854775807 - 1"), Ok(i64::MIN));
+    assert_eq!(evaluate("(-9223372036854775807 - 1) / -1"), Err(EvalError::Overflow));
+    assert_eq!(evaluate("x + 1"), Err(EvalError::UnknownVariable(String::from("x"))));
+    assert_eq!(evaluate("(1 + 2"), Err(EvalError::UnexpectedEnd));
+    assert_eq!(evaluate("1 2"), Err(EvalError::UnexpectedToken(Token::Num(2))));
+    assert_eq!(evaluate("let x = 1 x"), Err(EvalError::UnexpectedToken(Token::Name(String::from("x")))));
+    assert_eq!(evaluate("").unwrap_err().to_string(), "unexpected end of input");
+  }
+
+  #[test]
+  fn test_simplify_and_rename() {
+    assert_eq!(simplify(parse_str("x * (3 - 2) + 0")), parse_str("x"));
+    assert_eq!(simplify(parse_str("--(x / 1)")), parse_str("--x"));
+    assert_eq!(simplify(parse_str("--(5 * 1)")), Expr::Num(5));
+    let minus_six_plus_y: Expr = Expr::Binary(BinOp::Add, Box::new(Expr::Num(-6)), Box::new(Expr::Var(String::from("y"))));
+    assert_eq!(simplify(parse_str("-(2 * 3) + y")), minus_six_plus_y);
+    assert_eq!(simplify(parse_str("let a = 2 + 2 in a * 1")), parse_str("let a = 4 in a"));
+    // Failing operations are kept, for `eval` to report.
+    assert_eq!(simplify(parse_str("1 / 0")), parse_str("1 / 0"));
+    // As is a double negation, which overflows for `i64::MIN`.
+    let min_twice_negated: Expr = parse_str("let x = -9223372036854775807 - 1 in --x");
+    assert_eq!(eval(&min_twice_negated, &mut Vec::new()), Err(EvalError::Overflow));
+    assert_eq!(eval(&simplify(min_twice_negated), &mut Vec::new()), Err(EvalError::Overflow));
+    let mut expr: Expr = parse_str("let x = 1 in x + y");
+    rename(&mut expr, "x", "z");
+    assert_eq!(expr, parse_str("let z = 1 in z + y"));
+    assert_eq!(eval(&expr, &mut vec![(String::from("y"), 2)]), Ok(3));
+  }
+}
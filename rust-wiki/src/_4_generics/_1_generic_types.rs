@@ -33,7 +33,14 @@
 // fn f<T : Traits, ...>(x : T) {
 //    ...
 // }
-fn generics_in_functions<T: PartialOrd>(list: &[T]) -> &T {
+/// Example:
+/// ```
+/// use rust_wiki::_4_generics::_1_generic_types::generics_in_functions;
+///
+/// assert_eq!(generics_in_functions(&[34, 50, 25, 100, 65]), &100);
+/// assert_eq!(generics_in_functions(&['y', 'm', 'a', 'q']), &'y');
+/// ```
+pub fn generics_in_functions<T: PartialOrd>(list: &[T]) -> &T {
   let mut largest = &list[0];
   for item in list {
       if item > largest {
@@ -43,11 +50,11 @@ fn generics_in_functions<T: PartialOrd>(list: &[T]) -> &T {
   largest
 }
 
-fn generics_in_functions_implsugar(list: &[impl PartialOrd]) -> &impl PartialOrd {
+pub fn generics_in_functions_implsugar(list: &[impl PartialOrd]) -> &impl PartialOrd {
   generics_in_functions(list)
 }
 
-fn generics_in_functions_wheresugar<T>(list: &[T]) -> &T
+pub fn generics_in_functions_wheresugar<T>(list: &[T]) -> &T
 where
   T: PartialOrd
 {
@@ -72,14 +79,17 @@ where
 //      }
 //    }
 //
-struct Point<T, U>{
-  x : T,
-  y : U
+// (Deriving `Clone`, `Copy`, `PartialEq` and `Debug` only implements them when `T` and `U` do. Operators such as
+//  `+` and `p[0]` are implemented for `Point` in _14_advanced_traits/_1_operator_overloading.rs.)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<T, U>{
+  pub x : T,
+  pub y : U
 }
 
 // Ad-hoc methods for specialised structs
 impl Point<f32, f32> {
-  fn distance_from_origin(&self) -> f32 {
+  pub fn distance_from_origin(&self) -> f32 {
       (self.x.powi(2) + self.y.powi(2)).sqrt()
   }
 }
@@ -87,13 +97,19 @@ impl Point<f32, f32> {
 // Generic methods for abstract parameterised structs
 impl<T : PartialOrd> Point<T, T> {
   // warning: will cause move of ownership unless T and Point<T, T> implement copy
-  fn largest_coord(self) -> T {
+  /// Example:
+  /// ```
+  /// use rust_wiki::_4_generics::_1_generic_types::Point;
+  ///
+  /// assert_eq!(Point { x: 3, y: 7 }.largest_coord(), 7);
+  /// ```
+  pub fn largest_coord(self) -> T {
       if self.x > self.y  {self.x} else {self.y}
   }
 }
 
 // Ad-hoc functions for specialised structs
-fn using_generic_structs1(p1 : Point<i32, i32>, p2 : Point<i32, i32>) -> Point<i32, i32>{
+pub fn using_generic_structs1(p1 : Point<i32, i32>, p2 : Point<i32, i32>) -> Point<i32, i32>{
   // Note: (+) is sugar for a regular function `add(self : T, rhs : T)`, so will cause MOVE of ownership unless T implements COPY.
   // Although Point is not copyable, i32 is copyable, so below passes a copy of p1.x and p1.y from p1. Likewise for p2.
   let sum1: i32 = p1.x + p1.y;
@@ -107,7 +123,7 @@ fn using_generic_structs1(p1 : Point<i32, i32>, p2 : Point<i32, i32>) -> Point<i
 }
 
 // Generic functions for abstract parameterised structs
-fn using_generic_structs2<T : PartialOrd
+pub fn using_generic_structs2<T : PartialOrd
                    + std::ops::Add<Output = T>  // Output is an associated type of the Add trait
                    + Copy>
                 (p1 : Point<T, T>, p2 : Point<T, T>) ->  Point<T, T> {
@@ -133,15 +149,18 @@ fn using_generic_structs2<T : PartialOrd
 // enum EnumName<T : Traits, ...> {
 //   ...
 // }
+//
+// (This `Result` shadows the standard library's, which is defined the same way; how it is used to handle errors
+// is covered in _9_error_handling.)
 
-enum Result<T, E> {
+pub enum Result<T, E> {
   Ok(T),
   Err(E)
 }
 
 // Ad-hoc methods for specialised enums
 impl<E> Result<u32, E> {
-  fn from_ok_u32(self) -> u32 {
+  pub fn from_ok_u32(self) -> u32 {
     match self {
       Result::Ok(res) => res,
       Result::Err(_) => 0
@@ -151,7 +170,7 @@ impl<E> Result<u32, E> {
 
 // Generic methods for abstract parameterised enums
 impl<T, E> Result<T, E> {
-  fn from_ok(self, default : T) -> T {
+  pub fn from_ok(self, default : T) -> T {
     match self {
       Result::Ok(res) => res,
       Result::Err(_) => default
@@ -160,7 +179,7 @@ impl<T, E> Result<T, E> {
 }
 
 // Ad-hoc functions for specialised enums
-fn using_generic_enums1() -> Result<i32, std::num::ParseIntError>{
+pub fn using_generic_enums1() -> Result<i32, std::num::ParseIntError>{
   let number_str: &str = "10";
   let number: i32 = match number_str.parse::<i32>() {
       Ok(number)  => number,
@@ -170,7 +189,7 @@ fn using_generic_enums1() -> Result<i32, std::num::ParseIntError>{
 }
 
 // Generic functions for abstract parameterised enums
-fn using_generic_enums2<T : std::ops::Add
+pub fn using_generic_enums2<T : std::ops::Add
                           + std::ops::Add<Output = T>
                           + Clone
                           ,
@@ -219,7 +238,7 @@ fn using_generic_enums2<T : std::ops::Add
 //
 //
 //
-trait Decrement<T> {
+pub trait Decrement<T> {
   fn decrement(self: &Self) -> T;
 }
 
@@ -274,4 +293,92 @@ impl<T : Copy> Decrement<Point<i32, T>> for Point<i32, T> {
 //  1. Type annotation:
 //      let v: Vec<bool> = Vec::new();
 //  2. Generic parameter binding:
-//      let v = Vec::<bool>::new();
\ No newline at end of file
+//      let v = Vec::<bool>::new();
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_generics_in_functions() {
+    assert_eq!(generics_in_functions(&[34, 50, 25, 100, 65]), &100);
+    assert_eq!(generics_in_functions(&[1.5, -2.0]), &1.5);
+    assert_eq!(generics_in_functions(&["b", "c", "a"]), &"c");
+    assert_eq!(generics_in_functions_wheresugar(&[3, 9, 2]), &9);
+  }
+
+  #[test]
+  fn test_generics_in_functions_returns_first_largest() {
+    let list: [i32; 3] = [7, 7, 1];
+    assert!(std::ptr::eq(generics_in_functions(&list), &list[0]));
+  }
+
+  #[test]
+  fn test_generics_in_functions_implsugar() {
+    let list: [i32; 3] = [1, 5, 3];
+    let largest = generics_in_functions_implsugar(&list);
+    // The opaque `&impl PartialOrd` can't be compared with an i32, but it still points into the list.
+    assert!(std::ptr::eq(largest as *const _ as *const i32, &list[1]));
+  }
+
+  #[test]
+  #[should_panic(expected = "index out of bounds")]
+  fn test_generics_in_functions_empty_list() {
+    generics_in_functions::<i32>(&[]);
+  }
+
+  #[test]
+  fn test_distance_from_origin() {
+    assert_eq!(Point { x: 3.0_f32, y: 4.0_f32 }.distance_from_origin(), 5.0);
+  }
+
+  #[test]
+  fn test_largest_coord() {
+    assert_eq!(Point { x: 3, y: 7 }.largest_coord(), 7);
+    assert_eq!(Point { x: 7, y: 3 }.largest_coord(), 7);
+    assert_eq!(Point { x: String::from("b"), y: String::from("a") }.largest_coord(), "b");
+  }
+
+  #[test]
+  fn test_using_generic_structs() {
+    let p: Point<i32, i32> = using_generic_structs1(Point { x: 1, y: 2 }, Point { x: 0, y: 2 });
+    assert_eq!((p.x, p.y), (1, 2));
+    let p: Point<f64, f64> = using_generic_structs2(Point { x: 0.5, y: 0.5 }, Point { x: 1.0, y: 0.5 });
+    assert_eq!((p.x, p.y), (1.0, 0.5));
+  }
+
+  #[test]
+  fn test_result_from_ok() {
+    assert_eq!(Result::<u32, ()>::Ok(5).from_ok_u32(), 5);
+    assert_eq!(Result::<u32, ()>::Err(()).from_ok_u32(), 0);
+    assert_eq!(Result::<&str, ()>::Ok("ok").from_ok("default"), "ok");
+    assert_eq!(Result::<&str, ()>::Err(()).from_ok("default"), "default");
+  }
+
+  #[test]
+  fn test_using_generic_enums1() {
+    assert_eq!(using_generic_enums1().from_ok(0), 10);
+  }
+
+  #[test]
+  fn test_using_generic_enums2() {
+    let ok1: Result<i32, &str> = Result::Ok(1);
+    let ok2: Result<i32, &str> = Result::Ok(2);
+    let err: Result<i32, &str> = Result::Err("bad");
+    assert_eq!(using_generic_enums2(&ok1, &ok2).from_ok(0), 3);
+    assert!(matches!(using_generic_enums2(&ok1, &err), Result::Err("bad")));
+    assert!(matches!(using_generic_enums2(&err, &ok2), Result::Err("bad")));
+  }
+
+  #[test]
+  fn test_decrement() {
+    assert_eq!(5.decrement(), 4);
+    let p: Point<i32, char> = Point { x: 5, y: 'a' };
+    let x: i32 = p.decrement();
+    let xf: f32 = p.decrement();
+    let q: Point<i32, char> = p.decrement();
+    assert_eq!(x, 4);
+    assert_eq!(xf, 4.0);
+    assert_eq!((q.x, q.y), (4, 'a'));
+  }
+}
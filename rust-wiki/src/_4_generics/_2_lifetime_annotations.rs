@@ -32,13 +32,13 @@
 
 // This function takes as two i32 references with lifetimes 'a and 'b that outlive the function.
 // It returns a reference with lifetime 'a which corresponds to one of the inputs' lifetimes.
-fn lifetimes_in_functions<'a, 'b>(xref: &'a i32, yref: &'b mut i32) -> &'a i32{
+pub fn lifetimes_in_functions<'a, 'b>(xref: &'a i32, yref: &'b mut i32) -> &'a i32{
   *yref += 1;
   println!("`print_multi`: x is {}, y is {}", xref, yref);
   &xref
 }
 
-fn main1() {
+pub fn main1() {
   let x: i32 = 7;
   let mut y: i32 = 9;
 
@@ -61,21 +61,35 @@ fn main1() {
 
 // This struct contains two references that must outlive the structure
 #[derive(Debug)]
-struct NamedBorrowed<'a> {
-  x: &'a i32,
-  y: &'a mut i32,
+pub struct NamedBorrowed<'a> {
+  pub x: &'a i32,
+  pub y: &'a mut i32,
 }
 
 impl<'a> NamedBorrowed<'a> {
   // This method refers to the lifetime parameter of the struct.
-  fn add_one(&'a mut self) { (*self.y) += 1; }
+  // Beware: `&'a mut self` is a `&'a mut NamedBorrowed<'a>`, so it borrows the struct mutably for as long as the
+  // references inside it live, i.e. until the struct itself is last used. After one call to `add_one`, `double`
+  // can no longer be used at all:
+  //
+  //    double.add_one();
+  //    double.add_one();     // Error! cannot borrow `double` as mutable more than once at a time
+  //
+  // A method only needs the struct's lifetime parameter when it returns or stores one of the struct's references.
+  pub fn add_one(&'a mut self) { (*self.y) += 1; }
+  // This is the signature `add_one` should have: `&mut self` gets its own, shorter lifetime, which ends with the call.
+  // (See _15_advanced_lifetimes/_1_borrowing_self.rs.)
+  pub fn add_one_fixed(&mut self) { (*self.y) += 1; }
+  // This method refers to the lifetime parameter of the struct on purpose: the returned reference borrows from `x`,
+  // not from `self`, so it stays valid after `self` is gone.
+  pub fn get_x(&self) -> &'a i32 { self.x }
   // This associated function declares a new lifetime parameter
-  fn makeStruct<'b>(x : &'b  i32, y : &'b mut i32) -> NamedBorrowed<'b>{
+  pub fn makeStruct<'b>(x : &'b  i32, y : &'b mut i32) -> NamedBorrowed<'b>{
       NamedBorrowed {x , y}
   }
 }
 
-fn main2() {
+pub fn main2() {
   let x = 18;
   let mut y = 15;
   let double = NamedBorrowed { x: & x, y: &mut y };
@@ -100,26 +114,114 @@ fn main2() {
 //        }
 //    }
 
-// // Trait Definition with a Lifetime Parameter:
-// trait Summary<'a> {
-//   fn summarize(&self) -> &'a str;
-// }
-
-// // A struct with annotation of lifetimes.
-// struct Article<'a> {
-//   title: &'a str,
-//   content: &'a str,
-// }
-
-// // Trait Implementation for the Struct:
-// impl<'a> Summary<'a> for Article<'a> {
-//   fn summarize(&self) -> &'a str {
-//       self.title
-//   }
-// }
-
-// fn main3() {
-//     let article: Article = Article {title: "hello", content: "world"};
-//     let title: &str = article.summarize();
-//     println!("Article article is {:?}", title);
-// }
\ No newline at end of file
+// Trait Definition with a Lifetime Parameter:
+// `summarize` returns a reference that lives for 'a, a lifetime chosen by the implementation, rather than for as long
+// as the borrow of `self`.
+pub trait Summary<'a> {
+  fn summarize(&self) -> &'a str;
+}
+
+// A struct with annotation of lifetimes.
+#[derive(Debug)]
+pub struct Article<'a> {
+  pub title: &'a str,
+  pub content: &'a str,
+}
+
+// Trait Implementation for the Struct:
+// the same 'a ties the returned title to the text the article borrows from, not to the article.
+impl<'a> Summary<'a> for Article<'a> {
+  fn summarize(&self) -> &'a str {
+      self.title
+  }
+}
+
+// A struct can implement the trait for lifetimes it does not contain. A `Tweet` owns its text, so it cannot hand out
+// references to it that outlive the tweet; its summary is a string literal instead, a `&'static str`, which is valid
+// for any 'a. So `Tweet` implements `Summary<'a>` for every 'a.
+// (Implementing only `Summary<'static>` would be too strict: a `&dyn Summary<'a>` holding a tweet would then force
+//  'a to be 'static, and with it every other item of `summaries` below.)
+#[derive(Debug)]
+pub struct Tweet {
+  pub username: String,
+  pub content: String,
+}
+
+impl<'a> Summary<'a> for Tweet {
+  fn summarize(&self) -> &'a str {
+      "(read more...)"
+  }
+}
+
+// Generic code over the trait keeps the lifetime: the summaries outlive the borrowed slice of items.
+pub fn summaries<'a>(items: &[&dyn Summary<'a>]) -> Vec<&'a str> {
+  items.iter().map(|item| item.summarize()).collect()
+}
+
+pub fn main3() -> &'static str {
+  let title: &str;
+  {
+    let article: Article = Article {title: "hello", content: "world"};
+    title = article.summarize();
+  } // `article` is dropped here, but `title` borrows the string literal, not `article`.
+  //
+  // Had `summarize` been declared with elided lifetimes, `fn summarize(&self) -> &str`, its result would borrow from
+  // `article`, and the assignment above would be rejected:
+  //
+  //    title = article.summarize();    // Error! `article` does not live long enough
+  println!("Article title is {:?}", title);
+  title
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_lifetimes_in_functions() {
+    let x: i32 = 7;
+    let mut y: i32 = 9;
+    let zref: &i32 = lifetimes_in_functions(&x, &mut y);
+    assert!(std::ptr::eq(zref, &x));
+    assert_eq!(y, 10);
+  }
+
+  #[test]
+  fn test_named_borrowed() {
+    let x: i32 = 18;
+    let mut y: i32 = 15;
+    let mut double: NamedBorrowed = NamedBorrowed::makeStruct(&x, &mut y);
+    assert_eq!((*double.x, *double.y), (18, 15));
+    // add_one borrows `double` for the whole of 'a, so it can't be used again afterwards.
+    double.add_one();
+    assert_eq!(y, 16);
+  }
+
+  #[test]
+  fn test_named_borrowed_fixed() {
+    let x: i32 = 18;
+    let mut y: i32 = 15;
+    let mut double: NamedBorrowed = NamedBorrowed::makeStruct(&x, &mut y);
+    double.add_one_fixed();
+    double.add_one_fixed();
+    let x_ref: &i32 = double.get_x();
+    drop(double);
+    assert_eq!((*x_ref, y), (18, 17));
+  }
+
+  #[test]
+  fn test_summaries() {
+    let text: String = String::from("Lifetimes\nA reference must not outlive its referent.");
+    let (title, content) = text.split_once('\n').unwrap();
+    let article: Article = Article { title, content };
+    let tweet: Tweet = Tweet { username: String::from("ferris"), content: String::from("hi") };
+    assert_eq!(summaries(&[&article, &tweet]), vec!["Lifetimes", "(read more...)"]);
+  }
+
+  #[test]
+  fn test_mains() {
+    main1();
+    main2();
+    assert_eq!(main3(), "hello");
+  }
+}
@@ -19,7 +19,7 @@
 //   | lambda_arg : T, ...| -> T { lambda_body }
 //
 
-fn closures_vs_fns(){
+pub fn closures_vs_fns(){
   // A local function definition (not a variable).
   // Although this is local to the current scope, it is included in the scope's static environment. (We *could* declare it at the end.)
   fn one_fn() -> u32{ 1 }
@@ -57,7 +57,7 @@ fn closures_vs_fns(){
 // The closure decides which of these to use based on what its body does with the captured values.
 // (It is also possible to *selectively* move, copy, or borrow the captured values, by using some extra code.)
 
-fn closure_move_in() {
+pub fn closure_move_in() {
   // x owns an i32 which implements Copy, and xs owns a Vec which does not.
   let x: i32 = 2;
   let xs: Vec<i32> = vec![1, 2, 3];
@@ -71,7 +71,7 @@ fn closure_move_in() {
   println!("{x:?}");
 }
 
-fn closure_immutborrow_in() {
+pub fn closure_immutborrow_in() {
   // x owns an i32 which implements Copy, and xs owns a Vec which does not.
   let x: i32 = 2;
   let xs: Vec<i32> = vec![1, 2, 3];
@@ -84,7 +84,7 @@ fn closure_immutborrow_in() {
   println!("After calling closure: {xs:?}");
 }
 
-fn closure_mutborrow_in() {
+pub fn closure_mutborrow_in() {
   // x owns an i32 which implements Copy, and xs *mutably* owns a Vec which does not.
   let x: i32 = 2;
   let mut xs: Vec<i32> = vec![1, 2, 3];
@@ -107,7 +107,7 @@ fn closure_mutborrow_in() {
 //  2. Mutate the captured value, requiring us to first Mutably Borrow or Mutably Move that Mutable value *into* the closure.
 //  3. Neither Move nor Mutate the value.
 
-fn closure_move_out() {
+pub fn closure_move_out() {
   // x owns an i32 which implements Copy, and xs owns a Vec which does not.
   let x: i32 = 2;
   let xs: Vec<i32> = vec![1, 2, 3];
@@ -122,7 +122,7 @@ fn closure_move_out() {
 }
 
 
-fn closure_mutate_out() {
+pub fn closure_mutate_out() {
   let mut xs: Vec<i32> = vec![1, 2, 3];
 
   // this closure mutates xs, where we Mutably Borrowed xs into the body.
@@ -159,7 +159,7 @@ fn closure_mutate_out() {
 //
 
 
-fn fn_trait() {
+pub fn fn_trait() {
 
   //// 1. FnOnce
   // this closure when called Moves xs out of the body, meaning that its declaration has (implicitly) Moves xs into the body
@@ -187,3 +187,34 @@ fn fn_trait() {
 
 }
 
+// Closures as values that cross function boundaries (returned with `impl Fn` or `Box<dyn Fn>`, stored in a callback
+// registry, or replaced by function pointers) are covered in _4_closure_apis.rs.
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_closures_vs_fns() {
+    closures_vs_fns();
+  }
+
+  #[test]
+  fn test_closures_moving_and_borrowing_in() {
+    closure_move_in();
+    closure_immutborrow_in();
+    closure_mutborrow_in();
+  }
+
+  #[test]
+  fn test_closures_moving_and_mutating_out() {
+    closure_move_out();
+    closure_mutate_out();
+  }
+
+  #[test]
+  fn test_fn_trait() {
+    fn_trait();
+  }
+}
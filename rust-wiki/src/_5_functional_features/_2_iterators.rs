@@ -42,9 +42,16 @@
 use std::{collections::vec_deque::Iter, iter::{Filter, Map}};
 
 // Struct for a Sequence of Fibonacci numbers.
-struct Fibonacci {
-  curr: u32,
-  next: u32,
+/// Example:
+/// ```
+/// use rust_wiki::_5_functional_features::_2_iterators::Fibonacci;
+///
+/// let fib: Vec<u32> = Fibonacci { curr: 0, next: 1 }.take(10).collect();
+/// assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+/// ```
+pub struct Fibonacci {
+  pub curr: u32,
+  pub next: u32,
 }
 
 // Implements `Iterator` for `Fibonacci`.
@@ -60,6 +67,10 @@ impl Iterator for Fibonacci {
 
       // Since there's no endpoint to a Fibonacci sequence, the `Iterator`
       // will never return `None`, and `Some` is always returned.
+      //
+      // Careful: `u32` only holds the first 48 terms. The 47th call computes F(48) and overflows: a panic in debug
+      // builds, a silently wrong (wrapped) number in release. See _16_big_numbers for checked, wrapping and
+      // saturating variants, and a `Fibonacci<BigUint>` that never overflows.
       Some(current)
   }
 }
@@ -86,7 +97,7 @@ impl Iterator for Fibonacci {
 //
 //  (These are methods directly associated with structs like Vec<T> or slices &[U], and are not part of any trait.)
 
-fn borrowing_iterator(){
+pub fn borrowing_iterator(){
   let v = vec![Box::new(1)];
   // Creates an iterator that borrows v1 and its elements
   let v_iter = v.iter();
@@ -98,14 +109,14 @@ fn borrowing_iterator(){
   // let x : Vec<i32> = bad_borrowing_iter.map(|x| x + 1).collect();
 }
 
-fn mutably_borrowing_iterator(){
+pub fn mutably_borrowing_iterator(){
   let mut v: Vec<i32> = vec![1,2,3];
   // Creates an iterator that mutably borrows v1 and its elements
   let v_iter: std::slice::IterMut<'_, i32> = v.iter_mut();
   v_iter.map(|x: &mut i32| *x + 1);
 }
 
-fn owning_iterator(){
+pub fn owning_iterator(){
   let v: Vec<i32> = vec![1,2,3];
   // Creates an iterator that takes ownership of v1 and its elements
   let v_iter: std::vec::IntoIter<i32> = v.into_iter();
@@ -127,19 +138,19 @@ fn owning_iterator(){
 //  - Note that `next(&mut self)` *within* any iterator still only mutably borrows each item.
 
 // Consuming Adaptor: sum()
-fn iterator_sum() {
+pub fn iterator_sum() {
   let v1: Vec<i32> = vec![1, 2, 3];
   let v1_iter: std::slice::Iter<'_, i32> = v1.iter();
   let total: i32 = v1_iter.sum();
 }
 // Consuming Adaptor: for_each()
-fn iterator_for_each() {
+pub fn iterator_for_each() {
   let v1: Vec<i32> = vec![1, 2, 3];
   let v1_iter: std::slice::Iter<'_, i32> = v1.iter();
   let unit : () = v1_iter.for_each(|x| println!("{}", x));
 }
 // Consuming Adaptor: collect()
-fn iterator_collect() {
+pub fn iterator_collect() {
   let v1: Vec<i32> = vec![1, 2, 3];
   let v1_iter: std::slice::Iter<'_, i32> = v1.iter();
   let v1_refs: Vec<&i32> = v1_iter.collect();
@@ -152,8 +163,10 @@ fn iterator_collect() {
 // - They instead produce new Iterators by nesting them.
 // - To use the result of the iterator adaptors, they must eventually be consumed
 
+// Writing new iterator adaptors (chunks_exact, windows, lookahead, ...) is covered in _3_iterator_adaptors.rs.
+
 // Iterator adaptor: map() and filter()
-fn iterator_map_filter() {
+pub fn iterator_map_filter() {
   let v: Vec<i32> =  vec![1, 2, 3];
 
   // Creates an iterator (a concrete Iter<> struct) that borrows elements
@@ -169,3 +182,45 @@ fn iterator_map_filter() {
   // Consumes all iterators and collects the resulting values into a collection datatype
   let v_refs : Vec<&i32> = v_filter_map_iter.collect();
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_fibonacci_first_ten() {
+    let fib: Vec<u32> = Fibonacci { curr: 0, next: 1 }.take(10).collect();
+    assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+  }
+
+  #[test]
+  fn test_fibonacci_from_other_seeds() {
+    let lucas: Vec<u32> = Fibonacci { curr: 2, next: 1 }.take(6).collect();
+    assert_eq!(lucas, vec![2, 1, 3, 4, 7, 11]);
+  }
+
+  #[test]
+  fn test_fibonacci_is_lazy() {
+    // The sequence is infinite, so this only terminates because `nth` stops calling next() after the 21st item.
+    assert_eq!(Fibonacci { curr: 0, next: 1 }.nth(20), Some(6765));
+  }
+
+  #[test]
+  fn test_iterator_ownership() {
+    borrowing_iterator();
+    mutably_borrowing_iterator();
+    owning_iterator();
+  }
+
+  #[test]
+  fn test_consuming_adaptors() {
+    iterator_sum();
+    iterator_for_each();
+    iterator_collect();
+  }
+
+  #[test]
+  fn test_iterator_map_filter() {
+    iterator_map_filter();
+  }
+}
@@ -0,0 +1,620 @@
+// ----------------------------------------
+// # ITERATOR ADAPTORS, FROM SCRATCH
+//
+// `map` and `filter` (_2_iterators.rs) are nothing special: each is a struct wrapping another iterator, whose `next`
+// calls the inner `next` and does a little work. This module writes a few more of them, the way std and the
+// `itertools` crate do:
+//   - `chunks_exact(n)`:      groups of exactly `n` items, like `slice::chunks_exact` but for any iterator.
+//   - `interleave(other)`:    alternates between two iterators.
+//   - `dedup_by_key(f)`:      skips items whose key equals the previous item's key, like `Vec::dedup_by_key`.
+//   - `windows(n)`:           overlapping groups of `n` items, like `slice::windows`.
+//   - `scan_while(init, f)`:  `fold` that yields each step, until `f` returns `None`.
+//   - `lookahead()`:          `peek` at the next item without consuming it, like `peekable`.
+//
+// ## Extension Traits
+//
+// The methods are made available on every iterator by an EXTENSION TRAIT: a trait whose methods all have default
+// bodies, implemented for every `I: Iterator` by one blanket impl. Importing `IteratorExt` is then enough to write
+// `numbers.iter().windows(2)`. (See _14_advanced_traits/_2_blanket_impls.rs.)
+//
+// ## Beyond `next`
+//
+// An adaptor should forward what it can of its inner iterator's extra abilities:
+//   - `size_hint`:               bounds on the number of remaining items; used by `collect` to preallocate.
+//   - `ExactSizeIterator`:       the exact number (`len()`), when the bounds are equal.
+//   - `DoubleEndedIterator`:     `next_back`, which makes `.rev()` and `.last()`-from-the-back work.
+//   - `FusedIterator`:           a promise that once `next` returns `None`, it always will. Without it, an iterator
+//                                may resume after a `None`; `.fuse()` adds the guarantee at the cost of a flag.
+// Each impl is conditional: `ChunksExact<I>` is only `ExactSizeIterator` if `I` is.
+//
+// ## Laziness
+//
+// Building an adaptor chain calls nothing; each adaptor pulls from its inner iterator only as many items as it needs
+// to produce its next one. `laziness_example` counts the pulls.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::iter::{Fuse, FusedIterator};
+
+pub trait IteratorExt: Iterator + Sized {
+  fn chunks_exact(self, size: usize) -> ChunksExact<Self> {
+    ChunksExact::new(self, size)
+  }
+
+  fn interleave<J: IntoIterator<Item = Self::Item>>(self, other: J) -> Interleave<Self, J::IntoIter> {
+    Interleave { a: self.fuse(), b: other.into_iter().fuse(), next_from_b: false }
+  }
+
+  fn dedup_by_key<K: PartialEq, F: FnMut(&Self::Item) -> K>(self, key: F) -> DedupByKey<Self, F, K> {
+    DedupByKey { iter: self, key, last: None }
+  }
+
+  fn windows(self, size: usize) -> Windows<Self>
+  where
+    Self::Item: Clone,
+  {
+    Windows::new(self, size)
+  }
+
+  fn scan_while<S, B, F: FnMut(&mut S, Self::Item) -> Option<B>>(self, initial_state: S, f: F) -> ScanWhile<Self, S, F> {
+    ScanWhile { iter: self, state: initial_state, f, done: false }
+  }
+
+  fn lookahead(self) -> Lookahead<Self> {
+    Lookahead { iter: self, peeked: None }
+  }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+// -----------------------------------------------
+// ## chunks_exact
+//
+// The last, incomplete chunk is not yielded: it is kept, and available from `remainder` once the iterator is
+// exhausted (from either end).
+pub struct ChunksExact<I: Iterator> {
+  iter: I,
+  size: usize,
+  remainder: Vec<I::Item>,
+}
+
+impl<I: Iterator> ChunksExact<I> {
+  fn new(iter: I, size: usize) -> ChunksExact<I> {
+    assert!(size > 0, "chunk size must be non-zero");
+    ChunksExact { iter, size, remainder: Vec::new() }
+  }
+
+  pub fn remainder(&self) -> &[I::Item] {
+    &self.remainder
+  }
+}
+
+impl<I: Iterator> Iterator for ChunksExact<I> {
+  type Item = Vec<I::Item>;
+
+  fn next(&mut self) -> Option<Vec<I::Item>> {
+    let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+    if chunk.len() == self.size {
+      Some(chunk)
+    } else {
+      if !chunk.is_empty() {
+        self.remainder = chunk;
+      }
+      None
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (lower, upper) = self.iter.size_hint();
+    (lower / self.size, upper.map(|upper| upper / self.size))
+  }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for ChunksExact<I> {}
+
+impl<I: FusedIterator> FusedIterator for ChunksExact<I> {}
+
+// From the back, the remainder is the first thing in the way: the chunks line up with the FRONT of the sequence, so
+// the inner iterator's length says how many items to set aside before the first full chunk.
+impl<I: DoubleEndedIterator + ExactSizeIterator> DoubleEndedIterator for ChunksExact<I> {
+  fn next_back(&mut self) -> Option<Vec<I::Item>> {
+    let extra: usize = self.iter.len() % self.size;
+    if extra > 0 {
+      let mut remainder: Vec<I::Item> = self.iter.by_ref().rev().take(extra).collect();
+      remainder.reverse();
+      self.remainder = remainder;
+    }
+    if self.iter.len() < self.size {
+      return None;
+    }
+    let mut chunk: Vec<I::Item> = self.iter.by_ref().rev().take(self.size).collect();
+    chunk.reverse();
+    Some(chunk)
+  }
+}
+
+// -----------------------------------------------
+// ## interleave
+//
+// Once one side is exhausted, the rest of the other follows. Both sides are wrapped in `Fuse`, so a side that
+// returned `None` is never asked again, and the adaptor is fused itself.
+pub struct Interleave<I, J> {
+  a: Fuse<I>,
+  b: Fuse<J>,
+  next_from_b: bool,
+}
+
+impl<I: Iterator, J: Iterator<Item = I::Item>> Iterator for Interleave<I, J> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<I::Item> {
+    let item: Option<I::Item> = if self.next_from_b {
+      self.b.next().or_else(|| self.a.next())
+    } else {
+      self.a.next().or_else(|| self.b.next())
+    };
+    self.next_from_b = !self.next_from_b;
+    item
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (a_lower, a_upper) = self.a.size_hint();
+    let (b_lower, b_upper) = self.b.size_hint();
+    let upper: Option<usize> = match (a_upper, b_upper) {
+      (Some(a), Some(b)) => a.checked_add(b),
+      _ => None,
+    };
+    (a_lower.saturating_add(b_lower), upper)
+  }
+}
+
+impl<I: ExactSizeIterator, J: ExactSizeIterator<Item = I::Item>> ExactSizeIterator for Interleave<I, J> {}
+
+impl<I: Iterator, J: Iterator<Item = I::Item>> FusedIterator for Interleave<I, J> {}
+
+// -----------------------------------------------
+// ## dedup_by_key
+//
+// Only CONSECUTIVE duplicates are removed, so it takes O(1) memory: the key of the last item yielded.
+pub struct DedupByKey<I, F, K> {
+  iter: I,
+  key: F,
+  last: Option<K>,
+}
+
+impl<I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K> Iterator for DedupByKey<I, F, K> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<I::Item> {
+    loop {
+      let item: I::Item = self.iter.next()?;
+      let key: K = (self.key)(&item);
+      if self.last.as_ref() != Some(&key) {
+        self.last = Some(key);
+        return Some(item);
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    // Anything from none (all duplicates of the last item yielded) or one (if none was yielded yet), to all of them.
+    let (lower, upper) = self.iter.size_hint();
+    (if self.last.is_some() { 0 } else { lower.min(1) }, upper)
+  }
+}
+
+impl<I: FusedIterator, K: PartialEq, F: FnMut(&I::Item) -> K> FusedIterator for DedupByKey<I, F, K> {}
+
+// -----------------------------------------------
+// ## windows
+//
+// Each window is a fresh `Vec` of clones: an `Iterator` cannot lend out a view of its own buffer, as the item would
+// borrow the iterator (see the lending iterators of _14_advanced_traits/_6_generic_associated_types.rs).
+pub struct Windows<I: Iterator> {
+  iter: Fuse<I>,
+  size: usize,
+  window: VecDeque<I::Item>,
+}
+
+impl<I: Iterator> Windows<I> {
+  fn new(iter: I, size: usize) -> Windows<I> {
+    assert!(size > 0, "window size must be non-zero");
+    Windows { iter: iter.fuse(), size, window: VecDeque::with_capacity(size) }
+  }
+
+  // The number of windows still to come, if `remaining` items are left in the inner iterator.
+  fn windows_left(&self, remaining: usize) -> usize {
+    if self.window.len() == self.size {
+      remaining
+    } else {
+      (remaining + self.window.len() + 1).saturating_sub(self.size)
+    }
+  }
+}
+
+impl<I: Iterator> Iterator for Windows<I>
+where
+  I::Item: Clone,
+{
+  type Item = Vec<I::Item>;
+
+  fn next(&mut self) -> Option<Vec<I::Item>> {
+    if self.window.len() < self.size {
+      // The first window: fill the buffer.
+      while self.window.len() < self.size {
+        self.window.push_back(self.iter.next()?);
+      }
+    } else {
+      // Slide by one. The new item is pulled first, so that an exhausted iterator leaves the buffer full.
+      let item: I::Item = self.iter.next()?;
+      self.window.pop_front();
+      self.window.push_back(item);
+    }
+    Some(self.window.iter().cloned().collect())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (lower, upper) = self.iter.size_hint();
+    (self.windows_left(lower), upper.map(|upper| self.windows_left(upper)))
+  }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Windows<I> where I::Item: Clone {}
+
+impl<I: Iterator> FusedIterator for Windows<I> where I::Item: Clone {}
+
+// -----------------------------------------------
+// ## scan_while
+//
+// `f` gets a mutable state and each item, and returns `Some(output)` to continue or `None` to stop. std's `scan` does
+// the same, but is not fused: it may yield again after a `None` if it is called again. `scan_while` records that it
+// is done.
+pub struct ScanWhile<I, S, F> {
+  iter: I,
+  state: S,
+  f: F,
+  done: bool,
+}
+
+impl<I: Iterator, S, B, F: FnMut(&mut S, I::Item) -> Option<B>> Iterator for ScanWhile<I, S, F> {
+  type Item = B;
+
+  fn next(&mut self) -> Option<B> {
+    if self.done {
+      return None;
+    }
+    let output: Option<B> = self.iter.next().and_then(|item| (self.f)(&mut self.state, item));
+    self.done = output.is_none();
+    output
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    if self.done {
+      (0, Some(0))
+    } else {
+      (0, self.iter.size_hint().1)
+    }
+  }
+}
+
+impl<I: Iterator, S, B, F: FnMut(&mut S, I::Item) -> Option<B>> FusedIterator for ScanWhile<I, S, F> {}
+
+// -----------------------------------------------
+// ## lookahead
+//
+// `peeked` has three states: `None` (nothing pulled in advance), `Some(Some(item))` (an item is waiting), and
+// `Some(None)` (the inner iterator was found to be exhausted, and must not be asked again).
+pub struct Lookahead<I: Iterator> {
+  iter: I,
+  peeked: Option<Option<I::Item>>,
+}
+
+impl<I: Iterator> Lookahead<I> {
+  pub fn peek(&mut self) -> Option<&I::Item> {
+    let iter: &mut I = &mut self.iter;
+    self.peeked.get_or_insert_with(|| iter.next()).as_ref()
+  }
+
+  pub fn peek_mut(&mut self) -> Option<&mut I::Item> {
+    let iter: &mut I = &mut self.iter;
+    self.peeked.get_or_insert_with(|| iter.next()).as_mut()
+  }
+
+  // Consumes the next item only if it satisfies `predicate`.
+  pub fn next_if(&mut self, predicate: impl FnOnce(&I::Item) -> bool) -> Option<I::Item> {
+    match self.peek() {
+      Some(item) if predicate(item) => self.next(),
+      _ => None,
+    }
+  }
+
+  // Consumes items while they satisfy `predicate`: `take_while` without losing the first item that fails it.
+  pub fn next_while(&mut self, mut predicate: impl FnMut(&I::Item) -> bool) -> Vec<I::Item> {
+    std::iter::from_fn(|| self.next_if(&mut predicate)).collect()
+  }
+}
+
+impl<I: Iterator> Iterator for Lookahead<I> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<I::Item> {
+    match self.peeked.take() {
+      Some(peeked) => peeked,
+      None => self.iter.next(),
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let peeked: usize = match self.peeked {
+      Some(None) => return (0, Some(0)),
+      Some(Some(_)) => 1,
+      None => 0,
+    };
+    let (lower, upper) = self.iter.size_hint();
+    (lower.saturating_add(peeked), upper.and_then(|upper| upper.checked_add(peeked)))
+  }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Lookahead<I> {}
+
+impl<I: FusedIterator> FusedIterator for Lookahead<I> {}
+
+// A peeked item is the FRONT of the sequence, so it comes last from the back.
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Lookahead<I> {
+  fn next_back(&mut self) -> Option<I::Item> {
+    match &mut self.peeked {
+      Some(None) => None,
+      Some(peeked) => self.iter.next_back().or_else(|| peeked.take()),
+      None => self.iter.next_back(),
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Putting Them Together
+
+// Splits an expression into number and operator tokens, with `lookahead` and `next_while`.
+pub fn tokenize(expression: &str) -> Vec<String> {
+  let mut chars: Lookahead<std::str::Chars> = expression.chars().lookahead();
+  let mut tokens: Vec<String> = Vec::new();
+  while let Some(&c) = chars.peek() {
+    if c.is_ascii_digit() {
+      tokens.push(chars.next_while(char::is_ascii_digit).into_iter().collect());
+    } else {
+      chars.next();
+      if !c.is_whitespace() {
+        tokens.push(c.to_string());
+      }
+    }
+  }
+  tokens
+}
+
+// A running balance that stops at the first overdraft.
+pub fn balances_until_overdrawn(start: i64, movements: &[i64]) -> Vec<i64> {
+  movements
+    .iter()
+    .scan_while(start, |balance, &movement| {
+      *balance += movement;
+      (*balance >= 0).then_some(*balance)
+    })
+    .collect()
+}
+
+// Counts how many items each step pulls from an infinite source.
+pub fn laziness_example() -> Vec<(&'static str, usize)> {
+  let pulled: Cell<usize> = Cell::new(0);
+  let source = || (1_u64..).inspect(|_| pulled.set(pulled.get() + 1));
+  let mut counts: Vec<(&'static str, usize)> = Vec::new();
+  let mut record = |label: &'static str| {
+    counts.push((label, pulled.get()));
+    pulled.set(0);
+  };
+
+  let mut chunks = source().chunks_exact(3).windows(2);
+  record("build chunks_exact(3).windows(2)");
+  chunks.next();
+  record("first window of 2 chunks");
+  chunks.next();
+  record("second window (one more chunk)");
+
+  let mut dedup = source().map(|n| n / 4).dedup_by_key(|&n| n);
+  dedup.nth(2);
+  record("3 items of dedup_by_key(n / 4)");
+
+  source().scan_while(0, |total, n| {
+    *total += n;
+    (*total <= 10).then_some(*total)
+  }).for_each(drop);
+  record("scan_while total <= 10");
+
+  let mut ahead = source().interleave(source()).lookahead();
+  ahead.peek();
+  ahead.peek();
+  record("peek twice");
+  ahead.next();
+  record("next after peek");
+
+  counts
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_11_collections::_5_benchmarks::shuffled_keys;
+
+  // Random small vectors with many repeated values, for comparing the adaptors against `Vec`-based versions.
+  fn random_cases() -> Vec<Vec<u8>> {
+    (0..300)
+      .map(|case| {
+        let keys: Vec<u64> = shuffled_keys(case % 20 + 1, case as u64 + 1);
+        let len: usize = (keys[0] % 20) as usize;
+        keys.iter().cycle().skip(1).take(len).map(|key| (key % 4) as u8).collect()
+      })
+      .collect()
+  }
+
+  // Checks `len()` against the number of items actually produced, at every step.
+  fn check_exact_size<I: ExactSizeIterator>(mut iter: I) {
+    loop {
+      let expected: usize = iter.len();
+      assert_eq!(iter.size_hint(), (expected, Some(expected)));
+      if iter.next().is_none() {
+        assert_eq!(expected, 0);
+        return;
+      }
+      assert!(expected > 0);
+    }
+  }
+
+  #[test]
+  fn test_chunks_exact_matches_slice() {
+    for v in random_cases() {
+      for size in 1..5 {
+        let expected: Vec<Vec<u8>> = v.chunks_exact(size).map(<[u8]>::to_vec).collect();
+        let mut chunks = v.iter().copied().chunks_exact(size);
+        assert_eq!(chunks.by_ref().collect::<Vec<_>>(), expected);
+        assert_eq!(chunks.remainder(), v.chunks_exact(size).remainder());
+
+        let mut backwards = v.iter().copied().chunks_exact(size);
+        let reversed: Vec<Vec<u8>> = backwards.by_ref().rev().collect();
+        assert_eq!(reversed, expected.iter().rev().cloned().collect::<Vec<_>>());
+        assert_eq!(backwards.remainder(), v.chunks_exact(size).remainder());
+        check_exact_size(v.iter().chunks_exact(size));
+      }
+    }
+  }
+
+  #[test]
+  fn test_chunks_exact_from_both_ends() {
+    let mut chunks = (1..8).chunks_exact(2);
+    assert_eq!(chunks.next(), Some(vec![1, 2]));
+    assert_eq!(chunks.next_back(), Some(vec![5, 6]));
+    assert_eq!(chunks.remainder(), &[7]);
+    assert_eq!(chunks.next_back(), Some(vec![3, 4]));
+    assert_eq!(chunks.next(), None);
+  }
+
+  #[test]
+  fn test_interleave_matches_reference() {
+    let cases: Vec<Vec<u8>> = random_cases();
+    for (a, b) in cases.iter().zip(cases.iter().skip(1)) {
+      let mut expected: Vec<u8> = Vec::new();
+      for i in 0..a.len().max(b.len()) {
+        expected.extend(a.get(i));
+        expected.extend(b.get(i));
+      }
+      assert_eq!(a.iter().copied().interleave(b.iter().copied()).collect::<Vec<u8>>(), expected);
+      check_exact_size(a.iter().interleave(b.iter()));
+    }
+  }
+
+  #[test]
+  fn test_dedup_by_key_matches_vec() {
+    for v in random_cases() {
+      let mut expected: Vec<u8> = v.clone();
+      expected.dedup_by_key(|x| *x / 2);
+      assert_eq!(v.iter().copied().dedup_by_key(|x| *x / 2).collect::<Vec<u8>>(), expected);
+      let (lower, upper) = v.iter().dedup_by_key(|x| **x).size_hint();
+      assert!(lower <= expected.len() && upper >= Some(expected.len()));
+    }
+  }
+
+  #[test]
+  fn test_windows_matches_slice() {
+    for v in random_cases() {
+      for size in 1..5 {
+        let expected: Vec<Vec<u8>> = v.windows(size).map(<[u8]>::to_vec).collect();
+        assert_eq!(v.iter().copied().windows(size).collect::<Vec<_>>(), expected);
+        check_exact_size(v.iter().windows(size));
+      }
+    }
+  }
+
+  #[test]
+  fn test_scan_while_matches_loop() {
+    for v in random_cases() {
+      let mut expected: Vec<u32> = Vec::new();
+      let mut total: u32 = 0;
+      for &x in &v {
+        if x == 3 {
+          break;
+        }
+        total += u32::from(x);
+        expected.push(total);
+      }
+      let scanned: Vec<u32> = v
+        .iter()
+        .scan_while(0_u32, |total, &x| {
+          *total += u32::from(x);
+          (x != 3).then_some(*total)
+        })
+        .collect();
+      assert_eq!(scanned, expected);
+    }
+  }
+
+  #[test]
+  fn test_scan_while_is_fused() {
+    // An inner iterator that resumes after `None`: [1, None, 2, None, ...].
+    let mut calls: u32 = 0;
+    let flaky = std::iter::from_fn(|| {
+      calls += 1;
+      (calls % 2 == 1).then_some(calls)
+    });
+    let mut scan = flaky.scan_while((), |_, n| Some(n));
+    assert_eq!((scan.next(), scan.next(), scan.next()), (Some(1), None, None));
+  }
+
+  #[test]
+  fn test_lookahead_matches_vec() {
+    for v in random_cases() {
+      let mut ahead = v.iter().copied().lookahead();
+      let mut collected: Vec<u8> = Vec::new();
+      while let Some(&next) = ahead.peek() {
+        assert_eq!(ahead.len(), v.len() - collected.len());
+        collected.extend(ahead.next_if(|&x| x == next));
+      }
+      assert_eq!(collected, v);
+      check_exact_size(v.iter().lookahead());
+    }
+  }
+
+  #[test]
+  fn test_lookahead_double_ended() {
+    let mut ahead = (1..=3).lookahead();
+    assert_eq!(ahead.peek(), Some(&1));
+    assert_eq!(ahead.next_back(), Some(3));
+    assert_eq!(ahead.next_back(), Some(2));
+    // The peeked item is the last one left, from either end.
+    assert_eq!(ahead.next_back(), Some(1));
+    assert_eq!(ahead.peek(), None);
+    assert_eq!(ahead.next(), None);
+
+    let mut edited = [1, 2].into_iter().lookahead();
+    *edited.peek_mut().unwrap() *= 10;
+    assert_eq!(edited.rev().collect::<Vec<i32>>(), vec![2, 10]);
+  }
+
+  #[test]
+  fn test_tokenize_and_balances() {
+    assert_eq!(tokenize("12+ 3*(45 -6)"), vec!["12", "+", "3", "*", "(", "45", "-", "6", ")"]);
+    assert_eq!(balances_until_overdrawn(10, &[5, -12, 4, -9, 20]), vec![15, 3, 7]);
+  }
+
+  #[test]
+  fn test_laziness() {
+    assert_eq!(
+      laziness_example(),
+      vec![
+        ("build chunks_exact(3).windows(2)", 0),
+        ("first window of 2 chunks", 6),
+        ("second window (one more chunk)", 3),
+        ("3 items of dedup_by_key(n / 4)", 8),
+        ("scan_while total <= 10", 5),
+        ("peek twice", 1),
+        ("next after peek", 0),
+      ]
+    );
+  }
+}
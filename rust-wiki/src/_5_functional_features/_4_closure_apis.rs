@@ -0,0 +1,443 @@
+// -----------------------------------------------
+// # CLOSURES ACROSS API BOUNDARIES
+//
+// _1_closures.rs covers how a closure captures its environment, and which of `FnOnce`/`FnMut`/`Fn` it implements.
+// This module is about closures as VALUES that cross function boundaries: returned from functions, stored in
+// structs, and kept in tables. The questions are always the same:
+//   - What is the closure's TYPE? Each closure has its own unique, unnameable type, so it is named either through a
+//     generic or `impl Trait` (one concrete type, static dispatch, no allocation), or through a trait object,
+//     `Box<dyn Fn..>` (any closure, dynamic dispatch, one allocation).
+//   - How LONG may it live? A closure that borrows a local cannot outlive it: `Box<dyn Fn()>` means
+//     `Box<dyn Fn() + 'static>`, which rules out borrowing closures; `Box<dyn Fn() + 'a>` allows them for 'a.
+//   - Which TRAIT does the caller need? A caller that calls a closure many times through `&self` needs `Fn`; one
+//     that holds it mutably can accept `FnMut`; one that calls it once can accept any closure with `FnOnce`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// -----------------------------------------------
+// ## Returning Closures: `impl Fn`
+//
+// The function's caller sees "some type implementing `Fn(i32) -> i32`". The closure must take ownership of what it
+// captures (`move`): `n` is a parameter, and would be gone when `make_adder` returns.
+pub fn make_adder(n: i32) -> impl Fn(i32) -> i32 {
+  move |x| x + n
+}
+
+// A returned closure can own mutable state: each call to `make_counter` gives a new, independent counter.
+pub fn make_counter() -> impl FnMut() -> u32 {
+  let mut count: u32 = 0;
+  move || {
+    count += 1;
+    count
+  }
+}
+
+// Closures combine into closures: `compose(f, g)` is `x -> g(f(x))`.
+pub fn compose<A, B, C>(f: impl Fn(A) -> B, g: impl Fn(B) -> C) -> impl Fn(A) -> C {
+  move |x| g(f(x))
+}
+
+// -----------------------------------------------
+// ## Returning Closures: `Box<dyn Fn>`
+//
+// `impl Fn` stands for ONE concrete type. Returning different closures from different branches fails, because two
+// closures never have the same type, even with identical bodies:
+//
+//    fn make_scaled(op: char, scale: i32) -> impl Fn(i32, i32) -> i32 {
+//      if op == '+' { move |a, b| (a + b) * scale } else { move |a, b| (a - b) * scale }     // Error! `if` and `else` have incompatible types
+//    }
+//
+// (Without the captured `scale` it would compile: closures that capture nothing both coerce to the same function
+// pointer type, see below.) Boxing both turns them into the same type, `Box<dyn Fn(i32, i32) -> i32>`.
+pub type Operation = Box<dyn Fn(i32, i32) -> i32>;
+
+pub fn make_operation(op: char) -> Option<Operation> {
+  match op {
+    '+' => Some(Box::new(|a, b| a + b)),
+    '-' => Some(Box::new(|a, b| a - b)),
+    '*' => Some(Box::new(|a, b| a * b)),
+    _ => None,
+  }
+}
+
+// A boxed closure may also capture: `scale` is moved into the box.
+pub fn make_scaled_operation(op: char, scale: i32) -> Option<Operation> {
+  let operation: Operation = make_operation(op)?;
+  Some(Box::new(move |a, b| operation(a, b) * scale))
+}
+
+// -----------------------------------------------
+// ## Function Pointers
+//
+// `fn(i32, i32) -> i32` (lower-case `fn`) is a FUNCTION POINTER type: a plain code address, `Copy`, with no captured
+// state. Named functions coerce to it, and so do closures THAT CAPTURE NOTHING. It needs no box, and can be stored in
+// a `const` or `static` table. A capturing closure does not coerce:
+//
+//    let offset: i32 = 1;
+//    let f: fn(i32) -> i32 = |x| x + offset;     // Error! closures can only be coerced to `fn` types if they do not capture any variables
+//
+// Every function pointer implements all three `Fn` traits, so one can be passed wherever a closure is accepted.
+pub type BinaryOp = fn(i32, i32) -> i32;
+
+fn power(base: i32, exp: i32) -> i32 {
+  base.pow(exp.max(0) as u32)
+}
+
+// A dispatch table, from operator symbols to functions: named functions and non-capturing closures, side by side.
+pub const OPERATIONS: [(&str, BinaryOp); 5] = [
+  ("+", |a, b| a + b),
+  ("-", |a, b| a - b),
+  ("*", |a, b| a * b),
+  ("max", i32::max),
+  ("^", power),
+];
+
+pub fn apply(symbol: &str, a: i32, b: i32) -> Option<i32> {
+  OPERATIONS.iter().find(|(name, _)| *name == symbol).map(|(_, operation)| operation(a, b))
+}
+
+// Function pointers are values like any other: here, a `HashMap` built from the table.
+pub fn operation_table() -> HashMap<&'static str, BinaryOp> {
+  OPERATIONS.into_iter().collect()
+}
+
+// -----------------------------------------------
+// ## Memoization: A Closure With a Cache
+//
+// `memoize(f)` returns a closure that calls `f` once per distinct argument, and answers repeated calls from a
+// `HashMap` it owns. The cache is captured state that changes on every call, so the result is `FnMut`, not `Fn`.
+pub fn memoize<A, R, F>(f: F) -> impl FnMut(A) -> R
+where
+  A: Eq + Hash + Clone,
+  R: Clone,
+  F: Fn(A) -> R,
+{
+  let mut cache: HashMap<A, R> = HashMap::new();
+  move |arg: A| cache.entry(arg.clone()).or_insert_with(|| f(arg)).clone()
+}
+
+// A recursive function cannot simply be wrapped by `memoize`: its recursive calls would go to the original, not to
+// the cached version. Instead, the function is written to call back through the cache, which it receives as an
+// argument. (A closure cannot refer to itself, so the recursive step is passed in as a function pointer.)
+pub struct RecursiveMemo<A, R> {
+  cache: HashMap<A, R>,
+  step: fn(&mut RecursiveMemo<A, R>, A) -> R,
+  pub evaluations: usize,
+}
+
+impl<A: Eq + Hash + Clone, R: Clone> RecursiveMemo<A, R> {
+  pub fn new(step: fn(&mut RecursiveMemo<A, R>, A) -> R) -> RecursiveMemo<A, R> {
+    RecursiveMemo { cache: HashMap::new(), step, evaluations: 0 }
+  }
+
+  pub fn get(&mut self, arg: A) -> R {
+    if let Some(result) = self.cache.get(&arg) {
+      return result.clone();
+    }
+    self.evaluations += 1;
+    let result: R = (self.step)(self, arg.clone());
+    self.cache.insert(arg, result.clone());
+    result
+  }
+}
+
+// Exponential without the cache, linear with it.
+pub fn memoized_fibonacci(n: u64) -> (u64, usize) {
+  let mut fib: RecursiveMemo<u64, u64> =
+    RecursiveMemo::new(|fib, n| if n < 2 { n } else { fib.get(n - 1) + fib.get(n - 2) });
+  (fib.get(n), fib.evaluations)
+}
+
+// -----------------------------------------------
+// ## Storing Closures: An Event Registry
+//
+// Handlers of different closure types are stored together as `Box<dyn FnMut(&Event) -> bool + 'a>`:
+//   - `FnMut`, so that a handler can update its own captured state (a counter, a log) each time it runs.
+//   - `-> bool`, whether the handler stays subscribed. `subscribe` wraps the caller's closure into one that always
+//     returns `true`; `subscribe_once` into one that returns `false`, so it is removed after its first event.
+//   - `+ 'a`, so that handlers may borrow locals that outlive the registry. With the default `'static`, every
+//     handler would have to own its state, or share it through `Rc<RefCell<..>>`.
+//
+// `subscribe` returns a `Subscription` HANDLE, the key to later `unsubscribe` that one handler. The handle is
+// `#[must_use]`: dropping it means the handler can never be removed individually.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+  Click { x: i32, y: i32 },
+  KeyPress(char),
+  Resize { width: u32, height: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use = "the subscription is needed to unsubscribe the handler"]
+pub struct Subscription(u64);
+
+pub type Handler<'a> = Box<dyn FnMut(&Event) -> bool + 'a>;
+
+#[derive(Default)]
+pub struct EventBus<'a> {
+  // In subscription order, which is the order handlers run in.
+  handlers: Vec<(Subscription, Handler<'a>)>,
+  next_id: u64,
+}
+
+impl<'a> EventBus<'a> {
+  pub fn new() -> EventBus<'a> {
+    EventBus { handlers: Vec::new(), next_id: 0 }
+  }
+
+  pub fn subscribe(&mut self, mut handler: impl FnMut(&Event) + 'a) -> Subscription {
+    self.add(Box::new(move |event| {
+      handler(event);
+      true
+    }))
+  }
+
+  // An `FnOnce` cannot be stored as an `FnMut`: it could be called twice. Wrapped in an `Option` that the first call
+  // `take`s, it becomes an `FnMut`, and returning `false` has `emit` remove it before it could be called again.
+  pub fn subscribe_once(&mut self, handler: impl FnOnce(&Event) + 'a) -> Subscription {
+    let mut handler: Option<_> = Some(handler);
+    self.add(Box::new(move |event| {
+      if let Some(handler) = handler.take() {
+        handler(event);
+      }
+      false
+    }))
+  }
+
+  fn add(&mut self, handler: Handler<'a>) -> Subscription {
+    let subscription: Subscription = Subscription(self.next_id);
+    self.next_id += 1;
+    self.handlers.push((subscription, handler));
+    subscription
+  }
+
+  // Returns whether the handler was still subscribed.
+  pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+    let before: usize = self.handlers.len();
+    self.handlers.retain(|(id, _)| *id != subscription);
+    self.handlers.len() != before
+  }
+
+  // Runs every handler on the event, returning how many ran, and removes those that asked to be (once-handlers).
+  // `retain_mut` passes each element as `&mut`, which calling an `FnMut` requires.
+  pub fn emit(&mut self, event: &Event) -> usize {
+    let ran: usize = self.handlers.len();
+    self.handlers.retain_mut(|(_, handler)| handler(event));
+    ran
+  }
+
+  pub fn len(&self) -> usize {
+    self.handlers.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.handlers.is_empty()
+  }
+}
+
+// Handlers borrowing locals: `clicks` and `keys` outlive `bus`, so the borrows are fine. They are usable again once
+// the bus, and the handlers holding the borrows, are gone.
+pub fn event_bus_example() -> (u32, String) {
+  let mut clicks: u32 = 0;
+  let mut keys: String = String::new();
+  {
+    let mut bus: EventBus = EventBus::new();
+    let click_handler: Subscription = bus.subscribe(|event| {
+      if let Event::Click { .. } = event {
+        clicks += 1;
+      }
+    });
+    let _key_handler: Subscription = bus.subscribe(|event| {
+      if let Event::KeyPress(key) = event {
+        keys.push(*key);
+      }
+    });
+    bus.emit(&Event::Click { x: 1, y: 2 });
+    bus.emit(&Event::KeyPress('h'));
+    bus.unsubscribe(click_handler);
+    bus.emit(&Event::Click { x: 3, y: 4 });
+    bus.emit(&Event::KeyPress('i'));
+  }
+  (clicks, keys)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::cell::{Cell, RefCell};
+  use std::rc::Rc;
+
+  fn call_fn<F: Fn() -> i32>(f: &F) -> i32 {
+    f()
+  }
+
+  fn call_fn_mut<F: FnMut() -> i32>(f: &mut F) -> i32 {
+    f()
+  }
+
+  fn call_fn_once<F: FnOnce() -> Vec<i32>>(f: F) -> Vec<i32> {
+    f()
+  }
+
+  #[test]
+  fn test_returned_closures() {
+    let add_five = make_adder(5);
+    assert_eq!(add_five(1), 6);
+    let (mut first, mut second) = (make_counter(), make_counter());
+    assert_eq!((first(), first(), first()), (1, 2, 3));
+    assert_eq!(second(), 1);
+    let describe = compose(make_adder(1), |n: i32| format!("<{n}>"));
+    assert_eq!(describe(41), "<42>");
+  }
+
+  #[test]
+  fn test_boxed_operations() {
+    let operations: Vec<Operation> = "+-*".chars().filter_map(make_operation).collect();
+    let results: Vec<i32> = operations.iter().map(|operation| operation(6, 3)).collect();
+    assert_eq!(results, vec![9, 3, 18]);
+    assert!(make_operation('/').is_none());
+    assert_eq!(make_scaled_operation('+', 10).map(|operation| operation(1, 2)), Some(30));
+  }
+
+  #[test]
+  fn test_function_pointer_table() {
+    assert_eq!(apply("^", 2, 10), Some(1024));
+    assert_eq!(apply("max", -4, 3), Some(3));
+    assert_eq!(apply("%", 1, 1), None);
+    let table: HashMap<&str, BinaryOp> = operation_table();
+    assert_eq!(table["-"](10, 4), 6);
+    // A function pointer is one machine word, with no captured state; a boxed closure is a fat pointer.
+    assert_eq!(std::mem::size_of::<BinaryOp>(), std::mem::size_of::<usize>());
+    assert_eq!(std::mem::size_of::<Operation>(), 2 * std::mem::size_of::<usize>());
+  }
+
+  #[test]
+  fn test_memoize() {
+    let calls: Cell<u32> = Cell::new(0);
+    let mut square = memoize(|n: u64| {
+      calls.set(calls.get() + 1);
+      n * n
+    });
+    assert_eq!((square(4), square(4), square(5), square(4)), (16, 16, 25, 16));
+    assert_eq!(calls.get(), 2);
+    // F(90), with one evaluation per n from 0 to 90.
+    assert_eq!(memoized_fibonacci(90), (2_880_067_194_370_816_120, 91));
+  }
+
+  #[test]
+  fn test_event_bus() {
+    assert_eq!(event_bus_example(), (1, String::from("hi")));
+    let mut sizes: Vec<(u32, u32)> = Vec::new();
+    let mut first_event: Option<Event> = None;
+    {
+      let mut bus: EventBus = EventBus::new();
+      let resize: Subscription = bus.subscribe(|event| {
+        if let Event::Resize { width, height } = *event {
+          sizes.push((width, height));
+        }
+      });
+      let _once: Subscription = bus.subscribe_once(|event| first_event = Some(event.clone()));
+      assert_eq!(bus.emit(&Event::Resize { width: 800, height: 600 }), 2);
+      // The once-handler has run, and was removed.
+      assert_eq!(bus.len(), 1);
+      assert_eq!(bus.emit(&Event::Click { x: 0, y: 0 }), 1);
+      assert!(bus.unsubscribe(resize));
+      assert!(!bus.unsubscribe(resize));
+      assert_eq!(bus.emit(&Event::Resize { width: 1, height: 1 }), 0);
+      assert!(bus.is_empty());
+    }
+    assert_eq!(sizes, vec![(800, 600)]);
+    assert_eq!(first_event, Some(Event::Resize { width: 800, height: 600 }));
+  }
+
+  #[test]
+  fn test_static_handlers_share_state() {
+    // `'static` handlers cannot borrow: shared state goes through `Rc<RefCell<..>>`, one clone per handler.
+    let log: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+    let mut bus: EventBus<'static> = EventBus::new();
+    for name in ["a", "b"] {
+      let log: Rc<RefCell<Vec<String>>> = Rc::clone(&log);
+      let _subscription: Subscription = bus.subscribe(move |event| log.borrow_mut().push(format!("{name}: {event:?}")));
+    }
+    assert_eq!(Rc::strong_count(&log), 3);
+    bus.emit(&Event::KeyPress('x'));
+    assert_eq!(*log.borrow(), vec!["a: KeyPress('x')", "b: KeyPress('x')"]);
+    // Dropping the bus drops the handlers, and the `Rc`s they own.
+    drop(bus);
+    assert_eq!(Rc::strong_count(&log), 1);
+  }
+
+  // -----------------------------------------------
+  // Capture semantics
+
+  #[test]
+  fn test_borrowing_closure_sees_later_changes() {
+    // Captured by reference: the closure reads the current value of `value` each time.
+    let value: Cell<i32> = Cell::new(1);
+    let read = || value.get();
+    value.set(2);
+    assert_eq!(call_fn(&read), 2);
+  }
+
+  #[test]
+  fn test_move_closure_copies_copy_values() {
+    // Captured by `move` of a `Copy` value: the closure has its own copy, taken at creation.
+    let mut value: i32 = 1;
+    let read = move || value;
+    value += 1;
+    assert_eq!((call_fn(&read), value), (1, 2));
+  }
+
+  #[test]
+  fn test_move_closure_owns_and_drops_captures() {
+    let shared: Rc<i32> = Rc::new(0);
+    let owner = {
+      let shared: Rc<i32> = Rc::clone(&shared);
+      move || *shared
+    };
+    assert_eq!(Rc::strong_count(&shared), 2);
+    assert_eq!(owner(), 0);
+    drop(owner);
+    assert_eq!(Rc::strong_count(&shared), 1);
+  }
+
+  #[test]
+  fn test_fn_mut_and_fn_once() {
+    let mut total: i32 = 0;
+    let mut add = || {
+      total += 10;
+      total
+    };
+    assert_eq!((call_fn_mut(&mut add), call_fn_mut(&mut add)), (10, 20));
+    assert_eq!(total, 20);
+    // Moves `numbers` out of its body: only `FnOnce`, consumed by the call.
+    let numbers: Vec<i32> = vec![1, 2];
+    let give_back = move || numbers;
+    assert_eq!(call_fn_once(give_back), vec![1, 2]);
+    // Any `Fn` is also `FnMut` and `FnOnce`, and so is a function pointer.
+    let answer: fn() -> i32 = || 42;
+    assert_eq!((call_fn(&answer), call_fn_mut(&mut make_counter_i32())), (42, 1));
+  }
+
+  fn make_counter_i32() -> impl FnMut() -> i32 {
+    let mut counter = make_counter();
+    move || counter() as i32
+  }
+
+  #[test]
+  fn test_disjoint_field_capture() {
+    // Since edition 2021 a closure captures the fields it uses, not the whole struct: `point.x` is borrowed mutably
+    // by the closure while `point.y` stays readable.
+    struct Point {
+      x: i32,
+      y: i32,
+    }
+    let mut point: Point = Point { x: 0, y: 5 };
+    let mut move_right = || point.x += 1;
+    let y: i32 = point.y;
+    move_right();
+    move_right();
+    assert_eq!((point.x, y), (2, 5));
+  }
+}
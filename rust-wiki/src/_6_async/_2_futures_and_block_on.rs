@@ -0,0 +1,256 @@
+// -----------------------------------------------
+// # FUTURES, WAKERS AND BLOCK_ON
+//
+// Async Rust needs a runtime (e.g. tokio) to run, but the runtime is ordinary library code built on a single trait from
+// the standard library:
+//
+//    pub trait Future {
+//      type Output;
+//      fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output>;
+//    }
+//
+//    pub enum Poll<T> {
+//      Ready(T),
+//      Pending,
+//    }
+//
+// This chapter builds a minimal runtime from scratch, using only `std`:
+//   1. `block_on` (here): runs one future to completion on the current thread.
+//   2. `Executor` (_3_executor.rs): a task queue that runs many futures concurrently, with `spawn` and `join`.
+//   3. `sleep` (_4_timer.rs): a future that completes after a duration.
+//   4. `channel` (_5_channel.rs): a queue that one task can `.await` values from while others send them.
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+// -----------------------------------------------
+// ## Polling
+//
+// A future is polled by calling `poll`, which tries to make progress and returns either:
+//   1. `Poll::Ready(output)`, when it has finished. It must not be polled again.
+//   2. `Poll::Pending`, when it is waiting on something (a timer, a socket, another task ...).
+//      Before returning `Pending`, the future must arrange for `cx.waker().wake()` to be called once it can make
+//      progress again. Whoever polls the future then knows to poll it again, and not before.
+//
+// `self: Pin<&mut Self>` promises the future won't be moved in memory once polling has started. This is necessary
+// because the futures generated by `async` blocks can hold references into themselves (to their own local variables).
+
+// Below is a hand-written future that returns `Pending` once, and then `Ready`.
+// It wakes itself immediately, as it can make progress as soon as it is polled again.
+pub struct YieldNow {
+  pub yielded: bool,
+}
+
+impl Future for YieldNow {
+  type Output = ();
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    if self.yielded {
+      Poll::Ready(())
+    } else {
+      self.yielded = true;
+      cx.waker().wake_by_ref();
+      Poll::Pending
+    }
+  }
+}
+
+// Yields control back to the runtime once, letting other tasks run.
+pub fn yield_now() -> YieldNow {
+  YieldNow { yielded: false }
+}
+
+// -----------------------------------------------
+// ## What Async Desugars To
+//
+// An `async fn` (or block) is compiled into an anonymous type that implements `Future`: an enum with one variant per
+// `.await` point, holding the local variables that are alive across that point.
+//
+//    async fn add_after_yield(a: u32, b: u32) -> u32 {
+//      yield_now().await;
+//      a + b
+//    }
+//
+// desugars to (roughly) the state machine below, where each call to `poll` resumes from the last `.await`.
+
+pub enum AddAfterYield {
+  // Not yet polled: holds the arguments.
+  Start { a: u32, b: u32 },
+  // Suspended at `yield_now().await`: holds the locals still needed, and the future being awaited.
+  Yielding { a: u32, b: u32, yield_now: YieldNow },
+  // Returned `Ready`.
+  Done,
+}
+
+pub fn add_after_yield(a: u32, b: u32) -> AddAfterYield {
+  // Like calling an `async fn`, this only creates the future; nothing runs until it is polled.
+  AddAfterYield::Start { a, b }
+}
+
+impl Future for AddAfterYield {
+  type Output = u32;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+    loop {
+      match &mut *self {
+        AddAfterYield::Start { a, b } => {
+          *self = AddAfterYield::Yielding { a: *a, b: *b, yield_now: yield_now() };
+        }
+        AddAfterYield::Yielding { a, b, yield_now } => {
+          // `.await` polls the inner future, and returns `Pending` straight away if it is not ready.
+          match Pin::new(yield_now).poll(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(()) => {
+              let sum: u32 = *a + *b;
+              *self = AddAfterYield::Done;
+              return Poll::Ready(sum);
+            }
+          }
+        }
+        AddAfterYield::Done => panic!("`AddAfterYield` polled after completion"),
+      }
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Wakers
+//
+// A `Waker` is a handle that a pending future keeps, to tell its runtime "poll me again".
+// What waking actually does is up to the runtime, which provides it by implementing the `Wake` trait:
+//
+//    pub trait Wake {
+//      fn wake(self: Arc<Self>);
+//    }
+//
+// `block_on` runs on a single thread, which can sleep (`park`) while its future is pending. Waking it is hence
+// just unparking that thread.
+struct ThreadWaker {
+  thread: Thread,
+}
+
+impl Wake for ThreadWaker {
+  fn wake(self: Arc<Self>) {
+    self.thread.unpark();
+  }
+}
+
+// -----------------------------------------------
+// ## block_on
+//
+// `block_on` is the bridge from synchronous code into async code: it polls a future until it is ready, parking the
+// current thread whenever the future is pending instead of spinning.
+//
+// (`thread::park` may also return spuriously, without a wake. That is harmless here: the future is then polled once
+// more than needed, returns `Pending` again, and we go back to sleep.)
+pub fn block_on<F: Future>(future: F) -> F::Output {
+  // `pin!` pins the future on this stack frame, which is fine because it is never moved out of it.
+  let mut future: Pin<&mut F> = pin!(future);
+  let waker: Waker = Waker::from(Arc::new(ThreadWaker { thread: thread::current() }));
+  let mut cx: Context<'_> = Context::from_waker(&waker);
+  loop {
+    match future.as_mut().poll(&mut cx) {
+      Poll::Ready(output) => return output,
+      Poll::Pending => thread::park(),
+    }
+  }
+}
+
+// Below wraps a future to count how many times it is polled, to show when a runtime polls what.
+pub struct CountPolls<F> {
+  pub future: F,
+  pub polls: Arc<AtomicUsize>,
+}
+
+impl<F: Future + Unpin> Future for CountPolls<F> {
+  type Output = F::Output;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+    self.polls.fetch_add(1, Ordering::SeqCst);
+    Pin::new(&mut self.future).poll(cx)
+  }
+}
+
+pub fn block_on_example() {
+  async fn example() -> &'static str {
+    println!("Async function started");
+    "finished"
+  }
+  let future = example(); // Future is created, but nothing is executed yet
+  let output: &str = block_on(future); // Now the async function `example` runs
+  println!("{output}");
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_block_on_ready_future() {
+    assert_eq!(block_on(async { 1 + 2 }), 3);
+  }
+
+  #[test]
+  fn test_block_on_pending_future() {
+    let polls: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    block_on(CountPolls { future: yield_now(), polls: Arc::clone(&polls) });
+    // Pending on the first poll, then ready on the second.
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+  }
+
+  #[test]
+  fn test_async_fn_is_lazy() {
+    let polls: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    let future = CountPolls { future: yield_now(), polls: Arc::clone(&polls) };
+    assert_eq!(polls.load(Ordering::SeqCst), 0);
+    block_on(future);
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+  }
+
+  #[test]
+  fn test_desugared_state_machine_matches_async_fn() {
+    async fn add_after_yield_async(a: u32, b: u32) -> u32 {
+      yield_now().await;
+      a + b
+    }
+    assert_eq!(block_on(add_after_yield(2, 3)), 5);
+    assert_eq!(block_on(add_after_yield_async(2, 3)), 5);
+  }
+
+  #[test]
+  fn test_block_on_wakes_from_other_thread() {
+    // A future that is only woken from another thread, after a delay.
+    struct WokenLater {
+      started: bool,
+      done: Arc<std::sync::atomic::AtomicBool>,
+    }
+    impl Future for WokenLater {
+      type Output = ();
+      fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.done.load(Ordering::SeqCst) {
+          return Poll::Ready(());
+        }
+        if !self.started {
+          self.started = true;
+          let (done, waker) = (Arc::clone(&self.done), cx.waker().clone());
+          thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(20));
+            done.store(true, Ordering::SeqCst);
+            waker.wake();
+          });
+        }
+        Poll::Pending
+      }
+    }
+    block_on(WokenLater { started: false, done: Arc::default() });
+  }
+
+  #[test]
+  fn test_block_on_example() {
+    block_on_example();
+  }
+}
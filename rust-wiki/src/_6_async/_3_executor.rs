@@ -0,0 +1,328 @@
+// -----------------------------------------------
+// # EXECUTOR: SPAWNING AND JOINING TASKS
+//
+// `block_on` runs a single future. To run many futures concurrently on one thread, an executor keeps a queue of
+// *tasks* (spawned futures) that are ready to make progress:
+//   1. `spawn` wraps a future in a task and pushes it onto the queue.
+//   2. `run` pops a task and polls it:
+//        - if it returns `Ready`, the task is finished and dropped.
+//        - if it returns `Pending`, it is *not* re-queued. It is instead pushed again by its waker when it is woken.
+//   3. When the queue is empty but tasks are still pending, `run` sleeps until one of them is woken.
+// Tasks therefore take turns on the thread at each `.await` that is not ready: this is cooperative multitasking.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+// -----------------------------------------------
+// ## Tasks
+//
+// A task owns its future, and is its own waker: waking a task sends it back onto the executor's queue.
+// The queue is an `mpsc` channel, so tasks can be woken from any thread (e.g. a timer thread).
+struct Task {
+  // `None` once the future has completed.
+  future: Mutex<Option<BoxFuture>>,
+  queue: Sender<Arc<Task>>,
+}
+
+impl Wake for Task {
+  fn wake(self: Arc<Self>) {
+    let queue: Sender<Arc<Task>> = self.queue.clone();
+    // The executor may already have been dropped, in which case there is nothing left to run the task.
+    let _ = queue.send(self);
+  }
+}
+
+// -----------------------------------------------
+// ## Join Handles
+//
+// `spawn` returns a `JoinHandle`, which is itself a future that completes with the task's output.
+// The task and its handle share a slot for that output, plus the waker of whoever is awaiting the handle.
+struct JoinState<T> {
+  output: Option<T>,
+  waker: Option<Waker>,
+}
+
+pub struct JoinHandle<T> {
+  state: Arc<Mutex<JoinState<T>>>,
+}
+
+impl<T> Future for JoinHandle<T> {
+  type Output = T;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+    let mut state = self.state.lock().unwrap();
+    match state.output.take() {
+      Some(output) => Poll::Ready(output),
+      None => {
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Spawning
+//
+// A `Spawner` is a cloneable handle to the queue, so tasks can spawn other tasks.
+#[derive(Clone)]
+pub struct Spawner {
+  queue: Sender<Arc<Task>>,
+  // Tasks that have been spawned but not yet completed.
+  pending: Arc<AtomicUsize>,
+}
+
+impl Spawner {
+  pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+  where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+  {
+    let state: Arc<Mutex<JoinState<F::Output>>> = Arc::new(Mutex::new(JoinState { output: None, waker: None }));
+    let task_state: Arc<Mutex<JoinState<F::Output>>> = Arc::clone(&state);
+    // The task runs the future, then stores its output and wakes whoever is awaiting the handle.
+    let task_future = async move {
+      let output: F::Output = future.await;
+      let mut state = task_state.lock().unwrap();
+      state.output = Some(output);
+      if let Some(waker) = state.waker.take() {
+        waker.wake();
+      }
+    };
+    self.pending.fetch_add(1, Ordering::SeqCst);
+    let task: Arc<Task> = Arc::new(Task { future: Mutex::new(Some(Box::pin(task_future))), queue: self.queue.clone() });
+    self.queue.send(task).expect("the executor has been dropped");
+    JoinHandle { state }
+  }
+}
+
+// -----------------------------------------------
+// ## The Executor
+pub struct Executor {
+  spawner: Spawner,
+  ready: Receiver<Arc<Task>>,
+}
+
+impl Default for Executor {
+  fn default() -> Self {
+    Executor::new()
+  }
+}
+
+impl Executor {
+  pub fn new() -> Self {
+    let (queue, ready) = mpsc::channel();
+    Executor { spawner: Spawner { queue, pending: Arc::new(AtomicUsize::new(0)) }, ready }
+  }
+
+  pub fn spawner(&self) -> Spawner {
+    self.spawner.clone()
+  }
+
+  pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+  where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+  {
+    self.spawner.spawn(future)
+  }
+
+  // Runs until every spawned task (including tasks spawned by tasks) has completed.
+  pub fn run(&self) {
+    while self.spawner.pending.load(Ordering::SeqCst) > 0 {
+      // Blocks until a task is woken: this is where the executor sleeps while all tasks are pending.
+      let task: Arc<Task> = self.ready.recv().expect("the executor holds a sender, so the queue stays open");
+      let mut slot = task.future.lock().unwrap();
+      // A task can be woken more than once before it is polled, and so be queued after it has already completed.
+      let Some(future) = slot.as_mut() else { continue };
+      let waker: Waker = Waker::from(Arc::clone(&task));
+      if future.as_mut().poll(&mut Context::from_waker(&waker)).is_ready() {
+        *slot = None;
+        self.spawner.pending.fetch_sub(1, Ordering::SeqCst);
+      }
+    }
+  }
+
+  // Spawns `future`, runs all tasks to completion, and returns the future's output.
+  pub fn block_on<F>(&self, future: F) -> F::Output
+  where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+  {
+    let handle: JoinHandle<F::Output> = self.spawn(future);
+    self.run();
+    let output: Option<F::Output> = handle.state.lock().unwrap().output.take();
+    output.expect("`run` returns only once every task has completed")
+  }
+}
+
+// -----------------------------------------------
+// ## Joining Without Spawning
+//
+// `join(a, b)` polls two futures concurrently *within one task*, completing when both have (like `tokio::join!`).
+// Each poll of the `Join` polls whichever of the two is not yet done.
+enum MaybeDone<F: Future> {
+  Pending(F),
+  Done(F::Output),
+  Taken,
+}
+
+impl<F: Future + Unpin> MaybeDone<F> {
+  fn poll(&mut self, cx: &mut Context<'_>) -> bool {
+    if let MaybeDone::Pending(future) = self {
+      match Pin::new(future).poll(cx) {
+        Poll::Ready(output) => *self = MaybeDone::Done(output),
+        Poll::Pending => return false,
+      }
+    }
+    true
+  }
+
+  fn take(&mut self) -> F::Output {
+    match std::mem::replace(self, MaybeDone::Taken) {
+      MaybeDone::Done(output) => output,
+      _ => panic!("`MaybeDone::take` called before the future completed"),
+    }
+  }
+}
+
+pub struct Join<A: Future, B: Future> {
+  a: MaybeDone<A>,
+  b: MaybeDone<B>,
+}
+
+// The futures are boxed so that `Join` is `Unpin`, which keeps `poll` free of `unsafe` pin projections.
+pub fn join<A: Future, B: Future>(a: A, b: B) -> Join<Pin<Box<A>>, Pin<Box<B>>> {
+  Join { a: MaybeDone::Pending(Box::pin(a)), b: MaybeDone::Pending(Box::pin(b)) }
+}
+
+// The outputs stored in `MaybeDone::Done` are never pinned, only moved out, so `Join` may be `Unpin` even if they
+// are not.
+impl<A: Future + Unpin, B: Future + Unpin> Unpin for Join<A, B> {}
+
+impl<A: Future + Unpin, B: Future + Unpin> Future for Join<A, B> {
+  type Output = (A::Output, B::Output);
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let this: &mut Self = self.get_mut();
+    let a_done: bool = this.a.poll(cx);
+    let b_done: bool = this.b.poll(cx);
+    if a_done && b_done {
+      Poll::Ready((this.a.take(), this.b.take()))
+    } else {
+      Poll::Pending
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Example: Interleaving Tasks
+//
+// Each task records a step, then yields back to the executor. The steps of the three tasks hence interleave, even
+// though there is only one thread.
+pub fn interleaving_example() -> Vec<String> {
+  use super::_2_futures_and_block_on::yield_now;
+
+  let executor: Executor = Executor::new();
+  let log: Arc<Mutex<VecDeque<String>>> = Arc::default();
+  let handles: Vec<JoinHandle<usize>> = (1..=3)
+    .map(|id| {
+      let log: Arc<Mutex<VecDeque<String>>> = Arc::clone(&log);
+      executor.spawn(async move {
+        for step in 1..=2 {
+          log.lock().unwrap().push_back(format!("task {id} step {step}"));
+          yield_now().await;
+        }
+        id * 10
+      })
+    })
+    .collect();
+  // A fourth task joins the other three, summing their outputs.
+  let total: usize = executor.block_on(async move {
+    let mut total: usize = 0;
+    for handle in handles {
+      total += handle.await;
+    }
+    total
+  });
+  let mut log: Vec<String> = log.lock().unwrap().drain(..).collect();
+  log.push(format!("total {total}"));
+  log
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_6_async::_2_futures_and_block_on::{block_on, yield_now};
+
+  #[test]
+  fn test_spawn_and_join() {
+    let executor: Executor = Executor::new();
+    let handle: JoinHandle<i32> = executor.spawn(async { 40 + 2 });
+    executor.run();
+    assert_eq!(block_on(handle), 42);
+  }
+
+  #[test]
+  fn test_block_on_executor() {
+    assert_eq!(Executor::new().block_on(async { "done" }), "done");
+  }
+
+  #[test]
+  fn test_tasks_interleave() {
+    assert_eq!(
+      interleaving_example(),
+      vec![
+        "task 1 step 1", "task 2 step 1", "task 3 step 1",
+        "task 1 step 2", "task 2 step 2", "task 3 step 2",
+        "total 60",
+      ]
+    );
+  }
+
+  #[test]
+  fn test_tasks_spawn_tasks() {
+    let executor: Executor = Executor::new();
+    let spawner: Spawner = executor.spawner();
+    let output: u32 = executor.block_on(async move {
+      let inner: JoinHandle<u32> = spawner.spawn(async {
+        yield_now().await;
+        7
+      });
+      inner.await * 2
+    });
+    assert_eq!(output, 14);
+  }
+
+  #[test]
+  fn test_run_with_no_tasks_returns() {
+    Executor::new().run();
+  }
+
+  #[test]
+  fn test_join() {
+    let order: Arc<Mutex<Vec<&str>>> = Arc::default();
+    let (a_order, b_order) = (Arc::clone(&order), Arc::clone(&order));
+    let a = async move {
+      a_order.lock().unwrap().push("a1");
+      yield_now().await;
+      a_order.lock().unwrap().push("a2");
+      1
+    };
+    let b = async move {
+      b_order.lock().unwrap().push("b1");
+      yield_now().await;
+      b_order.lock().unwrap().push("b2");
+      "two"
+    };
+    assert_eq!(block_on(join(a, b)), (1, "two"));
+    assert_eq!(*order.lock().unwrap(), vec!["a1", "b1", "a2", "b2"]);
+  }
+}
@@ -0,0 +1,117 @@
+// -----------------------------------------------
+// # TIMERS
+//
+// A timer is a future that is `Pending` until a deadline, and then `Ready`.
+// The interesting part is *who wakes it*: nothing in the future itself runs while it is pending, so something else
+// must call its waker once the deadline has passed.
+//
+// Real runtimes keep one timer thread (or an OS timer such as `timerfd`) for all timers. For simplicity, the first
+// poll of each `Timer` here starts a thread that sleeps until the deadline and then wakes the most recent waker.
+// (The waker is stored behind a mutex and updated on every poll, because a future may be moved between tasks and so
+// be polled with a different waker each time.)
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct Timer {
+  deadline: Instant,
+  // `None` until the first poll starts the sleeping thread.
+  waker: Option<Arc<Mutex<Waker>>>,
+}
+
+pub fn sleep(duration: Duration) -> Timer {
+  Timer { deadline: Instant::now() + duration, waker: None }
+}
+
+impl Future for Timer {
+  type Output = ();
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    if Instant::now() >= self.deadline {
+      return Poll::Ready(());
+    }
+    match &self.waker {
+      Some(waker) => waker.lock().unwrap().clone_from(cx.waker()),
+      None => {
+        let waker: Arc<Mutex<Waker>> = Arc::new(Mutex::new(cx.waker().clone()));
+        let thread_waker: Arc<Mutex<Waker>> = Arc::clone(&waker);
+        let deadline: Instant = self.deadline;
+        thread::spawn(move || {
+          thread::sleep(deadline.saturating_duration_since(Instant::now()));
+          thread_waker.lock().unwrap().wake_by_ref();
+        });
+        self.waker = Some(waker);
+      }
+    }
+    Poll::Pending
+  }
+}
+
+// -----------------------------------------------
+// ## Example: Non-blocking Sleeps
+//
+// This is the example from _1_async.md, on this chapter's runtime rather than tokio:
+//
+//    async fn task1() {
+//      println!("Task 1 started");
+//      sleep(Duration::from_secs(2)).await;
+//      println!("Task 1 completed");
+//    }
+//
+// `sleep(..).await` suspends only the task that awaits it, so while task 1 sleeps, task 2 runs. The whole example
+// hence takes as long as the longest sleep rather than the sum of both.
+pub fn concurrent_sleeps_example(task1_sleep: Duration, task2_sleep: Duration) -> Vec<&'static str> {
+  use super::_3_executor::{join, Executor};
+
+  let events: Arc<Mutex<Vec<&'static str>>> = Arc::default();
+  let (events1, events2) = (Arc::clone(&events), Arc::clone(&events));
+  let task1 = async move {
+    events1.lock().unwrap().push("Task 1 started");
+    sleep(task1_sleep).await;
+    events1.lock().unwrap().push("Task 1 completed");
+  };
+  let task2 = async move {
+    events2.lock().unwrap().push("Task 2 started");
+    sleep(task2_sleep).await;
+    events2.lock().unwrap().push("Task 2 completed");
+  };
+  Executor::new().block_on(join(task1, task2));
+  let events: Vec<&'static str> = events.lock().unwrap().clone();
+  for event in &events {
+    crate::debug!("{event}");
+  }
+  events
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_6_async::_2_futures_and_block_on::block_on;
+
+  #[test]
+  fn test_sleep_waits_for_deadline() {
+    let start: Instant = Instant::now();
+    block_on(sleep(Duration::from_millis(50)));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+  }
+
+  #[test]
+  fn test_elapsed_timer_is_ready_immediately() {
+    block_on(sleep(Duration::ZERO));
+  }
+
+  #[test]
+  fn test_concurrent_sleeps_overlap() {
+    let start: Instant = Instant::now();
+    let events: Vec<&str> = concurrent_sleeps_example(Duration::from_millis(200), Duration::from_millis(100));
+    let elapsed: Duration = start.elapsed();
+    // The order proves the overlap, without an upper bound on the (machine-dependent) wall-clock time: run one after
+    // the other, task 1 would complete before task 2 starts. Task 2, sleeping less, completes first.
+    assert_eq!(events, vec!["Task 1 started", "Task 2 started", "Task 2 completed", "Task 1 completed"]);
+    assert!(elapsed >= Duration::from_millis(200));
+  }
+}
@@ -0,0 +1,203 @@
+// -----------------------------------------------
+// # ASYNC CHANNELS
+//
+// A channel is a queue with a sending half and a receiving half, used to pass values between tasks.
+// Unlike `std::sync::mpsc::Receiver::recv`, which blocks the whole thread until a value arrives, receiving from an
+// async channel is a future: a task awaiting an empty channel is suspended, and other tasks keep running on the thread.
+//
+// This channel is unbounded and multi-producer, single-consumer:
+//   - `Sender::send` never waits, so it is a plain method. `Sender` can be cloned to have many producers.
+//   - `Receiver::recv` is async. It returns `Some(value)`, or `None` once every `Sender` has been dropped and the
+//     queue is empty, which is how the consumer knows no more values will come.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct Shared<T> {
+  queue: VecDeque<T>,
+  senders: usize,
+  receiver_alive: bool,
+  // The waker of the task awaiting `recv`, if it found the queue empty.
+  receiver_waker: Option<Waker>,
+}
+
+pub struct Sender<T> {
+  shared: Arc<Mutex<Shared<T>>>,
+}
+
+pub struct Receiver<T> {
+  shared: Arc<Mutex<Shared<T>>>,
+}
+
+// Returned by `send` when the receiver has been dropped, handing the unsent value back.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+  let shared: Arc<Mutex<Shared<T>>> =
+    Arc::new(Mutex::new(Shared { queue: VecDeque::new(), senders: 1, receiver_alive: true, receiver_waker: None }));
+  (Sender { shared: Arc::clone(&shared) }, Receiver { shared })
+}
+
+impl<T> Sender<T> {
+  pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+    let mut shared = self.shared.lock().unwrap();
+    if !shared.receiver_alive {
+      return Err(SendError(value));
+    }
+    shared.queue.push_back(value);
+    if let Some(waker) = shared.receiver_waker.take() {
+      waker.wake();
+    }
+    Ok(())
+  }
+}
+
+impl<T> Clone for Sender<T> {
+  fn clone(&self) -> Self {
+    self.shared.lock().unwrap().senders += 1;
+    Sender { shared: Arc::clone(&self.shared) }
+  }
+}
+
+impl<T> Drop for Sender<T> {
+  fn drop(&mut self) {
+    let mut shared = self.shared.lock().unwrap();
+    shared.senders -= 1;
+    // The last sender wakes the receiver, so that a pending `recv` can return `None`.
+    if shared.senders == 0 {
+      if let Some(waker) = shared.receiver_waker.take() {
+        waker.wake();
+      }
+    }
+  }
+}
+
+impl<T> Receiver<T> {
+  // Takes `&mut self`, so only one `recv` future can exist at a time, and so only one waker needs to be stored.
+  pub fn recv(&mut self) -> Recv<'_, T> {
+    Recv { receiver: self }
+  }
+}
+
+impl<T> Drop for Receiver<T> {
+  fn drop(&mut self) {
+    self.shared.lock().unwrap().receiver_alive = false;
+  }
+}
+
+pub struct Recv<'a, T> {
+  receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Recv<'_, T> {
+  type Output = Option<T>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+    let mut shared = self.receiver.shared.lock().unwrap();
+    if let Some(value) = shared.queue.pop_front() {
+      Poll::Ready(Some(value))
+    } else if shared.senders == 0 {
+      Poll::Ready(None)
+    } else {
+      shared.receiver_waker = Some(cx.waker().clone());
+      Poll::Pending
+    }
+  }
+}
+
+// -----------------------------------------------
+// ## Example: Producers and a Consumer
+//
+// Two producer tasks send values at different paces while a consumer task sums them.
+// The consumer's loop ends when `recv` returns `None`, i.e. once both producers have finished and dropped their senders.
+pub fn producer_consumer_example() -> Vec<u32> {
+  use super::_3_executor::{Executor, JoinHandle};
+  use super::_4_timer::sleep;
+  use std::time::Duration;
+
+  let executor: Executor = Executor::new();
+  let (tx, mut rx) = channel::<u32>();
+  for (start, pause) in [(0, 10), (100, 15)] {
+    let tx: Sender<u32> = tx.clone();
+    executor.spawn(async move {
+      for i in start..start + 3 {
+        tx.send(i).unwrap();
+        sleep(Duration::from_millis(pause)).await;
+      }
+    });
+  }
+  // Only the producers' clones should keep the channel open.
+  drop(tx);
+  let consumer: JoinHandle<Vec<u32>> = executor.spawn(async move {
+    let mut received: Vec<u32> = Vec::new();
+    while let Some(value) = rx.recv().await {
+      received.push(value);
+    }
+    received
+  });
+  executor.run();
+  super::_2_futures_and_block_on::block_on(consumer)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::_6_async::_2_futures_and_block_on::block_on;
+  use crate::_6_async::_3_executor::join;
+
+  #[test]
+  fn test_values_are_received_in_order() {
+    let (tx, mut rx) = channel();
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.recv()), Some(1));
+    assert_eq!(block_on(rx.recv()), Some(2));
+    assert_eq!(block_on(rx.recv()), None);
+  }
+
+  #[test]
+  fn test_recv_waits_for_send() {
+    let (tx, mut rx) = channel();
+    let consumer = async move { rx.recv().await };
+    let producer = async move {
+      crate::_6_async::_2_futures_and_block_on::yield_now().await;
+      tx.send("hello").unwrap();
+    };
+    assert_eq!(block_on(join(consumer, producer)), (Some("hello"), ()));
+  }
+
+  #[test]
+  fn test_recv_ends_when_all_senders_drop() {
+    let (tx, mut rx) = channel::<i32>();
+    let tx2: Sender<i32> = tx.clone();
+    drop(tx);
+    tx2.send(5).unwrap();
+    drop(tx2);
+    assert_eq!(block_on(rx.recv()), Some(5));
+    assert_eq!(block_on(rx.recv()), None);
+  }
+
+  #[test]
+  fn test_send_after_receiver_dropped() {
+    let (tx, rx) = channel();
+    drop(rx);
+    assert_eq!(tx.send(3), Err(SendError(3)));
+  }
+
+  #[test]
+  fn test_producer_consumer_example() {
+    let mut received: Vec<u32> = producer_consumer_example();
+    assert_eq!(received.len(), 6);
+    // Each producer's values arrive in the order they were sent ...
+    let firsts: Vec<u32> = received.iter().copied().filter(|v| *v < 100).collect();
+    assert_eq!(firsts, vec![0, 1, 2]);
+    // ... and all of them arrive.
+    received.sort();
+    assert_eq!(received, vec![0, 1, 2, 100, 101, 102]);
+  }
+}
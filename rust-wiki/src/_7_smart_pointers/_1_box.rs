@@ -0,0 +1,126 @@
+// -----------------------------------------------
+// # SMART POINTERS
+//
+// A pointer is a variable that stores an address, e.g. a reference `&T`.
+// A SMART POINTER is a struct that acts like a pointer (it implements `Deref`, so `*p` and method calls reach the
+// value it points to) but also manages that value (it usually implements `Drop`, to free it).
+// `String` and `Vec<T>` are smart pointers, managing a buffer on the heap.
+//
+// This chapter covers the smart pointers of the standard library:
+//   - `Box<T>` (here):                 a single owner of a value on the heap.
+//   - `Rc<T>`, `Weak<T>` (_2_rc_and_weak.rs):   many owners of a value, counted at runtime.
+//   - `Cell<T>`, `RefCell<T>`, `OnceCell<T>` (_3_cells.rs): mutation through a shared reference.
+//   - `Arc<T>` (_4_arc.rs):            `Rc<T>` that can be shared across threads.
+//   - `Cow<T>` (_5_cow.rs):            borrow a value, and only clone it once it needs to be changed.
+
+// -----------------------------------------------
+// ## Box<T>
+//
+// `Box::new(v)` moves `v` to the heap. The box itself is just { ptr }, and is the single owner of the value:
+// when the box is dropped, so is the value, and its heap memory is freed.
+//
+//    let b: Box<i32> = Box::new(5);
+//
+//    Stack                Heap
+//    +-----------+        +-----+
+//    | b: { ptr }| -----> |  5  |
+//    +-----------+        +-----+
+pub fn box_basics() -> i32 {
+  let b: Box<i32> = Box::new(5);
+  // A box derefs to its value, like a reference would.
+  let doubled: i32 = *b * 2;
+  // Moving a box moves only the pointer; the value stays where it is on the heap.
+  let c: Box<i32> = b;
+  doubled + *c
+} // <<-- c goes out of scope, and the 5 on the heap is freed.
+
+// -----------------------------------------------
+// ## Recursive Types
+//
+// The compiler must know the size of every type. A type that contains itself would be infinitely large:
+//
+//    enum List {
+//      Cons(i32, List),   // Error! recursive type `List` has infinite size
+//      Nil,
+//    }
+//
+// A `Box<List>` however is always the size of one pointer, whatever the list it points to. So boxing the recursive
+// part gives `List` a known size: that of an i32 plus a pointer (plus the tag saying which variant it is).
+//
+// This "cons list", from functional languages, holds a value and the rest of the list, until `Nil`:
+//
+//    Cons(1, Box) --> Cons(2, Box) --> Cons(3, Box) --> Nil
+#[derive(PartialEq, Debug)]
+pub enum List {
+  Cons(i32, Box<List>),
+  Nil,
+}
+
+pub fn create_empty_list() -> List {
+  List::Nil
+}
+
+pub fn create_non_empty_list() -> List {
+  List::Cons(1, Box::new(List::Cons(2, Box::new(List::Cons(3, Box::new(List::Nil))))))
+}
+
+impl List {
+  // Prepends a value, moving the current list into a new box.
+  pub fn prepend(self, value: i32) -> List {
+    List::Cons(value, Box::new(self))
+  }
+
+  // Follows the boxes to the end. `&**rest` derefs the `&Box<List>` to a `&List`.
+  pub fn sum(&self) -> i32 {
+    let mut total: i32 = 0;
+    let mut current: &List = self;
+    while let List::Cons(value, rest) = current {
+      total += value;
+      current = rest;
+    }
+    total
+  }
+}
+
+// -----------------------------------------------
+// ## Box<dyn Trait>
+//
+// A box can also hold a value whose type (and hence size) is only known at runtime, e.g. one of several types
+// implementing a trait. The box then stores { ptr, vtable }: the vtable finds the right method for the actual type.
+pub fn boxed_closures() -> Vec<i32> {
+  let operations: Vec<Box<dyn Fn(i32) -> i32>> = vec![Box::new(|x| x + 1), Box::new(|x| x * 10), Box::new(i32::abs)];
+  operations.iter().map(|op| op(-4)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_box_basics() {
+    assert_eq!(box_basics(), 15);
+  }
+
+  #[test]
+  fn test_create_empty_list() {
+    assert_eq!(create_empty_list(), List::Nil);
+  }
+
+  #[test]
+  fn test_create_non_empty_list() {
+    assert_ne!(create_empty_list(), create_non_empty_list());
+    assert_eq!(create_non_empty_list().sum(), 6);
+    assert_eq!(create_empty_list().prepend(3).prepend(2).prepend(1), create_non_empty_list());
+  }
+
+  #[test]
+  fn test_list_has_a_known_size() {
+    // One i32 and one pointer; the null-pointer niche of `Box` leaves room for the `Nil` tag.
+    assert_eq!(std::mem::size_of::<List>(), 2 * std::mem::size_of::<usize>());
+  }
+
+  #[test]
+  fn test_boxed_closures() {
+    assert_eq!(boxed_closures(), vec![-3, -40, 4]);
+  }
+}
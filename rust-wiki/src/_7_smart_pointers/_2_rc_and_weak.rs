@@ -0,0 +1,223 @@
+// -----------------------------------------------
+// # Rc<T>: REFERENCE COUNTING
+//
+// Ownership rule 1 says each value has a single owner. Some data however has no single natural owner, e.g. a sun
+// that many planets revolve around: it should live as long as *any* planet still needs it.
+//
+// `Rc<T>` ("reference counted") allows many owners of one value on the heap:
+//   - `Rc::new(v)` moves `v` to the heap, next to a STRONG COUNT of 1.
+//   - `Rc::clone(&rc)` does not clone the value; it returns another pointer to it and increments the count.
+//   - Dropping an `Rc` decrements the count. The value is dropped when the count reaches 0.
+//
+//    let sun = Rc::new(Sun);
+//    let mercury = Planet::Mercury(Rc::clone(&sun));
+//
+//    Stack                            Heap
+//    +------------------+             +-----------------+
+//    | sun: { ptr }     | ----------> | strong: 2       |
+//    +------------------+      /      | weak:   1 (*)   |
+//    | mercury: { ptr } | ----/       | value:  Sun     |
+//    +------------------+             +-----------------+
+//    (*) all strong pointers together hold one weak count, released when the value is dropped.
+//
+// An `Rc` only gives shared (`&T`) access to the value, as there may be other owners reading it.
+// (To mutate it, combine it with a cell: `Rc<RefCell<T>>`, see _3_cells.rs.)
+// `Rc` is not thread-safe: its count is a plain integer. `Arc` (_4_arc.rs) is the thread-safe version.
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+#[derive(Debug)]
+pub struct Sun;
+
+#[derive(Debug)]
+pub enum Planet {
+  Mercury(Rc<Sun>),
+  Venus(Rc<Sun>),
+  Earth(Rc<Sun>),
+}
+
+impl Planet {
+  pub fn details(&self) -> String {
+    format!("Hi from {self:?}!")
+  }
+}
+
+// -----------------------------------------------
+// ## Example: Reference Count Trace
+//
+// Records the strong count of the sun after each step, i.e. what `Rc::strong_count(&sun)` would print.
+pub fn planets_trace() -> Vec<(&'static str, usize)> {
+  let mut trace: Vec<(&'static str, usize)> = Vec::new();
+  let sun: Rc<Sun> = Rc::new(Sun);
+  trace.push(("Rc::new(Sun)", Rc::strong_count(&sun)));
+
+  let mercury: Planet = Planet::Mercury(Rc::clone(&sun));
+  trace.push(("mercury = Mercury(Rc::clone(&sun))", Rc::strong_count(&sun)));
+  let venus: Planet = Planet::Venus(Rc::clone(&sun));
+  trace.push(("venus = Venus(Rc::clone(&sun))", Rc::strong_count(&sun)));
+  {
+    let earth: Planet = Planet::Earth(Rc::clone(&sun));
+    trace.push(("earth = Earth(Rc::clone(&sun))", Rc::strong_count(&sun)));
+    earth.details();
+  } // <<-- earth is dropped, and with it its Rc.
+  trace.push(("end of earth's scope", Rc::strong_count(&sun)));
+
+  drop(venus);
+  trace.push(("drop(venus)", Rc::strong_count(&sun)));
+  drop(mercury);
+  trace.push(("drop(mercury)", Rc::strong_count(&sun)));
+
+  for (step, count) in &trace {
+    crate::debug!("{step:<40} strong_count = {count}");
+  }
+  trace
+}
+
+// -----------------------------------------------
+// ## Weak<T>: Breaking Cycles
+//
+// A cycle of `Rc`s never reaches a count of 0, so it is never dropped: a memory leak.
+// E.g. a tree where each child points to its parent, and each parent to its children.
+//
+// `Weak<T>` is a non-owning pointer to the value of an `Rc`:
+//   - `Rc::downgrade(&rc)` creates one, incrementing the WEAK COUNT. The weak count does not keep the value alive.
+//   - `weak.upgrade()` returns `Some(Rc<T>)` if the value is still alive, `None` if it has been dropped.
+//
+// The rule for trees: owners point down with `Rc` (a parent owns its children), and back up with `Weak`
+// (a child refers to its parent, but does not own it).
+//
+//               +-----------------+
+//               | branch          |
+//               |  parent: (none) |
+//               |  children: ---------+
+//               +-----------------+   | Rc
+//                       ^             v
+//                       |     +-----------------+
+//                       +----------- parent     |
+//                 Weak          | leaf          |
+//                               |  children: [] |
+//                               +-----------------+
+#[derive(Debug)]
+pub struct Node {
+  pub value: i32,
+  // A cell lets us set the parent after the node has been shared, see _3_cells.rs.
+  pub parent: RefCell<Weak<Node>>,
+  pub children: RefCell<Vec<Rc<Node>>>,
+}
+
+impl Node {
+  pub fn new(value: i32) -> Rc<Node> {
+    Rc::new(Node { value, parent: RefCell::new(Weak::new()), children: RefCell::new(Vec::new()) })
+  }
+
+  pub fn add_child(parent: &Rc<Node>, child: Rc<Node>) {
+    *child.parent.borrow_mut() = Rc::downgrade(parent);
+    parent.children.borrow_mut().push(child);
+  }
+
+  pub fn parent(&self) -> Option<Rc<Node>> {
+    self.parent.borrow().upgrade()
+  }
+
+  // The values from this node up to the root.
+  pub fn path_to_root(self: &Rc<Self>) -> Vec<i32> {
+    let mut path: Vec<i32> = vec![self.value];
+    let mut current: Option<Rc<Node>> = self.parent();
+    while let Some(node) = current {
+      path.push(node.value);
+      current = node.parent();
+    }
+    path
+  }
+}
+
+// Records the (strong, weak) counts of a leaf and its parent as the tree is built and the parent is dropped.
+pub fn weak_parent_trace() -> Vec<String> {
+  let mut trace: Vec<String> = Vec::new();
+  let counts = |name: &str, node: &Rc<Node>| {
+    format!("{name}: strong = {}, weak = {}", Rc::strong_count(node), Rc::weak_count(node))
+  };
+
+  let leaf: Rc<Node> = Node::new(3);
+  trace.push(counts("leaf", &leaf));
+  {
+    let branch: Rc<Node> = Node::new(5);
+    Node::add_child(&branch, Rc::clone(&leaf));
+    // branch owns leaf (leaf: strong 2), leaf refers back to branch (branch: weak 1).
+    trace.push(counts("branch", &branch));
+    trace.push(counts("leaf", &leaf));
+    trace.push(format!("leaf parent = {:?}", leaf.parent().map(|parent| parent.value)));
+  } // <<-- branch's strong count reaches 0, so it is dropped (along with its Rc to leaf), despite leaf's Weak.
+  trace.push(format!("leaf parent = {:?}", leaf.parent().map(|parent| parent.value)));
+  trace.push(counts("leaf", &leaf));
+
+  for line in &trace {
+    crate::debug!("{line}");
+  }
+  trace
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_planets_trace() {
+    let counts: Vec<usize> = planets_trace().into_iter().map(|(_, count)| count).collect();
+    assert_eq!(counts, vec![1, 2, 3, 4, 3, 2, 1]);
+  }
+
+  #[test]
+  fn test_rc_clone_shares_the_value() {
+    let sun: Rc<Sun> = Rc::new(Sun);
+    let planet: Planet = Planet::Mercury(Rc::clone(&sun));
+    let Planet::Mercury(planet_sun) = &planet else { unreachable!() };
+    assert!(Rc::ptr_eq(&sun, planet_sun));
+    assert_eq!(planet.details(), "Hi from Mercury(Sun)!");
+  }
+
+  #[test]
+  fn test_weak_parent_trace() {
+    assert_eq!(
+      weak_parent_trace(),
+      vec![
+        "leaf: strong = 1, weak = 0",
+        "branch: strong = 1, weak = 1",
+        "leaf: strong = 2, weak = 0",
+        "leaf parent = Some(5)",
+        "leaf parent = None",
+        "leaf: strong = 1, weak = 0",
+      ]
+    );
+  }
+
+  #[test]
+  fn test_path_to_root() {
+    let root: Rc<Node> = Node::new(1);
+    let middle: Rc<Node> = Node::new(2);
+    let leaf: Rc<Node> = Node::new(3);
+    Node::add_child(&root, Rc::clone(&middle));
+    Node::add_child(&middle, Rc::clone(&leaf));
+    assert_eq!(leaf.path_to_root(), vec![3, 2, 1]);
+    assert_eq!(root.children.borrow()[0].children.borrow()[0].value, 3);
+  }
+
+  #[test]
+  fn test_rc_cycle_leaks() {
+    // Two nodes owning each other with strong pointers are never dropped: each keeps the other's count above 0.
+    let a: Rc<Node> = Node::new(1);
+    let b: Rc<Node> = Node::new(2);
+    a.children.borrow_mut().push(Rc::clone(&b));
+    b.children.borrow_mut().push(Rc::clone(&a));
+    let weak_a: Weak<Node> = Rc::downgrade(&a);
+    drop(a);
+    drop(b);
+    assert!(weak_a.upgrade().is_some());
+    // Break the cycle by hand so the test does not leak.
+    let a: Rc<Node> = weak_a.upgrade().unwrap();
+    a.children.borrow_mut().clear();
+    drop(a);
+    assert!(weak_a.upgrade().is_none());
+  }
+}
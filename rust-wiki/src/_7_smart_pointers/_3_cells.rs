@@ -0,0 +1,169 @@
+// -----------------------------------------------
+// # CELLS: INTERIOR MUTABILITY
+//
+// The borrowing rules say a value can be mutated through a `&mut T`, and never through a `&T`.
+// That is too strict when a value is shared (e.g. through an `Rc`, which only ever hands out `&T`) but still needs
+// to change, e.g. a counter of how often it was read, or a tree node whose parent is set after it was shared.
+//
+// A CELL is a type whose value can be changed through a shared reference `&Cell<T>`: this is INTERIOR MUTABILITY.
+// It stays safe by restricting *how* the value is accessed, in a different way for each cell:
+//   - `Cell<T>`:     values are only ever copied or moved in and out, never borrowed, so no reference to the inside
+//                    can be invalidated by a change.
+//   - `RefCell<T>`:  hands out borrows, but checks the borrowing rules at runtime instead of at compile time,
+//                    panicking if they are broken.
+//   - `OnceCell<T>`: can be written only once; after that it hands out `&T` forever.
+//
+// None of them are thread-safe (they are not `Sync`). Their thread-safe counterparts are `Mutex`/`RwLock`,
+// the atomics, and `OnceLock` (see _8_concurrency).
+
+use std::cell::{Cell, OnceCell, RefCell};
+use std::rc::Rc;
+
+// -----------------------------------------------
+// ## Cell<T>
+//
+// `get` copies the value out (so needs `T: Copy`), `set` and `replace` move a new one in, `take` swaps in the default.
+pub struct Counter {
+  pub name: String,
+  reads: Cell<u32>,
+}
+
+impl Counter {
+  pub fn new(name: &str) -> Counter {
+    Counter { name: name.to_string(), reads: Cell::new(0) }
+  }
+
+  // Takes `&self`, yet updates the count.
+  pub fn read(&self) -> &str {
+    self.reads.set(self.reads.get() + 1);
+    &self.name
+  }
+
+  pub fn reads(&self) -> u32 {
+    self.reads.get()
+  }
+}
+
+// -----------------------------------------------
+// ## RefCell<T>
+//
+// `borrow()` returns a `Ref<T>` (acting as a `&T`) and `borrow_mut()` a `RefMut<T>` (acting as a `&mut T`).
+// The RefCell counts the borrows that are alive, and applies the usual rules when a new one is requested:
+//   - any number of `borrow()`s, or
+//   - exactly one `borrow_mut()`.
+// Breaking a rule panics (`try_borrow` and `try_borrow_mut` return an error instead).
+// The borrows end when the `Ref`/`RefMut` guards are dropped.
+// (_19_interior_mutability/_2_tracked_ref_cell.rs logs each of these steps, with the number of borrows alive.)
+//
+// `Rc<RefCell<T>>` is the usual way to have many owners of a value that they can all change.
+pub fn shared_mutable_list() -> Vec<i32> {
+  let list: Rc<RefCell<Vec<i32>>> = Rc::new(RefCell::new(vec![1]));
+  let owner_a: Rc<RefCell<Vec<i32>>> = Rc::clone(&list);
+  let owner_b: Rc<RefCell<Vec<i32>>> = Rc::clone(&list);
+
+  owner_a.borrow_mut().push(2);
+  owner_b.borrow_mut().push(3);
+  {
+    let first: std::cell::Ref<Vec<i32>> = list.borrow();
+    let second: std::cell::Ref<Vec<i32>> = list.borrow();   // Allowed: many shared borrows.
+    assert_eq!(first.len(), second.len());
+    assert!(list.try_borrow_mut().is_err());                 // Not allowed while `first` and `second` are alive.
+  } // <<-- first and second are dropped, so the list can be mutably borrowed again.
+  assert!(list.try_borrow_mut().is_ok());
+
+  let result: Vec<i32> = list.borrow().clone();
+  result
+}
+
+// Two overlapping `borrow_mut`s compile fine, but panic at runtime with "already borrowed: BorrowMutError".
+pub fn double_borrow_mut_panics() {
+  let cell: RefCell<i32> = RefCell::new(0);
+  let mut first = cell.borrow_mut();
+  let mut second = cell.borrow_mut();   // panics
+  *first += 1;
+  *second += 1;
+}
+
+// -----------------------------------------------
+// ## OnceCell<T>
+//
+// Starts empty, and is set at most once, through a shared reference. Useful to compute a value lazily and cache it.
+// `get_or_init(f)` runs `f` only on the first call; every call returns a `&T` to the same value.
+pub struct Config {
+  pub source: String,
+  parsed: OnceCell<Vec<(String, String)>>,
+  pub parse_count: Cell<u32>,
+}
+
+impl Config {
+  pub fn new(source: &str) -> Config {
+    Config { source: source.to_string(), parsed: OnceCell::new(), parse_count: Cell::new(0) }
+  }
+
+  pub fn entries(&self) -> &[(String, String)] {
+    self.parsed.get_or_init(|| {
+      self.parse_count.set(self.parse_count.get() + 1);
+      self
+        .source
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+    })
+  }
+
+  pub fn get(&self, key: &str) -> Option<&str> {
+    self.entries().iter().find(|(k, _)| k == key).map(|(_, value)| value.as_str())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_cell_counts_reads_through_shared_refs() {
+    let counter: Counter = Counter::new("c");
+    let (a, b): (&Counter, &Counter) = (&counter, &counter);
+    a.read();
+    b.read();
+    assert_eq!(counter.reads(), 2);
+  }
+
+  #[test]
+  fn test_cell_replace_and_take() {
+    let cell: Cell<i32> = Cell::new(1);
+    assert_eq!(cell.replace(2), 1);
+    assert_eq!(cell.take(), 2);
+    assert_eq!(cell.get(), 0);
+  }
+
+  #[test]
+  fn test_shared_mutable_list() {
+    assert_eq!(shared_mutable_list(), vec![1, 2, 3]);
+  }
+
+  #[test]
+  #[should_panic(expected = "already")]
+  fn test_double_borrow_mut_panics() {
+    double_borrow_mut_panics();
+  }
+
+  #[test]
+  fn test_once_cell_initialises_once() {
+    let config: Config = Config::new("name = wiki\nedition = 2021");
+    assert_eq!(config.get("name"), Some("wiki"));
+    assert_eq!(config.get("edition"), Some("2021"));
+    assert_eq!(config.get("missing"), None);
+    assert_eq!(config.parse_count.get(), 1);
+  }
+
+  #[test]
+  fn test_once_cell_set() {
+    let cell: OnceCell<i32> = OnceCell::new();
+    assert_eq!(cell.get(), None);
+    assert_eq!(cell.set(1), Ok(()));
+    assert_eq!(cell.set(2), Err(2));
+    assert_eq!(cell.get(), Some(&1));
+  }
+}
@@ -0,0 +1,78 @@
+// -----------------------------------------------
+// # Arc<T>: ATOMIC REFERENCE COUNTING
+//
+// `Arc<T>` is `Rc<T>` with an atomic count, so that clones of it can be sent to, and dropped on, other threads.
+// (`Rc` is not `Send`: two threads changing its plain integer count at once could lose an update, and free the
+// value while it is still in use.) Atomic operations are a little slower, which is why `Rc` exists at all.
+//
+// Like `Rc`, an `Arc` only gives shared access. To mutate the shared value from several threads, wrap it in a
+// `Mutex` or `RwLock`, or use atomics: see _8_concurrency.
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+// -----------------------------------------------
+// ## Example: Sharing Read-only Data Between Threads
+//
+// Each of 8 threads sums every eighth number, starting at its offset. Each thread owns a clone of the `Arc`, so the
+// numbers live until the last thread is done with them; without the `Arc`, the vector would have to outlive every
+// thread, or be copied into each of them.
+pub fn sums_by_offset() -> Vec<u32> {
+  let numbers: Vec<u32> = (0..100u32).collect();
+  let shared_numbers: Arc<Vec<u32>> = Arc::new(numbers);
+
+  let mut join_handles: Vec<JoinHandle<u32>> = Vec::new();
+  for offset in 0..8 {
+    let child_numbers: Arc<Vec<u32>> = Arc::clone(&shared_numbers);
+    let handle = thread::spawn(move || {
+      let sum: u32 = child_numbers.iter().filter(|&&n| n % 8 == offset).sum();
+      println!("Sum of offset {offset} is {sum}");
+      sum
+    });
+    join_handles.push(handle);
+  }
+
+  join_handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+}
+
+// The count is shared by all threads: here, each thread's clone is still alive when we read it.
+pub fn arc_count_across_threads() -> usize {
+  let shared: Arc<String> = Arc::new(String::from("shared"));
+  let clones: Vec<Arc<String>> = (0..3).map(|_| Arc::clone(&shared)).collect();
+  let handles: Vec<JoinHandle<Arc<String>>> =
+    clones.into_iter().map(|clone| thread::spawn(move || clone)).collect();
+  // Each thread returns its clone, keeping it alive.
+  let returned: Vec<Arc<String>> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+  let count: usize = Arc::strong_count(&shared);
+  drop(returned);
+  count
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_sums_by_offset() {
+    let sums: Vec<u32> = sums_by_offset();
+    assert_eq!(sums.len(), 8);
+    assert_eq!(sums.iter().sum::<u32>(), (0..100).sum());
+    // 0 + 8 + ... + 96
+    assert_eq!(sums[0], (0..100).step_by(8).sum());
+  }
+
+  #[test]
+  fn test_arc_count_across_threads() {
+    assert_eq!(arc_count_across_threads(), 4);
+  }
+
+  #[test]
+  fn test_arc_try_unwrap() {
+    // The value can be taken back out of an `Arc` once it is the only owner.
+    let a: Arc<i32> = Arc::new(5);
+    let b: Arc<i32> = Arc::clone(&a);
+    let a: Arc<i32> = Arc::try_unwrap(a).unwrap_err();
+    drop(b);
+    assert_eq!(Arc::try_unwrap(a), Ok(5));
+  }
+}
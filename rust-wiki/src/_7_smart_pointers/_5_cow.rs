@@ -0,0 +1,98 @@
+// -----------------------------------------------
+// # Cow<T>: CLONE ON WRITE
+//
+// `Cow<'a, B>` holds either a borrowed `&'a B` or an owned value (`B::Owned`, e.g. `Vec<T>` for `[T]`, `String` for
+// `str`):
+//
+//    pub enum Cow<'a, B: ?Sized + ToOwned> {
+//      Borrowed(&'a B),
+//      Owned(<B as ToOwned>::Owned),
+//    }
+//
+// It derefs to `&B` either way, so reading costs nothing extra. `to_mut()` returns a `&mut B::Owned`, cloning the
+// borrowed data into an owned value first if needed: the data is only cloned if, and when, it is actually changed.
+// Typical use: a function that usually returns its input unchanged, but sometimes needs to fix it up.
+
+use std::borrow::Cow;
+
+// Replaces each negative number by its absolute value, cloning the input only if there is one. `i32::MIN` has no
+// positive counterpart, so it saturates to `i32::MAX` (plain `-value` would overflow).
+pub fn abs_all(input: &mut Cow<[i32]>) {
+  for ind in 0..input.len() {
+    let value: i32 = input[ind];
+    if value < 0 {
+      // Clones into a vector if not already owned.
+      input.to_mut()[ind] = value.saturating_abs();
+    }
+  }
+}
+
+// The same idea with strings: returns the input itself unless it contains tabs.
+pub fn expand_tabs(input: &str) -> Cow<'_, str> {
+  if input.contains('\t') {
+    Cow::Owned(input.replace('\t', "    "))
+  } else {
+    Cow::Borrowed(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_reference_mutation() {
+    // Clone occurs because `input` needs to be mutated.
+    let vec: Vec<i32> = vec![-1, 0, 1];
+    let mut input: Cow<[i32]> = Cow::from(&vec[..]);
+    abs_all(&mut input);
+    assert!(matches!(input, Cow::Owned(_)));
+    assert_eq!(*input, [1, 0, 1]);
+    // The original is untouched.
+    assert_eq!(vec, [-1, 0, 1]);
+  }
+
+  #[test]
+  fn test_reference_no_mutation() {
+    // No clone occurs because `input` doesn't need to be mutated.
+    let vec: Vec<i32> = vec![0, 1, 2];
+    let mut input: Cow<[i32]> = Cow::from(&vec[..]);
+    abs_all(&mut input);
+    assert!(matches!(input, Cow::Borrowed(_)));
+  }
+
+  #[test]
+  fn test_owned_no_mutation() {
+    // An owned input stays owned, and is not cloned.
+    let vec: Vec<i32> = vec![0, 1, 2];
+    let mut input: Cow<[i32]> = Cow::from(vec);
+    abs_all(&mut input);
+    assert!(matches!(input, Cow::Owned(_)));
+  }
+
+  #[test]
+  fn test_owned_mutation() {
+    // `to_mut()` on an owned value returns a reference to it; again nothing is cloned.
+    let vec: Vec<i32> = vec![-1, 0, 1];
+    let ptr: *const i32 = vec.as_ptr();
+    let mut input: Cow<[i32]> = Cow::from(vec);
+    abs_all(&mut input);
+    assert!(matches!(input, Cow::Owned(_)));
+    assert_eq!(input.as_ptr(), ptr);
+  }
+
+  #[test]
+  fn test_expand_tabs() {
+    assert!(matches!(expand_tabs("no tabs"), Cow::Borrowed("no tabs")));
+    let expanded: Cow<str> = expand_tabs("a\tb");
+    assert!(matches!(expanded, Cow::Owned(_)));
+    assert_eq!(expanded, "a    b");
+  }
+
+  #[test]
+  fn test_abs_of_min_saturates() {
+    let mut input: Cow<[i32]> = Cow::from(&[i32::MIN, -5][..]);
+    abs_all(&mut input);
+    assert_eq!(*input, [i32::MAX, 5]);
+  }
+}
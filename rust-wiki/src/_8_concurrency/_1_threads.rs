@@ -0,0 +1,123 @@
+// -----------------------------------------------
+// # THREADS
+//
+// A thread is an independent sequence of execution within a process. All threads of a process share its memory
+// (statics, the heap), but each has its own stack. The OS scheduler decides when each thread runs, so the order in
+// which the instructions of different threads happen is not known in advance.
+//
+// This chapter covers:
+//   - spawning and joining threads, and scoped threads (here)
+//   - passing messages through channels (_2_channels.rs)
+//   - sharing state behind locks: Mutex, RwLock, Condvar (_3_locks.rs)
+//   - atomics and memory orderings (_4_atomics.rs)
+//   - barriers, and how locks can deadlock (_5_barriers_and_deadlocks.rs)
+//
+// Rust prevents DATA RACES (two threads accessing the same memory at once, at least one of them writing) at compile
+// time, through two marker traits that the compiler implements automatically:
+//   - `Send`: a value of the type can be moved to another thread. (`Rc<T>` is not `Send`.)
+//   - `Sync`: a `&T` can be shared with other threads. (`Cell<T>` and `RefCell<T>` are not `Sync`.)
+// It does not prevent deadlocks or other logic errors, like the order of operations not being what was expected.
+
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// -----------------------------------------------
+// ## Spawning and Joining
+//
+// `thread::spawn(f)` runs the closure `f` on a new thread, and returns a `JoinHandle`.
+// `handle.join()` waits for the thread to finish and returns what `f` returned, or `Err` if the thread panicked.
+//
+// The closure must be `'static`: the new thread may outlive the function that spawned it, so the closure cannot
+// borrow its locals. Hence `move` closures, which take ownership of what they capture.
+pub fn spawn_and_join() -> Vec<u64> {
+  let mut handles: Vec<JoinHandle<u64>> = Vec::new();
+  for i in 0..5u64 {
+    // `i` is copied into the closure.
+    let handle: JoinHandle<u64> = thread::spawn(move || {
+      thread::sleep(Duration::from_millis(10 * (5 - i)));
+      println!("thread {i} done");
+      i * i
+    });
+    handles.push(handle);
+  }
+  // The threads finish in reverse order (the later ones sleep less), but joining them in order collects their
+  // results in order.
+  handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+}
+
+// A panic ends only the thread it happens in. Its `join` returns the panic's payload as an `Err`.
+pub fn panicking_thread() -> Result<(), String> {
+  let handle: JoinHandle<()> = thread::spawn(|| panic!("something went wrong"));
+  handle.join().map_err(|payload| payload.downcast_ref::<&str>().map(|s| s.to_string()).unwrap_or_default())
+}
+
+// Captured values are moved into the thread; this fails to compile without `move`, as `v` could be dropped
+// before the thread uses it:
+//
+//    let v = vec![1, 2, 3];
+//    let handle = thread::spawn(|| println!("{v:?}"));   // Error! closure may outlive the current function
+pub fn move_into_thread() -> usize {
+  let v: Vec<i32> = vec![1, 2, 3];
+  let handle: JoinHandle<usize> = thread::spawn(move || v.len());
+  // println!("{v:?}");  // Error! `v` was moved into the thread.
+  handle.join().unwrap()
+}
+
+// -----------------------------------------------
+// ## Scoped Threads
+//
+// `thread::scope(|s| ...)` joins every thread spawned with `s.spawn` before it returns. Since the threads cannot
+// outlive the scope, they *can* borrow locals of the enclosing function: no `move`, no `Arc`.
+// The borrow checker applies as usual: many threads may share `&data`, or one may have `&mut` to a part of it.
+pub fn scoped_sum(data: &[u64], chunks: usize) -> u64 {
+  let chunk_len: usize = data.len().div_ceil(chunks.max(1)).max(1);
+  thread::scope(|s| {
+    let handles: Vec<thread::ScopedJoinHandle<u64>> =
+      data.chunks(chunk_len).map(|chunk| s.spawn(move || chunk.iter().sum())).collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+  })
+}
+
+// Each thread gets a `&mut` to a disjoint chunk, so they can all write at once.
+pub fn scoped_double_in_place(data: &mut [u64]) {
+  thread::scope(|s| {
+    for chunk in data.chunks_mut(2) {
+      s.spawn(move || chunk.iter_mut().for_each(|x| *x *= 2));
+    }
+  }); // <<-- all threads are joined here, so `data` is no longer borrowed.
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_spawn_and_join() {
+    assert_eq!(spawn_and_join(), vec![0, 1, 4, 9, 16]);
+  }
+
+  #[test]
+  fn test_panicking_thread() {
+    assert_eq!(panicking_thread(), Err(String::from("something went wrong")));
+  }
+
+  #[test]
+  fn test_move_into_thread() {
+    assert_eq!(move_into_thread(), 3);
+  }
+
+  #[test]
+  fn test_scoped_sum() {
+    let data: Vec<u64> = (1..=100).collect();
+    assert_eq!(scoped_sum(&data, 4), 5050);
+    assert_eq!(scoped_sum(&data, 1), 5050);
+    assert_eq!(scoped_sum(&[], 3), 0);
+  }
+
+  #[test]
+  fn test_scoped_double_in_place() {
+    let mut data: Vec<u64> = vec![1, 2, 3, 4, 5];
+    scoped_double_in_place(&mut data);
+    assert_eq!(data, vec![2, 4, 6, 8, 10]);
+  }
+}
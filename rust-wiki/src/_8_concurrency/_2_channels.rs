@@ -0,0 +1,153 @@
+// -----------------------------------------------
+// # CHANNELS: MESSAGE PASSING
+//
+// "Do not communicate by sharing memory; instead, share memory by communicating."
+//
+// A channel is a queue between threads: `Sender`s put values in, a `Receiver` takes them out, in order.
+// Sending a value moves it, so ownership passes from one thread to another and no two threads ever access it at once.
+//
+// `std::sync::mpsc` ("multi-producer, single-consumer") has two kinds:
+//   - `mpsc::channel()`: unbounded. `send` never blocks; if the receiver is slow, the queue grows.
+//   - `mpsc::sync_channel(n)`: bounded to `n` values. `send` blocks while the queue is full, which slows the
+//     producers down to the pace of the consumer: BACK-PRESSURE. With `n = 0`, every `send` waits for a `recv`
+//     (a "rendezvous").
+//
+// `recv` blocks until a value arrives, and returns `Err` once the queue is empty and every `Sender` is dropped.
+// So `for value in rx` receives until all producers are done.
+
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub struct Queue {
+  pub length: u32,
+  pub first_half: Vec<u32>,
+  pub second_half: Vec<u32>,
+}
+
+impl Queue {
+  pub fn new() -> Self {
+    Self { length: 10, first_half: vec![1, 2, 3, 4, 5], second_half: vec![6, 7, 8, 9, 10] }
+  }
+}
+
+impl Default for Queue {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Sends both halves of the queue from two threads. `tx` would be moved into the first thread, so the second gets
+// a clone: each producer owns a `Sender`.
+pub fn send_tx(q: Queue, tx: Sender<u32>) -> Vec<JoinHandle<()>> {
+  let tx_clone: Sender<u32> = tx.clone();
+  let first: JoinHandle<()> = thread::spawn(move || {
+    for val in q.first_half {
+      println!("Sending {val:?}");
+      tx_clone.send(val).unwrap();
+      thread::sleep(Duration::from_millis(5));
+    }
+  });
+  let second: JoinHandle<()> = thread::spawn(move || {
+    for val in q.second_half {
+      println!("Sending {val:?}");
+      tx.send(val).unwrap();
+      thread::sleep(Duration::from_millis(5));
+    }
+  });
+  vec![first, second]
+}
+
+pub fn receive_all(rx: Receiver<u32>) -> Vec<u32> {
+  // Ends once both threads are done, dropping both Senders.
+  rx.into_iter().inspect(|received| println!("Got: {received}")).collect()
+}
+
+// -----------------------------------------------
+// ## Back-pressure
+//
+// A producer sends 6 values through a channel holding at most 2, to a consumer that takes 20ms per value.
+// Both log their steps in one shared list, so the interleaving can be seen: after filling the channel, the
+// producer can only send once the consumer has made room, so it ends up paced by the consumer.
+//
+//    sent 0, sent 1, (full: waits) recv 0, sent 2, recv 1, sent 3, ...
+pub fn back_pressure_example() -> Vec<String> {
+  let log: Arc<Mutex<Vec<String>>> = Arc::default();
+  let (tx, rx): (SyncSender<u32>, Receiver<u32>) = mpsc::sync_channel(2);
+
+  let producer_log: Arc<Mutex<Vec<String>>> = Arc::clone(&log);
+  let producer: JoinHandle<()> = thread::spawn(move || {
+    for i in 0..6 {
+      tx.send(i).unwrap();
+      producer_log.lock().unwrap().push(format!("sent {i}"));
+    }
+  });
+  for i in rx {
+    thread::sleep(Duration::from_millis(20));
+    log.lock().unwrap().push(format!("recv {i}"));
+  }
+  producer.join().unwrap();
+
+  let log: Vec<String> = log.lock().unwrap().clone();
+  crate::debug!("{}", log.join(", "));
+  log
+}
+
+// `try_send` never blocks: it fails with `Full` instead, handing the value back.
+pub fn try_send_when_full() -> Vec<Result<(), TrySendError<u32>>> {
+  let (tx, _rx): (SyncSender<u32>, Receiver<u32>) = mpsc::sync_channel(2);
+  (1..=3).map(|i| tx.try_send(i)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_threads3() {
+    let (tx, rx) = mpsc::channel();
+    let queue: Queue = Queue::new();
+    let queue_length: u32 = queue.length;
+
+    send_tx(queue, tx);
+    let mut received: Vec<u32> = receive_all(rx);
+
+    assert_eq!(received.len() as u32, queue_length);
+    received.sort();
+    assert_eq!(received, (1..=10).collect::<Vec<u32>>());
+  }
+
+  #[test]
+  fn test_back_pressure_paces_the_producer() {
+    let log: Vec<String> = back_pressure_example();
+    let position = |entry: &str| log.iter().position(|e| e == entry).unwrap();
+    // The channel holds 2 values and one more can be in the consumer's hands: 3 sends can finish before the first
+    // recv is logged, but never the 4th.
+    assert!(position("sent 3") > position("recv 0"));
+    assert!(position("sent 5") > position("recv 2"));
+    assert_eq!(log.iter().filter(|e| e.starts_with("recv")).count(), 6);
+  }
+
+  #[test]
+  fn test_try_send_when_full() {
+    assert_eq!(try_send_when_full(), vec![Ok(()), Ok(()), Err(TrySendError::Full(3))]);
+  }
+
+  #[test]
+  fn test_rendezvous_channel() {
+    // With a bound of 0, nothing can be queued: `try_send` only succeeds if a receiver is already waiting.
+    let (tx, rx) = mpsc::sync_channel::<u32>(0);
+    assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+    let receiver: JoinHandle<u32> = thread::spawn(move || rx.recv().unwrap());
+    tx.send(2).unwrap();
+    assert_eq!(receiver.join().unwrap(), 2);
+  }
+
+  #[test]
+  fn test_recv_fails_when_senders_are_gone() {
+    let (tx, rx) = mpsc::channel::<u32>();
+    drop(tx);
+    assert!(rx.recv().is_err());
+  }
+}
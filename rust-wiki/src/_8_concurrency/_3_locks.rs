@@ -0,0 +1,202 @@
+// -----------------------------------------------
+// # LOCKS: SHARED STATE
+//
+// `Arc<T>` shares a value between threads, but only gives `&T`. To mutate shared state, it must be behind a lock,
+// the thread-safe counterpart of `RefCell` (see _7_smart_pointers/_3_cells.rs):
+//   - `Mutex<T>`:  `lock()` blocks until no other thread holds the lock, then returns a `MutexGuard` (acting as
+//                  `&mut T`). The lock is released when the guard is dropped.
+//   - `RwLock<T>`: many readers (`read()`) or one writer (`write()`) at a time, like the borrowing rules.
+//   - `Condvar`:   lets a thread holding a lock sleep until another thread signals that the state has changed.
+//
+// The value is *inside* the lock, so it cannot be accessed without locking: forgetting to lock is a compile error.
+//
+// POISONING: if a thread panics while holding the lock, the data may be half-updated, so the lock becomes
+// "poisoned", and later `lock()`s return `Err`. (The data can still be recovered with `into_inner` on the error.)
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// -----------------------------------------------
+// ## Mutex
+//
+// Ten threads each finish a job and count it in a shared `JobStatus`. Without the `Mutex`,
+// `status_shared.jobs_done += 1` does not compile: `Arc` only gives a shared reference.
+pub struct JobStatus {
+  pub jobs_done: u32,
+}
+
+pub fn count_jobs() -> u32 {
+  let status: Arc<Mutex<JobStatus>> = Arc::new(Mutex::new(JobStatus { jobs_done: 0 }));
+  let mut handles: Vec<JoinHandle<()>> = Vec::new();
+
+  for _ in 0..10 {
+    let status_shared: Arc<Mutex<JobStatus>> = Arc::clone(&status);
+    let handle: JoinHandle<()> = thread::spawn(move || {
+      thread::sleep(Duration::from_millis(10));
+      // The guard is a temporary, dropped (and so the lock released) at the end of the statement.
+      status_shared.lock().unwrap().jobs_done += 1;
+    });
+    handles.push(handle);
+  }
+
+  // Waiting for all jobs to complete.
+  for handle in handles {
+    handle.join().unwrap();
+  }
+
+  let jobs_done: u32 = status.lock().unwrap().jobs_done;
+  println!("Jobs done: {jobs_done}");
+  jobs_done
+}
+
+// A thread panicking while holding the lock poisons it.
+pub fn poisoned_lock() -> (bool, i32) {
+  let lock: Arc<Mutex<i32>> = Arc::new(Mutex::new(0));
+  let lock_clone: Arc<Mutex<i32>> = Arc::clone(&lock);
+  let _ = thread::spawn(move || {
+    let mut guard: MutexGuard<i32> = lock_clone.lock().unwrap();
+    *guard = 1;
+    panic!("panicked while holding the lock");
+  })
+  .join();
+
+  let poisoned: bool = lock.lock().is_err();
+  // Recover the data anyway: it holds whatever the panicking thread left in it.
+  let value: i32 = *lock.lock().unwrap_or_else(|poison| poison.into_inner());
+  (poisoned, value)
+}
+
+// -----------------------------------------------
+// ## RwLock
+//
+// A cache that many threads read at once, and one thread occasionally updates.
+// Readers block only while a writer holds the lock, and a writer waits for all readers to finish.
+pub fn read_mostly_cache() -> Vec<String> {
+  let cache: Arc<RwLock<Vec<String>>> = Arc::new(RwLock::new(vec![String::from("a")]));
+
+  let readers: Vec<JoinHandle<usize>> = (0..4)
+    .map(|_| {
+      let cache: Arc<RwLock<Vec<String>>> = Arc::clone(&cache);
+      thread::spawn(move || cache.read().unwrap().len())
+    })
+    .collect();
+  {
+    let mut writer = cache.write().unwrap();
+    writer.push(String::from("b"));
+  } // <<-- the write lock is released here.
+
+  for reader in readers {
+    // Each reader saw the cache either before or after the write, never in between.
+    let len: usize = reader.join().unwrap();
+    assert!(len == 1 || len == 2);
+  }
+  let result: Vec<String> = cache.read().unwrap().clone();
+  result
+}
+
+// -----------------------------------------------
+// ## Condvar
+//
+// A bounded queue, like `sync_channel`, built from a `Mutex` and two condition variables.
+// A `Condvar` is always used with a lock and a condition on the locked state:
+//   - `wait(guard)` atomically releases the lock and sleeps; when woken, it re-locks and returns the guard.
+//   - `notify_one()`/`notify_all()` wake waiting threads.
+// Wake-ups can be spurious, so the condition is re-checked in a loop (`wait_while` does the loop for us).
+pub struct BoundedQueue<T> {
+  items: Mutex<VecDeque<T>>,
+  capacity: usize,
+  not_empty: Condvar,
+  not_full: Condvar,
+}
+
+impl<T> BoundedQueue<T> {
+  pub fn new(capacity: usize) -> BoundedQueue<T> {
+    BoundedQueue { items: Mutex::new(VecDeque::new()), capacity, not_empty: Condvar::new(), not_full: Condvar::new() }
+  }
+
+  pub fn push(&self, item: T) {
+    let items: MutexGuard<VecDeque<T>> = self.items.lock().unwrap();
+    let mut items = self.not_full.wait_while(items, |items| items.len() >= self.capacity).unwrap();
+    items.push_back(item);
+    self.not_empty.notify_one();
+  }
+
+  pub fn pop(&self) -> T {
+    let items: MutexGuard<VecDeque<T>> = self.items.lock().unwrap();
+    let mut items = self.not_empty.wait_while(items, |items| items.is_empty()).unwrap();
+    let item: T = items.pop_front().unwrap();
+    self.not_full.notify_one();
+    item
+  }
+
+  pub fn len(&self) -> usize {
+    self.items.lock().unwrap().len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+pub fn condvar_example() -> Vec<u32> {
+  let queue: Arc<BoundedQueue<u32>> = Arc::new(BoundedQueue::new(2));
+  let producer_queue: Arc<BoundedQueue<u32>> = Arc::clone(&queue);
+  let producer: JoinHandle<()> = thread::spawn(move || {
+    for i in 0..10 {
+      producer_queue.push(i);
+      assert!(producer_queue.len() <= 2);
+    }
+  });
+  let received: Vec<u32> = (0..10).map(|_| queue.pop()).collect();
+  producer.join().unwrap();
+  received
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_count_jobs() {
+    assert_eq!(count_jobs(), 10);
+  }
+
+  #[test]
+  fn test_poisoned_lock() {
+    assert_eq!(poisoned_lock(), (true, 1));
+  }
+
+  #[test]
+  fn test_read_mostly_cache() {
+    assert_eq!(read_mostly_cache(), vec!["a", "b"]);
+  }
+
+  #[test]
+  fn test_many_readers_at_once() {
+    let lock: RwLock<i32> = RwLock::new(5);
+    let r1 = lock.read().unwrap();
+    let r2 = lock.read().unwrap();
+    assert!(lock.try_write().is_err());
+    assert_eq!(*r1 + *r2, 10);
+    drop((r1, r2));
+    assert!(lock.try_write().is_ok());
+  }
+
+  #[test]
+  fn test_condvar_example() {
+    assert_eq!(condvar_example(), (0..10).collect::<Vec<u32>>());
+  }
+
+  #[test]
+  fn test_bounded_queue_pop_waits_for_push() {
+    let queue: Arc<BoundedQueue<&str>> = Arc::new(BoundedQueue::new(1));
+    let consumer_queue: Arc<BoundedQueue<&str>> = Arc::clone(&queue);
+    let consumer: JoinHandle<&str> = thread::spawn(move || consumer_queue.pop());
+    thread::sleep(Duration::from_millis(20));
+    queue.push("hello");
+    assert_eq!(consumer.join().unwrap(), "hello");
+    assert!(queue.is_empty());
+  }
+}
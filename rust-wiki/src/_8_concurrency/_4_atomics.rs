@@ -0,0 +1,173 @@
+// -----------------------------------------------
+// # ATOMICS AND MEMORY ORDERING
+//
+// Atomic types (`AtomicBool`, `AtomicUsize`, `AtomicI32`, `AtomicPtr<T>`, ...) are integers (and pointers) that
+// threads can read and modify at the same time through a shared reference, without a lock. Each operation, e.g.
+// `fetch_add`, happens as one indivisible step: no thread can see it half done.
+//
+// Every atomic operation takes an `Ordering`, which says what it guarantees about *other* memory accesses around it.
+// Compilers and CPUs reorder memory accesses when a single thread cannot tell the difference; another thread can.
+//   - `Relaxed`: only the atomic itself is consistent. Nothing is implied about other memory. Enough for counters.
+//   - `Release` (on a store) + `Acquire` (on a load that reads that store): everything the releasing thread wrote
+//      before the store is visible to the acquiring thread after the load. This is how a lock hands over its data.
+//   - `AcqRel`: both, for read-modify-write operations like `swap` and `compare_exchange`.
+//   - `SeqCst`: Acquire/Release, plus a single order of all SeqCst operations that every thread agrees on.
+//      The strongest and simplest to reason about; a safe default when unsure.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+// -----------------------------------------------
+// ## Relaxed: A Shared Counter
+//
+// `fetch_add` is a single atomic read-modify-write, so no increment is lost, even with `Relaxed`.
+// (With a separate `load` then `store`, two threads could read the same value and both store value + 1.)
+pub fn relaxed_counter(threads: usize, increments: usize) -> usize {
+  let counter: AtomicUsize = AtomicUsize::new(0);
+  thread::scope(|s| {
+    for _ in 0..threads {
+      s.spawn(|| {
+        for _ in 0..increments {
+          counter.fetch_add(1, Ordering::Relaxed);
+        }
+      });
+    }
+  });
+  counter.into_inner()
+}
+
+// The lost update that `fetch_add` prevents: a load and a store are each atomic, but the pair is not.
+pub fn racy_counter(threads: usize, increments: usize) -> usize {
+  let counter: AtomicUsize = AtomicUsize::new(0);
+  thread::scope(|s| {
+    for _ in 0..threads {
+      s.spawn(|| {
+        for _ in 0..increments {
+          let current: usize = counter.load(Ordering::Relaxed);
+          counter.store(current + 1, Ordering::Relaxed);
+        }
+      });
+    }
+  });
+  counter.into_inner()
+}
+
+// -----------------------------------------------
+// ## Release/Acquire: Publishing Data
+//
+// A producer writes data, then sets a `ready` flag with `Release`. A consumer that sees the flag with `Acquire`
+// is guaranteed to also see the data. (With `Relaxed` on the flag, it could see `ready == true` and stale data.)
+pub fn release_acquire_example() -> u64 {
+  let data: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+  let ready: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+
+  let (producer_data, producer_ready) = (Arc::clone(&data), Arc::clone(&ready));
+  let producer: JoinHandle<()> = thread::spawn(move || {
+    producer_data.store(42, Ordering::Relaxed);
+    producer_ready.store(true, Ordering::Release);   // <<-- publishes everything written above.
+  });
+
+  while !ready.load(Ordering::Acquire) {            // <<-- once true, everything before the Release is visible.
+    std::hint::spin_loop();
+  }
+  let value: u64 = data.load(Ordering::Relaxed);
+  producer.join().unwrap();
+  value
+}
+
+// -----------------------------------------------
+// ## compare_exchange: A Spin Lock
+//
+// `compare_exchange(current, new, success, failure)` stores `new` only if the value is still `current`, and
+// reports whether it did. This is the building block of lock-free code. Here, a minimal lock: `lock` spins until it
+// changes `locked` from false to true (Acquire: see what the previous holder wrote); `unlock` stores false (Release:
+// publish what we wrote).
+pub struct SpinLock {
+  locked: AtomicBool,
+}
+
+impl SpinLock {
+  pub const fn new() -> SpinLock {
+    SpinLock { locked: AtomicBool::new(false) }
+  }
+
+  pub fn lock(&self) {
+    while self.locked.compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+      std::hint::spin_loop();
+    }
+  }
+
+  pub fn try_lock(&self) -> bool {
+    self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok()
+  }
+
+  pub fn unlock(&self) {
+    self.locked.store(false, Ordering::Release);
+  }
+}
+
+impl Default for SpinLock {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// The spin lock guards a non-atomic update (a load and a store), which then cannot lose increments.
+pub fn spin_lock_counter(threads: usize, increments: usize) -> usize {
+  let lock: SpinLock = SpinLock::new();
+  let counter: AtomicUsize = AtomicUsize::new(0);
+  thread::scope(|s| {
+    for _ in 0..threads {
+      s.spawn(|| {
+        for _ in 0..increments {
+          lock.lock();
+          let current: usize = counter.load(Ordering::Relaxed);
+          counter.store(current + 1, Ordering::Relaxed);
+          lock.unlock();
+        }
+      });
+    }
+  });
+  counter.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_relaxed_counter_loses_nothing() {
+    assert_eq!(relaxed_counter(8, 10_000), 80_000);
+  }
+
+  #[test]
+  fn test_racy_counter_never_overcounts() {
+    // Updates may be lost (how many depends on scheduling), but never invented.
+    assert!(racy_counter(8, 10_000) <= 80_000);
+  }
+
+  #[test]
+  fn test_release_acquire_example() {
+    for _ in 0..100 {
+      assert_eq!(release_acquire_example(), 42);
+    }
+  }
+
+  #[test]
+  fn test_spin_lock() {
+    let lock: SpinLock = SpinLock::new();
+    assert!(lock.try_lock());
+    assert!(!lock.try_lock());
+    lock.unlock();
+    assert!(lock.try_lock());
+    assert_eq!(spin_lock_counter(4, 10_000), 40_000);
+  }
+
+  #[test]
+  fn test_compare_exchange() {
+    let value: AtomicUsize = AtomicUsize::new(5);
+    assert_eq!(value.compare_exchange(5, 6, Ordering::SeqCst, Ordering::SeqCst), Ok(5));
+    assert_eq!(value.compare_exchange(5, 7, Ordering::SeqCst, Ordering::SeqCst), Err(6));
+  }
+}
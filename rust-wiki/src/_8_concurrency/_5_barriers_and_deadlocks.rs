@@ -0,0 +1,150 @@
+// -----------------------------------------------
+// # BARRIERS
+//
+// A `Barrier::new(n)` makes threads wait for each other: each `wait()` blocks until `n` threads have called it, and
+// then all of them continue. This splits work into phases, where no thread starts phase 2 before every thread has
+// finished phase 1. One of the `n` threads is told it is the "leader", e.g. to do some work once per phase.
+
+use std::sync::{Arc, Barrier, BarrierWaitResult, Mutex, MutexGuard, TryLockError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Each of 3 threads logs "phase 1" then "phase 2". The barrier between the phases means every "phase 1" entry comes
+// before every "phase 2" entry, whatever the scheduling.
+pub fn barrier_phases() -> Vec<String> {
+  let threads: usize = 3;
+  let barrier: Arc<Barrier> = Arc::new(Barrier::new(threads));
+  let log: Arc<Mutex<Vec<String>>> = Arc::default();
+
+  let handles: Vec<JoinHandle<bool>> = (0..threads)
+    .map(|id| {
+      let (barrier, log) = (Arc::clone(&barrier), Arc::clone(&log));
+      thread::spawn(move || {
+        // Later threads start later, so without the barrier thread 0 would be done before thread 2 starts.
+        thread::sleep(Duration::from_millis(10 * id as u64));
+        log.lock().unwrap().push(format!("thread {id} phase 1"));
+        let result: BarrierWaitResult = barrier.wait();
+        log.lock().unwrap().push(format!("thread {id} phase 2"));
+        result.is_leader()
+      })
+    })
+    .collect();
+
+  let leaders: usize = handles.into_iter().map(|handle| handle.join().unwrap() as usize).sum();
+  assert_eq!(leaders, 1);
+  let log: Vec<String> = log.lock().unwrap().clone();
+  log
+}
+
+// -----------------------------------------------
+// # DEADLOCKS
+//
+// A DEADLOCK is a set of threads each waiting for a lock held by another thread of the set, so none of them ever
+// continues. The classic case is two threads taking the same two locks in opposite orders:
+//
+//    thread A: lock(first)  ... lock(second)   <<-- waits for B to release `second`
+//    thread B: lock(second) ... lock(first)    <<-- waits for A to release `first`
+//
+// Rust's type system rules out data races, not deadlocks: this compiles fine.
+//
+// To make the demonstration deterministic, a barrier ensures both threads hold their first lock before either
+// tries its second, and neither releases it before both have tried. And so that it terminates, the second lock is
+// taken with a timeout: a blocking `lock()` would hang the threads forever. The fix is to always take locks in one
+// global order, e.g. `first` before `second`.
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LockOutcome {
+  Completed,
+  TimedOut,
+}
+
+// Polls `try_lock` until it succeeds or the timeout passes. (std has no `lock` with a timeout.)
+pub fn lock_with_timeout<T>(mutex: &Mutex<T>, timeout: Duration) -> Option<MutexGuard<'_, T>> {
+  let deadline: Instant = Instant::now() + timeout;
+  loop {
+    match mutex.try_lock() {
+      Ok(guard) => return Some(guard),
+      Err(TryLockError::Poisoned(poison)) => return Some(poison.into_inner()),
+      Err(TryLockError::WouldBlock) if Instant::now() >= deadline => return None,
+      Err(TryLockError::WouldBlock) => thread::sleep(Duration::from_millis(1)),
+    }
+  }
+}
+
+// Runs two threads that each take both locks, in the given orders. `true` means `first` then `second`.
+fn run_two_lockers(a_in_order: bool, b_in_order: bool, timeout: Duration) -> (LockOutcome, LockOutcome) {
+  let first: Arc<Mutex<u32>> = Arc::new(Mutex::new(0));
+  let second: Arc<Mutex<u32>> = Arc::new(Mutex::new(0));
+  // With opposite orders, the barrier makes both threads hold their first lock before either tries its second.
+  // With the same order it would itself deadlock (the thread holding `first` waiting at the barrier for the thread
+  // waiting for `first`), so then it lets each thread through alone.
+  let barrier: Arc<Barrier> = Arc::new(Barrier::new(if a_in_order == b_in_order { 1 } else { 2 }));
+
+  let spawn_locker = |in_order: bool| {
+    let (first, second, barrier) = (Arc::clone(&first), Arc::clone(&second), Arc::clone(&barrier));
+    thread::spawn(move || {
+      let (outer, inner) = if in_order { (&first, &second) } else { (&second, &first) };
+      let mut outer_guard: MutexGuard<u32> = outer.lock().unwrap();
+      barrier.wait();
+      let outcome: LockOutcome = match lock_with_timeout(inner, timeout) {
+        Some(mut inner_guard) => {
+          *outer_guard += 1;
+          *inner_guard += 1;
+          LockOutcome::Completed
+        }
+        None => LockOutcome::TimedOut,
+      };
+      // Keep holding the first lock until the other thread has given up too; releasing it earlier would let the
+      // other thread through, which is the point of backing off, but would make the demonstration racy.
+      barrier.wait();
+      outcome
+    })
+  };
+  let a: JoinHandle<LockOutcome> = spawn_locker(a_in_order);
+  let b: JoinHandle<LockOutcome> = spawn_locker(b_in_order);
+  (a.join().unwrap(), b.join().unwrap())
+}
+
+// Opposite lock orders: each thread holds the lock the other needs, so both time out.
+pub fn deadlock_example(timeout: Duration) -> (LockOutcome, LockOutcome) {
+  run_two_lockers(true, false, timeout)
+}
+
+// Same lock order: one thread waits for the other to finish, then proceeds.
+pub fn lock_ordering_example(timeout: Duration) -> (LockOutcome, LockOutcome) {
+  run_two_lockers(true, true, timeout)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_barrier_phases() {
+    let log: Vec<String> = barrier_phases();
+    assert_eq!(log.len(), 6);
+    assert!(log[..3].iter().all(|entry| entry.ends_with("phase 1")));
+    assert!(log[3..].iter().all(|entry| entry.ends_with("phase 2")));
+  }
+
+  #[test]
+  fn test_deadlock_is_detected_by_timeout() {
+    let start: Instant = Instant::now();
+    assert_eq!(deadlock_example(Duration::from_millis(100)), (LockOutcome::TimedOut, LockOutcome::TimedOut));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+  }
+
+  #[test]
+  fn test_lock_ordering_avoids_deadlock() {
+    assert_eq!(lock_ordering_example(Duration::from_secs(5)), (LockOutcome::Completed, LockOutcome::Completed));
+  }
+
+  #[test]
+  fn test_lock_with_timeout() {
+    let mutex: Mutex<i32> = Mutex::new(1);
+    let guard: MutexGuard<i32> = lock_with_timeout(&mutex, Duration::ZERO).unwrap();
+    assert!(lock_with_timeout(&mutex, Duration::from_millis(10)).is_none());
+    drop(guard);
+    assert_eq!(*lock_with_timeout(&mutex, Duration::ZERO).unwrap(), 1);
+  }
+}
@@ -0,0 +1,207 @@
+// -----------------------------------------------
+// # ERROR HANDLING
+//
+// Rust has two kinds of errors:
+//   - UNRECOVERABLE errors are bugs: a broken invariant, an index out of bounds. They `panic!`, unwinding the
+//     thread's stack (dropping everything on it) and ending the thread. See _3_panics_and_exit_codes.rs.
+//   - RECOVERABLE errors are expected: a file that does not exist, input that does not parse. They are values,
+//     returned as the `Err` variant of a `Result`, which the caller must handle:
+//
+//        enum Result<T, E> {
+//          Ok(T),
+//          Err(E),
+//        }
+//
+// (This is the real definition that _4_generics/_1_generic_types.rs shadows to show how generic enums work.)
+//
+// This chapter covers:
+//   - propagating errors with `?`, and converting them with `From` (here)
+//   - error types: `Box<dyn Error>`, custom error enums, `source()` chains, context (_2_error_types.rs)
+//   - panics, and exit codes for `main` (_3_panics_and_exit_codes.rs)
+
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+// -----------------------------------------------
+// ## A Custom Error Type
+//
+// An error type is an ordinary type. Implementing `Display` (a message for users) and `Debug` (for programmers)
+// lets it implement the `Error` trait, which is what generic error-handling code, e.g. `Box<dyn Error>`, expects.
+#[derive(PartialEq, Debug)]
+pub enum CreationError {
+  Negative,
+  Zero,
+}
+
+// This is required so that `CreationError` can implement `Error`.
+impl fmt::Display for CreationError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let description: &str = match *self {
+      CreationError::Negative => "number is negative",
+      CreationError::Zero => "number is zero",
+    };
+    f.write_str(description)
+  }
+}
+
+impl Error for CreationError {}
+
+#[derive(PartialEq, Debug)]
+pub struct PositiveNonzeroInteger(pub u64);
+
+impl PositiveNonzeroInteger {
+  pub fn new(value: i64) -> Result<PositiveNonzeroInteger, CreationError> {
+    match value {
+      x if x < 0 => Err(CreationError::Negative),
+      0 => Err(CreationError::Zero),
+      x => Ok(PositiveNonzeroInteger(x as u64)),
+    }
+  }
+
+  // `parse` can fail in two ways, so its error is an enum of both. See below.
+  pub fn parse(s: &str) -> Result<PositiveNonzeroInteger, ParsePosNonzeroError> {
+    let x: i64 = s.parse()?;
+    Ok(Self::new(x)?)
+  }
+}
+
+// -----------------------------------------------
+// ## The ? Operator
+//
+// `expr?` on a `Result` evaluates to the `Ok` value, or returns the `Err` early from the enclosing function:
+//
+//    let x: i64 = s.parse()?;
+//
+// is short for
+//
+//    let x: i64 = match s.parse() {
+//      Ok(x) => x,
+//      Err(e) => return Err(From::from(e)),
+//    };
+//
+// The `From::from` converts the error into the function's error type. So `?` works whenever there is a
+// `From<SourceError> for ReturnError` impl, and does nothing when they are the same type.
+// (`?` also works on `Option`, in functions returning `Option`: `None` is returned early.)
+#[derive(PartialEq, Debug)]
+pub enum ParsePosNonzeroError {
+  Creation(CreationError),
+  ParseInt(ParseIntError),
+}
+
+impl From<CreationError> for ParsePosNonzeroError {
+  fn from(err: CreationError) -> Self {
+    ParsePosNonzeroError::Creation(err)
+  }
+}
+
+impl From<ParseIntError> for ParsePosNonzeroError {
+  fn from(err: ParseIntError) -> Self {
+    ParsePosNonzeroError::ParseInt(err)
+  }
+}
+
+impl fmt::Display for ParsePosNonzeroError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParsePosNonzeroError::Creation(_) => f.write_str("invalid value"),
+      ParsePosNonzeroError::ParseInt(_) => f.write_str("not a number"),
+    }
+  }
+}
+
+impl Error for ParsePosNonzeroError {
+  // The error that caused this one; see `source()` chains in _2_error_types.rs.
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ParsePosNonzeroError::Creation(err) => Some(err),
+      ParsePosNonzeroError::ParseInt(err) => Some(err),
+    }
+  }
+}
+
+// Without `From` impls, each error would be converted by hand with `map_err`:
+pub fn parse_with_map_err(s: &str) -> Result<PositiveNonzeroInteger, ParsePosNonzeroError> {
+  let x: i64 = s.parse().map_err(ParsePosNonzeroError::ParseInt)?;
+  PositiveNonzeroInteger::new(x).map_err(ParsePosNonzeroError::Creation)
+}
+
+// `?` on an `Option`.
+pub fn first_word_length(text: &str) -> Option<usize> {
+  let word: &str = text.split_whitespace().next()?;
+  Some(word.len())
+}
+
+// -----------------------------------------------
+// ## Box<dyn Error>
+//
+// When the caller only needs to report an error, not to handle each kind differently, the function can return
+// `Box<dyn Error>`: "any error". There is a `From<E> for Box<dyn Error>` for every `E: Error`, so `?` converts
+// any error into it.
+//
+// Convenient for applications and examples, but a library should prefer an enum: callers can then `match` on it.
+pub fn parse_and_create(input: &str) -> Result<PositiveNonzeroInteger, Box<dyn Error>> {
+  let x: i64 = input.parse()?;                    // ParseIntError -> Box<dyn Error>
+  let number = PositiveNonzeroInteger::new(x)?;   // CreationError -> Box<dyn Error>
+  Ok(number)
+}
+
+// The concrete error can still be recovered from the box by downcasting.
+pub fn as_creation_error<'a>(err: &'a (dyn Error + 'static)) -> Option<&'a CreationError> {
+  err.downcast_ref::<CreationError>()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_error() {
+    assert!(matches!(PositiveNonzeroInteger::parse("not a number"), Err(ParsePosNonzeroError::ParseInt(_))));
+  }
+
+  #[test]
+  fn test_negative() {
+    assert_eq!(PositiveNonzeroInteger::parse("-555"), Err(ParsePosNonzeroError::Creation(CreationError::Negative)));
+  }
+
+  #[test]
+  fn test_zero() {
+    assert_eq!(PositiveNonzeroInteger::parse("0"), Err(ParsePosNonzeroError::Creation(CreationError::Zero)));
+  }
+
+  #[test]
+  fn test_positive() {
+    let x: PositiveNonzeroInteger = PositiveNonzeroInteger::new(42).unwrap();
+    assert_eq!(x.0, 42);
+    assert_eq!(PositiveNonzeroInteger::parse("42"), Ok(x));
+  }
+
+  #[test]
+  fn test_map_err_matches_question_mark() {
+    for input in ["42", "0", "-1", "x"] {
+      assert_eq!(parse_with_map_err(input), PositiveNonzeroInteger::parse(input));
+    }
+  }
+
+  #[test]
+  fn test_question_mark_on_option() {
+    assert_eq!(first_word_length("hello world"), Some(5));
+    assert_eq!(first_word_length("   "), None);
+  }
+
+  #[test]
+  fn test_box_dyn_error() {
+    assert_eq!(parse_and_create("42").unwrap(), PositiveNonzeroInteger(42));
+    assert_eq!(parse_and_create("x").unwrap_err().to_string(), "invalid digit found in string");
+    let err: Box<dyn Error> = parse_and_create("-5").unwrap_err();
+    assert_eq!(as_creation_error(err.as_ref()), Some(&CreationError::Negative));
+  }
+
+  #[test]
+  fn test_source_of_enum_error() {
+    let err: ParsePosNonzeroError = PositiveNonzeroInteger::parse("0").unwrap_err();
+    assert_eq!(err.to_string(), "invalid value");
+    assert_eq!(err.source().unwrap().to_string(), "number is zero");
+  }
+}
@@ -0,0 +1,223 @@
+// -----------------------------------------------
+// # ERROR TYPES, SOURCES AND CONTEXT
+//
+// The `Error` trait has one method worth overriding:
+//
+//    pub trait Error: Debug + Display {
+//      fn source(&self) -> Option<&(dyn Error + 'static)> { None }
+//    }
+//
+// `source()` returns the lower-level error that caused this one, if any. Following `source()` repeatedly gives a
+// CHAIN of errors, from the most general ("could not load the config") to the root cause ("permission denied").
+// Each error's `Display` should describe only its own level, and leave the cause to `source()`: whoever reports the
+// error then decides how much of the chain to print.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+// Every error in the chain, starting with `err` itself.
+pub fn error_chain(err: &(dyn Error + 'static)) -> Vec<String> {
+  std::iter::successors(Some(err), |&err| err.source()).map(|err| err.to_string()).collect()
+}
+
+// Formats a chain the way command-line tools usually do:
+//
+//    error: loading server configuration
+//      caused by: invalid number on line 2
+//      caused by: invalid digit found in string
+pub fn report(err: &(dyn Error + 'static)) -> String {
+  let chain: Vec<String> = error_chain(err);
+  let mut out: String = format!("error: {}", chain[0]);
+  for cause in &chain[1..] {
+    out.push_str(&format!("\n  caused by: {cause}"));
+  }
+  out
+}
+
+// -----------------------------------------------
+// ## A thiserror-like Error Enum, by Hand
+//
+// Libraries usually give each module an error enum, with one variant per way it can fail. The `thiserror` crate
+// derives the boilerplate from attributes:
+//
+//    #[derive(Debug, thiserror::Error)]
+//    pub enum ConfigError {
+//      #[error("could not read {path}")]
+//      Io { path: String, #[source] source: io::Error },
+//      #[error("invalid number on line {line}")]
+//      Parse { line: usize, #[source] source: ParseIntError },
+//      #[error("missing key `{0}`")]
+//      Missing(&'static str),
+//    }
+//
+// Below is what it generates, written out: a `Display` impl with the messages, and an `Error` impl whose `source`
+// returns the `#[source]` fields. (`#[from]` would additionally generate `From` impls, as in
+// _1_results_and_propagation.rs; here the variants carry extra data, the path and line, so they are built with
+// `map_err` instead.)
+#[derive(Debug)]
+pub enum ConfigError {
+  Io { path: String, source: io::Error },
+  Parse { line: usize, source: ParseIntError },
+  Missing(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ConfigError::Io { path, .. } => write!(f, "could not read {path}"),
+      ConfigError::Parse { line, .. } => write!(f, "invalid number on line {line}"),
+      ConfigError::Missing(key) => write!(f, "missing key `{key}`"),
+    }
+  }
+}
+
+impl Error for ConfigError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ConfigError::Io { source, .. } => Some(source),
+      ConfigError::Parse { source, .. } => Some(source),
+      ConfigError::Missing(_) => None,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ServerConfig {
+  pub port: u16,
+  pub workers: u16,
+}
+
+// Parses lines of `key = number`.
+pub fn parse_config(text: &str) -> Result<ServerConfig, ConfigError> {
+  let (mut port, mut workers): (Option<u16>, Option<u16>) = (None, None);
+  for (index, line) in text.lines().enumerate() {
+    let Some((key, value)) = line.split_once('=') else { continue };
+    let value: u16 = value.trim().parse().map_err(|source| ConfigError::Parse { line: index + 1, source })?;
+    match key.trim() {
+      "port" => port = Some(value),
+      "workers" => workers = Some(value),
+      _ => {}
+    }
+  }
+  Ok(ServerConfig { port: port.ok_or(ConfigError::Missing("port"))?, workers: workers.unwrap_or(1) })
+}
+
+pub fn load_config(path: &str) -> Result<ServerConfig, ConfigError> {
+  let text: String = std::fs::read_to_string(path).map_err(|source| ConfigError::Io { path: path.to_string(), source })?;
+  parse_config(&text)
+}
+
+// -----------------------------------------------
+// ## Context Wrapping
+//
+// Low-level errors lack context: "invalid digit found in string" does not say *what* was being parsed, or why.
+// Applications often wrap an error in a message describing what they were doing, keeping the original as the
+// `source()`. (The `anyhow` crate does this with its `.context()` method; here is a small version.)
+//
+//    parse_config(text).context("loading server configuration")?;
+#[derive(Debug)]
+pub struct ContextError {
+  pub context: String,
+  pub source: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl fmt::Display for ContextError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.context)
+  }
+}
+
+impl Error for ContextError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(self.source.as_ref())
+  }
+}
+
+// An extension trait: adds `context` and `with_context` methods to every `Result` whose error is an `Error`.
+pub trait Context<T> {
+  fn context(self, context: &str) -> Result<T, ContextError>;
+  // Builds the message only if there is an error.
+  fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T, ContextError>;
+}
+
+impl<T, E: Error + Send + Sync + 'static> Context<T> for Result<T, E> {
+  fn context(self, context: &str) -> Result<T, ContextError> {
+    self.with_context(|| context.to_string())
+  }
+
+  fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T, ContextError> {
+    self.map_err(|err| ContextError { context: f(), source: Box::new(err) })
+  }
+}
+
+pub fn start_server(config_text: &str) -> Result<String, ContextError> {
+  let config: ServerConfig = parse_config(config_text).context("loading server configuration")?;
+  let address: String = format!("127.0.0.1:{}", config.port);
+  // Contexts can be layered: each one wraps the previous error.
+  check_workers(config.workers).with_context(|| format!("starting {address}"))?;
+  Ok(address)
+}
+
+fn check_workers(workers: u16) -> Result<(), ContextError> {
+  if workers == 0 {
+    let err = io::Error::new(io::ErrorKind::InvalidInput, "at least one worker is needed");
+    return Err(err).context("spawning workers");
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_config() {
+    assert_eq!(parse_config("port = 8080\nworkers = 4").unwrap(), ServerConfig { port: 8080, workers: 4 });
+    assert_eq!(parse_config("# comment\nport=80").unwrap(), ServerConfig { port: 80, workers: 1 });
+  }
+
+  #[test]
+  fn test_parse_error_chain() {
+    let err: ConfigError = parse_config("workers = 2\nport = eighty").unwrap_err();
+    assert_eq!(error_chain(&err), vec!["invalid number on line 2", "invalid digit found in string"]);
+  }
+
+  #[test]
+  fn test_missing_key_has_no_source() {
+    let err: ConfigError = parse_config("workers = 2").unwrap_err();
+    assert!(matches!(err, ConfigError::Missing("port")));
+    assert!(err.source().is_none());
+    assert_eq!(error_chain(&err), vec!["missing key `port`"]);
+  }
+
+  #[test]
+  fn test_io_error_chain() {
+    let err: ConfigError = load_config("/nonexistent/wiki.conf").unwrap_err();
+    let chain: Vec<String> = error_chain(&err);
+    assert_eq!(chain[0], "could not read /nonexistent/wiki.conf");
+    let io_error: &io::Error = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+    assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+  }
+
+  #[test]
+  fn test_context_wraps_the_chain() {
+    let err: ContextError = start_server("port = 80a").unwrap_err();
+    assert_eq!(
+      error_chain(&err),
+      vec!["loading server configuration", "invalid number on line 1", "invalid digit found in string"]
+    );
+    assert_eq!(
+      report(&err),
+      "error: loading server configuration\n  caused by: invalid number on line 1\n  caused by: invalid digit found in string"
+    );
+  }
+
+  #[test]
+  fn test_layered_context() {
+    let err: ContextError = start_server("port = 80\nworkers = 0").unwrap_err();
+    assert_eq!(error_chain(&err), vec!["starting 127.0.0.1:80", "spawning workers", "at least one worker is needed"]);
+    assert_eq!(start_server("port = 80").unwrap(), "127.0.0.1:80");
+  }
+}
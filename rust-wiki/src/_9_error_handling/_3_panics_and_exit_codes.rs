@@ -0,0 +1,143 @@
+// -----------------------------------------------
+// # PANICS VS ERRORS
+//
+// `panic!` is for bugs: situations that the code calling you could not have handled, because it should never have
+// got there. Return a `Result` for everything a caller could reasonably expect and react to.
+//
+//   Panic when...                                     Return an Err when...
+//   - an invariant of your own code is broken        - input from a user, file or network is invalid
+//   - a caller broke a documented precondition       - an operation can fail in normal use (a missing file)
+//     (an index out of bounds, dividing by zero)     - the caller may want to retry, or try something else
+//   - in tests, examples and prototypes
+//     (`unwrap` and `expect`)
+//
+// `unwrap()` panics on `Err`/`None`; `expect("msg")` does the same with a message saying why that cannot happen.
+//
+// A panic unwinds the stack of its thread, dropping every value on it, and ends the thread. If that is the main
+// thread, the program exits with code 101. Panics are not exceptions: `std::panic::catch_unwind` can stop one, but
+// is meant for boundaries like thread pools or FFI, not for control flow.
+
+use std::error::Error;
+use std::panic;
+use std::process::ExitCode;
+
+use super::_2_error_types::{parse_config, report, ConfigError, ServerConfig};
+
+// Panics if the precondition is broken: the caller's bug.
+pub fn average(values: &[f64]) -> f64 {
+  assert!(!values.is_empty(), "average of an empty slice");
+  values.iter().sum::<f64>() / values.len() as f64
+}
+
+// Returns an error: an empty input is an expected case the caller can handle.
+pub fn checked_average(values: &[f64]) -> Option<f64> {
+  if values.is_empty() { None } else { Some(average(values)) }
+}
+
+// Runs `f`, turning a panic into an `Err` with the panic message.
+pub fn catch_panic<R>(f: impl FnOnce() -> R + panic::UnwindSafe) -> Result<R, String> {
+  panic::catch_unwind(f).map_err(|payload| {
+    // The payload is a `&str` for `panic!("literal")`, and a `String` for `panic!("{}", formatted)`.
+    payload
+      .downcast_ref::<&str>()
+      .map(|s| s.to_string())
+      .or_else(|| payload.downcast_ref::<String>().cloned())
+      .unwrap_or_else(|| String::from("unknown panic"))
+  })
+}
+
+// -----------------------------------------------
+// ## Errors from main: Exit Codes
+//
+// `main` can return:
+//   - `()`: exit code 0, or 101 after a panic.
+//   - `Result<(), E>` with `E: Debug`: on `Err`, prints "Error: {err:?}" to stderr and exits with code 1.
+//   - `std::process::ExitCode`: the exit code to return, e.g. `ExitCode::SUCCESS`, `ExitCode::from(2)`.
+// (Any type implementing the `std::process::Termination` trait works.)
+//
+// `Result<(), Box<dyn Error>>` is the quickest, but gives every failure the same exit code and prints the `Debug`
+// form. Scripts calling a program often rely on distinct codes, so a common pattern is a `run` function returning a
+// `Result`, and a `main` that reports the error chain and picks the code:
+//
+//    fn main() -> ExitCode {
+//      match run(std::env::args()) {
+//        Ok(()) => ExitCode::SUCCESS,
+//        Err(err) => {
+//          eprintln!("{}", report(&err));
+//          ExitCode::from(exit_code_for(&err))
+//        }
+//      }
+//    }
+
+// Exit codes from BSD's sysexits.h, a common convention.
+pub const EX_USAGE: u8 = 64;
+pub const EX_DATAERR: u8 = 65;
+pub const EX_IOERR: u8 = 74;
+
+pub fn exit_code_for(err: &ConfigError) -> u8 {
+  match err {
+    ConfigError::Io { .. } => EX_IOERR,
+    ConfigError::Parse { .. } | ConfigError::Missing(_) => EX_DATAERR,
+  }
+}
+
+// The body of such a `main`, taking the config text instead of reading arguments, and returning the code and what
+// would be written to stderr.
+pub fn main_with_config(config_text: Option<&str>) -> (ExitCode, String) {
+  let Some(text) = config_text else {
+    return (ExitCode::from(EX_USAGE), String::from("usage: server <config>"));
+  };
+  match parse_config(text) {
+    Ok(ServerConfig { port, .. }) => (ExitCode::SUCCESS, format!("listening on port {port}")),
+    Err(err) => (ExitCode::from(exit_code_for(&err)), report(&err)),
+  }
+}
+
+// What `main() -> Result<(), Box<dyn Error>>` would print on error: the `Debug` form, not the `Display` chain.
+pub fn default_main_report(result: Result<(), Box<dyn Error>>) -> Option<String> {
+  result.err().map(|err| format!("Error: {err:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_average() {
+    assert_eq!(average(&[1.0, 2.0, 3.0]), 2.0);
+    assert_eq!(checked_average(&[]), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "average of an empty slice")]
+  fn test_average_of_empty_slice_panics() {
+    average(&[]);
+  }
+
+  #[test]
+  fn test_catch_panic() {
+    assert_eq!(catch_panic(|| average(&[])), Err(String::from("average of an empty slice")));
+    assert_eq!(catch_panic(|| panic!("code {}", 7)), Err::<(), _>(String::from("code 7")));
+    assert_eq!(catch_panic(|| 5), Ok(5));
+  }
+
+  #[test]
+  fn test_exit_codes() {
+    let (code, output) = main_with_config(Some("port = 8080"));
+    assert_eq!((code, output.as_str()), (ExitCode::SUCCESS, "listening on port 8080"));
+
+    let (code, output) = main_with_config(Some("port = x"));
+    assert_eq!(code, ExitCode::from(EX_DATAERR));
+    assert_eq!(output, "error: invalid number on line 1\n  caused by: invalid digit found in string");
+
+    let (code, _) = main_with_config(None);
+    assert_eq!(code, ExitCode::from(EX_USAGE));
+  }
+
+  #[test]
+  fn test_default_main_report() {
+    let result: Result<(), Box<dyn Error>> = Err("boom".into());
+    assert_eq!(default_main_report(result).unwrap(), "Error: \"boom\"");
+    assert_eq!(default_main_report(Ok(())), None);
+  }
+}
@@ -1,32 +1,137 @@
 // To build lib.rs:
 // > cargo build --lib
+//
+// This is the single module tree of the wiki: every chapter is a public module, and the binaries (main.rs) and tests
+// (tests/) consume the examples through it, e.g. `rust_wiki::_4_generics::_1_generic_types::Point`.
 
-mod _1_basics {
-     mod _1_variables;
-     mod _2_datatypes;
-     mod _3_functions;
-     mod _4_control_flow;
-}
-mod _2_ownership {
-    mod _2_owners_and_scope;
-    mod _3_references_and_lifetimes;
-    mod _4_slices;
-}
-mod _3_datatypes_and_traits {
-    mod _1_structs;
-    mod _2_enums;
-    mod _3_methods;
-    mod _4_traits;
-}
-mod _4_generics{
-    mod _1_generic_types;
-    mod _2_lifetime_annotations;
-}
-mod _5_functional_features{
-    mod _1_closures;
-    mod _2_iterators;
-}
+// Lets the code generated by `#[derive(Show)]` (show_derive/) name the trait as `::rust_wiki::...` both here and in
+// crates depending on the wiki.
+extern crate self as rust_wiki;
 
-fn main() {
-    // reference_example_2()
+// The examples deliberately bind values that are never read, return early, shadow names and spell out what the
+// compiler would otherwise elide, so that each rule can be shown in isolation. Those lints are silenced here rather
+// than rewriting the examples around them.
+#[allow(unused, unreachable_code, non_snake_case, clippy::all)]
+pub mod _1_basics {
+    pub mod _1_variables;
+    pub mod _2_datatypes;
+    pub mod _3_functions;
+    pub mod _4_control_flow;
+    pub mod _5_io_and_logging;
+}
+#[allow(unused, unreachable_code, non_snake_case, clippy::all)]
+pub mod _2_ownership {
+    pub mod _1_traced;
+    pub mod _2_owners_and_scope;
+    pub mod _3_references_and_lifetimes;
+    pub mod _4_slices;
+    pub mod _5_utf8_text;
+}
+#[allow(unused, unreachable_code, non_snake_case, clippy::all)]
+pub mod _3_datatypes_and_traits {
+    pub mod _1_structs;
+    pub mod _2_enums;
+    pub mod _3_methods;
+    pub mod _4_traits;
+    pub mod _5_patterns;
+}
+#[allow(unused, unreachable_code, non_snake_case, clippy::all)]
+pub mod _4_generics {
+    pub mod _1_generic_types;
+    pub mod _2_lifetime_annotations;
+}
+#[allow(unused, unreachable_code, non_snake_case, clippy::all)]
+pub mod _5_functional_features {
+    pub mod _1_closures;
+    pub mod _2_iterators;
+    pub mod _3_iterator_adaptors;
+    pub mod _4_closure_apis;
+}
+pub mod _6_async {
+    pub mod _2_futures_and_block_on;
+    pub mod _3_executor;
+    pub mod _4_timer;
+    pub mod _5_channel;
+}
+pub mod _7_smart_pointers {
+    pub mod _1_box;
+    pub mod _2_rc_and_weak;
+    pub mod _3_cells;
+    pub mod _4_arc;
+    pub mod _5_cow;
+}
+pub mod _8_concurrency {
+    pub mod _1_threads;
+    pub mod _2_channels;
+    pub mod _3_locks;
+    pub mod _4_atomics;
+    pub mod _5_barriers_and_deadlocks;
+}
+pub mod _9_error_handling {
+    pub mod _1_results_and_propagation;
+    pub mod _2_error_types;
+    pub mod _3_panics_and_exit_codes;
+}
+pub mod _10_macros {
+    pub mod _1_macro_rules;
+    pub mod _2_derive_macros;
+}
+pub mod _11_collections {
+    pub mod _1_sequences;
+    pub mod _2_maps_and_sets;
+    pub mod _3_binary_heap;
+    pub mod _4_custom_hash_and_ord;
+    pub mod _5_benchmarks;
+}
+pub mod _12_conversions {
+    pub mod _1_from_into_as_ref;
+    pub mod _2_casts;
+    pub mod _3_from_str;
+    pub mod _4_try_from;
+}
+pub mod _13_trait_objects {
+    pub mod _1_dyn_trait;
+    pub mod _2_object_safety;
+    pub mod _3_fat_pointers;
+    pub mod _4_any;
+    pub mod _5_plugin_registry;
+}
+pub mod _14_advanced_traits {
+    pub mod _1_operator_overloading;
+    pub mod _2_blanket_impls;
+    pub mod _3_associated_consts;
+    pub mod _4_supertraits;
+    pub mod _5_newtype;
+    pub mod _6_generic_associated_types;
+}
+pub mod _15_advanced_lifetimes {
+    pub mod _1_borrowing_self;
+    pub mod _2_variance;
+    pub mod _3_higher_ranked_bounds;
+    pub mod _4_static;
+    pub mod _5_reborrowing;
+}
+pub mod _16_big_numbers {
+    pub mod _1_integer_overflow;
+    pub mod _2_big_uint;
+    pub mod _3_generic_fibonacci;
+}
+pub mod _17_unsafe {
+    pub mod _1_raw_pointers;
+    pub mod _2_my_vec;
+}
+pub mod _18_ffi {
+    pub mod _1_calling_c;
+    pub mod _2_exporting_to_c;
+}
+pub mod _19_interior_mutability {
+    pub mod _1_aliasing_xor_mutation;
+    pub mod _2_tracked_ref_cell;
+}
+#[allow(unused, unreachable_code, non_snake_case, clippy::all)]
+pub mod memory_old_notes {
+    pub mod _1_memory_layout;
+    pub mod _2_memory_ownership;
+    pub mod _3_memory_references;
+    pub mod _4_memory_inspector;
 }
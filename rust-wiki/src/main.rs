@@ -1,25 +1,20 @@
 // To build main.rs:
 // > cargo build
+//
+// The binary has no modules of its own; it runs the examples exposed by the library (lib.rs).
+// Set RUST_LOG to see which example is running, and the traces the examples log, e.g.
+// > RUST_LOG=info cargo run
+// > RUST_LOG=debug cargo run
 
-mod _1_basics {
-     mod _1_variables;
-     mod _2_datatypes;
-     mod _3_functions;
-     mod _4_control_flow;
-}
-mod _2_ownership {
-    mod _2_owners_and_scope;
-    mod _3_references_and_lifetimes;
-    mod _4_slices;
-}
-mod _3_datatypes_and_traits {
-    mod _1_structs;
-    mod _2_enums;
-    mod _3_methods;
-    pub mod _4_traits;
-}
+use rust_wiki::_1_basics::_5_io_and_logging;
+use rust_wiki::_3_datatypes_and_traits::_4_traits;
+use rust_wiki::_7_smart_pointers::_2_rc_and_weak;
+use rust_wiki::info;
 
-// mod submodule2;
 fn main() {
-    _3_datatypes_and_traits::_4_traits::using_traits_example();
+    _5_io_and_logging::init();
+    info!("running using_traits_example");
+    _4_traits::using_traits_example();
+    info!("running planets_trace");
+    _2_rc_and_weak::planets_trace();
 }
@@ -37,6 +37,9 @@
 // +-------------------------------+
 // |      Command Line Args        |  <-- Managed by OS at runtime
 // +-------------------------------+
+//
+// The addresses in the diagrams of this file are made up. _4_memory_inspector.rs redraws them (on Linux) from the
+// real addresses of the running program: `memory_inspector_example()`.
 
 // -------------------------------------------------------------------------------------------------
 // ## Binary
@@ -69,13 +72,13 @@
 //       3. Every function exit pops all the data in the stack frame off the stack.
 
 // Example:
-fn _main() {
+pub fn _main() {
     let x = 48;
     let y = &x;
     let z = _double(y);
     println!("{z}");
 }
-fn _double(n: &i32) -> i32 {
+pub fn _double(n: &i32) -> i32 {
     n * 2
 }
     // Informal Mental Model: what *COULD* happen (specific details are imprecise but harmless for understanding.):
@@ -195,3 +198,14 @@ fn _double(n: &i32) -> i32 {
 //
 //   Stack pushing is faster because the allocator never has to search for a place to store new data; that location is always at the current stack pointer, which is trivially maintained (by simple pointer arithmetic).
 //   Heap allocation requires more work because the allocator must first find a big enough space to hold the data and then perform bookkeeping to prepare for the next allocation.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double() {
+        assert_eq!(_double(&48), 96);
+        _main();
+    }
+}
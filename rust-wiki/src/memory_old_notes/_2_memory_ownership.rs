@@ -23,7 +23,7 @@
 //       This refers back Rust's rule of mutability i.e. that mutability is inherited in Rust.
 
 
-fn owner_example() {
+pub fn owner_example() {
   // x1('s value) trivially owns 42 on the stack
   let x1 = 42;
   // xmut('s value) mutably owns an i32 on the stack, which is *copied* from another i32 (as i32 implements copy)
@@ -44,7 +44,7 @@ fn owner_example() {
   }
   // vec2('s value) is a Vec owns a vector on the heap that was moved from vec1 to vecmut and modified
   let vec2: Vec<i32> = add_ones(vec1);
-  print!("{0}", vec2[4]);
+  print!("{0}", vec2[3]);
 }
   // Informal Mental Model: what *COULD* happen:
   // STACK:
@@ -77,7 +77,7 @@ fn owner_example() {
 //   Each heap allocation must be paired with exactly one free when we’re done with it.
 //   Heap memory is automatically freed once its owner goes out of scope, and is done via a special function "drop".
 //
-fn ownership_scope_example(){
+pub fn ownership_scope_example(){
     {
       // s (i.e. its value) is a String object that owns a string allocated on the heap
       let s: String = String::from("hello"); // <<-- s is valid from this point forward
@@ -155,7 +155,7 @@ fn ownership_scope_example(){
 //
 // -------------------------------------------------------------------------------------------------
 // [MOVE]
-fn move_data(){
+pub fn move_data(){
     {
         // s1('s value), a String object, manages a string on the heap
         let s1: String = String::from("hello"); // <<-- s1 is valid hereon.
@@ -166,7 +166,7 @@ fn move_data(){
 }
 
 // [COPY]
-fn copy_data(){
+pub fn copy_data(){
     {
         // x('s value) trivially manages 5 on the stack
         let x: i32 = 5;      // <<-- x is valid hereon
@@ -176,7 +176,7 @@ fn copy_data(){
 }
 
 // [CLONE]
-fn clone_data(){
+pub fn clone_data(){
     {
         // s1('s value) manages a string on the heap
         let s1: String = String::from("hello");   // <<-- s1 is valid hereon
@@ -191,7 +191,7 @@ fn clone_data(){
 //
 // Passing a value as a function argument will transfer ownership exactly like when a value is assigned to a variable.
 //
-fn ownership_in_function_calls() {
+pub fn ownership_in_function_calls() {
     // s1 manages a string on the heap
     let s: String = String::from("hello"); // <<-- s is valid hereon
     // some_string manages the string whose ownership was moved from s.
@@ -211,12 +211,12 @@ fn ownership_in_function_calls() {
 } // <<-- Both x and s are out of scope (and invalid).
   //      Because s's ownership was already moved, there is nothing to drop.
 
-fn takes_ownership(some_string: String) {   // <<-- some_string is valid hereon
+pub fn takes_ownership(some_string: String) {   // <<-- some_string is valid hereon
   println!("{some_string}");
 } // <<-- some_string goes out of scope (and is no longer valid).
   //      Its managed data is dropped.
 
-fn makes_copy(some_integer: i32) {          // <<-- some_integer is valid hereon
+pub fn makes_copy(some_integer: i32) {          // <<-- some_integer is valid hereon
   println!("{some_integer}");
 } // <<-- some_integer goes out of scope (and is no longer valid).
 
@@ -225,7 +225,7 @@ fn makes_copy(some_integer: i32) {          // <<-- some_integer is valid hereon
 //
 // Returning a value from a function can also transfer ownership.
 //
-fn ownership_in_function_returns() {
+pub fn ownership_in_function_returns() {
     // s1 manages a string on the heap
     let s1 = gives_ownership();         // <<-- s1 is valid hereon
     // s2 manages a string on the heap
@@ -235,7 +235,7 @@ fn ownership_in_function_returns() {
 } // <<-- s3, s2, s1 go out of scope (and become invalid), and only s3's and s1's data are dropped.
   //      Because s2's ownership was moved, there is nothing to drop.
 
-fn gives_ownership() -> String {
+pub fn gives_ownership() -> String {
     // some_string manages a string on the heap
     let some_string = String::from("yours"); // <<-- some_string is valid hereon
     // some_string is returned and its ownership is moved
@@ -243,9 +243,31 @@ fn gives_ownership() -> String {
 } // <<-- some_string goes out of scope (and is no longer valid).
   //      Because its ownership was already moved, there is nothing to drop.
 
-fn takes_and_gives_back(a_string: String) -> String { // <<-- a_string is valid hereon
+pub fn takes_and_gives_back(a_string: String) -> String { // <<-- a_string is valid hereon
     // a_string is returned and its ownership is moved
     a_string
 }  // <<-- a_string goes out of scope (and is no longer valid).
   //       Because its ownership was already moved, there is nothing to drop.
 
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_examples() {
+    owner_example();
+    ownership_scope_example();
+    move_data();
+    copy_data();
+    clone_data();
+    ownership_in_function_calls();
+    ownership_in_function_returns();
+  }
+
+  #[test]
+  fn test_gives_and_takes_ownership() {
+    assert_eq!(gives_ownership(), "yours");
+    assert_eq!(takes_and_gives_back(String::from("hello")), "hello");
+  }
+}
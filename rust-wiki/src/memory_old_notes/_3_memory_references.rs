@@ -16,7 +16,7 @@
 // A reference is represented in memory as just one part:
 //   - { ptr : 0x... } A pointer to another variable or value.
 
-fn refs_vs_owners() -> String{
+pub fn refs_vs_owners() -> String{
     // x manages a string "golly" in memory (allocated on the heap)
     let x: String = String::from("golly");
     // y refers to, and borrows the value of, a string "gosh" in memory (allocated on the heap)
@@ -29,7 +29,7 @@ fn refs_vs_owners() -> String{
     return *y;  // Error: scope of y's borrowed value ends
 }
 
-fn refs_example_1(arg: &i32) -> &i32{
+pub fn refs_example_1(arg: &i32) -> &i32{
     // x manages 42
     let x = 42;
     // y is a reference to x
@@ -60,7 +60,7 @@ fn refs_example_1(arg: &i32) -> &i32{
     // | Temporary Value: 7                      | 4 bytes (stack-allocated integer, not owned by any variable)
     // +-----------------------------------------+
 
-fn ref_example_2(arg: &Box<i32>) -> &Box<i32> {
+pub fn ref_example_2(arg: &Box<i32>) -> &Box<i32> {
     // x manages 42 (allocated on the heap)
     let x = Box::new(42);
     // y is a reference to x
@@ -109,7 +109,7 @@ fn ref_example_2(arg: &Box<i32>) -> &Box<i32> {
 //    Note that it is not always unambigious to the Rust compiler whether it should automatically deference a variable,
 //    and so sometimes, we need to explicitly dereference it ourself
 
-fn reference_lifetime_and_deferencing_example() {
+pub fn reference_lifetime_and_deferencing_example() {
     // x refers to a value 10
     let ref_x: &u32 = &10;     // <<-- start of ref_x's lifetime (as a reference)
     // Explicitly deference and print out the value
@@ -138,7 +138,7 @@ fn reference_lifetime_and_deferencing_example() {
 //    a. a mutable reference cannot be declared
 //    b. the owner cannot change the referenced data
 
-fn shared_reference_example() {
+pub fn shared_reference_example() {
     // s is a mutable variable that manages the string "hello" (on the heap)
     let mut s = String::from("hello");
 
@@ -170,7 +170,7 @@ fn shared_reference_example() {
 //     b. No existing references can be used.
 //     c. The owner cannot be used.
 //  In other words, only one variable may actively refer to a value while it is being mutated.
-fn mut_reference_example() {
+pub fn mut_reference_example() {
     // s is a mutable owner of "hello" in memory
     let mut s = String::from("hello");
 
@@ -201,7 +201,7 @@ fn mut_reference_example() {
 //
 // As variables themselves can be mutable, we can also have combinations of (im)mutable variables that are (im)mutable references.
 // In other words, as well as modifying the referenced value, it is possible to modify what a reference points to.
-fn mutable_variables_and_references(){
+pub fn mutable_variables_and_references(){
     let mut x : i32 = 2;
 
     // r1 is a constant variable that is a shared reference to a (possibly mutable or immutable) i32 value.
@@ -243,3 +243,29 @@ fn mutable_variables_and_references(){
 //  2. Mutable references cannot be aliased.
 // Using pointers entails using unsafe Rust.
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refs_vs_owners() {
+        assert_eq!(refs_vs_owners(), "golly");
+    }
+
+    #[test]
+    fn test_ref_examples_return_arg() {
+        let arg: i32 = 1;
+        assert!(std::ptr::eq(refs_example_1(&arg), &arg));
+        let boxed: Box<i32> = Box::new(1);
+        assert!(std::ptr::eq(ref_example_2(&boxed), &boxed));
+    }
+
+    #[test]
+    fn test_examples() {
+        reference_lifetime_and_deferencing_example();
+        shared_reference_example();
+        mut_reference_example();
+        mutable_variables_and_references();
+    }
+}
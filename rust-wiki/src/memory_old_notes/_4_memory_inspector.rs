@@ -0,0 +1,541 @@
+// -----------------------------------------------
+// # MEMORY INSPECTOR: The Diagrams of _1_memory_layout.rs, From Live Data
+//
+// The diagrams in _1_memory_layout.rs are an informal mental model with made-up addresses. This module asks the
+// running process where things really are, and redraws them:
+//   1. The address of a static, a string literal, a local, a heap allocation and a function.
+//   2. Which segment each address falls in, looked up in /proc/self/maps (so this part only works on Linux).
+//   3. The size, alignment, field offsets, padding and niche of the wiki's own types, `User` and `Message`.
+//
+// Run it with:
+//    memory_inspector_example();
+
+use std::fmt::Write;
+use std::io;
+use std::mem::{align_of, size_of};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::_3_datatypes_and_traits::_1_structs::User;
+use crate::_3_datatypes_and_traits::_2_enums::Message;
+
+// -------------------------------------------------------------------------------------------------
+// ## Reading /proc/self/maps
+//
+// Each line of /proc/self/maps describes one mapped range of the process' virtual memory:
+//
+//    557b8b646000-557b8b64b000 r-xp 00002000 fe:00 317563     /usr/bin/cat
+//    557b9cefa000-557b9cf1b000 rw-p 00000000 00:00 0          [heap]
+//    ^ start      ^ end        ^ permissions                  ^ backing file, or a [pseudo] name, or nothing
+//
+// The executable itself is mapped several times, once per group of sections with the same permissions. That is
+// how the text and data segments of the overview diagram show up:
+//   - r-x  the text segment (machine code)
+//   - r--  read-only data: string literals, constant tables
+//   - rw-  the data segment: statics with an initial value
+//   - an anonymous rw- range directly after the last of these: the BSS, statics initialised to zero.
+//     (The BSS has no bytes in the file, so the OS maps fresh zeroed pages instead. The first few zeroed statics
+//     may still share the last file-backed page with the data segment, so a zeroed static can show up as Data.)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub start: usize,
+    pub end: usize,
+    pub perms: String,
+    pub path: Option<String>,
+}
+
+impl Mapping {
+    pub fn parse(line: &str) -> Option<Mapping> {
+        let mut parts = line.split_whitespace();
+        let (start, end) = parts.next()?.split_once('-')?;
+        let perms: String = parts.next()?.to_string();
+        // Skip the offset, device and inode columns; the rest (if any) is the path, which may contain spaces.
+        let path: String = parts.skip(3).collect::<Vec<&str>>().join(" ");
+        Some(Mapping {
+            start: usize::from_str_radix(start, 16).ok()?,
+            end: usize::from_str_radix(end, 16).ok()?,
+            perms,
+            path: if path.is_empty() { None } else { Some(path) },
+        })
+    }
+
+    pub fn contains(&self, addr: usize) -> bool {
+        self.start <= addr && addr < self.end
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Segment {
+    // Ordered from the bottom of the overview diagram (low addresses) to the top.
+    Text,
+    ReadOnlyData,
+    Data,
+    Bss,
+    Heap,
+    // Anonymous memory from `mmap`: large heap allocations, the heaps of other threads, and the stacks of
+    // threads other than the main one all live here.
+    Anonymous,
+    // A shared library or other mapped file.
+    OtherFile,
+    Stack,
+    Unmapped,
+}
+
+pub struct MemoryMap {
+    pub mappings: Vec<Mapping>,
+    pub exe: Option<PathBuf>,
+}
+
+impl MemoryMap {
+    // Reads the maps of the current process. Fails on systems without /proc, i.e. anything but Linux.
+    pub fn current() -> io::Result<MemoryMap> {
+        let maps: String = std::fs::read_to_string("/proc/self/maps")?;
+        let exe: Option<PathBuf> = std::env::current_exe().ok();
+        Ok(MemoryMap::parse(&maps, exe.as_deref()))
+    }
+
+    pub fn parse(maps: &str, exe: Option<&Path>) -> MemoryMap {
+        MemoryMap { mappings: maps.lines().filter_map(Mapping::parse).collect(), exe: exe.map(Path::to_path_buf) }
+    }
+
+    pub fn find(&self, addr: usize) -> Option<&Mapping> {
+        self.mappings.iter().find(|mapping| mapping.contains(addr))
+    }
+
+    fn is_exe(&self, mapping: &Mapping) -> bool {
+        match (&mapping.path, &self.exe) {
+            (Some(path), Some(exe)) => Path::new(path.trim_end_matches(" (deleted)")) == exe,
+            _ => false,
+        }
+    }
+
+    pub fn classify(&self, addr: usize) -> Segment {
+        let Some(index) = self.mappings.iter().position(|mapping| mapping.contains(addr)) else {
+            return Segment::Unmapped;
+        };
+        let mapping: &Mapping = &self.mappings[index];
+        match mapping.path.as_deref() {
+            Some("[heap]") => Segment::Heap,
+            Some("[stack]") => Segment::Stack,
+            Some(_) if self.is_exe(mapping) => {
+                if mapping.perms.contains('x') {
+                    Segment::Text
+                } else if mapping.perms.contains('w') {
+                    Segment::Data
+                } else {
+                    Segment::ReadOnlyData
+                }
+            }
+            Some(_) => Segment::OtherFile,
+            None => {
+                let follows_exe: bool = index > 0 && {
+                    let previous: &Mapping = &self.mappings[index - 1];
+                    previous.end == mapping.start && self.is_exe(previous)
+                };
+                if follows_exe { Segment::Bss } else { Segment::Anonymous }
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// ## Probing Addresses
+//
+// A reference converted to a raw pointer, and the pointer to an integer, is the address of the value it refers to.
+// (This is safe: only *dereferencing* a raw pointer needs `unsafe`.)
+pub fn address_of<T: ?Sized>(value: &T) -> usize {
+    (value as *const T).cast::<u8>() as usize
+}
+
+#[derive(Debug, Clone)]
+pub struct Probe {
+    pub label: String,
+    pub addr: usize,
+    pub segment: Segment,
+}
+
+// Statics with and without an initial value. They are atomics so that they are mutable without `unsafe`, which
+// also keeps the compiler from placing them in read-only memory.
+pub static INITIALISED_STATIC: AtomicUsize = AtomicUsize::new(42);
+pub static ZEROED_STATIC: AtomicUsize = AtomicUsize::new(0);
+// An immutable static may be placed in read-only memory.
+pub static CONSTANT_TABLE: [u32; 4] = [1, 2, 3, 4];
+
+// Probes one value of each kind, from the current thread. The maps are read while the values are still alive, since
+// e.g. the large allocation's mapping disappears as soon as it is freed.
+pub fn probe_process() -> io::Result<(MemoryMap, Vec<Probe>)> {
+    INITIALISED_STATIC.fetch_add(1, Ordering::Relaxed);
+    ZEROED_STATIC.fetch_add(1, Ordering::Relaxed);
+    let literal: &str = "hello";
+    let local: i32 = 48;
+    let small_box: Box<i32> = Box::new(48);
+    // Allocations above glibc's mmap threshold (128 KiB by default) get their own anonymous mapping.
+    let large_vec: Vec<u8> = vec![0; 1 << 20];
+    let function: fn(&i32) -> i32 = super::_1_memory_layout::_double;
+
+    let addresses: [(&str, usize); 8] = [
+        ("fn _double (function pointer)", function as usize),
+        ("\"hello\" (string literal)", address_of(literal)),
+        ("static CONSTANT_TABLE", address_of(&CONSTANT_TABLE)),
+        ("static INITIALISED_STATIC = 42", address_of(&INITIALISED_STATIC)),
+        ("static ZEROED_STATIC = 0", address_of(&ZEROED_STATIC)),
+        ("Box::new(48) (small allocation)", address_of(&*small_box)),
+        ("vec![0; 1 MiB] (large allocation)", address_of(&large_vec[..])),
+        ("let local = 48", address_of(&local)),
+    ];
+    let map: MemoryMap = MemoryMap::current()?;
+    let probes: Vec<Probe> = addresses
+        .into_iter()
+        .map(|(label, addr)| Probe { label: label.to_string(), addr, segment: map.classify(addr) })
+        .collect();
+    Ok((map, probes))
+}
+
+// Redraws the overview diagram: one box per segment, from high addresses (top) to low, listing the probes in it.
+pub fn render_memory_diagram(map: &MemoryMap, probes: &[Probe]) -> String {
+    const WIDTH: usize = 72;
+    let border: String = format!("+{}+\n", "-".repeat(WIDTH));
+    let mut probes: Vec<&Probe> = probes.iter().collect();
+    probes.sort_by(|a, b| b.segment.cmp(&a.segment).then(b.addr.cmp(&a.addr)));
+
+    let mut out = String::new();
+    let mut current: Option<Segment> = None;
+    for probe in probes {
+        if current != Some(probe.segment) {
+            current = Some(probe.segment);
+            out.push_str(&border);
+            let range: String = match map.find(probe.addr) {
+                Some(mapping) => format!("{:#x}-{:#x} {}", mapping.start, mapping.end, mapping.perms),
+                None => String::from("unmapped"),
+            };
+            let _ = writeln!(out, "| {:<w$} |", format!("{:?}  ({range})", probe.segment), w = WIDTH - 2);
+            out.push_str(&border);
+        }
+        let _ = writeln!(out, "| {:<w$} |", format!("  {:#016x}  {}", probe.addr, probe.label), w = WIDTH - 2);
+    }
+    out.push_str(&border);
+    out
+}
+
+// -------------------------------------------------------------------------------------------------
+// ## The Stack Frames of `_main` and `_double`
+//
+// The same program as the stack example in _1_memory_layout.rs, recording where each variable really lives.
+// `#[inline(never)]` keeps `_double` a real function call, with a stack frame of its own.
+pub struct StackSlot {
+    pub frame: &'static str,
+    pub name: &'static str,
+    pub addr: usize,
+    pub value: String,
+}
+
+pub fn stack_frames() -> Vec<StackSlot> {
+    let mut slots: Vec<StackSlot> = Vec::new();
+    let x: i32 = 48;
+    let y: &i32 = &x;
+    slots.push(StackSlot { frame: "_main()", name: "x", addr: address_of(&x), value: x.to_string() });
+    slots.push(StackSlot { frame: "_main()", name: "y", addr: address_of(&y), value: format!("{:#x}", address_of(y)) });
+    let z: i32 = traced_double(y, &mut slots);
+    slots.push(StackSlot { frame: "_main()", name: "z", addr: address_of(&z), value: z.to_string() });
+    slots
+}
+
+#[inline(never)]
+fn traced_double(n: &i32, slots: &mut Vec<StackSlot>) -> i32 {
+    slots.push(StackSlot { frame: "_double()", name: "n", addr: address_of(&n), value: format!("{:#x}", address_of(n)) });
+    n * 2
+}
+
+// Drawn in the order the slots were pushed, like the original diagram. Compare the addresses: `n`, in the frame of
+// the called function, is *below* the variables of `_main`, because the stack grows downwards.
+pub fn render_stack_frames(slots: &[StackSlot]) -> String {
+    const WIDTH: usize = 40;
+    let border: String = format!("+{}+", "-".repeat(WIDTH));
+    let mut out = String::new();
+    let mut frame: Option<&str> = None;
+    for slot in slots {
+        if frame != Some(slot.frame) {
+            frame = Some(slot.frame);
+            let _ = writeln!(out, "{border}\n| {:<w$} |", format!("Stack Frame: {}", slot.frame), w = WIDTH - 2);
+        }
+        let _ = writeln!(out, "{border} {:#x}\n| {:<w$} |", slot.addr, format!("{}: {}", slot.name, slot.value), w = WIDTH - 2);
+    }
+    let _ = writeln!(out, "{border}");
+    out
+}
+
+// -------------------------------------------------------------------------------------------------
+// ## Type Layouts
+//
+// `size_of` and `align_of` give the size and alignment of a type, and `offset_of!` the position of a struct field.
+// Rust is free to reorder fields (unless the type is `#[repr(C)]`), so the order in memory is often not the order
+// of declaration: fields are usually sorted by alignment, largest first, to minimise padding.
+//
+// A "niche" is a bit pattern a type never uses, e.g. 0 for a reference or `Box`, or 2..=255 for a `bool`.
+// An enum can store its discriminant in a niche of its payload instead of in extra bytes, which is why
+// `Option<&T>` is as small as `&T`. `Option<T>` being as large as `T` is a simple test for a niche.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub offset: usize,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeLayout {
+    pub name: &'static str,
+    pub size: usize,
+    pub align: usize,
+    pub option_size: usize,
+    // Sorted by offset.
+    pub fields: Vec<FieldLayout>,
+}
+
+impl TypeLayout {
+    pub fn new<T>(name: &'static str, mut fields: Vec<FieldLayout>) -> TypeLayout {
+        fields.sort_by_key(|field| field.offset);
+        TypeLayout { name, size: size_of::<T>(), align: align_of::<T>(), option_size: size_of::<Option<T>>(), fields }
+    }
+
+    pub fn padding(&self) -> usize {
+        self.size - self.fields.iter().map(|field| field.size).sum::<usize>()
+    }
+
+    pub fn has_niche(&self) -> bool {
+        self.option_size == self.size
+    }
+}
+
+// Gets the size of a field's type from a closure that selects the field, e.g. `|user: &User| &user.active`.
+pub fn field_size<T, F>(_select: fn(&T) -> &F) -> usize {
+    size_of::<F>()
+}
+
+pub fn field_type_name<T, F>(_select: fn(&T) -> &F) -> &'static str {
+    let name: &'static str = std::any::type_name::<F>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+// Builds the layout of a struct from its type and field names.
+macro_rules! struct_layout {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        TypeLayout::new::<$ty>(stringify!($ty), vec![$(
+            FieldLayout {
+                name: stringify!($field),
+                type_name: field_type_name(|value: &$ty| &value.$field),
+                offset: std::mem::offset_of!($ty, $field),
+                size: field_size(|value: &$ty| &value.$field),
+            }
+        ),*])
+    };
+}
+
+pub fn user_layout() -> TypeLayout {
+    struct_layout!(User { active, sign_in_count, username })
+}
+
+pub fn render_type_layout(layout: &TypeLayout) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{}: size {}, align {}, Option<{}>: size {} ({})",
+        layout.name,
+        layout.size,
+        layout.align,
+        layout.name,
+        layout.option_size,
+        if layout.has_niche() { "has a niche" } else { "no niche" },
+    );
+    let border = "+--------+--------------------------------+";
+    let _ = writeln!(out, "{border}\n| offset | field                          |\n{border}");
+    let mut end: usize = 0;
+    for field in &layout.fields {
+        if field.offset > end {
+            let _ = writeln!(out, "| {:<6} | {:<30} |", end, format!("(padding: {} bytes)", field.offset - end));
+        }
+        let _ = writeln!(out, "| {:<6} | {:<30} |", field.offset, format!("{}: {} ({} bytes)", field.name, field.type_name, field.size));
+        end = field.offset + field.size;
+    }
+    if layout.size > end {
+        let _ = writeln!(out, "| {:<6} | {:<30} |", end, format!("(padding: {} bytes)", layout.size - end));
+    }
+    let _ = writeln!(out, "{border}");
+    out
+}
+
+// The fields of enum variants have no stable `offset_of!`, so for an enum we report each variant's payload size.
+// If the enum is no larger than its largest payload, the discriminant is hidden in a niche of that payload.
+// For `Message`, the niche is in `String`'s capacity, which can never exceed `isize::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumLayout {
+    pub name: &'static str,
+    pub size: usize,
+    pub align: usize,
+    pub option_size: usize,
+    pub variants: Vec<(&'static str, usize)>,
+}
+
+impl EnumLayout {
+    pub fn largest_payload(&self) -> usize {
+        self.variants.iter().map(|(_, size)| *size).max().unwrap_or(0)
+    }
+
+    // Bytes used by the discriminant (and any padding around it); 0 if it is stored in a niche.
+    pub fn tag_overhead(&self) -> usize {
+        self.size - self.largest_payload()
+    }
+}
+
+pub fn message_layout() -> EnumLayout {
+    EnumLayout {
+        name: "Message",
+        size: size_of::<Message>(),
+        align: align_of::<Message>(),
+        option_size: size_of::<Option<Message>>(),
+        variants: vec![
+            ("Quit", size_of::<()>()),
+            ("Move { x: i32, y: i32 }", size_of::<(i32, i32)>()),
+            ("Write(String)", size_of::<String>()),
+            ("ChangeColor(i32, i32, i32)", size_of::<(i32, i32, i32)>()),
+        ],
+    }
+}
+
+pub fn render_enum_layout(layout: &EnumLayout) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{}: size {}, align {}, Option<{}>: size {}",
+        layout.name, layout.size, layout.align, layout.name, layout.option_size
+    );
+    for (variant, size) in &layout.variants {
+        let _ = writeln!(out, "  {variant:<28} payload {size:>2} bytes");
+    }
+    let _ = match layout.tag_overhead() {
+        0 => writeln!(out, "  discriminant: stored in a niche of the largest payload (0 extra bytes)"),
+        n => writeln!(out, "  discriminant: {n} extra bytes (tag and padding)"),
+    };
+    out
+}
+
+// -------------------------------------------------------------------------------------------------
+// ## Example
+pub fn memory_inspector_example() -> io::Result<()> {
+    let (map, probes) = probe_process()?;
+    println!("{}", render_memory_diagram(&map, &probes));
+    println!("{}", render_stack_frames(&stack_frames()));
+    println!("{}", render_type_layout(&user_layout()));
+    println!("{}", render_enum_layout(&message_layout()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPS: &str = "\
+55d0a0000000-55d0a0002000 r--p 00000000 fe:00 1234  /usr/bin/wiki
+55d0a0002000-55d0a0005000 r-xp 00002000 fe:00 1234  /usr/bin/wiki
+55d0a0005000-55d0a0006000 rw-p 00005000 fe:00 1234  /usr/bin/wiki
+55d0a0006000-55d0a0008000 rw-p 00000000 00:00 0
+55d0a1000000-55d0a1021000 rw-p 00000000 00:00 0     [heap]
+7f0000000000-7f0000100000 rw-p 00000000 00:00 0
+7f0000100000-7f0000120000 r-xp 00000000 fe:00 99    /usr/lib/libc.so.6
+7ffe00000000-7ffe00021000 rw-p 00000000 00:00 0     [stack]
+";
+
+    #[test]
+    fn test_parse_mapping() {
+        let mapping = Mapping::parse("7f0000100000-7f0000120000 r-xp 00000000 fe:00 99  /usr/lib/libc.so.6").unwrap();
+        assert_eq!(mapping.start, 0x7f0000100000);
+        assert_eq!(mapping.end, 0x7f0000120000);
+        assert_eq!(mapping.perms, "r-xp");
+        assert_eq!(mapping.path.as_deref(), Some("/usr/lib/libc.so.6"));
+        assert!(mapping.contains(0x7f0000100000));
+        assert!(!mapping.contains(0x7f0000120000));
+        assert_eq!(Mapping::parse("7f0000000000-7f0000100000 rw-p 00000000 00:00 0").unwrap().path, None);
+        assert_eq!(Mapping::parse("not a mapping"), None);
+    }
+
+    #[test]
+    fn test_classify_sample_maps() {
+        let map = MemoryMap::parse(MAPS, Some(Path::new("/usr/bin/wiki")));
+        assert_eq!(map.classify(0x55d0a0000010), Segment::ReadOnlyData);
+        assert_eq!(map.classify(0x55d0a0003000), Segment::Text);
+        assert_eq!(map.classify(0x55d0a0005008), Segment::Data);
+        assert_eq!(map.classify(0x55d0a0006008), Segment::Bss);
+        assert_eq!(map.classify(0x55d0a1000010), Segment::Heap);
+        assert_eq!(map.classify(0x7f0000000010), Segment::Anonymous);
+        assert_eq!(map.classify(0x7f0000100010), Segment::OtherFile);
+        assert_eq!(map.classify(0x7ffe00000010), Segment::Stack);
+        assert_eq!(map.classify(0x10), Segment::Unmapped);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_live_addresses_are_classified() {
+        let (map, probes) = probe_process().unwrap();
+        let segment_of = |prefix: &str| probes.iter().find(|probe| probe.label.starts_with(prefix)).unwrap().segment;
+
+        assert_eq!(segment_of("fn _double"), Segment::Text);
+        assert_eq!(segment_of("\"hello\""), Segment::ReadOnlyData);
+        assert_eq!(segment_of("static CONSTANT_TABLE"), Segment::ReadOnlyData);
+        assert_eq!(segment_of("static INITIALISED_STATIC"), Segment::Data);
+        assert!(matches!(segment_of("static ZEROED_STATIC"), Segment::Data | Segment::Bss));
+        // Test threads are not the main thread: their stacks and malloc arenas are anonymous mappings.
+        assert!(matches!(segment_of("Box::new"), Segment::Heap | Segment::Anonymous));
+        // Which one depends on the allocator (glibc's mmap threshold and arena placement), not on Rust.
+        assert!(matches!(segment_of("vec!"), Segment::Heap | Segment::Anonymous));
+        assert!(matches!(segment_of("let local"), Segment::Stack | Segment::Anonymous));
+
+        let diagram = render_memory_diagram(&map, &probes);
+        assert!(diagram.contains("Text"));
+        assert!(diagram.contains(&format!("{:#016x}", address_of(&INITIALISED_STATIC))));
+    }
+
+    #[test]
+    fn test_stack_grows_downwards() {
+        let slots = stack_frames();
+        let addr_of = |name: &str| slots.iter().find(|slot| slot.name == name).unwrap().addr;
+        assert!(addr_of("n") < addr_of("x"));
+        assert!(addr_of("n") < addr_of("y"));
+        // `y` and `n` both hold the address of `x`.
+        let x = format!("{:#x}", addr_of("x"));
+        assert!(slots.iter().filter(|slot| slot.value == x).count() == 2);
+        assert!(render_stack_frames(&slots).contains("Stack Frame: _double()"));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_user_layout() {
+        let layout = user_layout();
+        assert_eq!((layout.size, layout.align), (40, 8));
+        // 33 bytes of fields rounded up to a multiple of the alignment: 7 bytes of padding in total. Where they go
+        // depends on the field order rustc picks, which is unspecified for the default `repr(Rust)`.
+        assert_eq!(layout.padding(), 7);
+        assert!(layout.fields.windows(2).all(|pair| pair[0].offset + pair[0].size <= pair[1].offset));
+        // The `bool` (and `String`) have niches, so `Option<User>` needs no extra space.
+        assert!(layout.has_niche());
+        assert!(render_type_layout(&layout).contains("(padding: "));
+    }
+
+    #[test]
+    fn test_message_layout() {
+        let layout = message_layout();
+        // rustc does not guarantee where (or whether) `Message` hides its discriminant. Current rustc uses the niche
+        // in `String`'s capacity, so `size == size_of::<String>()`, but only the bounds below hold for any layout.
+        assert!(layout.size >= layout.largest_payload());
+        assert!(layout.option_size >= layout.size);
+        assert_eq!(render_enum_layout(&layout).contains("stored in a niche"), layout.tag_overhead() == 0);
+        // The niches the language does guarantee: a `Box` or a reference is never null.
+        assert_eq!(size_of::<Option<Box<Message>>>(), size_of::<Box<Message>>());
+        assert_eq!(size_of::<Option<&Message>>(), size_of::<&Message>());
+    }
+
+    #[test]
+    fn test_field_helpers() {
+        assert_eq!(field_size(|user: &User| &user.sign_in_count), 8);
+        assert_eq!(field_type_name(|user: &User| &user.username), "String");
+    }
+}
@@ -0,0 +1,88 @@
+// Compile-fail harness for the wiki's commented-out "Error!" lines.
+//
+// Each file in tests/compile_fail/ reproduces one of those lines in just enough context to compile on its own, and
+// starts with a header naming where the line lives and which error the compiler should report:
+//
+//    // wiki: src/_1_basics/_1_variables.rs
+//    // snippet: // x = 6;        // not allowed
+//    // error: E0384
+//
+// For every case, the harness checks that:
+//   1. The snippet is still a line of the wiki file, so a case cannot outlive the claim it verifies.
+//   2. rustc rejects the case, reporting the documented error code.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+struct Case {
+    path: PathBuf,
+    wiki: String,
+    snippet: String,
+    error: String,
+}
+
+impl Case {
+    fn parse(path: &Path) -> Result<Case, String> {
+        let source: String = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let header = |key: &str| -> Result<String, String> {
+            let prefix = format!("// {key}: ");
+            source
+                .lines()
+                .find_map(|line| line.strip_prefix(&prefix))
+                .map(str::to_string)
+                .ok_or(format!("missing `{prefix}` header"))
+        };
+        Ok(Case { path: path.to_path_buf(), wiki: header("wiki")?, snippet: header("snippet")?, error: header("error")? })
+    }
+
+    fn check(&self, manifest_dir: &Path, out_dir: &Path) -> Result<(), String> {
+        let wiki_source: String =
+            fs::read_to_string(manifest_dir.join(&self.wiki)).map_err(|e| format!("{}: {e}", self.wiki))?;
+        if !wiki_source.lines().any(|line| line.trim() == self.snippet.trim()) {
+            return Err(format!("snippet `{}` no longer appears in {}", self.snippet, self.wiki));
+        }
+
+        let rustc: String = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+        let output = Command::new(rustc)
+            .args(["--edition", "2021", "--crate-type", "lib", "--emit=metadata", "--out-dir"])
+            .arg(out_dir)
+            .arg(&self.path)
+            .output()
+            .map_err(|e| format!("could not run rustc: {e}"))?;
+        let stderr: String = String::from_utf8_lossy(&output.stderr).into_owned();
+
+        if output.status.success() {
+            return Err(String::from("compiled successfully, but the wiki says it should not"));
+        }
+        if !stderr.contains(&format!("error[{}]", self.error)) {
+            return Err(format!("expected error[{}], but rustc reported:\n{stderr}", self.error));
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn wiki_error_examples_fail_to_compile() {
+    let manifest_dir: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir: PathBuf = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_fail");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(manifest_dir.join("tests/compile_fail"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no compile-fail cases found");
+
+    let failures: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            let result = Case::parse(path).and_then(|case| case.check(manifest_dir, &out_dir));
+            result.err().map(|e| format!("{}: {e}", path.display()))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "{} of {} compile-fail cases failed:\n{}", failures.len(), paths.len(), failures.join("\n\n"));
+}
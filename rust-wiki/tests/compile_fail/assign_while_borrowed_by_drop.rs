@@ -0,0 +1,14 @@
+// wiki: src/_2_ownership/_3_references_and_lifetimes.rs
+// snippet: //  x = 6; // ERROR: we haven't finished using the immutable reference to `data` that `x` contains.
+// error: E0506
+
+#[derive(Debug)]
+struct X<'a>(&'a i32);
+impl Drop for X<'_> { fn drop(&mut self) {} }
+
+fn refs_lifetimes_subtleties() {
+    let mut x: i32 = 5;
+    let xrefcontainer: X<'_> = X(&x);
+    println!("{:?}", xrefcontainer);
+    x = 6;
+}
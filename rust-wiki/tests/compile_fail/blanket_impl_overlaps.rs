@@ -0,0 +1,21 @@
+// wiki: src/_14_advanced_traits/_2_blanket_impls.rs
+// snippet: //    impl Show for i32 { .. }    // Error! conflicting implementations of trait `Show` for type `i32`
+// error: E0119
+
+use std::fmt::Display;
+
+pub trait Show {
+    fn show(&self) -> String;
+}
+
+impl<T: Display> Show for T {
+    fn show(&self) -> String {
+        format!("<{self}>")
+    }
+}
+
+impl Show for i32 {
+    fn show(&self) -> String {
+        format!("i32 {self}")
+    }
+}
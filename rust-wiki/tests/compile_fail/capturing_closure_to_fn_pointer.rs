@@ -0,0 +1,9 @@
+// wiki: src/_5_functional_features/_4_closure_apis.rs
+// snippet: //    let f: fn(i32) -> i32 = |x| x + offset;     // Error! closures can only be coerced to `fn` types if they do not capture any variables
+// error: E0308
+
+pub fn pointer() -> i32 {
+    let offset: i32 = 1;
+    let f: fn(i32) -> i32 = |x| x + offset;
+    f(1)
+}
@@ -0,0 +1,12 @@
+// wiki: src/_15_advanced_lifetimes/_3_higher_ranked_bounds.rs
+// snippet: //      f(&line).to_string()           // Error! `line` does not live long enough
+// error: E0597
+
+pub fn apply_to_lines<'a, F: Fn(&'a str) -> &'a str>(text: &str, f: F) -> Vec<String> {
+    let mut results = Vec::new();
+    for line in text.lines() {
+        let line: String = line.replace('\t', " ");
+        results.push(f(&line).to_string());
+    }
+    results
+}
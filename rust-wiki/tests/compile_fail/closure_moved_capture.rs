@@ -0,0 +1,10 @@
+// wiki: src/_5_functional_features/_1_closures.rs
+// snippet: // println!("{xs:?}"); // Error!
+// error: E0382
+
+fn closure_move_in() {
+    let x: i32 = 2;
+    let xs: Vec<i32> = vec![1, 2, 3];
+    let fn_move_in = move || { print!("{x:?}, {xs:?}") };
+    println!("{xs:?}");
+}
@@ -0,0 +1,5 @@
+// wiki: src/_4_generics/_2_lifetime_annotations.rs
+// snippet: //   fn invalid_output<'a>() -> &'a String { &String::from("foo") }
+// error: E0515
+
+fn invalid_output<'a>() -> &'a String { &String::from("foo") }
@@ -0,0 +1,22 @@
+// wiki: src/_15_advanced_lifetimes/_1_borrowing_self.rs
+// snippet: //    let second = tokens.next_token_elided();   // Error! cannot borrow `tokens` as mutable more than once at a time
+// error: E0499
+
+pub struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn next_token_elided(&mut self) -> Option<&str> {
+        let (token, rest) = self.rest.split_once(' ')?;
+        self.rest = rest;
+        Some(token)
+    }
+}
+
+pub fn two_tokens(input: &str) -> String {
+    let mut tokens = Tokens { rest: input };
+    let first = tokens.next_token_elided();
+    let second = tokens.next_token_elided();
+    format!("{first:?} {second:?}")
+}
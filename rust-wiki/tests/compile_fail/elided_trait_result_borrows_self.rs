@@ -0,0 +1,27 @@
+// wiki: src/_4_generics/_2_lifetime_annotations.rs
+// snippet: //    title = article.summarize();    // Error! `article` does not live long enough
+// error: E0597
+
+pub trait Summary {
+    fn summarize(&self) -> &str;
+}
+
+pub struct Article<'a> {
+    pub title: &'a str,
+    pub content: &'a str,
+}
+
+impl<'a> Summary for Article<'a> {
+    fn summarize(&self) -> &str {
+        self.title
+    }
+}
+
+pub fn main3() -> String {
+    let title: &str;
+    {
+        let article = Article { title: "hello", content: "world" };
+        title = article.summarize();
+    }
+    title.to_string()
+}
@@ -0,0 +1,11 @@
+// wiki: src/_2_ownership/_3_references_and_lifetimes.rs
+// snippet: // print!("{immut_ref_s}"); // ERROR: mut_ref_s is still being used
+// error: E0502
+
+fn mut_reference_example() {
+    let mut s = String::from("hello");
+    let immut_ref_s: &String = &s;
+    let mut_ref_s: &mut String = &mut s;
+    print!("{immut_ref_s}");
+    mut_ref_s.push_str("s");
+}
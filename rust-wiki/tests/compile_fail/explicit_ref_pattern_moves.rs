@@ -0,0 +1,10 @@
+// wiki: src/_3_datatypes_and_traits/_5_patterns.rs
+// snippet: //    if let &Some(s) = &name { }    // Error! cannot move out of a shared reference
+// error: E0507
+
+fn name_length(name: Option<String>) -> usize {
+    if let &Some(s) = &name {
+        return s.len();
+    }
+    0
+}
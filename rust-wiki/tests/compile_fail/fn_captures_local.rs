@@ -0,0 +1,8 @@
+// wiki: src/_5_functional_features/_1_closures.rs
+// snippet: // fn add_one_fundef (y: u32) -> u32 { y + one_var } ERROR!
+// error: E0434
+
+fn closures_vs_fns() {
+    let one_var: u32 = 1;
+    fn add_one_fundef(y: u32) -> u32 { y + one_var }
+}
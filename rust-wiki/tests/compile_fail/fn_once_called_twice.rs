@@ -0,0 +1,10 @@
+// wiki: src/_5_functional_features/_1_closures.rs
+// snippet: // fn_once(); // Error: use of moved value `xs` after it has already been moved
+// error: E0382
+
+fn fn_trait() {
+    let xs: Vec<i32> = vec![1, 2, 3];
+    let fn_once = || { return xs };
+    fn_once();
+    fn_once();
+}
@@ -0,0 +1,14 @@
+// wiki: src/_15_advanced_lifetimes/_5_reborrowing.rs
+// snippet: //    r.push(1);        // Error! borrow of moved value: `r`
+// error: E0382
+
+pub fn take<T>(value: T) -> T {
+    value
+}
+
+pub fn moved() {
+    let mut numbers: Vec<i32> = Vec::new();
+    let r: &mut Vec<i32> = &mut numbers;
+    take(r);
+    r.push(1);
+}
@@ -0,0 +1,9 @@
+// wiki: src/_1_basics/_1_variables.rs
+// snippet: // x = 6;        // not allowed
+// error: E0384
+
+fn immutable_variables() {
+    let x: i32 = 5;
+    x = 6;
+    println!("{x}");
+}
@@ -0,0 +1,31 @@
+// wiki: src/_13_trait_objects/_1_dyn_trait.rs
+// snippet: //      if ... { Circle { .. } } else { Square { .. } }    // Error! `if` and `else` have incompatible types
+// error: E0308
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Circle {
+    pub radius: f64,
+}
+
+pub struct Square {
+    pub side: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        3.14 * self.radius * self.radius
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+
+pub fn parse_shape(s: &str) -> impl Shape {
+    if s.starts_with("circle") { Circle { radius: 1.0 } } else { Square { side: 1.0 } }
+}
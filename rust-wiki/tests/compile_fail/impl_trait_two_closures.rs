@@ -0,0 +1,7 @@
+// wiki: src/_5_functional_features/_4_closure_apis.rs
+// snippet: //      if op == '+' { move |a, b| (a + b) * scale } else { move |a, b| (a - b) * scale }     // Error! `if` and `else` have incompatible types
+// error: E0308
+
+pub fn make_scaled(op: char, scale: i32) -> impl Fn(i32, i32) -> i32 {
+    if op == '+' { move |a, b| (a + b) * scale } else { move |a, b| (a - b) * scale }
+}
@@ -0,0 +1,16 @@
+// wiki: src/_15_advanced_lifetimes/_2_variance.rs
+// snippet: //      overwrite(&mut greeting, &local);    // Error! `local` does not live long enough
+// error: E0597
+
+pub fn overwrite<'a>(target: &mut &'a str, value: &'a str) {
+    *target = value;
+}
+
+pub fn dangling() {
+    let mut greeting: &'static str = "hello";
+    {
+        let local: String = String::from("bye");
+        overwrite(&mut greeting, &local);
+    }
+    println!("{greeting}");
+}
@@ -0,0 +1,8 @@
+// wiki: src/_5_functional_features/_2_iterators.rs
+// snippet: // let bad_borrowing_iter = vec![1,2,3].iter(); // Error: temporary vector [1,2,3] is dropped while borrowed.
+// error: E0716
+
+fn borrowing_iterator() {
+    let bad_borrowing_iter = vec![1, 2, 3].iter();
+    let x: Vec<i32> = bad_borrowing_iter.map(|x| x + 1).collect();
+}
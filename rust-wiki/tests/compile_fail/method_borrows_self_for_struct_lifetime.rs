@@ -0,0 +1,21 @@
+// wiki: src/_15_advanced_lifetimes/_1_borrowing_self.rs
+// snippet: //    tokens.next_token_locked();                // Error! cannot borrow `tokens` as mutable more than once at a time
+// error: E0499
+
+pub struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn next_token_locked(&'a mut self) -> Option<&'a str> {
+        let (token, rest) = self.rest.split_once(' ')?;
+        self.rest = rest;
+        Some(token)
+    }
+}
+
+pub fn skip_two(input: &str) {
+    let mut tokens = Tokens { rest: input };
+    tokens.next_token_locked();
+    tokens.next_token_locked();
+}
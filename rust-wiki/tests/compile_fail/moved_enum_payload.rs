@@ -0,0 +1,13 @@
+// wiki: src/_3_datatypes_and_traits/_2_enums.rs
+// snippet: // let msg2 = msg; // Error: msg was partially moved
+// error: E0382
+
+enum Message {
+    Quit,
+    Write(String),
+}
+
+fn matching_with_if_let(msg: Message) {
+    let msg1: Message = if let Message::Write(s) = msg { Message::Write(s) } else { Message::Quit };
+    let msg2 = msg;
+}
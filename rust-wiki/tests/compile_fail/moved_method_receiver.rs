@@ -0,0 +1,21 @@
+// wiki: src/_3_datatypes_and_traits/_3_methods.rs
+// snippet: // let m2: Message = m; // Error: Use of moved value m.
+// error: E0382
+
+enum Message {
+    Move { x: i32, y: i32 },
+}
+
+impl Message {
+    fn sum_withselfval(self) -> i32 {
+        match self {
+            Message::Move { x, y } => x + y,
+        }
+    }
+}
+
+fn using_methods_example_2() {
+    let m: Message = Message::Move { x: 5, y: 5 };
+    let x1: i32 = m.sum_withselfval();
+    let m2: Message = m;
+}
@@ -0,0 +1,13 @@
+// wiki: src/_3_datatypes_and_traits/_1_structs.rs
+// snippet: // print!("{0}", moveable_user.active); // Error: moveable_user's value, and hence moveable_user.active, was moved
+// error: E0382
+
+struct UserMove {
+    active: bool,
+    username: String,
+}
+
+fn move_struct(moveable_user: UserMove) {
+    let moved_user = moveable_user;
+    print!("{0}", moveable_user.active);
+}
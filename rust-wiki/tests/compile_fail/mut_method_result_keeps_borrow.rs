@@ -0,0 +1,27 @@
+// wiki: src/_15_advanced_lifetimes/_5_reborrowing.rs
+// snippet: //    let size = cache.len();         // Error! cannot borrow `cache` as immutable because it is also borrowed as mutable
+// error: E0502
+
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct Cache {
+    values: HashMap<u64, u64>,
+}
+
+impl Cache {
+    pub fn get_or_compute(&mut self, n: u64) -> &u64 {
+        self.values.entry(n).or_insert_with(|| (1..=n).sum())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+pub fn both() {
+    let mut cache = Cache::default();
+    let value = cache.get_or_compute(10);
+    let size = cache.len();
+    println!("{value} {size}");
+}
@@ -0,0 +1,20 @@
+// wiki: src/_14_advanced_traits/_5_newtype.rs
+// snippet: //    let total = Meters(1.0) + 500.0;    // Error! mismatched types: expected `Meters`, found floating-point number
+// error: E0308
+
+use std::ops::Add;
+
+pub struct Meters(pub f64);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, rhs: Meters) -> Meters {
+        Meters(self.0 + rhs.0)
+    }
+}
+
+pub fn total() -> Meters {
+    let total = Meters(1.0) + 500.0;
+    total
+}
@@ -0,0 +1,14 @@
+// wiki: src/_3_datatypes_and_traits/_5_patterns.rs
+// snippet: //    match op { BinOp::Add => 1, BinOp::Sub => 2 }    // Error! non-exhaustive patterns: `BinOp::Mul` and `BinOp::Div` not covered
+// error: E0004
+
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+fn precedence(op: BinOp) -> u8 {
+    match op { BinOp::Add => 1, BinOp::Sub => 2 }
+}
@@ -0,0 +1,11 @@
+// wiki: src/_2_ownership/_4_slices.rs
+// snippet: // println!("First element of the slice: {}", r1[0]); // Error: this would mean r2 was used during the lifetime of r1.
+// error: E0499
+
+fn mutable_slices() {
+    let mut xs: [i32; 5] = [1, 2, 3, 4, 5];
+    let r1: &mut [i32] = &mut xs[1..4];
+    let r2: &mut [i32] = &mut xs[3..];
+    println!("First element of the slice: {}", r2[0]);
+    println!("First element of the slice: {}", r1[0]);
+}
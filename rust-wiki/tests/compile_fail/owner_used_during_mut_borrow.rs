@@ -0,0 +1,10 @@
+// wiki: src/_2_ownership/_3_references_and_lifetimes.rs
+// snippet: // s.push_str("s"); // ERROR: mut_ref_s is still being used
+// error: E0499
+
+fn mut_reference_example() {
+    let mut s = String::from("hello");
+    let mut_ref_s: &mut String = &mut s;
+    s.push_str("s");
+    mut_ref_s.push_str("s");
+}
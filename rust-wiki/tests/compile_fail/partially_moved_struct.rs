@@ -0,0 +1,16 @@
+// wiki: src/_2_ownership/_2_owners_and_scope.rs
+// snippet: // println!("The person struct is {:?}", person); // Error! borrow of partially moved value.
+// error: E0382
+
+#[derive(Debug)]
+struct Person {
+    first_name: String,
+    last_name: String,
+    age: u8,
+    id: Box<u8>,
+}
+
+fn partial_move_copy_clone_reference(person: Person) {
+    let last_name: String = person.last_name;
+    println!("The person struct is {:?}", person);
+}
@@ -0,0 +1,13 @@
+// wiki: src/_3_datatypes_and_traits/_1_structs.rs
+// snippet: // print!("{0}", moveable_user.username);   // Error: moveable_user.username was moved
+// error: E0382
+
+struct UserMove {
+    active: bool,
+    username: String,
+}
+
+fn move_struct(moveable_user: UserMove) {
+    let moved_username: String = moveable_user.username;
+    print!("{0}", moveable_user.username);
+}
@@ -0,0 +1,10 @@
+// wiki: src/_17_unsafe/_1_raw_pointers.rs
+// snippet: //    let y: i32 = *p;             // Error! dereference of raw pointer is unsafe and requires unsafe function or block
+// error: E0133
+
+pub fn read() -> i32 {
+    let x: i32 = 5;
+    let p: *const i32 = &x;
+    let y: i32 = *p;
+    y
+}
@@ -0,0 +1,8 @@
+// wiki: src/_7_smart_pointers/_1_box.rs
+// snippet: //      Cons(i32, List),   // Error! recursive type `List` has infinite size
+// error: E0072
+
+enum List {
+    Cons(i32, List),
+    Nil,
+}
@@ -0,0 +1,8 @@
+// wiki: src/_3_datatypes_and_traits/_5_patterns.rs
+// snippet: //    let Some(n) = "42".parse::<i32>().ok();    // Error! refutable pattern in local binding
+// error: E0005
+
+fn parse() -> i32 {
+    let Some(n) = "42".parse::<i32>().ok();
+    n
+}
@@ -0,0 +1,10 @@
+// wiki: src/_2_ownership/_3_references_and_lifetimes.rs
+// snippet: // let new_immut_ref_s: &String = &s; // ERROR: mut_ref_s is used later
+// error: E0502
+
+fn mut_reference_example() {
+    let mut s = String::from("hello");
+    let mut_ref_s: &mut String = &mut s;
+    let new_immut_ref_s: &String = &s;
+    mut_ref_s.push_str("s");
+}
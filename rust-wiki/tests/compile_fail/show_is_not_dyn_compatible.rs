@@ -0,0 +1,28 @@
+// wiki: src/_13_trait_objects/_2_object_safety.rs
+// snippet: //    let shown: &dyn Show<AltShowType = Vec<char>> = &user;   // Error! the trait `Show` is not dyn compatible
+// error: E0038
+
+pub trait Show {
+    type AltShowType;
+    fn alt_show(s: String) -> Self::AltShowType;
+    fn show(&self) -> String;
+}
+
+pub struct User {
+    pub username: String,
+}
+
+impl Show for User {
+    type AltShowType = Vec<char>;
+    fn alt_show(s: String) -> Self::AltShowType {
+        s.chars().collect()
+    }
+    fn show(&self) -> String {
+        self.username.clone()
+    }
+}
+
+pub fn show_dyn(user: User) -> String {
+    let shown: &dyn Show<AltShowType = Vec<char>> = &user;
+    shown.show()
+}
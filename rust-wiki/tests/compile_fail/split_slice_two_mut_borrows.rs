@@ -0,0 +1,8 @@
+// wiki: src/_17_unsafe/_1_raw_pointers.rs
+// snippet: //    let (left, right) = (&mut values[..mid], &mut values[mid..]);   // Error! cannot borrow `*values` as mutable more than once at a time
+// error: E0499
+
+pub fn split_at_mut_example<T>(values: &mut [T], mid: usize) -> (&mut [T], &mut [T]) {
+    let (left, right) = (&mut values[..mid], &mut values[mid..]);
+    (left, right)
+}
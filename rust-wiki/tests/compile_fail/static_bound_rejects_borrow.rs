@@ -0,0 +1,15 @@
+// wiki: src/_15_advanced_lifetimes/_4_static.rs
+// snippet: //    describe_in_thread(&local).join();     // Error! `local` does not live long enough
+// error: E0597
+
+use std::fmt::Display;
+use std::thread;
+
+pub fn describe_in_thread<T: Display + Send + 'static>(value: T) -> thread::JoinHandle<String> {
+    thread::spawn(move || format!("thread got {value}"))
+}
+
+pub fn borrow_local() {
+    let local: String = String::from("borrowed");
+    let _ = describe_in_thread(&local).join();
+}
@@ -0,0 +1,22 @@
+// wiki: src/_4_generics/_2_lifetime_annotations.rs
+// snippet: //    double.add_one();     // Error! cannot borrow `double` as mutable more than once at a time
+// error: E0499
+
+pub struct NamedBorrowed<'a> {
+    pub x: &'a i32,
+    pub y: &'a mut i32,
+}
+
+impl<'a> NamedBorrowed<'a> {
+    pub fn add_one(&'a mut self) {
+        *self.y += 1;
+    }
+}
+
+pub fn add_two() {
+    let x = 18;
+    let mut y = 15;
+    let mut double = NamedBorrowed { x: &x, y: &mut y };
+    double.add_one();
+    double.add_one();
+}
@@ -0,0 +1,11 @@
+// wiki: src/_8_concurrency/_1_threads.rs
+// snippet: //    let handle = thread::spawn(|| println!("{v:?}"));   // Error! closure may outlive the current function
+// error: E0373
+
+use std::thread;
+
+fn move_into_thread() {
+    let v = vec![1, 2, 3];
+    let handle = thread::spawn(|| println!("{v:?}"));
+    handle.join().unwrap();
+}
@@ -0,0 +1,19 @@
+// wiki: src/_14_advanced_traits/_4_supertraits.rs
+// snippet: //    Animal::baby_name()                  // Error! cannot call associated function on trait without specifying the corresponding `impl` type
+// error: E0790
+
+pub trait Animal {
+    fn baby_name() -> String;
+}
+
+pub struct Dog;
+
+impl Animal for Dog {
+    fn baby_name() -> String {
+        String::from("puppy")
+    }
+}
+
+pub fn baby_name() -> String {
+    Animal::baby_name()
+}
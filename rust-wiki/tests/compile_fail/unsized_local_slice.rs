@@ -0,0 +1,7 @@
+// wiki: src/_2_ownership/_4_slices.rs
+// snippet: // let arr: [i32] = [1, 2, 3, 4, 5]; // Error: all local variables must have a known size at compile-time
+// error: E0277
+
+fn arrslice_example() {
+    let arr: [i32] = [1, 2, 3, 4, 5];
+}
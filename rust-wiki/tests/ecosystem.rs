@@ -0,0 +1,84 @@
+// Builds and tests the examples/ecosystem workspace, which is excluded from this one, by running cargo on it. Each
+// test exercises one part of the project layout described in src/_0_ecosystem/cargo.md.
+//
+// The workspace gets its own target directory under ours, so that these nested cargo runs do not wait on the lock
+// of the build that is running them.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn ecosystem_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join("ecosystem")
+}
+
+fn cargo(dir: &Path, args: &[&str]) -> Output {
+    let cargo: String = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let output: Output = Command::new(cargo)
+        .args(args)
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join("ecosystem"))
+        .output()
+        .expect("cargo can be run");
+    assert!(
+        output.status.success(),
+        "`cargo {}` failed:\n{}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+// Unit tests, the integration tests in tests/ of both crates (including the binary's), the example, and the
+// benchmark in test mode. `shapes` turns on geometry's `solids` feature, so it is compiled in here.
+#[test]
+fn workspace_tests_pass() {
+    let output: Output = cargo(&ecosystem_dir(), &["test", "--workspace", "--all-targets"]);
+    let log: String = String::from_utf8_lossy(&output.stderr).into_owned();
+    for target in ["tests/shapes.rs", "tests/cli.rs", "benches/area.rs", "examples/describe.rs"] {
+        assert!(log.contains(target), "{target} was not run:\n{log}");
+    }
+    assert!(stdout(&output).contains("test solids_feature ... ok"));
+}
+
+// The library on its own, without any feature: the feature-gated tests are compiled out.
+#[test]
+fn features_can_be_disabled() {
+    let output: Output = cargo(&ecosystem_dir(), &["test", "-p", "geometry", "--no-default-features"]);
+    let log: String = stdout(&output);
+    assert!(log.contains("test public_api ... ok"));
+    assert!(!log.contains("display_feature"));
+    assert!(!log.contains("solids_feature"));
+}
+
+// The path dependency: the binary calls into the library, including the `solids` module and the units generated
+// by build.rs.
+#[test]
+fn binary_uses_library() {
+    let output: Output = cargo(&ecosystem_dir(), &["run", "-q", "-p", "shapes", "--", "convert", "26.2", "mi", "km"]);
+    assert_eq!(stdout(&output), "42.165 km\n");
+}
+
+#[test]
+fn example_runs() {
+    let output: Output = cargo(&ecosystem_dir(), &["run", "-q", "-p", "geometry", "--example", "describe"]);
+    let log: String = stdout(&output);
+    assert!(log.starts_with("circle of radius 1: area 3.14"));
+    // One line per unit in geometry/units.txt.
+    assert!(log.contains("42.195 km = 26.219 mi"));
+}
+
+// The exclusion: neither a member of this workspace, nor picked up as an example of this package.
+#[test]
+fn ecosystem_is_separate_from_the_wiki() {
+    let wiki_dir: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let metadata: String = stdout(&cargo(wiki_dir, &["metadata", "--no-deps", "--format-version", "1"]));
+    let example = |name: &str| format!("\"kind\":[\"example\"],\"crate_types\":[\"bin\"],\"name\":\"{name}\"");
+    assert!(metadata.contains("\"name\":\"rust-wiki\""));
+    assert!(!metadata.contains("\"name\":\"geometry\""));
+    assert!(metadata.contains(&example("collections_bench")));
+    assert!(!metadata.contains(&example("ecosystem")));
+}
@@ -0,0 +1,47 @@
+// Builds ffi/harness.c as a standalone C program, linked against the cdylib (librust_wiki.so) that cargo builds
+// alongside the rlib, and runs it: the same checks as the library's `test_c_harness`, but through the dynamic library
+// and its exported symbols, as any C program would use it.
+
+#![cfg(target_os = "linux")]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// target/debug/, where cargo puts librust_wiki.so; the test itself runs from target/debug/deps/.
+fn library_dir() -> PathBuf {
+    let exe: PathBuf = std::env::current_exe().expect("the test binary has a path");
+    exe.parent().and_then(Path::parent).expect("the test binary is in target/<profile>/deps/").to_path_buf()
+}
+
+fn run(command: &mut Command) -> Output {
+    let output: Output = command.output().unwrap_or_else(|error| panic!("failed to run {command:?}: {error}"));
+    assert!(
+        output.status.success(),
+        "{command:?} failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn c_program_links_against_cdylib() {
+    let library_dir: PathBuf = library_dir();
+    assert!(library_dir.join("librust_wiki.so").exists(), "no librust_wiki.so in {}", library_dir.display());
+
+    let program: PathBuf = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi_harness");
+    let compiler: String = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    // `-rpath` records the library's directory in the program, so that the dynamic loader finds it at run time.
+    run(Command::new(compiler)
+        .args(["-std=c99", "-D_POSIX_C_SOURCE=200809L", "-Wall", "-Wextra", "-Werror", "-DWIKI_HARNESS_MAIN"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("ffi").join("harness.c"))
+        .arg("-o")
+        .arg(&program)
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-lrust_wiki")
+        .arg(format!("-Wl,-rpath,{}", library_dir.display())));
+
+    let output: Output = run(&mut Command::new(&program));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "all C checks passed\n");
+}
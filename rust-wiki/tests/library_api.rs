@@ -0,0 +1,58 @@
+// Integration tests only see the library's public module tree, so these check that each chapter's example types are
+// reachable from outside the crate.
+
+use rust_wiki::_3_datatypes_and_traits::_3_methods::Rectangle;
+use rust_wiki::_3_datatypes_and_traits::_4_traits::{Show, User};
+use rust_wiki::_3_datatypes_and_traits::_5_patterns::{evaluate, EvalError};
+use rust_wiki::_4_generics::_1_generic_types::Point;
+use rust_wiki::_5_functional_features::_2_iterators::Fibonacci;
+
+#[test]
+fn chapter_types_are_public() {
+    let rect = Rectangle::new(3, 4);
+    assert_eq!(rect.area_withselfref(), 12);
+
+    let user = User { active: true, sign_in_count: 0, username: String::from("hello") };
+    assert_eq!(user.show(), "hello");
+
+    let point: Point<i32, i32> = Point { x: 1, y: 2 };
+    assert_eq!(point.largest_coord(), 2);
+
+    let fib = Fibonacci { curr: 0, next: 1 };
+    assert_eq!(fib.take(5).collect::<Vec<u32>>(), vec![0, 1, 1, 2, 3]);
+}
+
+// The derive names the trait by its absolute path, `::rust_wiki::...`, so it works from other crates too.
+#[derive(rust_wiki::_10_macros::_2_derive_macros::Show)]
+struct Version(u32, u32);
+
+#[test]
+fn show_can_be_derived_outside_the_wiki() {
+    assert_eq!(Version(1, 95).show(), "Version(1, 95)");
+}
+
+// `EvalError` is `#[non_exhaustive]`: outside the wiki, a match on it needs the wildcard arm, even with every current
+// variant listed.
+fn error_kind(error: &EvalError) -> &'static str {
+    match error {
+        EvalError::UnexpectedChar(_) | EvalError::UnexpectedToken(_) | EvalError::UnexpectedEnd => "syntax",
+        EvalError::UnknownVariable(_) => "name",
+        EvalError::DivisionByZero | EvalError::Overflow => "arithmetic",
+        _ => "other",
+    }
+}
+
+#[test]
+fn non_exhaustive_errors_need_a_wildcard() {
+    assert_eq!(evaluate("let x = 6 in x * 7"), Ok(42));
+    assert_eq!(error_kind(&evaluate("1 +").unwrap_err()), "syntax");
+    assert_eq!(error_kind(&evaluate("y").unwrap_err()), "name");
+    assert_eq!(error_kind(&evaluate("1 / 0").unwrap_err()), "arithmetic");
+}
+
+#[test]
+fn chapters_are_wired_in() {
+    rust_wiki::_1_basics::_5_io_and_logging::stdout_and_stderr();
+    rust_wiki::memory_old_notes::_1_memory_layout::_main();
+    assert_eq!(rust_wiki::_6_async::_2_futures_and_block_on::block_on(async { 1 + 1 }), 2);
+}